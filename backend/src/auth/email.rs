@@ -11,6 +11,7 @@ use lettre::{
 
 use crate::config::Config;
 use crate::errors::{AppError, AppResult};
+use crate::models::{NotificationKind, NotificationPreferences};
 
 // ── Public helpers ────────────────────────────────────────────
 
@@ -44,6 +45,47 @@ pub async fn send_password_reset_email(config: &Config, to: &str, token: &str) -
     send(config, to, "Password reset — Carls Calendar", &body).await
 }
 
+/// Send an optional (non-transactional) email, honouring the recipient's
+/// notification preferences. Returns `Ok(false)` when the user has opted out.
+pub async fn send_notification_email(
+    config: &Config,
+    pool: &crate::db::Db,
+    user_id: &str,
+    kind: NotificationKind,
+    subject: &str,
+    body: &str,
+) -> AppResult<bool> {
+    #[derive(sqlx::FromRow)]
+    struct RecipientRow {
+        email: Option<String>,
+        notification_preferences: Option<String>,
+    }
+
+    let row = sqlx::query_as::<_, RecipientRow>(
+        "SELECT email, CAST(notification_preferences AS CHAR) AS notification_preferences
+         FROM users
+         WHERE id = ? AND is_active = 1 AND deleted_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(RecipientRow { email: Some(to), notification_preferences }) = row else {
+        return Ok(false);
+    };
+    if !NotificationPreferences::from_json(notification_preferences.as_deref()).allows(kind) {
+        return Ok(false);
+    }
+
+    if config.smtp_host.is_empty() {
+        tracing::warn!(to, subject, "SMTP not configured — notification email skipped");
+        return Ok(false);
+    }
+
+    send(config, &to, subject, body).await?;
+    Ok(true)
+}
+
 // ── Internal ──────────────────────────────────────────────────
 
 async fn send(config: &Config, to: &str, subject: &str, body: &str) -> AppResult<()> {
@@ -1,11 +1,18 @@
 pub mod email;
 pub mod seed;
+pub mod sweep;
 
 use argon2::{
-    password_hash::{rand_core::OsRng, SaltString},
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        SaltString,
+    },
     Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
 };
-use uuid::Uuid;
+use std::sync::OnceLock;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
 
 use crate::errors::{AppError, AppResult};
 
@@ -28,11 +35,99 @@ pub fn verify_password(password: &str, hash: &str) -> AppResult<()> {
         .map_err(|_| AppError::Unauthorized)
 }
 
+/// Run a full Argon2 verify against a throwaway hash; returns the error to send.
+///
+/// Login calls this when no account matches, so an unknown email costs the
+/// same as a wrong password and response timing does not reveal which
+/// addresses are registered.
+pub fn verify_password_dummy(password: &str) -> AppError {
+    let _ = verify_password(password, dummy_hash());
+    AppError::Unauthorized
+}
+
+/// A real Argon2 hash of a random password, made with the same parameters as
+/// [`hash_password`] so verifying against it costs the same.
+fn dummy_hash() -> &'static str {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+    DUMMY_HASH.get_or_init(|| hash_password(&generate_token()).expect("hashing a random password succeeds"))
+}
+
 // ── Token helper ──────────────────────────────────────────────
 
-/// Generate a 64-char hex token from two UUIDs (256 bits of entropy).
+/// Generate a 64-char hex token from 32 OS-random bytes (256 bits of entropy).
 pub fn generate_token() -> String {
-    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// SHA-256 hex digest of a raw token, as stored in `*_token_hash` /
+/// `token_hash` columns. Only the raw value ever leaves the server.
+pub fn hash_token(raw: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(raw.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// `WHERE` fragment matching a session or email token row by hash, falling
+/// back to the legacy plaintext column for rows that predate migration 012.
+/// Binds: the hashed token, then the raw token.
+pub const TOKEN_MATCH_SQL: &str = "(token_hash = ? OR (token_hash IS NULL AND token = ?))";
+
+/// [`TOKEN_MATCH_SQL`] for queries that alias `user_sessions` as `s`.
+pub const SESSION_TOKEN_MATCH_SQL: &str =
+    "(s.token_hash = ? OR (s.token_hash IS NULL AND s.token = ?))";
+
+// ── Signed download tokens ────────────────────────────────────
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Claims embedded in a signed export download token.
+pub struct ExportTokenClaims {
+    pub id:         String,
+    pub user_id:    String,
+}
+
+fn export_token_mac(secret: &str, payload: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+/// Build `{id}.{user_id}.{expires_at}.{hex hmac}` signed with `SESSION_SECRET`.
+pub fn sign_export_token(secret: &str, id: &str, user_id: &str, expires_at: i64) -> String {
+    let payload = format!("{id}.{user_id}.{expires_at}");
+    let sig = export_token_mac(secret, &payload).finalize().into_bytes();
+    let sig_hex: String = sig.iter().map(|b| format!("{b:02x}")).collect();
+    format!("{payload}.{sig_hex}")
+}
+
+/// Check signature and expiry. Single-use is enforced by the caller.
+pub fn verify_export_token(secret: &str, token: &str) -> AppResult<ExportTokenClaims> {
+    let (payload, sig_hex) = token.rsplit_once('.').ok_or(AppError::Unauthorized)?;
+    if sig_hex.len() != 64 || !sig_hex.is_ascii() {
+        return Err(AppError::Unauthorized);
+    }
+    let sig = (0..sig_hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&sig_hex[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| AppError::Unauthorized)?;
+    export_token_mac(secret, payload)
+        .verify_slice(&sig)
+        .map_err(|_| AppError::Unauthorized)?;
+
+    let mut parts = payload.splitn(3, '.');
+    let (Some(id), Some(user_id), Some(exp)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(AppError::Unauthorized);
+    };
+    let expires_at: i64 = exp.parse().map_err(|_| AppError::Unauthorized)?;
+    if expires_at <= chrono::Utc::now().timestamp() {
+        return Err(AppError::Unauthorized);
+    }
+
+    Ok(ExportTokenClaims { id: id.to_owned(), user_id: user_id.to_owned() })
 }
 
 // ── Password validation ───────────────────────────────────────
@@ -56,3 +151,32 @@ pub fn validate_password_strength(password: &str) -> AppResult<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_64_lowercase_hex_chars_and_unique() {
+        let tokens: std::collections::HashSet<String> = (0..10_000).map(|_| generate_token()).collect();
+        assert_eq!(tokens.len(), 10_000);
+        for token in &tokens {
+            assert_eq!(token.len(), 64);
+            assert!(token.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')), "{token}");
+        }
+    }
+
+    #[test]
+    fn dummy_verify_costs_the_same_as_a_real_one() {
+        // Same algorithm, version and cost parameters as a stored hash, so the
+        // unknown-email path runs an equally expensive verify.
+        let real = hash_password("correct horse").unwrap();
+        let real = PasswordHash::new(&real).unwrap();
+        let dummy = PasswordHash::new(dummy_hash()).unwrap();
+        assert_eq!(dummy.algorithm, real.algorithm);
+        assert_eq!(dummy.version, real.version);
+        assert_eq!(dummy.params, real.params);
+
+        assert!(matches!(verify_password_dummy("correct horse"), AppError::Unauthorized));
+    }
+}
@@ -1,65 +1,85 @@
 use uuid::Uuid;
 
-use crate::auth::hash_password;
+use crate::auth::{hash_password, verify_password};
+use crate::config::Config;
 use crate::db::Db;
 
+const ADMIN_USERNAME: &str = "admin";
+/// Used only when `SEED_ADMIN_PASSWORD` is unset; the account is then flagged
+/// `must_change_password` until the admin picks a real one.
+const DEFAULT_ADMIN_PASSWORD: &str = "admin";
+
 /// Seeds the admin account (username: "admin").
 /// Safe to call on every startup — existence is checked before inserting.
-pub async fn seed_accounts(pool: &Db) -> anyhow::Result<()> {
-    seed_admin(pool).await?;
+pub async fn seed_accounts(pool: &Db, config: &Config) -> anyhow::Result<()> {
+    seed_admin(pool, config).await?;
 
     Ok(())
 }
 
-async fn seed_admin(pool: &Db) -> anyhow::Result<()> {
-    const ADMIN_USERNAME: &str = "admin";
-    const ADMIN_EMAIL: &str = "admin@admin.dk";
-    const ADMIN_PASSWORD: &str = "admin";
-
+async fn seed_admin(pool: &Db, config: &Config) -> anyhow::Result<()> {
     #[derive(sqlx::FromRow)]
     struct AdminRow {
         id: String,
-        email: Option<String>,
         password_hash: String,
     }
 
+    let (password, using_default) = match config.seed_admin_password.as_deref() {
+        Some(p) => (p, false),
+        None => (DEFAULT_ADMIN_PASSWORD, true),
+    };
+
     let row: Option<AdminRow> = sqlx::query_as::<_, AdminRow>(
-        "SELECT id, email, password_hash FROM users WHERE username = ? AND role = 'admin' LIMIT 1"
+        "SELECT id, password_hash FROM users WHERE username = ? AND role = 'admin' LIMIT 1"
     )
     .bind(ADMIN_USERNAME)
     .fetch_optional(pool)
     .await?;
 
     match row {
-        Some(r) => {
-            // Ensure seeded credentials are consistent for local/dev access.
-            let hash = hash_password(ADMIN_PASSWORD)?;
-            sqlx::query("UPDATE users SET email = ?, password_hash = ?, updated_at = UTC_TIMESTAMP() WHERE id = ?")
-                .bind(ADMIN_EMAIL)
-                .bind(hash)
-                .bind(&r.id)
-                .execute(pool)
-                .await?;
+        // Row seeded by migration 002 without a usable hash: set credentials.
+        Some(r) if r.password_hash.contains("PLACEHOLDER") => {
+            let hash = hash_password(password)?;
+            sqlx::query(
+                "UPDATE users SET email = ?, password_hash = ?, must_change_password = ?, updated_at = UTC_TIMESTAMP()
+                 WHERE id = ?",
+            )
+            .bind(&config.seed_admin_email)
+            .bind(hash)
+            .bind(using_default)
+            .bind(&r.id)
+            .execute(pool)
+            .await?;
+            tracing::info!(email = %config.seed_admin_email, "Set seeded admin credentials");
+        }
 
-            if r.email.as_deref() != Some(ADMIN_EMAIL) || r.password_hash.contains("PLACEHOLDER") {
-                tracing::info!("Updated admin credentials (email + password hash)");
+        // Existing admin: never overwrite a password the admin chose, but keep
+        // forcing a change while the built-in default still works.
+        Some(r) => {
+            if verify_password(DEFAULT_ADMIN_PASSWORD, &r.password_hash).is_ok() {
+                sqlx::query("UPDATE users SET must_change_password = 1 WHERE id = ?")
+                    .bind(&r.id)
+                    .execute(pool)
+                    .await?;
+                tracing::warn!("Admin account still uses the default password; a change is required at next login");
             }
         }
 
         None => {
-            let hash = hash_password(ADMIN_PASSWORD)?;
+            let hash = hash_password(password)?;
             let id = Uuid::new_v4().to_string();
             sqlx::query(
-                 "INSERT INTO users (id, username, email, password_hash, role, language, is_verified, is_active, created_at, updated_at)
-                VALUES (?, ?, ?, ?, 'admin', 'en', 1, 1, UTC_TIMESTAMP(), UTC_TIMESTAMP())"
+                 "INSERT INTO users (id, username, email, password_hash, must_change_password, role, language, is_verified, is_active, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, 'admin', 'en', 1, 1, UTC_TIMESTAMP(), UTC_TIMESTAMP())"
             )
             .bind(id)
             .bind(ADMIN_USERNAME)
-            .bind(ADMIN_EMAIL)
+            .bind(&config.seed_admin_email)
             .bind(hash)
+            .bind(using_default)
             .execute(pool)
             .await?;
-            tracing::info!("Seeded admin account (email: admin@admin.dk)");
+            tracing::info!(email = %config.seed_admin_email, "Seeded admin account");
         }
     }
 
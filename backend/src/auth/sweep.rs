@@ -0,0 +1,176 @@
+//! Periodic sweep of expired auth rows.
+//!
+//! Sessions and email tokens are otherwise only deleted on logout or use, so
+//! expired ones pile up. Unlike the retention rules in `crate::compliance`
+//! (admin-configurable, per data table) this job has a fixed target list and
+//! deletes rows as soon as they are expired.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use serde::Serialize;
+
+use crate::{config::Config, db::Db, state::AppState};
+
+#[derive(sqlx::FromRow)]
+struct SweepStatusRow {
+    last_run_at: Option<chrono::NaiveDateTime>,
+    last_deleted_counts: Option<String>,
+    last_error: Option<String>,
+    next_run_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenSweepStatusDto {
+    pub enabled: bool,
+    pub interval_minutes: u64,
+    pub include_qr_tokens: bool,
+    /// Devices unused this many days are revoked; 0 when disabled.
+    pub stale_device_days: u64,
+    pub last_run_at: Option<String>,
+    /// Rows deleted per table during the last run.
+    pub last_deleted_counts: Option<serde_json::Value>,
+    pub last_error: Option<String>,
+    pub next_run_at: Option<String>,
+}
+
+pub fn spawn_token_sweep(state: AppState) {
+    if !state.config.auth_token_sweep_enabled {
+        tracing::info!("Auth token sweep disabled");
+        return;
+    }
+
+    let minutes = state.config.auth_token_sweep_interval_minutes;
+    tracing::info!(minutes, "Auth token sweep started");
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(minutes.saturating_mul(60)));
+        loop {
+            ticker.tick().await;
+            let outcome = sweep_expired_tokens(
+                &state.pool,
+                state.config.auth_token_sweep_qr_tokens,
+                state.config.auth_token_sweep_stale_device_days,
+            )
+            .await;
+            match &outcome {
+                Ok(counts) => tracing::info!(?counts, "Auth token sweep finished"),
+                Err(err) => tracing::error!(error = %err, "Auth token sweep failed"),
+            }
+            let next = chrono::Utc::now().naive_utc() + chrono::Duration::minutes(minutes as i64);
+            if let Err(err) = record_sweep_run(&state.pool, &outcome, next).await {
+                tracing::warn!(error = %err, "Unable to record auth token sweep status");
+            }
+        }
+    });
+}
+
+/// Delete expired sessions and email tokens, plus deactivated QR tokens when
+/// `include_qr_tokens` is set, and revoke child devices unused for
+/// `stale_device_days` (0 disables). Returns rows affected per table.
+pub async fn sweep_expired_tokens(
+    pool: &Db,
+    include_qr_tokens: bool,
+    stale_device_days: u64,
+) -> anyhow::Result<BTreeMap<String, u64>> {
+    let mut counts = BTreeMap::new();
+
+    let sessions = sqlx::query("DELETE FROM user_sessions WHERE expires_at <= NOW()")
+        .execute(pool)
+        .await?
+        .rows_affected();
+    counts.insert("user_sessions".to_string(), sessions);
+
+    let email_tokens = sqlx::query("DELETE FROM email_tokens WHERE expires_at <= NOW()")
+        .execute(pool)
+        .await?
+        .rows_affected();
+    counts.insert("email_tokens".to_string(), email_tokens);
+
+    if include_qr_tokens {
+        let qr_tokens = sqlx::query("DELETE FROM qr_tokens WHERE is_active = 0")
+            .execute(pool)
+            .await?
+            .rows_affected();
+        counts.insert("qr_tokens".to_string(), qr_tokens);
+    }
+
+    if stale_device_days > 0 {
+        let devices = sqlx::query(
+            "UPDATE child_device_tokens
+             SET revoked_at = NOW()
+             WHERE revoked_at IS NULL
+               AND COALESCE(last_used_at, created_at) < NOW() - INTERVAL ? DAY",
+        )
+        .bind(stale_device_days)
+        .execute(pool)
+        .await?
+        .rows_affected();
+        counts.insert("child_device_tokens".to_string(), devices);
+    }
+
+    Ok(counts)
+}
+
+async fn record_sweep_run(
+    pool: &Db,
+    outcome: &anyhow::Result<BTreeMap<String, u64>>,
+    next_run_at: chrono::NaiveDateTime,
+) -> anyhow::Result<()> {
+    let (counts, error) = match outcome {
+        Ok(counts) => (Some(serde_json::to_string(counts)?), None),
+        Err(err) => (None, Some(err.to_string())),
+    };
+
+    sqlx::query(
+        "UPDATE auth_token_sweep_status
+         SET last_run_at = UTC_TIMESTAMP(),
+             last_deleted_counts = ?,
+             last_error = ?,
+             next_run_at = ?
+         WHERE id = 1",
+    )
+    .bind(counts)
+    .bind(error)
+    .bind(next_run_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_sweep_status(pool: &Db, config: &Config) -> anyhow::Result<TokenSweepStatusDto> {
+    let row = sqlx::query_as::<_, SweepStatusRow>(
+        "SELECT last_run_at, CAST(last_deleted_counts AS CHAR) AS last_deleted_counts,
+                last_error, next_run_at
+         FROM auth_token_sweep_status
+         WHERE id = 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let fmt = |dt: chrono::NaiveDateTime| {
+        chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(dt, chrono::Utc)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    };
+
+    let enabled = config.auth_token_sweep_enabled;
+    let row = row.unwrap_or(SweepStatusRow {
+        last_run_at: None,
+        last_deleted_counts: None,
+        last_error: None,
+        next_run_at: None,
+    });
+    Ok(TokenSweepStatusDto {
+        enabled,
+        interval_minutes: config.auth_token_sweep_interval_minutes,
+        include_qr_tokens: config.auth_token_sweep_qr_tokens,
+        stale_device_days: config.auth_token_sweep_stale_device_days,
+        last_run_at: row.last_run_at.map(fmt),
+        last_deleted_counts: row
+            .last_deleted_counts
+            .as_deref()
+            .and_then(|raw| serde_json::from_str(raw).ok()),
+        last_error: row.last_error,
+        next_run_at: if enabled { row.next_run_at.map(fmt) } else { None },
+    })
+}
@@ -1,9 +1,15 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
+use serde::Serialize;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::{db::Db, state::AppState};
+use crate::{
+    config::Config,
+    db::Db,
+    events::{self, AdminEvent, EventBus},
+    state::AppState,
+};
 
 #[derive(sqlx::FromRow)]
 struct RetentionRuleRow {
@@ -15,9 +21,36 @@ struct RetentionRuleRow {
     enabled: bool,
 }
 
+#[derive(sqlx::FromRow)]
+struct RetentionStatusRow {
+    last_run_at: Option<chrono::NaiveDateTime>,
+    last_trigger: Option<String>,
+    last_deleted_counts: Option<String>,
+    last_error: Option<String>,
+    next_run_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RetentionStatusDto {
+    pub enabled: bool,
+    pub interval_minutes: u64,
+    pub last_run_at: Option<String>,
+    /// `scheduled` or `manual`.
+    pub last_trigger: Option<String>,
+    /// Rows deleted per retention rule name during the last run.
+    pub last_deleted_counts: Option<serde_json::Value>,
+    pub last_error: Option<String>,
+    pub next_run_at: Option<String>,
+}
+
 pub fn spawn_retention_cleanup(state: AppState) {
     if !state.config.retention_cleanup_enabled {
         tracing::info!("Retention cleanup job disabled");
+        tokio::spawn(async move {
+            if let Err(err) = set_next_run(&state.pool, None).await {
+                tracing::warn!(error = %err, "Unable to clear retention next run");
+            }
+        });
         return;
     }
 
@@ -28,17 +61,129 @@ pub fn spawn_retention_cleanup(state: AppState) {
         let mut ticker = tokio::time::interval(Duration::from_secs(minutes.saturating_mul(60)));
         // First immediate tick consumed so subsequent ticks wait the configured interval.
         ticker.tick().await;
+        if let Err(err) = set_next_run(&state.pool, Some(next_run_from_now(minutes))).await {
+            tracing::warn!(error = %err, "Unable to record retention next run");
+        }
 
         loop {
             ticker.tick().await;
-            if let Err(err) = run_retention_cleanup(&state.pool).await {
+            let outcome = run_retention_cleanup(&state.pool).await;
+            if let Err(err) = &outcome {
                 tracing::error!(error = %err, "Retention cleanup failed");
             }
+            let next = Some(next_run_from_now(minutes));
+            if let Err(err) = record_retention_run(&state.pool, "scheduled", &outcome, next).await {
+                tracing::warn!(error = %err, "Unable to record retention run status");
+            }
+            publish_retention_outcome(&state.events, "scheduled", &outcome);
         }
     });
 }
 
-pub async fn run_retention_cleanup(pool: &Db) -> anyhow::Result<()> {
+fn next_run_from_now(minutes: u64) -> chrono::NaiveDateTime {
+    chrono::Utc::now().naive_utc() + chrono::Duration::minutes(minutes as i64)
+}
+
+async fn set_next_run(pool: &Db, next_run_at: Option<chrono::NaiveDateTime>) -> anyhow::Result<()> {
+    sqlx::query("UPDATE retention_cleanup_status SET next_run_at = ? WHERE id = 1")
+        .bind(next_run_at)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Persist the outcome of a cleanup run. `next_run_at` is only updated for
+/// scheduled runs; a manual run does not shift the job's cadence.
+pub async fn record_retention_run(
+    pool: &Db,
+    trigger: &str,
+    outcome: &anyhow::Result<BTreeMap<String, u64>>,
+    next_run_at: Option<chrono::NaiveDateTime>,
+) -> anyhow::Result<()> {
+    let (counts, error) = match outcome {
+        Ok(counts) => (Some(serde_json::to_string(counts)?), None),
+        Err(err) => (None, Some(err.to_string())),
+    };
+
+    sqlx::query(
+        "UPDATE retention_cleanup_status
+         SET last_run_at = UTC_TIMESTAMP(),
+             last_trigger = ?,
+             last_deleted_counts = ?,
+             last_error = ?,
+             next_run_at = IF(? = 'scheduled', ?, next_run_at)
+         WHERE id = 1",
+    )
+    .bind(trigger)
+    .bind(counts)
+    .bind(error)
+    .bind(trigger)
+    .bind(next_run_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub fn publish_retention_outcome(
+    bus: &EventBus,
+    trigger: &str,
+    outcome: &anyhow::Result<BTreeMap<String, u64>>,
+) {
+    let (deleted_counts, error) = match outcome {
+        Ok(counts) => (serde_json::to_value(counts).ok(), None),
+        Err(err) => (None, Some(err.to_string())),
+    };
+    events::publish(bus, AdminEvent::RetentionCompleted {
+        trigger: trigger.to_string(),
+        deleted_counts,
+        error,
+    });
+}
+
+pub async fn get_retention_status(pool: &Db, config: &Config) -> anyhow::Result<RetentionStatusDto> {
+    let row = sqlx::query_as::<_, RetentionStatusRow>(
+        "SELECT last_run_at, last_trigger, CAST(last_deleted_counts AS CHAR) AS last_deleted_counts,
+                last_error, next_run_at
+         FROM retention_cleanup_status
+         WHERE id = 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let fmt = |dt: chrono::NaiveDateTime| {
+        chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(dt, chrono::Utc)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    };
+
+    let enabled = config.retention_cleanup_enabled;
+    Ok(match row {
+        Some(row) => RetentionStatusDto {
+            enabled,
+            interval_minutes: config.retention_cleanup_interval_minutes,
+            last_run_at: row.last_run_at.map(fmt),
+            last_trigger: row.last_trigger,
+            last_deleted_counts: row
+                .last_deleted_counts
+                .as_deref()
+                .and_then(|raw| serde_json::from_str(raw).ok()),
+            last_error: row.last_error,
+            next_run_at: if enabled { row.next_run_at.map(fmt) } else { None },
+        },
+        None => RetentionStatusDto {
+            enabled,
+            interval_minutes: config.retention_cleanup_interval_minutes,
+            last_run_at: None,
+            last_trigger: None,
+            last_deleted_counts: None,
+            last_error: None,
+            next_run_at: None,
+        },
+    })
+}
+
+/// Apply every enabled retention rule. Returns rows deleted per rule name.
+pub async fn run_retention_cleanup(pool: &Db) -> anyhow::Result<BTreeMap<String, u64>> {
     let rules: Vec<RetentionRuleRow> = sqlx::query_as::<_, RetentionRuleRow>(
         "SELECT id, name, table_name, timestamp_column, retention_days, enabled
          FROM retention_rules
@@ -48,12 +193,14 @@ pub async fn run_retention_cleanup(pool: &Db) -> anyhow::Result<()> {
     .fetch_all(pool)
     .await?;
 
+    let mut counts = BTreeMap::new();
     for rule in rules {
         if !rule.enabled || rule.retention_days <= 0 {
             continue;
         }
 
         let affected = apply_rule(pool, &rule).await?;
+        counts.insert(rule.name.clone(), affected);
         if affected > 0 {
             sqlx::query(
                 "INSERT INTO deletion_logs (id, table_name, record_id, deleted_at, reason, details, actor_user_id)
@@ -80,7 +227,7 @@ pub async fn run_retention_cleanup(pool: &Db) -> anyhow::Result<()> {
         }
     }
 
-    Ok(())
+    Ok(counts)
 }
 
 async fn apply_rule(pool: &Db, rule: &RetentionRuleRow) -> anyhow::Result<u64> {
@@ -113,6 +260,15 @@ async fn apply_rule(pool: &Db, rule: &RetentionRuleRow) -> anyhow::Result<u64> {
             .execute(pool)
             .await?
         }
+        ("idempotency_keys", "expires_at") => {
+            sqlx::query(
+                "DELETE FROM idempotency_keys
+                 WHERE expires_at < DATE_SUB(NOW(), INTERVAL ? DAY)",
+            )
+            .bind(rule.retention_days)
+            .execute(pool)
+            .await?
+        }
         ("child_device_tokens", "revoked_at") => {
             sqlx::query(
                 "DELETE FROM child_device_tokens
@@ -123,6 +279,16 @@ async fn apply_rule(pool: &Db, rule: &RetentionRuleRow) -> anyhow::Result<u64> {
             .execute(pool)
             .await?
         }
+        ("child_profiles", "deleted_at") => {
+            sqlx::query(
+                "DELETE FROM child_profiles
+                 WHERE deleted_at IS NOT NULL
+                   AND deleted_at < DATE_SUB(NOW(), INTERVAL ? DAY)",
+            )
+            .bind(rule.retention_days)
+            .execute(pool)
+            .await?
+        }
         ("users", "deleted_at") => {
             sqlx::query(
                 "DELETE FROM users
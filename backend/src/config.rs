@@ -16,9 +16,12 @@ pub struct Config {
     pub backend_port:     u16,
 
     // Session
-    #[allow(dead_code)]
     pub session_secret:   String,
 
+    // Seeded admin account
+    pub seed_admin_email:    String,
+    pub seed_admin_password: Option<String>,
+
     // Email
     pub smtp_host:        String,
     pub smtp_port:        u16,
@@ -29,16 +32,59 @@ pub struct Config {
     // App
     pub app_env:          String,
     pub app_base_url:     String,
+    pub qr_pairing_base_url: String,
+    pub public_asset_base_url: String,
+    pub app_default_timezone: chrono_tz::Tz,
+    /// `HH:MM` start time for activity cards whose schedule sets no default.
+    pub default_activity_start_time: String,
+    /// Assignment and override dates must lie within this many years of today.
+    pub assignment_date_max_years: u32,
+    /// Reject a schedule name the owner already uses on a non-archived schedule.
+    pub unique_schedule_names: bool,
+    /// Child profiles a parent may have (soft-deleted ones excluded); admins are exempt.
+    pub max_children_per_parent: u32,
+    pub supported_languages: Vec<String>,
+    pub hide_existence_on_forbidden: bool,
+
+    // Visual documents
+    pub document_json_max_depth: usize,
+    pub document_json_max_bytes: usize,
+    /// Responses smaller than this are sent uncompressed.
+    pub compression_min_bytes: u16,
+    /// `Cache-Control: max-age` for `/assets` and `/uploads`.
+    pub static_asset_max_age_seconds: u64,
+    /// Upload storage allowed per parent account.
+    pub user_storage_quota_bytes: u64,
 
     // Compliance / retention
     pub retention_cleanup_enabled: bool,
     pub retention_cleanup_interval_minutes: u64,
 
+    // Expired session / token sweep
+    pub auth_token_sweep_enabled: bool,
+    pub auth_token_sweep_interval_minutes: u64,
+    pub auth_token_sweep_qr_tokens: bool,
+    pub auth_token_sweep_stale_device_days: u64,
+
+    // Reminder emails
+    pub reminders_enabled: bool,
+    pub reminders_interval_minutes: u64,
+
     // Pictogram prefetch
     pub pictogram_prefetch_default_enabled: bool,
     pub pictogram_prefetch_idle_minutes: u64,
     pub pictogram_prefetch_batch_size: u64,
     pub pictogram_prefetch_interval_seconds: u64,
+    /// How long a language's ARASAAC keyword list is served from memory
+    /// before it is refreshed in the background.
+    pub pictogram_keywords_cache_ttl_seconds: u64,
+
+    // Public consent endpoints
+    /// Requests per minute one client IP may make to `/consent*`.
+    pub consent_rate_limit_per_minute: u32,
+    /// Reverse proxies in front of the backend whose `X-Forwarded-For` /
+    /// `X-Real-IP` are believed; 0 uses the connection's peer address only.
+    pub trusted_proxy_hops: usize,
 }
 
 #[derive(Debug, Error)]
@@ -70,6 +116,25 @@ impl Config {
             }
         }
 
+        /// UI language codes (not formatting locales), e.g. `en,da,sv,en-US`.
+        /// Stored in `users.language`, so each code must fit in 5 characters.
+        fn parse_languages(raw: &str) -> Result<Vec<String>, ConfigError> {
+            let mut out: Vec<String> = Vec::new();
+            for code in raw.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+                let valid = code.len() <= 5 && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+                if !valid {
+                    return Err(ConfigError::InvalidValue("SUPPORTED_LANGUAGES".into(), raw.to_string()));
+                }
+                if !out.iter().any(|c| c.eq_ignore_ascii_case(code)) {
+                    out.push(code.to_string());
+                }
+            }
+            if out.is_empty() {
+                out.push("en".into());
+            }
+            Ok(out)
+        }
+
         Ok(Self {
             db_host:      require("DB_HOST").unwrap_or_else(|_| "db".into()),
             db_port:      parse_port("DB_PORT").unwrap_or(3306),
@@ -82,6 +147,13 @@ impl Config {
 
             session_secret: require("SESSION_SECRET")?,
 
+            seed_admin_email: env::var("SEED_ADMIN_EMAIL")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "admin@admin.dk".into()),
+            seed_admin_password: env::var("SEED_ADMIN_PASSWORD").ok().filter(|v| !v.is_empty()),
+
             smtp_host:     env::var("SMTP_HOST").unwrap_or_default(),
             smtp_port:     env::var("SMTP_PORT").ok().and_then(|v| v.parse().ok()).unwrap_or(587),
             smtp_user:     env::var("SMTP_USER").unwrap_or_default(),
@@ -90,6 +162,78 @@ impl Config {
 
             app_env:      env::var("APP_ENV").unwrap_or_else(|_| "development".into()),
             app_base_url: env::var("APP_BASE_URL").unwrap_or_else(|_| "http://localhost".into()),
+            qr_pairing_base_url: env::var("QR_PAIRING_BASE_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .or_else(|| env::var("APP_BASE_URL").ok())
+                .unwrap_or_else(|| "http://localhost".into())
+                .trim_end_matches('/')
+                .to_string(),
+            public_asset_base_url: env::var("PUBLIC_ASSET_BASE_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .or_else(|| env::var("APP_BASE_URL").ok())
+                .unwrap_or_else(|| "http://localhost".into())
+                .trim_end_matches('/')
+                .to_string(),
+            app_default_timezone: match env::var("APP_DEFAULT_TIMEZONE") {
+                Ok(raw) if !raw.trim().is_empty() => raw
+                    .trim()
+                    .parse::<chrono_tz::Tz>()
+                    .map_err(|_| ConfigError::InvalidValue("APP_DEFAULT_TIMEZONE".into(), raw))?,
+                _ => chrono_tz::UTC,
+            },
+            default_activity_start_time: match env::var("DEFAULT_ACTIVITY_START_TIME") {
+                Ok(raw) if !raw.trim().is_empty() => {
+                    let value = raw.trim();
+                    if value.len() != 5 || chrono::NaiveTime::parse_from_str(value, "%H:%M").is_err() {
+                        return Err(ConfigError::InvalidValue("DEFAULT_ACTIVITY_START_TIME".into(), raw));
+                    }
+                    value.to_string()
+                }
+                _ => "08:00".into(),
+            },
+            assignment_date_max_years: env::var("ASSIGNMENT_DATE_MAX_YEARS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .filter(|v| (1..=100).contains(v))
+                .unwrap_or(5),
+            unique_schedule_names: parse_bool_env("UNIQUE_SCHEDULE_NAMES", true),
+            max_children_per_parent: env::var("MAX_CHILDREN_PER_PARENT")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .filter(|v| (1..=1000).contains(v))
+                .unwrap_or(20),
+
+            supported_languages: parse_languages(
+                &env::var("SUPPORTED_LANGUAGES").unwrap_or_else(|_| "en,da".into()),
+            )?,
+
+            hide_existence_on_forbidden: parse_bool_env("HIDE_EXISTENCE_ON_FORBIDDEN", true),
+
+            document_json_max_depth: env::var("DOCUMENT_JSON_MAX_DEPTH")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(16),
+            document_json_max_bytes: env::var("DOCUMENT_JSON_MAX_BYTES")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(256 * 1024),
+            compression_min_bytes: env::var("COMPRESSION_MIN_BYTES")
+                .ok()
+                .and_then(|v| v.parse::<u16>().ok())
+                .unwrap_or(1024),
+            static_asset_max_age_seconds: env::var("STATIC_ASSET_MAX_AGE_SECONDS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(86_400),
+            user_storage_quota_bytes: env::var("USER_STORAGE_QUOTA_BYTES")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(50 * 1024 * 1024),
 
             retention_cleanup_enabled: parse_bool_env("RETENTION_CLEANUP_ENABLED", true),
             retention_cleanup_interval_minutes: env::var("RETENTION_CLEANUP_INTERVAL_MINUTES")
@@ -98,6 +242,25 @@ impl Config {
                 .filter(|v| *v > 0)
                 .unwrap_or(60),
 
+            auth_token_sweep_enabled: parse_bool_env("AUTH_TOKEN_SWEEP_ENABLED", true),
+            auth_token_sweep_interval_minutes: env::var("AUTH_TOKEN_SWEEP_INTERVAL_MINUTES")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(30),
+            auth_token_sweep_qr_tokens: parse_bool_env("AUTH_TOKEN_SWEEP_QR_TOKENS", false),
+            auth_token_sweep_stale_device_days: env::var("AUTH_TOKEN_SWEEP_STALE_DEVICE_DAYS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0),
+
+            reminders_enabled: parse_bool_env("REMINDERS_ENABLED", false),
+            reminders_interval_minutes: env::var("REMINDERS_INTERVAL_MINUTES")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(5),
+
             pictogram_prefetch_default_enabled: parse_bool_env("PICTOGRAM_PREFETCH_DEFAULT_ENABLED", false),
             pictogram_prefetch_idle_minutes: env::var("PICTOGRAM_PREFETCH_IDLE_MINUTES")
                 .ok()
@@ -114,6 +277,129 @@ impl Config {
                 .and_then(|v| v.parse::<u64>().ok())
                 .filter(|v| *v > 0)
                 .unwrap_or(60),
+            pictogram_keywords_cache_ttl_seconds: env::var("PICTOGRAM_KEYWORDS_CACHE_TTL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(24 * 60 * 60),
+            consent_rate_limit_per_minute: env::var("CONSENT_RATE_LIMIT_PER_MINUTE")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(30),
+            trusted_proxy_hops: env::var("TRUSTED_PROXY_HOPS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0),
         })
     }
 }
+
+/// Cross-origin policy applied to the whole router.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorsPolicy {
+    /// Any origin, method and header (`CorsLayer::permissive`).
+    Permissive,
+}
+
+impl CorsPolicy {
+    pub fn layer(self) -> tower_http::cors::CorsLayer {
+        match self {
+            CorsPolicy::Permissive => tower_http::cors::CorsLayer::permissive(),
+        }
+    }
+}
+
+impl Config {
+    /// `APP_ENV=development`: local work with relaxed security.
+    pub fn is_development(&self) -> bool {
+        self.app_env == "development"
+    }
+
+    /// Cookies carry `Secure` everywhere but development.
+    pub fn secure_cookies(&self) -> bool {
+        !self.is_development()
+    }
+
+    /// Password strength rules apply to register, reset and change
+    /// everywhere but development.
+    pub fn password_policy_enforced(&self) -> bool {
+        !self.is_development()
+    }
+
+    /// 5xx responses include the underlying error only in development.
+    pub fn expose_internal_errors(&self) -> bool {
+        self.is_development()
+    }
+
+    /// CORS policy the router applies. Tighten in production.
+    pub fn cors_policy(&self) -> CorsPolicy {
+        CorsPolicy::Permissive
+    }
+
+    /// Absolute URL for a stored picture path. Relative paths such as
+    /// `/assets/pictograms/...` are prefixed with `PUBLIC_ASSET_BASE_URL`;
+    /// values that already carry a scheme are returned unchanged.
+    pub fn resolve_asset_url(&self, path: &str) -> String {
+        let path = path.trim();
+        if path.starts_with("http://") || path.starts_with("https://") || path.starts_with("data:") {
+            return path.to_string();
+        }
+        let base = &self.public_asset_base_url;
+        if path.starts_with('/') {
+            format!("{base}{path}")
+        } else {
+            format!("{base}/{path}")
+        }
+    }
+}
+
+/// The `picture_url` a DTO exposes next to its stored `picture_path`: the
+/// path resolved against `PUBLIC_ASSET_BASE_URL` (see
+/// [`Config::resolve_asset_url`]), or `None` when there is no picture.
+/// Every row type carrying both fields fills the URL through this.
+pub fn resolve_public_asset(config: &Config, picture_path: Option<&str>) -> Option<String> {
+    picture_path.map(|p| config.resolve_asset_url(p))
+}
+
+#[cfg(test)]
+impl Config {
+    /// `from_env` defaults, with placeholders for the variables it requires.
+    pub fn for_tests() -> Self {
+        static INIT: std::sync::Once = std::sync::Once::new();
+        INIT.call_once(|| {
+            for (key, value) in [
+                ("DB_NAME", "carlscalendar_test"),
+                ("DB_USER", "test"),
+                ("DB_PASSWORD", "test"),
+                ("SESSION_SECRET", "test-session-secret"),
+            ] {
+                if env::var(key).is_err() {
+                    env::set_var(key, value);
+                }
+            }
+        });
+        Self::from_env().expect("test config")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_asset_paths_resolve_against_the_base_url() {
+        let mut config = Config::for_tests();
+        config.public_asset_base_url = "https://cdn.example.org".into();
+        assert_eq!(
+            resolve_public_asset(&config, Some("/assets/pictograms/a.png")).as_deref(),
+            Some("https://cdn.example.org/assets/pictograms/a.png"),
+        );
+        assert_eq!(
+            resolve_public_asset(&config, Some("https://img.example.org/b.png")).as_deref(),
+            Some("https://img.example.org/b.png"),
+        );
+        assert_eq!(resolve_public_asset(&config, None), None);
+    }
+}
@@ -1,11 +1,29 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
 use thiserror::Error;
 
+/// Whether [`AppError::NotOwned`] is reported as 404 instead of 403.
+static HIDE_EXISTENCE_ON_FORBIDDEN: AtomicBool = AtomicBool::new(true);
+
+/// Set once at startup from `Config::hide_existence_on_forbidden`.
+pub fn set_hide_existence_on_forbidden(enabled: bool) {
+    HIDE_EXISTENCE_ON_FORBIDDEN.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether 5xx responses carry the underlying error text. Off outside
+/// development so SQL, filesystem and upstream details stay in the logs.
+static EXPOSE_INTERNAL_ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// Set once at startup; enabled only when `APP_ENV=development`.
+pub fn set_expose_internal_errors(enabled: bool) {
+    EXPOSE_INTERNAL_ERRORS.store(enabled, Ordering::Relaxed);
+}
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("Not found")]
@@ -17,12 +35,42 @@ pub enum AppError {
     #[error("Forbidden")]
     Forbidden,
 
+    /// The resource exists but belongs to someone else. Rendered as 404 when
+    /// `HIDE_EXISTENCE_ON_FORBIDDEN` is on so ids cannot be probed, at the
+    /// cost of a less precise error for legitimate clients.
+    #[error("Forbidden")]
+    NotOwned,
+
+    /// The account is flagged `must_change_password`; only the auth routes
+    /// (including `/auth/change-password`) are served until it is cleared.
+    #[error("Password change required")]
+    PasswordChangeRequired,
+
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    /// 400 with a machine-readable `code` and one entry per problem found,
+    /// for payloads where a single message would hide which part is wrong.
+    #[error("Validation failed: {message}")]
+    Validation { message: String, details: Vec<String> },
+
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// 422: well-formed, but cannot be applied as sent (e.g. a reused
+    /// `Idempotency-Key` with a different body).
+    #[error("Unprocessable: {0}")]
+    Unprocessable(String),
+
+    /// 409 with a machine-readable `code` and structured `details`, for
+    /// conflicts the client can resolve itself (e.g. by revoking a device).
+    #[error("Conflict: {message}")]
+    ConflictDetails { message: String, code: &'static str, details: serde_json::Value },
+
+    /// 429 with a `Retry-After` header, from a rate-limited public route.
+    #[error("Too many requests")]
+    TooManyRequests { retry_after_secs: u64 },
+
     #[error("Internal server error")]
     Internal(#[from] anyhow::Error),
 
@@ -32,23 +80,160 @@ pub enum AppError {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, message) = match &self {
-            AppError::NotFound       => (StatusCode::NOT_FOUND,            self.to_string()),
-            AppError::Unauthorized   => (StatusCode::UNAUTHORIZED,         self.to_string()),
-            AppError::Forbidden      => (StatusCode::FORBIDDEN,            self.to_string()),
-            AppError::BadRequest(m)  => (StatusCode::BAD_REQUEST,          m.clone()),
-            AppError::Conflict(m)    => (StatusCode::CONFLICT,             m.clone()),
-            AppError::Internal(_)    => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".into()),
-            AppError::Database(_)    => (StatusCode::INTERNAL_SERVER_ERROR, "Database error".into()),
-        };
-
-        // Log 5xx errors
-        if status.is_server_error() {
-            tracing::error!(error = %self, "Server error");
-        }
+        let client_error = |status: StatusCode, message: String| (status, Json(json!({ "error": message }))).into_response();
 
-        (status, Json(json!({ "error": message }))).into_response()
+        match self {
+            AppError::NotFound       => client_error(StatusCode::NOT_FOUND,    self.to_string()),
+            AppError::Unauthorized   => client_error(StatusCode::UNAUTHORIZED, self.to_string()),
+            AppError::Forbidden      => client_error(StatusCode::FORBIDDEN,    self.to_string()),
+            AppError::NotOwned if HIDE_EXISTENCE_ON_FORBIDDEN.load(Ordering::Relaxed) => {
+                client_error(StatusCode::NOT_FOUND, AppError::NotFound.to_string())
+            }
+            AppError::NotOwned       => client_error(StatusCode::FORBIDDEN,    self.to_string()),
+            AppError::PasswordChangeRequired => client_error(StatusCode::FORBIDDEN, self.to_string()),
+            AppError::BadRequest(m)  => client_error(StatusCode::BAD_REQUEST,  m),
+            AppError::Validation { message, details } => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": message, "code": "validation_failed", "details": details })),
+            )
+                .into_response(),
+            AppError::Conflict(m)    => client_error(StatusCode::CONFLICT,     m),
+            AppError::Unprocessable(m) => client_error(StatusCode::UNPROCESSABLE_ENTITY, m),
+            AppError::ConflictDetails { message, code, details } => (
+                StatusCode::CONFLICT,
+                Json(json!({ "error": message, "code": code, "details": details })),
+            )
+                .into_response(),
+            AppError::TooManyRequests { retry_after_secs } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                Json(json!({ "error": "Too many requests", "code": "rate_limited" })),
+            )
+                .into_response(),
+            AppError::Internal(e)    => server_error("Internal server error", format!("{e:#}")),
+            AppError::Database(e)    => server_error("Database error", e.to_string()),
+        }
     }
 }
 
+/// 500 with a generic `message`. The full `detail` is logged under an id the
+/// client can quote; the response only carries it in development.
+fn server_error(message: &str, detail: String) -> Response {
+    let error_id = uuid::Uuid::new_v4().to_string();
+    tracing::error!(%error_id, error = %detail, "Server error");
+
+    let message = if EXPOSE_INTERNAL_ERRORS.load(Ordering::Relaxed) {
+        format!("{message}: {detail}")
+    } else {
+        message.to_string()
+    };
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": message, "error_id": error_id }))).into_response()
+}
+
 pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn render(err: AppError) -> (StatusCode, axum::http::HeaderMap, serde_json::Value) {
+        let response = err.into_response();
+        let (parts, body) = response.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (parts.status, parts.headers, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn client_errors_carry_their_message() {
+        let (status, _, body) = render(AppError::BadRequest("bad name".into())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, json!({ "error": "bad name" }));
+
+        let (status, _, body) = render(AppError::Unprocessable("key reused".into())).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body, json!({ "error": "key reused" }));
+    }
+
+    #[tokio::test]
+    async fn structured_errors_keep_code_and_details() {
+        let (status, _, body) = render(AppError::Validation {
+            message: "Invalid layout".into(),
+            details: vec!["slot 2 is empty".into()],
+        })
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, json!({ "error": "Invalid layout", "code": "validation_failed", "details": ["slot 2 is empty"] }));
+
+        let (status, _, body) = render(AppError::ConflictDetails {
+            message: "Device limit reached".into(),
+            code: "device_limit",
+            details: json!({ "limit": 3 }),
+        })
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body, json!({ "error": "Device limit reached", "code": "device_limit", "details": { "limit": 3 } }));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_sets_retry_after() {
+        let (status, headers, body) = render(AppError::TooManyRequests { retry_after_secs: 17 }).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(headers[header::RETRY_AFTER], "17");
+        assert_eq!(body, json!({ "error": "Too many requests", "code": "rate_limited" }));
+    }
+
+    /// Held by tests that flip `EXPOSE_INTERNAL_ERRORS`, which is global.
+    static EXPOSE_FLAG: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    #[tokio::test]
+    async fn server_errors_hide_the_cause_outside_development() {
+        let _flag = EXPOSE_FLAG.lock().await;
+
+        set_expose_internal_errors(false);
+        let (status, _, body) = render(AppError::Internal(anyhow::anyhow!("disk on fire"))).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"], "Internal server error");
+        assert!(body["error_id"].is_string());
+
+        set_expose_internal_errors(true);
+        let (_, _, body) = render(AppError::Internal(anyhow::anyhow!("disk on fire"))).await;
+        assert_eq!(body["error"], "Internal server error: disk on fire");
+        set_expose_internal_errors(false);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn production_database_errors_do_not_leak_sql(pool: crate::db::Db) {
+        let _flag = EXPOSE_FLAG.lock().await;
+        set_expose_internal_errors(false);
+
+        let err: AppError = sqlx::query("SELECT secret_column FROM no_such_table")
+            .execute(&pool)
+            .await
+            .unwrap_err()
+            .into();
+        let (status, _, body) = render(err).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"], "Database error");
+        let text = body.to_string();
+        assert!(!text.contains("no_such_table") && !text.contains("secret_column"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn not_owned_follows_the_hide_existence_toggle() {
+        // The only test touching the flag; it is left at its default.
+        set_hide_existence_on_forbidden(false);
+        let (status, _, body) = render(AppError::NotOwned).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body, json!({ "error": "Forbidden" }));
+
+        set_hide_existence_on_forbidden(true);
+        let (status, _, body) = render(AppError::NotOwned).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body, json!({ "error": "Not found" }));
+
+        // Plain Forbidden (wrong role, not someone else's row) never hides.
+        let (status, _, _) = render(AppError::Forbidden).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
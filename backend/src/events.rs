@@ -0,0 +1,58 @@
+//! In-process admin event bus, streamed to dashboards via `GET /admin/events`.
+//!
+//! Backed by a `tokio::sync::broadcast` channel: publishing never blocks, and
+//! a receiver that falls more than [`CHANNEL_CAPACITY`] events behind skips
+//! ahead (it sees a `Lagged` error) instead of holding producers back.
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+const CHANNEL_CAPACITY: usize = 256;
+
+pub type EventBus = broadcast::Sender<AdminEvent>;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AdminEvent {
+    PrefetchProgress {
+        processed: u64,
+        total: u64,
+        downloaded: u64,
+    },
+    PrefetchCompleted {
+        processed_ids: u64,
+        downloaded: u64,
+        already_cached: u64,
+    },
+    RetentionCompleted {
+        trigger: String,
+        deleted_counts: Option<serde_json::Value>,
+        error: Option<String>,
+    },
+    BreachLogCreated {
+        id: String,
+        severity: String,
+        title: String,
+    },
+}
+
+impl AdminEvent {
+    /// SSE `event:` name, matching the serialized `type` tag.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdminEvent::PrefetchProgress { .. } => "prefetch_progress",
+            AdminEvent::PrefetchCompleted { .. } => "prefetch_completed",
+            AdminEvent::RetentionCompleted { .. } => "retention_completed",
+            AdminEvent::BreachLogCreated { .. } => "breach_log_created",
+        }
+    }
+}
+
+pub fn channel() -> EventBus {
+    broadcast::channel(CHANNEL_CAPACITY).0
+}
+
+/// Fire-and-forget publish; having no subscribers is not an error.
+pub fn publish(bus: &EventBus, event: AdminEvent) {
+    let _ = bus.send(event);
+}
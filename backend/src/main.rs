@@ -1,9 +1,20 @@
 use std::net::SocketAddr;
 
-use axum::Router;
+use axum::{
+    http::{header, HeaderValue},
+    Router,
+};
 use tokio::net::TcpListener;
 use tower_cookies::CookieManagerLayer;
-use tower_http::{cors::CorsLayer, services::ServeDir, trace::TraceLayer};
+use tower_http::{
+    compression::{
+        predicate::{NotForContentType, Predicate, SizeAbove},
+        CompressionLayer,
+    },
+    services::ServeDir,
+    set_header::SetResponseHeaderLayer,
+    trace::TraceLayer,
+};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 mod auth;
@@ -11,11 +22,15 @@ mod compliance;
 mod config;
 mod db;
 mod errors;
+mod events;
 mod middleware;
 mod models;
+mod reminders;
 mod routes;
 mod services;
 mod state;
+#[cfg(test)]
+mod test_support;
 
 use state::AppState;
 
@@ -30,13 +45,15 @@ async fn main() -> anyhow::Result<()> {
     // ── Config ────────────────────────────────────────────────
     let config = config::Config::from_env()?;
     tracing::info!(env = %config.app_env, "Starting CarlsCalendar backend");
+    errors::set_hide_existence_on_forbidden(config.hide_existence_on_forbidden);
+    errors::set_expose_internal_errors(config.expose_internal_errors());
 
     // ── Database ──────────────────────────────────────────────
     let pool = db::connect(&config).await?;
     db::run_migrations(&pool).await?;
 
     // ── Seed admin account ────────────────────────────────────
-    auth::seed::seed_accounts(&pool).await?;
+    auth::seed::seed_accounts(&pool, &config).await?;
 
     // Ensure seeded/system pictogram files exist on disk (backed by a persistent volume).
     match services::pictograms::ensure_seeded_activity_assets(&pool).await {
@@ -50,10 +67,19 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    let app_state = AppState { pool, config };
+    // Record sizes of images uploaded before storage quotas existed.
+    match services::storage::backfill_image_sizes(&pool).await {
+        Ok(count) if count > 0 => tracing::info!(images = count, "Backfilled image sizes"),
+        Ok(_) => {}
+        Err(err) => tracing::warn!(error = %err, "Failed to backfill image sizes"),
+    }
+
+    let app_state = AppState { pool, config, events: events::channel() };
 
     // ── Background jobs ───────────────────────────────────────
     compliance::spawn_retention_cleanup(app_state.clone());
+    auth::sweep::spawn_token_sweep(app_state.clone());
+    reminders::spawn_reminder_scheduler(app_state.clone());
     services::pictograms::spawn_idle_prefetch_worker(app_state.clone());
 
     // Read address before moving config into state
@@ -64,19 +90,140 @@ async fn main() -> anyhow::Result<()> {
     )
     .parse()?;
 
+    // User uploads are never rendered as documents of this origin, whatever
+    // their bytes turn out to be.
+    let uploads = static_files("uploads", app_state.config.static_asset_max_age_seconds)?
+        .layer(SetResponseHeaderLayer::overriding(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static("default-src 'none'; sandbox"),
+        ))
+        .layer(SetResponseHeaderLayer::overriding(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        ));
+    let assets = static_files("assets", app_state.config.static_asset_max_age_seconds)?;
+
     // ── Router ────────────────────────────────────────────────
+    let cors = app_state.config.cors_policy().layer();
     let app = Router::new()
         .nest("/api/v1", routes::all_routes(app_state.clone()))
-        .nest_service("/uploads", ServeDir::new("uploads"))
-        .nest_service("/assets", ServeDir::new("assets"))
+        .nest_service("/uploads", uploads)
+        .nest_service("/assets", assets)
         .layer(CookieManagerLayer::new())   // must come before state
-        .layer(CorsLayer::permissive())     // tighten in production
+        .layer(cors)
+        .layer(compression_layer(&app_state.config))
         .layer(TraceLayer::new_for_http())
         .with_state(app_state);
     tracing::info!(%addr, "Listening");
 
     let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses back rate limiting when no proxy header is present.
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
+
+/// gzip/br negotiated from Accept-Encoding for responses of at least
+/// `compression_min_bytes`. Images are already compressed and SSE must stream
+/// unbuffered, so both are excluded. The layer adds `Vary: Accept-Encoding`.
+fn compression_layer(config: &config::Config) -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().compress_when(
+        SizeAbove::new(config.compression_min_bytes)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::SSE),
+    )
+}
+
+/// Files under `dir`, with `Cache-Control: public, max-age=<max_age_seconds>`.
+/// `ServeDir` already answers HEAD, Range (206) and If-Modified-Since (304);
+/// the max-age lets clients skip even that revalidation for a while.
+fn static_files(dir: impl AsRef<std::path::Path>, max_age_seconds: u64) -> anyhow::Result<Router> {
+    let cache_control = HeaderValue::from_str(&format!("public, max-age={max_age_seconds}"))?;
+    Ok(Router::new()
+        .fallback_service(ServeDir::new(dir))
+        .layer(SetResponseHeaderLayer::if_not_present(header::CACHE_CONTROL, cache_control)))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Method, Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+
+    /// A directory holding `2345.png` with the bytes `0123456789`.
+    fn pictogram_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("carlscalendar-static-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("2345.png"), b"0123456789").unwrap();
+        dir
+    }
+
+    fn request(method: Method, range: Option<&str>) -> Request<Body> {
+        let mut builder = Request::builder().method(method).uri("/2345.png");
+        if let Some(range) = range {
+            builder = builder.header(header::RANGE, range);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    /// `Content-Encoding` of `path` on a router with [`compression_layer`]
+    /// (threshold 1 KiB), requested with `accept_encoding`.
+    async fn encoding(path: &str, accept_encoding: Option<&str>) -> Option<String> {
+        let mut config = config::Config::for_tests();
+        config.compression_min_bytes = 1024;
+        let large = serde_json::json!({ "items": vec!["breakfast"; 400] });
+        let app: Router = Router::new()
+            .route("/large.json", axum::routing::get(move || async move { axum::Json(large) }))
+            .route("/small.json", axum::routing::get(|| async { axum::Json(serde_json::json!({ "ok": true })) }))
+            .route("/image.png", axum::routing::get(|| async { ([(header::CONTENT_TYPE, "image/png")], vec![0u8; 8192]) }))
+            .layer(compression_layer(&config));
+        let mut builder = Request::builder().uri(path);
+        if let Some(accept_encoding) = accept_encoding {
+            builder = builder.header(header::ACCEPT_ENCODING, accept_encoding);
+        }
+        let response = app.oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.headers().get(header::CONTENT_ENCODING).map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn large_json_is_compressed_as_negotiated() {
+        assert_eq!(encoding("/large.json", Some("gzip")).await.as_deref(), Some("gzip"));
+        assert_eq!(encoding("/large.json", Some("br")).await.as_deref(), Some("br"));
+        assert_eq!(encoding("/large.json", None).await, None);
+    }
+
+    #[tokio::test]
+    async fn small_responses_and_images_stay_identity() {
+        assert_eq!(encoding("/small.json", Some("gzip, br")).await, None);
+        assert_eq!(encoding("/image.png", Some("gzip, br")).await, None);
+    }
+
+    #[tokio::test]
+    async fn head_reports_the_file_without_a_body() {
+        let dir = pictogram_dir();
+        let response = static_files(&dir, 600).unwrap().oneshot(request(Method::HEAD, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "10");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "public, max-age=600");
+        assert!(response.headers().contains_key(header::LAST_MODIFIED));
+        assert!(to_bytes(response.into_body(), usize::MAX).await.unwrap().is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn ranged_get_returns_partial_content() {
+        let dir = pictogram_dir();
+        let response = static_files(&dir, 600).unwrap().oneshot(request(Method::GET, Some("bytes=2-5"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "public, max-age=600");
+        assert_eq!(&to_bytes(response.into_body(), usize::MAX).await.unwrap()[..], b"2345");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
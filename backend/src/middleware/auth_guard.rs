@@ -11,6 +11,7 @@ use axum::{
 use tower_cookies::Cookies;
 
 use crate::{
+    auth::{hash_token, SESSION_TOKEN_MATCH_SQL},
     errors::AppError,
     models::UserRole,
     state::AppState,
@@ -43,24 +44,30 @@ pub async fn require_auth(
     struct SessionRow {
         id:   String,
         role: Option<String>,
+        must_change_password: bool,
     }
 
-    let row = sqlx::query_as::<_, SessionRow>(
-        "SELECT u.id, u.role
+    let row = sqlx::query_as::<_, SessionRow>(&format!(
+        "SELECT u.id, u.role, u.must_change_password
          FROM user_sessions s
          JOIN users u ON u.id = s.user_id
-         WHERE s.token = ?
+         WHERE {SESSION_TOKEN_MATCH_SQL}
            AND s.expires_at > NOW()
            AND u.is_active = 1
            AND u.deleted_at IS NULL
-         LIMIT 1",
-    )
+         LIMIT 1"
+    ))
+    .bind(hash_token(&token))
     .bind(&token)
     .fetch_optional(&state.pool)
     .await
     .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?
     .ok_or(AppError::Unauthorized)?;
 
+    if row.must_change_password {
+        return Err(AppError::PasswordChangeRequired);
+    }
+
     let role = match row.role.as_deref().unwrap_or("") {
         "admin"  => UserRole::Admin,
         "child"  => UserRole::Child,
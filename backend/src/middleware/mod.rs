@@ -1,2 +1,3 @@
 pub mod auth_guard;
+pub mod rate_limit;
 pub mod role_guard;
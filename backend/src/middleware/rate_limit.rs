@@ -0,0 +1,191 @@
+//! Per-client fixed-window rate limiting for public routes.
+//!
+//! A [`RateLimiter`] counts requests per client IP in windows of a fixed
+//! length and rejects the excess with `429 Too Many Requests`. Counters live
+//! in memory, so each backend instance limits on its own and a restart resets
+//! them; that is enough to stop a single client from flooding a table.
+//!
+//! The client IP is the peer address of the connection. Forwarding headers
+//! are only believed behind `TRUSTED_PROXY_HOPS` reverse proxies, since any
+//! client can send them.
+//!
+//! Attach it to a router with
+//! `middleware::from_fn_with_state(limiter, limit_by_ip)`; handlers behind it
+//! can read the resolved address as `Extension<ClientIp>`.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::errors::AppError;
+
+/// Above this many tracked clients, expired windows are dropped on the next
+/// request so the map cannot grow without bound.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+pub struct RateLimiter {
+    /// Route group name, for the log line on rejection.
+    name: &'static str,
+    max_requests: u32,
+    window: Duration,
+    /// Reverse proxies in front of the server that append to `X-Forwarded-For`.
+    trusted_proxy_hops: usize,
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+/// The client address [`limit_by_ip`] resolved for the request.
+#[derive(Debug, Clone)]
+pub struct ClientIp(pub IpAddr);
+
+impl RateLimiter {
+    pub fn new(name: &'static str, max_requests: u32, window: Duration, trusted_proxy_hops: usize) -> Arc<Self> {
+        Arc::new(Self {
+            name,
+            max_requests,
+            window,
+            trusted_proxy_hops,
+            windows: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Count one request from `key`. `Err` carries the time until the
+    /// client's window resets.
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        if windows.len() > PRUNE_THRESHOLD {
+            windows.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+        }
+
+        let entry = windows.entry(key.to_string()).or_insert((now, 0));
+        if now.duration_since(entry.0) >= self.window {
+            *entry = (now, 0);
+        }
+        if entry.1 >= self.max_requests {
+            return Err(self.window.saturating_sub(now.duration_since(entry.0)));
+        }
+        entry.1 += 1;
+        Ok(())
+    }
+}
+
+/// The client's IP. Without trusted proxies that is the connection's peer
+/// address. Behind `trusted_proxy_hops` proxies it is the right-most
+/// `X-Forwarded-For` entry those proxies did not add (entries further left
+/// are client-supplied), falling back to `X-Real-IP` from the nearest proxy
+/// when the list is shorter than expected.
+pub fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>, trusted_proxy_hops: usize) -> Option<IpAddr> {
+    if trusted_proxy_hops == 0 {
+        return peer.map(|p| p.ip());
+    }
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let forwarded: Vec<&str> = header("x-forwarded-for")
+        .map(|v| v.split(',').map(str::trim).filter(|v| !v.is_empty()).collect())
+        .unwrap_or_default();
+    let from_forwarded = forwarded
+        .len()
+        .checked_sub(trusted_proxy_hops)
+        .and_then(|i| forwarded[i].parse().ok());
+    from_forwarded
+        .or_else(|| header("x-real-ip").and_then(|v| v.trim().parse().ok()))
+        .or_else(|| peer.map(|p| p.ip()))
+}
+
+/// Middleware: reject a client that exceeded `limiter`'s budget. A request
+/// whose address cannot be determined (the server was started without peer
+/// addresses) is let through unlimited rather than sharing one budget with
+/// every other such request.
+pub async fn limit_by_ip(
+    State(limiter): State<Arc<RateLimiter>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let peer = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|c| c.0);
+    let Some(ip) = client_ip(req.headers(), peer, limiter.trusted_proxy_hops) else {
+        tracing::warn!(limiter = limiter.name, "No client address; request not rate limited");
+        return Ok(next.run(req).await);
+    };
+
+    if let Err(retry_after) = limiter.check(&ip.to_string()) {
+        tracing::warn!(limiter = limiter.name, %ip, "Rate limit exceeded");
+        return Err(AppError::TooManyRequests {
+            retry_after_secs: retry_after.as_secs().max(1),
+        });
+    }
+    req.extensions_mut().insert(ClientIp(ip));
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    fn peer() -> Option<SocketAddr> {
+        Some("10.0.0.2:40000".parse().unwrap())
+    }
+
+    #[test]
+    fn allows_up_to_the_limit_then_rejects() {
+        let limiter = RateLimiter::new("test", 3, Duration::from_secs(60), 0);
+        for _ in 0..3 {
+            assert!(limiter.check("1.2.3.4").is_ok());
+        }
+        let retry_after = limiter.check("1.2.3.4").unwrap_err();
+        assert!(retry_after <= Duration::from_secs(60));
+        // Other clients have their own budget.
+        assert!(limiter.check("5.6.7.8").is_ok());
+    }
+
+    #[test]
+    fn window_resets_after_it_elapses() {
+        let limiter = RateLimiter::new("test", 1, Duration::from_millis(20), 0);
+        assert!(limiter.check("1.2.3.4").is_ok());
+        assert!(limiter.check("1.2.3.4").is_err());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(limiter.check("1.2.3.4").is_ok());
+    }
+
+    #[test]
+    fn forwarding_headers_are_ignored_without_trusted_proxies() {
+        let h = headers(&[("x-forwarded-for", "203.0.113.9"), ("x-real-ip", "203.0.113.9")]);
+        assert_eq!(client_ip(&h, peer(), 0), Some("10.0.0.2".parse().unwrap()));
+        assert_eq!(client_ip(&h, None, 0), None);
+    }
+
+    #[test]
+    fn trusted_proxy_takes_right_most_untrusted_hop() {
+        // The client prepended a fake entry; the proxy appended the real one.
+        let h = headers(&[("x-forwarded-for", "198.51.100.1, 203.0.113.9")]);
+        assert_eq!(client_ip(&h, peer(), 1), Some("203.0.113.9".parse().unwrap()));
+
+        let h = headers(&[("x-forwarded-for", "198.51.100.1, 203.0.113.9, 10.0.0.5")]);
+        assert_eq!(client_ip(&h, peer(), 2), Some("203.0.113.9".parse().unwrap()));
+    }
+
+    #[test]
+    fn short_or_invalid_forwarded_list_falls_back() {
+        let h = headers(&[("x-real-ip", "203.0.113.7")]);
+        assert_eq!(client_ip(&h, peer(), 1), Some("203.0.113.7".parse().unwrap()));
+
+        let h = headers(&[("x-forwarded-for", "not-an-ip")]);
+        assert_eq!(client_ip(&h, peer(), 1), Some("10.0.0.2".parse().unwrap()));
+    }
+}
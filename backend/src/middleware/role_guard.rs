@@ -1,5 +1,5 @@
-/// Role-based authorization guard.
-/// TODO: Wrap routes that require specific roles.
+//! Role-based authorization guard.
+//! TODO: Wrap routes that require specific roles.
 
 use axum::{
     extract::{Extension, Request},
@@ -1,9 +1,11 @@
 #![allow(dead_code)]
 
-use chrono::NaiveDateTime;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::errors::{AppError, AppResult};
+
 // ── Users ────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -38,6 +40,65 @@ impl std::fmt::Display for UserRole {
     }
 }
 
+// ── Notification preferences ─────────────────────────────────
+
+/// Categories of optional (non-transactional) email.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationKind {
+    ScheduleReminder,
+    WeeklyDigest,
+    ProductUpdate,
+}
+
+/// Opt-in flags stored in `users.notification_preferences`. Everything is off
+/// until the parent opts in; verification and password-reset mail are
+/// transactional and not governed by these flags.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NotificationPreferences {
+    pub schedule_reminders: bool,
+    pub weekly_digest:      bool,
+    pub product_updates:    bool,
+}
+
+impl NotificationPreferences {
+    pub const KEYS: [&'static str; 3] = ["schedule_reminders", "weekly_digest", "product_updates"];
+
+    /// Parse the stored JSON, falling back to defaults for NULL or bad data.
+    pub fn from_json(raw: Option<&str>) -> Self {
+        raw.and_then(|r| serde_json::from_str(r).ok()).unwrap_or_default()
+    }
+
+    /// Apply a partial update. Unknown keys and non-boolean values are rejected.
+    pub fn apply_patch(&mut self, patch: &serde_json::Map<String, serde_json::Value>) -> Result<(), String> {
+        for (key, value) in patch {
+            let Some(v) = value.as_bool() else {
+                return Err(format!("Preference '{key}' must be true or false"));
+            };
+            match key.as_str() {
+                "schedule_reminders" => self.schedule_reminders = v,
+                "weekly_digest"      => self.weekly_digest = v,
+                "product_updates"    => self.product_updates = v,
+                _ => {
+                    return Err(format!(
+                        "Unknown notification preference '{key}'. Allowed: {}",
+                        Self::KEYS.join(", ")
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn allows(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::ScheduleReminder => self.schedule_reminders,
+            NotificationKind::WeeklyDigest     => self.weekly_digest,
+            NotificationKind::ProductUpdate    => self.product_updates,
+        }
+    }
+}
+
 // ── Sessions ─────────────────────────────────────────────────
 
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -120,12 +181,64 @@ pub struct ScheduleActivityCard {
 
 // ── Schedule day assignments ──────────────────────────────────
 
+/// ISO day of the week, 1 = Monday … 7 = Sunday. Assignments store it in
+/// `content_json` and read it back as a signed integer, so conversions from
+/// `i8`/`i64` are checked instead of cast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "i64", into = "u8")]
+pub struct DayOfWeek(u8);
+
+impl DayOfWeek {
+    pub const MONDAY: DayOfWeek = DayOfWeek(1);
+
+    /// The weekday `date` falls on.
+    pub fn of(date: NaiveDate) -> Self {
+        DayOfWeek(date.weekday().number_from_monday() as u8)
+    }
+
+    pub fn from_i8(value: i8) -> Option<Self> {
+        Self::try_from(i64::from(value)).ok()
+    }
+
+    pub fn to_i8(self) -> i8 {
+        self.0 as i8
+    }
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+
+    /// Validate client input, as a 400 naming `field`.
+    pub fn parse(field: &str, value: i64) -> AppResult<Self> {
+        Self::try_from(value).map_err(|_| AppError::BadRequest(format!("{field} must be 1–7")))
+    }
+}
+
+impl TryFrom<i64> for DayOfWeek {
+    type Error = String;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        if (1..=7).contains(&value) {
+            Ok(DayOfWeek(value as u8))
+        } else {
+            Err(format!("day of week {value} is outside 1–7"))
+        }
+    }
+}
+
+impl From<DayOfWeek> for u8 {
+    fn from(day: DayOfWeek) -> u8 {
+        day.0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ScheduleDayAssignment {
     pub id:          Uuid,
     pub schedule_id: Uuid,
     pub child_id:    Uuid,
-    pub day_of_week: u8, // 1=Mon … 7=Sun
+    #[sqlx(try_from = "i64")]
+    pub day_of_week: DayOfWeek,
     pub created_at:  NaiveDateTime,
 }
 
@@ -139,3 +252,36 @@ pub struct QrToken {
     pub created_at:    NaiveDateTime,
     pub is_active:     bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_of_week_round_trips_through_i8_and_json() {
+        for n in 1..=7i8 {
+            let day = DayOfWeek::from_i8(n).unwrap();
+            assert_eq!(day.to_i8(), n);
+            assert_eq!(day.get(), n as u8);
+            assert_eq!(serde_json::to_value(day).unwrap(), serde_json::json!(n));
+            assert_eq!(serde_json::from_value::<DayOfWeek>(serde_json::json!(n)).unwrap(), day);
+        }
+        // 2026-03-02 is a Monday, 2026-03-08 a Sunday.
+        assert_eq!(DayOfWeek::of(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()), DayOfWeek::MONDAY);
+        assert_eq!(DayOfWeek::of(NaiveDate::from_ymd_opt(2026, 3, 8).unwrap()).get(), 7);
+    }
+
+    #[test]
+    fn day_of_week_rejects_out_of_range_values() {
+        for n in [0i8, 8, -1, i8::MIN, i8::MAX] {
+            assert_eq!(DayOfWeek::from_i8(n), None, "{n}");
+        }
+        // 257 would wrap to Monday if truncated to u8 before the check.
+        for n in [0i64, 8, 257] {
+            assert!(serde_json::from_value::<DayOfWeek>(serde_json::json!(n)).is_err(), "{n}");
+            let err = DayOfWeek::parse("day_of_week", n).unwrap_err();
+            assert!(matches!(&err, AppError::BadRequest(m) if m == "day_of_week must be 1–7"), "{err:?}");
+        }
+        assert!(DayOfWeek::parse("day_of_week", 7).is_ok());
+    }
+}
@@ -0,0 +1,476 @@
+//! Opt-in reminder emails and the outbox they are delivered through.
+//!
+//! Each tick the scheduler looks at enabled `child_reminders`, and for those
+//! whose `send_at` has passed in the parent's timezone and that have not run
+//! yet on that local date, queues an email in `email_outbox`. Pending outbox
+//! rows are then claimed one by one and sent via
+//! [`crate::auth::email::send_notification_email`], which re-checks the
+//! parent's `schedule_reminders` preference at send time.
+//!
+//! The time of day is compared on the parent's local wall clock, so a
+//! `send_at` that falls in a DST gap simply goes out on the first tick after
+//! the jump, and a repeated hour does not send twice (`last_sent_on` is a
+//! local date).
+
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::{
+    auth::email,
+    config::Config,
+    db::Db,
+    models::{NotificationKind, NotificationPreferences},
+    routes::{day_plan, DayPlan},
+    state::AppState,
+};
+
+/// Sends attempted per outbox row before it is marked `failed`.
+const MAX_SEND_ATTEMPTS: i32 = 5;
+/// Outbox rows sent per tick.
+const OUTBOX_BATCH_SIZE: i64 = 100;
+/// A row claimed for sending longer ago than this is assumed abandoned by a
+/// crashed sender and may be claimed again.
+const OUTBOX_CLAIM_LEASE_MINUTES: i64 = 10;
+/// Delivered/skipped/failed outbox rows are kept this long.
+const OUTBOX_KEEP_DAYS: i64 = 30;
+
+const OUTBOX_KIND_SCHEDULE_REMINDER: &str = "schedule_reminder";
+
+pub fn spawn_reminder_scheduler(state: AppState) {
+    if !state.config.reminders_enabled {
+        tracing::info!("Reminder scheduler disabled");
+        return;
+    }
+
+    let minutes = state.config.reminders_interval_minutes;
+    tracing::info!(minutes, "Reminder scheduler started");
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(minutes.saturating_mul(60)));
+        loop {
+            ticker.tick().await;
+            match run_reminders(&state.pool, &state.config, Utc::now()).await {
+                Ok(counts) => tracing::debug!(?counts, "Reminder tick finished"),
+                Err(err) => tracing::error!(error = %err, "Reminder tick failed"),
+            }
+        }
+    });
+}
+
+/// One scheduler tick at `now`: queue due reminders, deliver the outbox and
+/// prune old outbox rows. Taking `now` as a parameter keeps the timezone
+/// logic independent of the wall clock.
+pub async fn run_reminders(pool: &Db, config: &Config, now: DateTime<Utc>) -> anyhow::Result<BTreeMap<String, u64>> {
+    let mut counts = BTreeMap::new();
+    counts.insert("queued".to_string(), queue_due_reminders(pool, config, now).await?);
+
+    let (sent, skipped, failed) = deliver_outbox(pool, config).await?;
+    counts.insert("sent".to_string(), sent);
+    counts.insert("skipped".to_string(), skipped);
+    counts.insert("failed".to_string(), failed);
+
+    let pruned = sqlx::query(
+        "DELETE FROM email_outbox
+         WHERE status IN ('sent', 'skipped', 'failed') AND created_at < UTC_TIMESTAMP() - INTERVAL ? DAY",
+    )
+    .bind(OUTBOX_KEEP_DAYS)
+    .execute(pool)
+    .await?
+    .rows_affected();
+    counts.insert("pruned".to_string(), pruned);
+
+    Ok(counts)
+}
+
+#[derive(sqlx::FromRow)]
+struct DueReminderRow {
+    id: String,
+    child_id: String,
+    parent_id: String,
+    schedule_id: Option<String>,
+    kind: String,
+    send_at: String,
+    last_sent_on: Option<NaiveDate>,
+    child_name: String,
+    timezone: String,
+    notification_preferences: Option<String>,
+}
+
+/// `now` on the wall clock of the parent's `timezone`, or of `fallback` when
+/// that is not a known zone.
+fn parent_local_now(now: DateTime<Utc>, timezone: &str, fallback: Tz) -> chrono::NaiveDateTime {
+    let tz = timezone.trim().parse::<Tz>().unwrap_or(fallback);
+    now.with_timezone(&tz).naive_local()
+}
+
+/// Whether a reminder set for `send_at` should run at `local_now`, given the
+/// local date it last ran on.
+fn reminder_is_due(send_at: NaiveTime, last_sent_on: Option<NaiveDate>, local_now: chrono::NaiveDateTime) -> bool {
+    local_now.time() >= send_at && last_sent_on.is_none_or(|d| d < local_now.date())
+}
+
+async fn queue_due_reminders(pool: &Db, config: &Config, now: DateTime<Utc>) -> anyhow::Result<u64> {
+    let rows = sqlx::query_as::<_, DueReminderRow>(
+        "SELECT r.id, r.child_id, cp.parent_id, r.schedule_id, CAST(r.kind AS CHAR) AS kind,
+                r.send_at, r.last_sent_on, cp.display_name AS child_name, u.timezone,
+                CAST(u.notification_preferences AS CHAR) AS notification_preferences
+         FROM child_reminders r
+         JOIN child_profiles cp ON cp.id = r.child_id AND cp.deleted_at IS NULL
+         JOIN users u ON u.id = cp.parent_id
+         WHERE r.is_enabled = 1
+           AND u.is_active = 1
+           AND u.deleted_at IS NULL
+           AND u.email IS NOT NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut queued = 0;
+    for row in rows {
+        let prefs = NotificationPreferences::from_json(row.notification_preferences.as_deref());
+        if !prefs.allows(NotificationKind::ScheduleReminder) {
+            continue;
+        }
+        let Ok(send_at) = NaiveTime::parse_from_str(&row.send_at, "%H:%M") else {
+            tracing::warn!(reminder_id = %row.id, "Ignoring reminder with malformed send_at");
+            continue;
+        };
+        let local_now = parent_local_now(now, &row.timezone, config.app_default_timezone);
+        if !reminder_is_due(send_at, row.last_sent_on, local_now) {
+            continue;
+        }
+        let today = local_now.date();
+
+        // Claim the local date first so overlapping ticks cannot both send.
+        let claimed = sqlx::query(
+            "UPDATE child_reminders
+             SET last_sent_on = ?
+             WHERE id = ? AND (last_sent_on IS NULL OR last_sent_on < ?)",
+        )
+        .bind(today)
+        .bind(&row.id)
+        .bind(today)
+        .execute(pool)
+        .await?
+        .rows_affected();
+        if claimed == 0 {
+            continue;
+        }
+
+        let message = match compose(pool, config, &row, today).await {
+            Ok(message) => message,
+            Err(err) => {
+                tracing::warn!(reminder_id = %row.id, error = %err, "Unable to build reminder");
+                continue;
+            }
+        };
+        if let Some((subject, body)) = message {
+            enqueue(pool, &row.parent_id, OUTBOX_KIND_SCHEDULE_REMINDER, &subject, &body).await?;
+            queued += 1;
+        }
+    }
+    Ok(queued)
+}
+
+/// Subject and body for a due reminder, or `None` when there is nothing to
+/// tell the parent today.
+async fn compose(
+    pool: &Db,
+    config: &Config,
+    row: &DueReminderRow,
+    today: NaiveDate,
+) -> anyhow::Result<Option<(String, String)>> {
+    let matches_filter = |plan: &Option<DayPlan>| match (&row.schedule_id, plan) {
+        (None, _) => true,
+        (Some(wanted), Some(plan)) => &plan.schedule_id == wanted,
+        (Some(_), None) => false,
+    };
+    let child = &row.child_name;
+
+    match row.kind.as_str() {
+        "daily_summary" => {
+            let plan = day_plan(pool, &row.child_id, today, config).await?;
+            if !matches_filter(&plan) {
+                return Ok(None);
+            }
+            let Some(plan) = plan else {
+                return Ok(None);
+            };
+            let mut lines = String::new();
+            for (start_time, title) in &plan.cards {
+                lines.push_str(&format!("  {start_time}  {title}\n"));
+            }
+            Ok(Some((
+                format!("Today's routine for {child} — Carls Calendar"),
+                format!(
+                    "Hi,\n\nToday ({today}) {child} follows \"{}\":\n\n{lines}\nCarls Calendar",
+                    plan.schedule_name
+                ),
+            )))
+        }
+        "upcoming_change" => {
+            let Some(tomorrow) = today.succ_opt() else {
+                return Ok(None);
+            };
+            let current = day_plan(pool, &row.child_id, today, config).await?;
+            let next = day_plan(pool, &row.child_id, tomorrow, config).await?;
+            let same = current.as_ref().map(|p| &p.schedule_id) == next.as_ref().map(|p| &p.schedule_id);
+            if same || !(matches_filter(&current) || matches_filter(&next)) {
+                return Ok(None);
+            }
+            let describe = |plan: &Option<DayPlan>| match plan {
+                Some(p) => format!("\"{}\"", p.schedule_name),
+                None => "no schedule".to_string(),
+            };
+            Ok(Some((
+                format!("{child}'s schedule changes tomorrow — Carls Calendar"),
+                format!(
+                    "Hi,\n\nTomorrow ({tomorrow}) {child} has {} instead of today's {}.\n\nCarls Calendar",
+                    describe(&next),
+                    describe(&current)
+                ),
+            )))
+        }
+        other => anyhow::bail!("unknown reminder kind '{other}'"),
+    }
+}
+
+/// Queue an optional email for `user_id`; it is sent on a later tick.
+pub async fn enqueue(pool: &Db, user_id: &str, kind: &str, subject: &str, body: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO email_outbox (id, user_id, kind, subject, body)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(user_id)
+    .bind(kind)
+    .bind(subject)
+    .bind(body)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct OutboxRow {
+    id: String,
+    user_id: String,
+    kind: String,
+    subject: String,
+    body: String,
+    attempts: i32,
+}
+
+/// Claim an outbox row for this sender: `pending`, or `sending` with an
+/// expired lease. Only one concurrent caller gets `true`.
+async fn claim_outbox_row(pool: &Db, id: &str) -> anyhow::Result<bool> {
+    let claimed = sqlx::query(
+        "UPDATE email_outbox
+         SET status = 'sending', claimed_at = UTC_TIMESTAMP()
+         WHERE id = ?
+           AND (status = 'pending'
+                OR (status = 'sending' AND claimed_at < UTC_TIMESTAMP() - INTERVAL ? MINUTE))",
+    )
+    .bind(id)
+    .bind(OUTBOX_CLAIM_LEASE_MINUTES)
+    .execute(pool)
+    .await?
+    .rows_affected();
+    Ok(claimed == 1)
+}
+
+/// Send pending outbox rows. Returns `(sent, skipped, failed)`; recipients
+/// that opted out in the meantime or have no SMTP configured count as skipped.
+/// Each row is claimed before it is sent, so overlapping ticks (or several
+/// backend instances) never deliver one row twice.
+async fn deliver_outbox(pool: &Db, config: &Config) -> anyhow::Result<(u64, u64, u64)> {
+    let rows = sqlx::query_as::<_, OutboxRow>(
+        "SELECT id, user_id, kind, subject, body, attempts
+         FROM email_outbox
+         WHERE status = 'pending'
+            OR (status = 'sending' AND claimed_at < UTC_TIMESTAMP() - INTERVAL ? MINUTE)
+         ORDER BY created_at
+         LIMIT ?",
+    )
+    .bind(OUTBOX_CLAIM_LEASE_MINUTES)
+    .bind(OUTBOX_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    let (mut sent, mut skipped, mut failed) = (0, 0, 0);
+    for row in rows {
+        if !claim_outbox_row(pool, &row.id).await? {
+            continue;
+        }
+        let outcome = match row.kind.as_str() {
+            OUTBOX_KIND_SCHEDULE_REMINDER => {
+                let kind = NotificationKind::ScheduleReminder;
+                email::send_notification_email(config, pool, &row.user_id, kind, &row.subject, &row.body).await
+            }
+            other => Err(anyhow::anyhow!("unknown outbox kind '{other}'").into()),
+        };
+        let (status, error) = match outcome {
+            Ok(true) => {
+                sent += 1;
+                ("sent", None)
+            }
+            Ok(false) => {
+                skipped += 1;
+                ("skipped", None)
+            }
+            Err(err) if row.attempts + 1 >= MAX_SEND_ATTEMPTS => {
+                failed += 1;
+                ("failed", Some(err.to_string()))
+            }
+            Err(err) => ("pending", Some(err.to_string())),
+        };
+        sqlx::query(
+            "UPDATE email_outbox
+             SET status = ?,
+                 attempts = attempts + 1,
+                 claimed_at = NULL,
+                 last_error = ?,
+                 sent_at = IF(? = 'sent', UTC_TIMESTAMP(), sent_at)
+             WHERE id = ?",
+        )
+        .bind(status)
+        .bind(error)
+        .bind(status)
+        .bind(&row.id)
+        .execute(pool)
+        .await?;
+    }
+    Ok((sent, skipped, failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::UserRole, test_support};
+
+    fn utc(raw: &str) -> DateTime<Utc> {
+        raw.parse().unwrap()
+    }
+
+    fn time(raw: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(raw, "%H:%M").unwrap()
+    }
+
+    fn date(raw: &str) -> NaiveDate {
+        raw.parse().unwrap()
+    }
+
+    fn due_at(now: &str, timezone: &str, send_at: &str, last_sent_on: Option<&str>) -> bool {
+        let local_now = parent_local_now(utc(now), timezone, chrono_tz::UTC);
+        reminder_is_due(time(send_at), last_sent_on.map(date), local_now)
+    }
+
+    #[test]
+    fn due_once_send_at_has_passed() {
+        assert!(!due_at("2026-03-02T06:59:00Z", "UTC", "07:00", None));
+        assert!(due_at("2026-03-02T07:00:00Z", "UTC", "07:00", None));
+        assert!(due_at("2026-03-02T23:59:00Z", "UTC", "07:00", Some("2026-03-01")));
+    }
+
+    #[test]
+    fn not_due_again_on_the_same_local_day() {
+        assert!(!due_at("2026-03-02T08:00:00Z", "UTC", "07:00", Some("2026-03-02")));
+        assert!(due_at("2026-03-03T07:00:00Z", "UTC", "07:00", Some("2026-03-02")));
+    }
+
+    #[test]
+    fn send_at_in_a_dst_gap_goes_out_after_the_jump() {
+        // Copenhagen skips 02:00–03:00 on 2026-03-29.
+        assert!(!due_at("2026-03-29T00:59:00Z", "Europe/Copenhagen", "02:30", Some("2026-03-28")));
+        assert!(due_at("2026-03-29T01:00:00Z", "Europe/Copenhagen", "02:30", Some("2026-03-28")));
+    }
+
+    #[test]
+    fn repeated_hour_does_not_send_twice() {
+        // Copenhagen repeats 02:00–03:00 on 2026-10-25.
+        assert!(due_at("2026-10-25T00:30:00Z", "Europe/Copenhagen", "02:30", Some("2026-10-24")));
+        assert!(!due_at("2026-10-25T01:30:00Z", "Europe/Copenhagen", "02:30", Some("2026-10-25")));
+    }
+
+    #[test]
+    fn uses_the_parents_timezone() {
+        // 19:00 UTC on the 15th is 08:00 on the 16th in Auckland (UTC+13).
+        assert!(due_at("2026-01-15T19:00:00Z", "Pacific/Auckland", "07:30", Some("2026-01-15")));
+        assert!(!due_at("2026-01-15T19:00:00Z", "UTC", "20:00", Some("2026-01-14")));
+        // 12:00 UTC is 07:00 in New York (UTC-5), before an 07:30 reminder.
+        assert!(!due_at("2026-01-15T12:00:00Z", "America/New_York", "07:30", None));
+        // Unknown zones fall back to the default.
+        assert!(due_at("2026-01-15T12:00:00Z", "Not/AZone", "07:30", None));
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn reminders_follow_the_child_to_a_new_parent(pool: Db) {
+        let old_parent = test_support::user(&pool, UserRole::Parent).await;
+        let new_parent = test_support::user(&pool, UserRole::Parent).await;
+        for (parent, timezone) in [(&old_parent, "UTC"), (&new_parent, "Pacific/Auckland")] {
+            sqlx::query("UPDATE users SET timezone = ?, notification_preferences = ? WHERE id = ?")
+                .bind(timezone)
+                .bind(r#"{"schedule_reminders": true}"#)
+                .bind(&parent.user_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        let child = test_support::child(&pool, Some(&old_parent.user_id)).await;
+        sqlx::query("INSERT INTO child_reminders (id, child_id, kind, send_at) VALUES (?, ?, 'daily_summary', '07:00')")
+            .bind(Uuid::new_v4().to_string())
+            .bind(&child)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // The child moves, together with a Monday schedule.
+        sqlx::query("UPDATE child_profiles SET parent_id = ? WHERE id = ?")
+            .bind(&new_parent.user_id)
+            .bind(&child)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let active = serde_json::json!({ "schedule": { "status": "active" } });
+        let schedule = test_support::schedule(&pool, &new_parent, "Week", active).await;
+        let monday = serde_json::json!({ "day_of_week": 1, "persistent": true });
+        test_support::assign(&pool, &new_parent, &child, &schedule, monday).await;
+
+        // Sunday evening in UTC is Monday morning in Auckland, so the reminder
+        // is only due if it runs on the new parent's clock.
+        let queued = queue_due_reminders(&pool, &Config::for_tests(), utc("2026-03-01T20:00:00Z")).await.unwrap();
+        assert_eq!(queued, 1);
+        let recipients: Vec<String> = sqlx::query_scalar("SELECT user_id FROM email_outbox")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(recipients, vec![new_parent.user_id]);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn outbox_row_is_claimed_only_once(pool: Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        enqueue(&pool, &parent.user_id, OUTBOX_KIND_SCHEDULE_REMINDER, "Subject", "Body").await.unwrap();
+        let id: String = sqlx::query_scalar("SELECT id FROM email_outbox WHERE user_id = ?")
+            .bind(&parent.user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        let (first, second) = tokio::join!(claim_outbox_row(&pool, &id), claim_outbox_row(&pool, &id));
+        assert!(first.unwrap() ^ second.unwrap());
+
+        // An abandoned claim can be taken over once the lease has run out.
+        sqlx::query("UPDATE email_outbox SET claimed_at = UTC_TIMESTAMP() - INTERVAL 1 HOUR WHERE id = ?")
+            .bind(&id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(claim_outbox_row(&pool, &id).await.unwrap());
+    }
+}
@@ -2,18 +2,28 @@
 //! All routes in this module require the `Admin` role (enforced via the
 //! `require_admin` role-guard applied in `all_routes`).
 
+use std::convert::Infallible;
+
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::{get, put},
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post, put},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
 use uuid::Uuid;
 
+use super::validate;
 use crate::{
+    config::{Config, CorsPolicy},
     errors::{AppError, AppResult},
     middleware::{auth_guard::AuthUser, role_guard::require_admin},
+    services::pictograms,
     state::AppState,
 };
 
@@ -25,6 +35,12 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/admin/users",         get(list_users))
         .route("/admin/users/{id}",     put(update_user).delete(delete_user))
+        .route("/admin/children/{id}/parent", put(reassign_child_parent))
+        .route("/admin/children/{id}/week/{iso_week}", get(preview_child_week))
+        .route("/admin/events",        get(event_stream))
+        .route("/admin/system/arasaac-health", get(arasaac_health))
+        .route("/admin/system/security", get(security_posture))
+        .route("/admin/visual-documents/activity-library/import", post(import_activity_library))
         .route("/admin/templates",     get(list_templates).post(create_template))
         .route("/admin/templates/{id}", put(update_template).delete(delete_template))
         .route_layer(admin_guard)
@@ -42,6 +58,14 @@ struct UserRow {
     parent_id:   Option<String>,
     is_verified: bool,
     is_active:   bool,
+    last_login_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(sqlx::FromRow, Serialize)]
+struct ChildRow {
+    id:           String,
+    parent_id:    Option<String>,
+    display_name: String,
 }
 
 #[derive(sqlx::FromRow, Serialize)]
@@ -50,16 +74,30 @@ struct TemplateRow {
     owner_id: String,
     name:    String,
     status:  String,
+    created_by: Option<String>,
+    updated_by: Option<String>,
 }
 
 // ── Request bodies ───────────────────────────────────────────
 
+#[derive(Deserialize)]
+struct ListUsersQuery {
+    /// Only accounts without a login in this many days (counting from
+    /// account creation for those that never logged in).
+    dormant_days: Option<u32>,
+}
+
 #[derive(Deserialize)]
 struct UpdateUserBody {
     is_active: Option<bool>,
     role:      Option<String>,
 }
 
+#[derive(Deserialize)]
+struct ReassignParentBody {
+    parent_id: String,
+}
+
 #[derive(Deserialize)]
 struct CreateTemplateBody {
     name: String,
@@ -70,20 +108,54 @@ struct UpdateTemplateBody {
     name: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct ImportActivityCardBody {
+    label:          String,
+    language:       String,
+    arasaac_id:     i32,
+    category:       Option<String>,
+    priority_order: Option<i32>,
+}
+
+#[derive(Serialize)]
+struct ImportActivityCardResult {
+    index:  usize,
+    /// `created`, `updated` or `error`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id:     Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error:  Option<String>,
+}
+
+#[derive(Serialize)]
+struct ImportActivityLibraryResponse {
+    created: usize,
+    updated: usize,
+    failed:  usize,
+    results: Vec<ImportActivityCardResult>,
+}
+
 // ── Handlers ─────────────────────────────────────────────────
 
 async fn list_users(
     State(state): State<AppState>,
     Extension(_admin): Extension<AuthUser>,
+    Query(q): Query<ListUsersQuery>,
 ) -> AppResult<Json<Vec<UserRow>>> {
     let pool = &state.pool;
+    // Child accounts never log in themselves, so they are never dormant.
     let rows: Vec<UserRow> = sqlx::query_as::<_, UserRow>(
         "SELECT id, email, username, role, language, parent_id,
-                is_verified, is_active
+                is_verified, is_active, last_login_at
          FROM users
          WHERE deleted_at IS NULL
+           AND (? IS NULL OR (role <> 'child'
+                AND COALESCE(last_login_at, created_at) < UTC_TIMESTAMP() - INTERVAL ? DAY))
          ORDER BY role, username",
     )
+    .bind(q.dormant_days)
+    .bind(q.dormant_days)
     .fetch_all(pool).await?;
     Ok(Json(rows))
 }
@@ -116,7 +188,7 @@ async fn update_user(
     }
     let row: UserRow = sqlx::query_as::<_, UserRow>(
         "SELECT id, email, username, role, language, parent_id,
-                is_verified, is_active
+                is_verified, is_active, last_login_at
          FROM users WHERE id = ?",
     )
     .bind(&id).fetch_one(pool).await?;
@@ -140,11 +212,182 @@ async fn delete_user(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Move a child profile to another parent account.
+///
+/// Assignments and documents for the child follow it, as do schedules bound to
+/// this child that no other child uses. Assignments of schedules that stay
+/// with the previous parent are removed, since the new parent cannot open
+/// them. Paired devices and QR codes are revoked so the previous parent's
+/// pairings stop working.
+async fn reassign_child_parent(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthUser>,
+    Path(child_id): Path<String>,
+    Json(body): Json<ReassignParentBody>,
+) -> AppResult<Json<ChildRow>> {
+    let pool = &state.pool;
+    let new_parent_id = body.parent_id.trim();
+
+    let mut tx = pool.begin().await?;
+
+    let child: ChildRow = sqlx::query_as::<_, ChildRow>(
+        "SELECT id, parent_id, display_name FROM child_profiles
+         WHERE id = ? AND deleted_at IS NULL
+         FOR UPDATE",
+    )
+    .bind(&child_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let parent_ok: bool = sqlx::query_scalar(
+        "SELECT EXISTS(
+            SELECT 1 FROM users
+            WHERE id = ? AND role = 'parent' AND is_active = 1 AND deleted_at IS NULL
+        )",
+    )
+    .bind(new_parent_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if !parent_ok {
+        return Err(AppError::BadRequest("Target parent must be an active parent account".into()));
+    }
+    if child.parent_id.as_deref() == Some(new_parent_id) {
+        return Err(AppError::BadRequest("Child already belongs to this parent".into()));
+    }
+
+    sqlx::query("UPDATE child_profiles SET parent_id = ? WHERE id = ?")
+        .bind(new_parent_id)
+        .bind(&child_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Schedules bound to this child move with it unless another child still uses them.
+    let moved_schedules = sqlx::query(
+        "UPDATE visual_support_documents_templates t
+         SET t.owner_id = ?
+         WHERE t.document_type = 'WEEKLY_SCHEDULE'
+           AND t.owner_id = ?
+           AND CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) = ?
+           AND NOT EXISTS (
+               SELECT 1 FROM visual_support_documents d
+               WHERE d.template_id = t.id AND d.child_id <> ?
+           )",
+    )
+    .bind(new_parent_id)
+    .bind(&child.parent_id)
+    .bind(&child_id)
+    .bind(&child_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let removed_assignments = sqlx::query(
+        "DELETE d FROM visual_support_documents d
+         JOIN visual_support_documents_templates t ON t.id = d.template_id
+         WHERE d.child_id = ?
+           AND d.document_type = 'WEEKLY_SCHEDULE'
+           AND t.owner_id <> ?",
+    )
+    .bind(&child_id)
+    .bind(new_parent_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let moved_documents = sqlx::query("UPDATE visual_support_documents SET owner_id = ? WHERE child_id = ?")
+        .bind(new_parent_id)
+        .bind(&child_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    let revoked_devices = sqlx::query(
+        "UPDATE child_device_tokens SET revoked_at = NOW() WHERE child_id = ? AND revoked_at IS NULL",
+    )
+    .bind(&child_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query("UPDATE qr_tokens SET is_active = 0 WHERE child_id = ?")
+        .bind(&child_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO admin_audit_logs (id, actor_user_id, action, target_table, target_id, details)
+         VALUES (?, ?, 'child_reassign_parent', 'child_profiles', ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&admin.user_id)
+    .bind(&child_id)
+    .bind(serde_json::json!({
+        "from_parent_id": child.parent_id,
+        "to_parent_id": new_parent_id,
+        "moved_schedules": moved_schedules,
+        "moved_documents": moved_documents,
+        "removed_assignments": removed_assignments,
+        "revoked_devices": revoked_devices,
+    }).to_string())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(ChildRow {
+        id: child.id,
+        parent_id: Some(new_parent_id.to_string()),
+        display_name: child.display_name,
+    }))
+}
+
+/// Resolve a child's week exactly as the parent calendar would, without the
+/// ownership check. Support tooling only; every preview is audited.
+async fn preview_child_week(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthUser>,
+    Path((child_id, iso_week)): Path<(String, String)>,
+) -> AppResult<Json<super::calendar::WeekResponse>> {
+    let pool = &state.pool;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ?)")
+        .bind(&child_id)
+        .fetch_one(pool)
+        .await?;
+    if !exists {
+        return Err(AppError::NotFound);
+    }
+
+    let week = super::calendar::load_week_for_child(
+        pool,
+        &child_id,
+        &iso_week,
+        &state.config,
+        false,
+    )
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO admin_audit_logs (id, actor_user_id, action, target_table, target_id, details)
+         VALUES (?, ?, 'child_week_preview', 'child_profiles', ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&admin.user_id)
+    .bind(&child_id)
+    .bind(serde_json::json!({ "iso_week": iso_week }).to_string())
+    .execute(pool)
+    .await?;
+
+    Ok(Json(week))
+}
+
 async fn create_template(
     State(state): State<AppState>,
     Extension(admin): Extension<AuthUser>,
     Json(body): Json<CreateTemplateBody>,
 ) -> AppResult<(StatusCode, Json<TemplateRow>)> {
+    let name = validate::title("name", &body.name)?;
     let pool = &state.pool;
     let id = Uuid::new_v4().to_string();
     let metadata_json = serde_json::to_string(&serde_json::json!({
@@ -164,12 +407,14 @@ async fn create_template(
 
     sqlx::query(
         "INSERT INTO visual_support_documents_templates
-            (id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json)
-         VALUES (?, ?, ?, NULL, 'DAILY_SCHEDULE', 'CUSTOM', 'en', 0, JSON_OBJECT())",
+            (id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by)
+         VALUES (?, ?, ?, NULL, 'DAILY_SCHEDULE', 'CUSTOM', 'en', 0, JSON_OBJECT(), ?, ?)",
     )
     .bind(&id)
     .bind(&admin.user_id)
-    .bind(&body.name)
+    .bind(&name)
+    .bind(&admin.user_id)
+    .bind(&admin.user_id)
     .execute(pool)
     .await?;
 
@@ -190,7 +435,9 @@ async fn create_template(
             id,
             COALESCE(owner_id, '') AS owner_id,
             name,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status
+            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
+            created_by,
+            updated_by
          FROM visual_support_documents_templates
          WHERE id = ?",
     )
@@ -208,7 +455,9 @@ async fn list_templates(
                         id,
                         COALESCE(owner_id, '') AS owner_id,
                         name,
-                    CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status
+                    CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
+                    created_by,
+                    updated_by
                  FROM visual_support_documents_templates
                  WHERE document_type = 'WEEKLY_SCHEDULE'
                      AND (is_system = 1 OR IFNULL(JSON_EXTRACT(metadata_json, '$.schedule.is_template') = true, 0) = 1)
@@ -221,7 +470,7 @@ async fn list_templates(
 
 async fn update_template(
     State(state): State<AppState>,
-    Extension(_admin): Extension<AuthUser>,
+    Extension(admin): Extension<AuthUser>,
     Path(id): Path<String>,
     Json(body): Json<UpdateTemplateBody>,
 ) -> AppResult<Json<TemplateRow>> {
@@ -238,9 +487,9 @@ async fn update_template(
     .bind(&id).fetch_one(pool).await?;
     if !exists { return Err(AppError::NotFound); }
 
-    if let Some(name) = &body.name {
-        sqlx::query("UPDATE visual_support_documents_templates SET name = ? WHERE id = ?")
-            .bind(name).bind(&id).execute(pool).await?;
+    if let Some(name) = validate::optional_title("name", body.name.as_deref())? {
+        sqlx::query("UPDATE visual_support_documents_templates SET name = ?, updated_by = ? WHERE id = ?")
+            .bind(name).bind(&admin.user_id).bind(&id).execute(pool).await?;
     }
 
     let row: TemplateRow = sqlx::query_as::<_, TemplateRow>(
@@ -248,7 +497,9 @@ async fn update_template(
             id,
             COALESCE(owner_id, '') AS owner_id,
             name,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status
+            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
+            created_by,
+            updated_by
          FROM visual_support_documents_templates
          WHERE id = ?",
     )
@@ -258,17 +509,19 @@ async fn update_template(
 
 async fn delete_template(
     State(state): State<AppState>,
-    Extension(_admin): Extension<AuthUser>,
+    Extension(admin): Extension<AuthUser>,
     Path(id): Path<String>,
 ) -> AppResult<StatusCode> {
     let pool = &state.pool;
     let affected = sqlx::query(
                 "UPDATE visual_support_documents_templates
-                 SET metadata_json = JSON_SET(COALESCE(metadata_json, JSON_OBJECT()), '$.schedule.status', 'archived')
+                 SET metadata_json = JSON_SET(COALESCE(metadata_json, JSON_OBJECT()), '$.schedule.status', 'archived'),
+                     updated_by = ?
                  WHERE id = ?
                      AND document_type = 'WEEKLY_SCHEDULE'
                      AND (is_system = 1 OR IFNULL(JSON_EXTRACT(metadata_json, '$.schedule.is_template') = true, 0) = 1)",
     )
+    .bind(&admin.user_id)
     .bind(&id)
     .execute(pool)
     .await?
@@ -280,3 +533,396 @@ async fn delete_template(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+// ── Event stream ─────────────────────────────────────────────
+
+/// `GET /admin/events` — Server-Sent Events feed of `events::AdminEvent`s.
+///
+/// Each connection holds its own broadcast receiver, dropped with the stream
+/// when the client disconnects. A client that falls behind gets a `lagged`
+/// event with the number of skipped events and then continues live.
+async fn event_stream(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(state.events.subscribe()).map(|item| {
+        let event = match item {
+            Ok(ev) => Event::default()
+                .event(ev.name())
+                .json_data(&ev)
+                .unwrap_or_else(|_| Event::default().event(ev.name())),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => Event::default()
+                .event("lagged")
+                .data(skipped.to_string()),
+        };
+        Ok(event)
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+// ── System health ────────────────────────────────────────────
+
+/// Upstream ARASAAC reachability plus recent failed calls. Probes are
+/// throttled in the service, so repeated polling returns a cached result.
+async fn arasaac_health() -> Json<pictograms::ArasaacHealthDto> {
+    Json(pictograms::arasaac_health().await)
+}
+
+/// Security-relevant settings as derived at runtime. Only flags and counts;
+/// never the secrets themselves.
+#[derive(Serialize)]
+struct SecurityPostureDto {
+    app_env: String,
+    /// Session cookies carry `Secure` (everything but `development`).
+    secure_cookies: bool,
+    /// CORS policy applied to the router.
+    cors: CorsPolicy,
+    /// Password strength rules on register/reset (skipped in `development`).
+    password_policy_enforced: bool,
+    /// 5xx responses include the underlying error (only in `development`).
+    internal_errors_exposed: bool,
+    hide_existence_on_forbidden: bool,
+    /// Two-factor authentication is not implemented.
+    two_factor_required: bool,
+    /// Sessions and email tokens are stored as SHA-256 hashes.
+    session_tokens_hashed: bool,
+    /// `SESSION_SECRET` is at least 32 characters and not the example value.
+    session_secret_strong: bool,
+    /// `SEED_ADMIN_PASSWORD` is set, so the seeded admin did not get the default.
+    seed_admin_password_configured: bool,
+    /// Active accounts still required to change their password.
+    accounts_must_change_password: i64,
+}
+
+impl SecurityPostureDto {
+    fn new(config: &Config, accounts_must_change_password: i64) -> Self {
+        Self {
+            app_env: config.app_env.clone(),
+            secure_cookies: config.secure_cookies(),
+            cors: config.cors_policy(),
+            password_policy_enforced: config.password_policy_enforced(),
+            internal_errors_exposed: config.expose_internal_errors(),
+            hide_existence_on_forbidden: config.hide_existence_on_forbidden,
+            two_factor_required: false,
+            session_tokens_hashed: true,
+            session_secret_strong: config.session_secret.len() >= 32
+                && !config.session_secret.starts_with("changeme"),
+            seed_admin_password_configured: config.seed_admin_password.is_some(),
+            accounts_must_change_password,
+        }
+    }
+}
+
+/// Admins can confirm production hardening without shell access.
+async fn security_posture(State(state): State<AppState>) -> AppResult<Json<SecurityPostureDto>> {
+    let accounts_must_change_password: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM users WHERE must_change_password = 1 AND is_active = 1 AND deleted_at IS NULL",
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(SecurityPostureDto::new(&state.config, accounts_must_change_password)))
+}
+
+// ── Activity library import ──────────────────────────────────
+
+const MAX_IMPORT_ROWS: usize = 1000;
+
+/// Upsert system activity cards, matched on (language, label). Rows are
+/// validated independently; one bad row does not stop the rest. Missing
+/// pictogram files are downloaded in the background afterwards.
+async fn import_activity_library(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<AuthUser>,
+    Json(rows): Json<Vec<ImportActivityCardBody>>,
+) -> AppResult<Json<ImportActivityLibraryResponse>> {
+    if rows.is_empty() {
+        return Err(AppError::BadRequest("Import must contain at least one card".into()));
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(AppError::BadRequest(format!(
+            "Import is limited to {MAX_IMPORT_ROWS} cards per request"
+        )));
+    }
+
+    let pool = &state.pool;
+    let mut results = Vec::with_capacity(rows.len());
+    for (index, row) in rows.into_iter().enumerate() {
+        let result = match import_activity_card(pool, &row).await {
+            Ok((status, id)) => ImportActivityCardResult { index, status, id: Some(id), error: None },
+            Err(AppError::BadRequest(msg)) => {
+                ImportActivityCardResult { index, status: "error", id: None, error: Some(msg) }
+            }
+            Err(err) => return Err(err),
+        };
+        results.push(result);
+    }
+
+    let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+    let response = ImportActivityLibraryResponse {
+        created: count("created"),
+        updated: count("updated"),
+        failed: count("error"),
+        results,
+    };
+
+    if response.created + response.updated > 0 {
+        let pool = state.pool.clone();
+        tokio::spawn(async move {
+            match pictograms::ensure_seeded_activity_assets(&pool).await {
+                Ok(hydrated) => tracing::info!(hydrated, "Activity library import assets hydrated"),
+                Err(err) => tracing::warn!(error = ?err, "Activity library import asset hydration failed"),
+            }
+        });
+    }
+
+    Ok(Json(response))
+}
+
+async fn import_activity_card(
+    pool: &crate::db::Db,
+    row: &ImportActivityCardBody,
+) -> AppResult<(&'static str, String)> {
+    let label = row.label.trim();
+    if label.is_empty() || label.chars().count() > 120 {
+        return Err(AppError::BadRequest("label must be 1-120 characters".into()));
+    }
+    let language = row.language.trim().to_ascii_lowercase();
+    if !(2..=8).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic() || c == '-') {
+        return Err(AppError::BadRequest("language must be a 2-8 letter code".into()));
+    }
+    if row.arasaac_id <= 0 {
+        return Err(AppError::BadRequest("arasaac_id must be positive".into()));
+    }
+    let category = row.category.as_deref().map(str::trim).filter(|c| !c.is_empty());
+    if category.is_some_and(|c| c.chars().count() > 120) {
+        return Err(AppError::BadRequest("category must be at most 120 characters".into()));
+    }
+
+    let local_image_path = pictograms::activity_asset_path(pool, row.arasaac_id, category).await?;
+    let priority_order = row.priority_order.unwrap_or(0);
+
+    // The unique key includes owner_id, which is NULL for system cards, so
+    // MariaDB would never report a duplicate: match explicitly instead.
+    let existing: Option<String> = sqlx::query_scalar(
+        "SELECT id FROM visual_support_activity_library
+         WHERE owner_id IS NULL AND is_system = 1 AND language = ? AND label_text = ?
+         LIMIT 1",
+    )
+    .bind(&language)
+    .bind(label)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(id) = existing {
+        sqlx::query(
+            "UPDATE visual_support_activity_library
+             SET pictogram_id = ?, arasaac_id = ?, local_image_path = ?, category = ?, priority_order = ?
+             WHERE id = ?",
+        )
+        .bind(row.arasaac_id.to_string())
+        .bind(row.arasaac_id)
+        .bind(&local_image_path)
+        .bind(category)
+        .bind(priority_order)
+        .bind(&id)
+        .execute(pool)
+        .await?;
+        return Ok(("updated", id));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO visual_support_activity_library
+         (id, owner_id, language, label_text, pictogram_id, arasaac_id, local_image_path, category, priority_order, is_system)
+         VALUES (?, NULL, ?, ?, ?, ?, ?, ?, ?, 1)",
+    )
+    .bind(&id)
+    .bind(&language)
+    .bind(label)
+    .bind(row.arasaac_id.to_string())
+    .bind(row.arasaac_id)
+    .bind(&local_image_path)
+    .bind(category)
+    .bind(priority_order)
+    .execute(pool)
+    .await?;
+    Ok(("created", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, models::UserRole, test_support};
+
+    #[test]
+    fn security_posture_never_contains_secrets() {
+        let mut config = Config::for_tests();
+        config.app_env = "production".into();
+        config.session_secret = "posture-test-session-secret-0123456789".into();
+        config.seed_admin_password = Some("posture-test-admin-password".into());
+        config.db_password = "posture-test-db-password".into();
+        config.smtp_password = "posture-test-smtp-password".into();
+
+        let json = serde_json::to_string(&SecurityPostureDto::new(&config, 2)).unwrap();
+        for secret in [
+            &config.session_secret,
+            config.seed_admin_password.as_ref().unwrap(),
+            &config.db_password,
+            &config.smtp_password,
+        ] {
+            assert!(!json.contains(secret.as_str()), "posture leaks {secret}");
+        }
+
+        let posture: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(posture["secure_cookies"], true);
+        assert_eq!(posture["password_policy_enforced"], true);
+        assert_eq!(posture["internal_errors_exposed"], false);
+        assert_eq!(posture["cors"], "permissive");
+        assert_eq!(posture["session_secret_strong"], true);
+        assert_eq!(posture["seed_admin_password_configured"], true);
+        assert_eq!(posture["accounts_must_change_password"], 2);
+    }
+
+    #[test]
+    fn security_posture_follows_development_mode() {
+        let mut config = Config::for_tests();
+        config.app_env = "development".into();
+        config.session_secret = "changeme".into();
+
+        let posture = SecurityPostureDto::new(&config, 0);
+        assert!(!posture.secure_cookies);
+        assert!(!posture.password_policy_enforced);
+        assert!(posture.internal_errors_exposed);
+        assert!(!posture.session_secret_strong);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn dormant_filter_uses_last_login_then_creation(pool: Db) {
+        let admin = test_support::user(&pool, UserRole::Admin).await;
+        let recent = test_support::user(&pool, UserRole::Parent).await;
+        let lapsed = test_support::user(&pool, UserRole::Parent).await;
+        let never_old = test_support::user(&pool, UserRole::Parent).await;
+        let never_new = test_support::user(&pool, UserRole::Parent).await;
+        let old_child = test_support::user(&pool, UserRole::Child).await;
+        for (user, sql) in [
+            (&admin, "UPDATE users SET last_login_at = UTC_TIMESTAMP() WHERE id = ?"),
+            (&recent, "UPDATE users SET last_login_at = UTC_TIMESTAMP() - INTERVAL 2 DAY, created_at = UTC_TIMESTAMP() - INTERVAL 400 DAY WHERE id = ?"),
+            (&lapsed, "UPDATE users SET last_login_at = UTC_TIMESTAMP() - INTERVAL 90 DAY WHERE id = ?"),
+            (&never_old, "UPDATE users SET created_at = UTC_TIMESTAMP() - INTERVAL 90 DAY WHERE id = ?"),
+            (&old_child, "UPDATE users SET created_at = UTC_TIMESTAMP() - INTERVAL 90 DAY WHERE id = ?"),
+        ] {
+            sqlx::query(sql).bind(&user.user_id).execute(&pool).await.unwrap();
+        }
+        let list = |dormant_days: Option<u32>| {
+            list_users(State(test_support::state(pool.clone())), Extension(admin.clone()), Query(ListUsersQuery { dormant_days }))
+        };
+
+        let Json(all) = list(None).await.unwrap();
+        for user in [&admin, &recent, &lapsed, &never_old, &never_new, &old_child] {
+            assert!(all.iter().any(|u| u.id == user.user_id), "{} missing", user.user_id);
+        }
+        assert!(all.iter().any(|u| u.id == recent.user_id && u.last_login_at.is_some()));
+
+        let Json(dormant) = list(Some(30)).await.unwrap();
+        let mut ids: Vec<&str> = dormant.iter().map(|u| u.id.as_str()).collect();
+        ids.sort();
+        let mut expected = vec![lapsed.user_id.as_str(), never_old.user_id.as_str()];
+        expected.sort();
+        assert_eq!(ids, expected);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn reassigning_a_child_moves_its_schedules_and_revokes_pairings(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let admin = test_support::user(&pool, UserRole::Admin).await;
+        let old_parent = test_support::user(&pool, UserRole::Parent).await;
+        let new_parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&old_parent.user_id)).await;
+
+        let bound = serde_json::json!({ "schedule": { "status": "active", "child_id": child } });
+        let own_schedule = test_support::schedule(&pool, &old_parent, "Kid's week", bound).await;
+        let shared_schedule = test_support::schedule(&pool, &old_parent, "Family week", serde_json::json!({})).await;
+        let monday = serde_json::json!({ "day_of_week": 1, "persistent": true });
+        let kept = test_support::assign(&pool, &old_parent, &child, &own_schedule, monday.clone()).await;
+        let dropped = test_support::assign(&pool, &old_parent, &child, &shared_schedule, monday).await;
+        sqlx::query(
+            "INSERT INTO child_device_tokens (id, parent_user_id, child_id, token_hash)
+             VALUES (?, ?, ?, REPEAT('a', 64))",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&old_parent.user_id)
+        .bind(&child)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO qr_tokens (id, child_id, token) VALUES (?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(&child)
+            .bind(crate::auth::generate_token())
+            .execute(&pool)
+            .await
+            .unwrap();
+        let reassign = |to: &AuthUser| {
+            let body = ReassignParentBody { parent_id: to.user_id.clone() };
+            reassign_child_parent(State(state.clone()), Extension(admin.clone()), Path(child.clone()), Json(body))
+        };
+
+        let Json(row) = reassign(&new_parent).await.unwrap();
+        assert_eq!(row.parent_id.as_deref(), Some(new_parent.user_id.as_str()));
+
+        let owner = |table: &'static str, id: String| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, Option<String>>(&format!("SELECT owner_id FROM {table} WHERE id = ?"))
+                    .bind(id)
+                    .fetch_optional(&pool)
+                    .await
+                    .unwrap()
+                    .flatten()
+            }
+        };
+        let templates = "visual_support_documents_templates";
+        assert_eq!(owner(templates, own_schedule).await, Some(new_parent.user_id.clone()));
+        assert_eq!(owner(templates, shared_schedule).await, Some(old_parent.user_id.clone()));
+        assert_eq!(owner("visual_support_documents", kept).await, Some(new_parent.user_id.clone()));
+        assert_eq!(owner("visual_support_documents", dropped).await, None);
+
+        let (devices, codes): (i64, i64) = sqlx::query_as(
+            "SELECT
+                (SELECT COUNT(*) FROM child_device_tokens WHERE child_id = ? AND revoked_at IS NULL),
+                (SELECT COUNT(*) FROM qr_tokens WHERE child_id = ? AND is_active = 1)",
+        )
+        .bind(&child)
+        .bind(&child)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((devices, codes), (0, 0));
+
+        let details: String = sqlx::query_scalar(
+            "SELECT CAST(details AS CHAR) FROM admin_audit_logs
+             WHERE action = 'child_reassign_parent' AND target_id = ?",
+        )
+        .bind(&child)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let details: serde_json::Value = serde_json::from_str(&details).unwrap();
+        assert_eq!(details["from_parent_id"], old_parent.user_id);
+        assert_eq!(details["to_parent_id"], new_parent.user_id);
+        assert_eq!(details["moved_schedules"], 1);
+        assert_eq!(details["removed_assignments"], 1);
+        assert_eq!(details["revoked_devices"], 1);
+
+        // Soft-deleted children cannot be moved.
+        sqlx::query("UPDATE child_profiles SET deleted_at = NOW() WHERE id = ?")
+            .bind(&child)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(matches!(test_support::expect_err(reassign(&old_parent).await), AppError::NotFound));
+    }
+}
@@ -7,17 +7,19 @@ use axum::{
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use tower_cookies::{
     cookie::{time::Duration as CookieDuration, SameSite},
     Cookie, Cookies,
 };
 use uuid::Uuid;
 
+use super::children::{active_child_device_count, revocable_child_devices, MAX_ACTIVE_CHILD_DEVICES};
 use crate::{
     auth::{
         email::{send_password_reset_email, send_verification_email},
-        generate_token, hash_password, validate_password_strength, verify_password,
+        generate_token, hash_password, hash_token, validate_password_strength, verify_password,
+        verify_password_dummy,
+        SESSION_TOKEN_MATCH_SQL, TOKEN_MATCH_SQL,
     },
     db::Db,
     errors::{AppError, AppResult},
@@ -31,11 +33,10 @@ const SESSION_DAYS:   i64  = 30;
 const VERIFY_HOURS:   i64  = 24;
 const RESET_HOURS:    i64  = 1;
 const CHILD_SESSION_COOKIE: &str = "child_session";
-const MAX_ACTIVE_CHILD_DEVICES: i64 = 3;
 
 // ── Request / response types ──────────────────────────────────
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct RegisterRequest {
     username: String,
     email:    Option<String>,
@@ -47,7 +48,7 @@ struct RegisterRequest {
     week_start: Option<u8>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct LoginRequest {
     /// email (for parent/admin) OR username (for child)
     email:    Option<String>,
@@ -55,23 +56,23 @@ struct LoginRequest {
     password: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct VerifyEmailRequest {
     token: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct ForgotPasswordRequest {
     email: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct ResetPasswordRequest {
     token:    String,
     password: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct ChangePasswordRequest {
     /// New password to set.
     password: String,
@@ -79,24 +80,26 @@ struct ChangePasswordRequest {
     current_password: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct ChildPairRequest {
     token: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 struct ChildPairResponse {
     child_id: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 struct ChildSessionResponse {
     device_id: String,
     parent_user_id: String,
     child_id: String,
+    display_name: String,
+    avatar_path: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 struct UserResponse {
     id:       String,
     email:    Option<String>,
@@ -108,6 +111,12 @@ struct UserResponse {
     date_format: String,
     time_format: String,
     week_start: u8,
+    theme: String,
+    /// Set until the user changes their password (e.g. the seeded admin);
+    /// every other authenticated route answers 403 in the meantime.
+    must_change_password: bool,
+    /// RFC 3339 time of the most recent password login.
+    last_login_at: Option<String>,
 }
 
 // ── Database row types (runtime queries — no DATABASE_URL at compile time) ──────
@@ -125,7 +134,9 @@ struct UserRow {
     date_format:   String,
     time_format:   String,
     week_start:    i16,
+    theme:         String,
     is_active:     bool,
+    must_change_password: bool,
 }
 
 #[derive(sqlx::FromRow)]
@@ -140,6 +151,9 @@ struct MeRow {
     date_format: String,
     time_format: String,
     week_start: i16,
+    theme: String,
+    must_change_password: bool,
+    last_login_at: Option<chrono::NaiveDateTime>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -147,6 +161,30 @@ struct TokenRow {
     user_id: String,
 }
 
+/// The calling device's own `child_device_tokens` row.
+#[derive(sqlx::FromRow)]
+struct CurrentDeviceRow {
+    id: String,
+    child_id: String,
+    label: Option<String>,
+    created_at: chrono::NaiveDateTime,
+    last_used_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+struct CurrentDeviceResponse {
+    device_id: String,
+    child_id: String,
+    label: Option<String>,
+    created_at: String,
+    last_used_at: Option<String>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+struct DeviceLabelRequest {
+    label: Option<String>,
+}
+
 #[derive(sqlx::FromRow)]
 struct ForgotRow {
     id: String,
@@ -154,6 +192,11 @@ struct ForgotRow {
 
 // ── Router ────────────────────────────────────────────────────
 
+/// OpenAPI paths for this module, merged in `super::openapi`.
+#[derive(utoipa::OpenApi)]
+#[openapi(paths(register, login, logout, me, child_pair, child_me, current_child_device, label_current_child_device, child_logout, verify_email, forgot_password, reset_password, change_password))]
+pub(super) struct ApiDoc;
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/auth/register",        post(register))
@@ -163,6 +206,8 @@ pub fn router() -> Router<AppState> {
         .route("/auth/child/pair",      post(child_pair))
         .route("/auth/child/me",        get(child_me))
         .route("/auth/child/logout",    post(child_logout))
+        .route("/auth/child/devices/current",
+            get(current_child_device).patch(label_current_child_device).delete(child_logout))
         .route("/auth/verify-email",    post(verify_email))
         .route("/auth/forgot-password", post(forgot_password))
         .route("/auth/reset-password",   post(reset_password))
@@ -173,6 +218,13 @@ pub fn router() -> Router<AppState> {
 // ── Handlers ──────────────────────────────────────────────────
 
 /// POST /auth/register — create a new parent account.
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    tag = "auth",
+    request_body = RegisterRequest,
+    responses((status = 201, description = "Account created", body = super::openapi::MessageResponse), (status = 400, description = "Invalid input", body = super::openapi::ErrorResponse), (status = 409, description = "Email or username taken", body = super::openapi::ErrorResponse)),
+)]
 async fn register(
     State(state): State<AppState>,
     Json(body): Json<RegisterRequest>,
@@ -195,7 +247,7 @@ async fn register(
 
     // DEV: password strength is disabled in development for easy testing.
     // PRODUCTION: remove this guard so all passwords are validated.
-    if config.app_env != "development" {
+    if config.password_policy_enforced() {
         validate_password_strength(&body.password)?;
     }
 
@@ -269,6 +321,13 @@ async fn register(
 }
 
 /// POST /auth/login — email+password login for parent/admin accounts.
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses((status = 200, description = "Logged in; sets the session cookie", body = UserResponse), (status = 401, description = "Bad credentials", body = super::openapi::ErrorResponse)),
+)]
 async fn login(
     State(state): State<AppState>,
     cookies: Cookies,
@@ -278,7 +337,7 @@ async fn login(
     // Find user by email or username
     let user_row = if let Some(ref email) = body.email {
         sqlx::query_as::<_, UserRow>(
-            "SELECT id, email, username, password_hash, role, language, timezone, locale, date_format, time_format, week_start, is_active
+            "SELECT id, email, username, password_hash, role, language, timezone, locale, date_format, time_format, week_start, theme, is_active, must_change_password
              FROM users WHERE email = ? AND deleted_at IS NULL LIMIT 1",
         )
         .bind(email)
@@ -292,7 +351,12 @@ async fn login(
         return Err(AppError::BadRequest("Provide email".into()));
     };
 
-    let row = user_row.ok_or(AppError::Unauthorized)?;
+    // Verify before any account-state checks so every failure path pays for
+    // one Argon2 run, whether or not the email exists.
+    let Some(row) = user_row else {
+        return Err(verify_password_dummy(&body.password));
+    };
+    verify_password(&body.password, &row.password_hash)?;
 
     if !row.is_active {
         return Err(AppError::Unauthorized);
@@ -305,11 +369,17 @@ async fn login(
 
     // Email verification enforcement is temporarily disabled.
 
-    verify_password(&body.password, &row.password_hash)?;
-
-    // Create session
-    let session_token = create_session(pool, &row.id, SESSION_DAYS).await?;
-    set_session_cookie(&cookies, &state.config.app_env, &session_token, SESSION_DAYS);
+    // Create session and record the login together.
+    let mut tx = pool.begin().await?;
+    let session_token = create_session(&mut tx, &row.id, SESSION_DAYS).await?;
+    let last_login_at = Utc::now().naive_utc();
+    sqlx::query("UPDATE users SET last_login_at = ? WHERE id = ?")
+        .bind(last_login_at)
+        .bind(&row.id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    set_session_cookie(&cookies, state.config.secure_cookies(), &session_token, SESSION_DAYS);
 
     Ok(Json(UserResponse {
         id:       row.id.clone(),
@@ -322,26 +392,42 @@ async fn login(
         date_format: row.date_format.clone(),
         time_format: row.time_format.clone(),
         week_start: row.week_start as u8,
+        theme: row.theme.clone(),
+        must_change_password: row.must_change_password,
+        last_login_at: Some(fmt_utc(last_login_at)),
     }))
 }
 
 /// POST /auth/logout — delete the current session.
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "auth",
+    responses((status = 204, description = "Session ended")),
+)]
 async fn logout(
     State(state): State<AppState>,
     cookies: Cookies,
 ) -> AppResult<impl IntoResponse> {
     let pool = &state.pool;
     if let Some(token) = cookies.get(SESSION_COOKIE).map(|c| c.value().to_owned()) {
-        sqlx::query("DELETE FROM user_sessions WHERE token = ?")
+        sqlx::query(&format!("DELETE FROM user_sessions WHERE {TOKEN_MATCH_SQL}"))
+            .bind(hash_token(&token))
             .bind(&token)
             .execute(pool)
             .await?;
     }
-    clear_session_cookie(&cookies, &state.config.app_env);
+    clear_session_cookie(&cookies, state.config.secure_cookies());
     Ok(StatusCode::NO_CONTENT)
 }
 
 /// GET /auth/me — return the currently logged-in user.
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    tag = "auth",
+    responses((status = 200, description = "Current user", body = UserResponse), (status = 401, description = "Not logged in", body = super::openapi::ErrorResponse)),
+)]
 async fn me(
     State(state): State<AppState>,
     cookies: Cookies,
@@ -352,14 +438,16 @@ async fn me(
         .map(|c| c.value().to_owned())
         .ok_or(AppError::Unauthorized)?;
 
-    let row = sqlx::query_as::<_, MeRow>(
+    let row = sqlx::query_as::<_, MeRow>(&format!(
         "SELECT u.id, u.email, u.username, u.role, u.language, u.timezone,
-            u.locale, u.date_format, u.time_format, u.week_start
+            u.locale, u.date_format, u.time_format, u.week_start, u.theme, u.must_change_password,
+            u.last_login_at
          FROM user_sessions s
          JOIN users u ON u.id = s.user_id
-         WHERE s.token = ? AND s.expires_at > NOW() AND u.is_active = 1 AND u.deleted_at IS NULL
-         LIMIT 1",
-    )
+         WHERE {SESSION_TOKEN_MATCH_SQL} AND s.expires_at > NOW() AND u.is_active = 1 AND u.deleted_at IS NULL
+         LIMIT 1"
+    ))
+    .bind(hash_token(&token))
     .bind(&token)
     .fetch_optional(pool)
     .await?
@@ -376,10 +464,20 @@ async fn me(
         date_format: row.date_format.clone(),
         time_format: row.time_format.clone(),
         week_start: row.week_start as u8,
+        theme: row.theme.clone(),
+        must_change_password: row.must_change_password,
+        last_login_at: row.last_login_at.map(fmt_utc),
     }))
 }
 
 /// POST /auth/child/pair — exchange an active QR token for a child device session.
+#[utoipa::path(
+    post,
+    path = "/auth/child/pair",
+    tag = "auth",
+    request_body = ChildPairRequest,
+    responses((status = 200, description = "Device paired; sets the child session cookie", body = ChildPairResponse), (status = 400, description = "Invalid or inactive QR code", body = super::openapi::ErrorResponse), (status = 409, description = "Device limit reached (`child_device_limit_reached`)", body = super::openapi::ErrorResponse)),
+)]
 async fn child_pair(
     State(state): State<AppState>,
     cookies: Cookies,
@@ -398,7 +496,7 @@ async fn child_pair(
     let pair = sqlx::query_as::<_, PairRow>(
         "SELECT q.id AS qr_id, q.child_id, cp.parent_id AS parent_user_id
          FROM qr_tokens q
-         JOIN child_profiles cp ON cp.id = q.child_id
+         JOIN child_profiles cp ON cp.id = q.child_id AND cp.deleted_at IS NULL
          WHERE q.token = ? AND q.is_active = 1
          LIMIT 1",
     )
@@ -411,17 +509,15 @@ async fn child_pair(
         .parent_user_id
         .ok_or_else(|| AppError::Forbidden)?;
 
-    let active_count: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM child_device_tokens WHERE child_id = ? AND revoked_at IS NULL",
-    )
-    .bind(&pair.child_id)
-    .fetch_one(pool)
-    .await?;
+    let active_count = active_child_device_count(pool, &pair.child_id).await?;
 
     if active_count >= MAX_ACTIVE_CHILD_DEVICES {
-        return Err(AppError::Conflict(format!(
-            "Maximum number of active devices reached ({MAX_ACTIVE_CHILD_DEVICES})"
-        )));
+        let devices = revocable_child_devices(pool, &pair.child_id).await?;
+        return Err(AppError::ConflictDetails {
+            message: format!("Maximum number of active devices reached ({MAX_ACTIVE_CHILD_DEVICES})"),
+            code: "child_device_limit_reached",
+            details: serde_json::json!({ "limit": MAX_ACTIVE_CHILD_DEVICES, "devices": devices }),
+        });
     }
 
     let raw_device_token = generate_token();
@@ -465,7 +561,7 @@ async fn child_pair(
         .execute(pool)
         .await?;
 
-    set_child_session_cookie(&cookies, &state.config.app_env, &raw_device_token);
+    set_child_session_cookie(&cookies, state.config.secure_cookies(), &raw_device_token);
 
     Ok(Json(ChildPairResponse {
         child_id: pair.child_id,
@@ -473,6 +569,12 @@ async fn child_pair(
 }
 
 /// GET /auth/child/me — return active child device session metadata.
+#[utoipa::path(
+    get,
+    path = "/auth/child/me",
+    tag = "auth",
+    responses((status = 200, description = "Child session", body = ChildSessionResponse), (status = 401, description = "No child session", body = super::openapi::ErrorResponse)),
+)]
 async fn child_me(
     State(state): State<AppState>,
     cookies: Cookies,
@@ -490,12 +592,16 @@ async fn child_me(
         id: String,
         parent_user_id: String,
         child_id: String,
+        display_name: String,
+        avatar_path: Option<String>,
     }
 
+    // Only the device's own child profile is joined in.
     let row = sqlx::query_as::<_, ChildSessionRow>(
-        "SELECT id, parent_user_id, child_id
-         FROM child_device_tokens
-         WHERE token_hash = ? AND revoked_at IS NULL
+        "SELECT d.id, d.parent_user_id, d.child_id, cp.display_name, cp.avatar_path
+         FROM child_device_tokens d
+         JOIN child_profiles cp ON cp.id = d.child_id AND cp.deleted_at IS NULL
+         WHERE d.token_hash = ? AND d.revoked_at IS NULL
          LIMIT 1",
     )
     .bind(&token_hash)
@@ -512,10 +618,96 @@ async fn child_me(
         device_id: row.id,
         parent_user_id: row.parent_user_id,
         child_id: row.child_id,
+        display_name: row.display_name,
+        avatar_path: row.avatar_path,
     }))
 }
 
-/// POST /auth/child/logout — revoke current child device session.
+async fn load_current_device(pool: &Db, cookies: &Cookies) -> AppResult<CurrentDeviceRow> {
+    let raw = cookies
+        .get(CHILD_SESSION_COOKIE)
+        .map(|c| c.value().to_owned())
+        .ok_or(AppError::Unauthorized)?;
+
+    sqlx::query_as::<_, CurrentDeviceRow>(
+        "SELECT id, child_id, label, created_at, last_used_at
+         FROM child_device_tokens
+         WHERE token_hash = ? AND revoked_at IS NULL
+         LIMIT 1",
+    )
+    .bind(hash_token(&raw))
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::Unauthorized)
+}
+
+fn fmt_utc(t: chrono::NaiveDateTime) -> String {
+    t.and_utc().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn to_current_device_response(row: CurrentDeviceRow) -> CurrentDeviceResponse {
+    CurrentDeviceResponse {
+        device_id: row.id,
+        child_id: row.child_id,
+        label: row.label,
+        created_at: fmt_utc(row.created_at),
+        last_used_at: row.last_used_at.map(fmt_utc),
+    }
+}
+
+/// GET /auth/child/devices/current — metadata for the calling device only.
+#[utoipa::path(
+    get,
+    path = "/auth/child/devices/current",
+    tag = "auth",
+    responses((status = 200, description = "Calling device", body = CurrentDeviceResponse), (status = 401, description = "No child session", body = super::openapi::ErrorResponse)),
+)]
+async fn current_child_device(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> AppResult<Json<CurrentDeviceResponse>> {
+    let row = load_current_device(&state.pool, &cookies).await?;
+    Ok(Json(to_current_device_response(row)))
+}
+
+/// PATCH /auth/child/devices/current — set or clear the calling device's label.
+#[utoipa::path(
+    patch,
+    path = "/auth/child/devices/current",
+    tag = "auth",
+    request_body = DeviceLabelRequest,
+    responses((status = 200, description = "Updated device", body = CurrentDeviceResponse), (status = 401, description = "No child session", body = super::openapi::ErrorResponse)),
+)]
+async fn label_current_child_device(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Json(body): Json<DeviceLabelRequest>,
+) -> AppResult<Json<CurrentDeviceResponse>> {
+    let pool = &state.pool;
+    let row = load_current_device(pool, &cookies).await?;
+
+    let label = body.label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+    if label.as_ref().is_some_and(|l| l.chars().count() > 64) {
+        return Err(AppError::BadRequest("label must be at most 64 characters".into()));
+    }
+
+    sqlx::query("UPDATE child_device_tokens SET label = ? WHERE id = ?")
+        .bind(&label)
+        .bind(&row.id)
+        .execute(pool)
+        .await?;
+
+    Ok(Json(to_current_device_response(CurrentDeviceRow { label, ..row })))
+}
+
+/// POST /auth/child/logout (also DELETE /auth/child/devices/current) —
+/// revoke current child device session.
+#[utoipa::path(
+    post,
+    path = "/auth/child/logout",
+    tag = "auth",
+    responses((status = 204, description = "Device revoked and cookie cleared")),
+)]
 async fn child_logout(
     State(state): State<AppState>,
     cookies: Cookies,
@@ -530,20 +722,29 @@ async fn child_logout(
             .await?;
     }
 
-    clear_child_session_cookie(&cookies, &state.config.app_env);
+    clear_child_session_cookie(&cookies, state.config.secure_cookies());
     Ok(StatusCode::NO_CONTENT)
 }
 
 /// POST /auth/verify-email — confirm an email address.
+#[utoipa::path(
+    post,
+    path = "/auth/verify-email",
+    tag = "auth",
+    request_body = VerifyEmailRequest,
+    responses((status = 200, description = "Email verified", body = super::openapi::MessageResponse), (status = 400, description = "Invalid or expired token", body = super::openapi::ErrorResponse)),
+)]
 async fn verify_email(
     State(state): State<AppState>,
     Json(body): Json<VerifyEmailRequest>,
 ) -> AppResult<impl IntoResponse> {
     let pool = &state.pool;
-    let row = sqlx::query_as::<_, TokenRow>(
+    let token_hash = hash_token(&body.token);
+    let row = sqlx::query_as::<_, TokenRow>(&format!(
         "SELECT user_id FROM email_tokens
-         WHERE token = ? AND kind = 'verify_email' AND expires_at > NOW() LIMIT 1",
-    )
+         WHERE {TOKEN_MATCH_SQL} AND kind = 'verify_email' AND expires_at > NOW() LIMIT 1"
+    ))
+    .bind(&token_hash)
     .bind(&body.token)
     .fetch_optional(pool)
     .await?
@@ -554,7 +755,8 @@ async fn verify_email(
         .execute(pool)
         .await?;
 
-    sqlx::query("DELETE FROM email_tokens WHERE token = ?")
+    sqlx::query(&format!("DELETE FROM email_tokens WHERE {TOKEN_MATCH_SQL}"))
+        .bind(&token_hash)
         .bind(&body.token)
         .execute(pool)
         .await?;
@@ -563,6 +765,13 @@ async fn verify_email(
 }
 
 /// POST /auth/forgot-password — request a password-reset link.
+#[utoipa::path(
+    post,
+    path = "/auth/forgot-password",
+    tag = "auth",
+    request_body = ForgotPasswordRequest,
+    responses((status = 200, description = "Reset email sent if the account exists", body = super::openapi::MessageResponse)),
+)]
 async fn forgot_password(
     State(state): State<AppState>,
     Json(body): Json<ForgotPasswordRequest>,
@@ -589,6 +798,13 @@ async fn forgot_password(
 }
 
 /// POST /auth/reset-password — apply a new password from a reset token.
+#[utoipa::path(
+    post,
+    path = "/auth/reset-password",
+    tag = "auth",
+    request_body = ResetPasswordRequest,
+    responses((status = 200, description = "Password updated", body = super::openapi::MessageResponse), (status = 400, description = "Invalid or expired token", body = super::openapi::ErrorResponse)),
+)]
 async fn reset_password(
     State(state): State<AppState>,
     Json(body): Json<ResetPasswordRequest>,
@@ -597,14 +813,16 @@ async fn reset_password(
     let config = &state.config;
     // DEV: password strength is disabled in development for easy testing.
     // PRODUCTION: remove this guard so all passwords are validated.
-    if config.app_env != "development" {
+    if config.password_policy_enforced() {
         validate_password_strength(&body.password)?;
     }
 
-    let row = sqlx::query_as::<_, TokenRow>(
+    let token_hash = hash_token(&body.token);
+    let row = sqlx::query_as::<_, TokenRow>(&format!(
         "SELECT user_id FROM email_tokens
-         WHERE token = ? AND kind = 'reset_password' AND expires_at > NOW() LIMIT 1",
-    )
+         WHERE {TOKEN_MATCH_SQL} AND kind = 'reset_password' AND expires_at > NOW() LIMIT 1"
+    ))
+    .bind(&token_hash)
     .bind(&body.token)
     .fetch_optional(pool)
     .await?
@@ -613,14 +831,15 @@ async fn reset_password(
     let hash = hash_password(&body.password)?;
 
     sqlx::query(
-        "UPDATE users SET password_hash = ?, updated_at = NOW() WHERE id = ?",
+        "UPDATE users SET password_hash = ?, must_change_password = 0, updated_at = NOW() WHERE id = ?",
     )
     .bind(hash)
     .bind(&row.user_id)
     .execute(pool)
     .await?;
 
-    sqlx::query("DELETE FROM email_tokens WHERE token = ?")
+    sqlx::query(&format!("DELETE FROM email_tokens WHERE {TOKEN_MATCH_SQL}"))
+        .bind(&token_hash)
         .bind(&body.token)
         .execute(pool)
         .await?;
@@ -643,6 +862,13 @@ async fn qr_login(
 }
 
 /// POST /auth/change-password — change password for the currently logged-in user.
+#[utoipa::path(
+    post,
+    path = "/auth/change-password",
+    tag = "auth",
+    request_body = ChangePasswordRequest,
+    responses((status = 200, description = "Password changed", body = super::openapi::MessageResponse), (status = 400, description = "Weak password or wrong current password", body = super::openapi::ErrorResponse)),
+)]
 async fn change_password(
     State(state): State<AppState>,
     cookies: Cookies,
@@ -656,15 +882,16 @@ async fn change_password(
         .map(|c| c.value().to_owned())
         .ok_or(AppError::Unauthorized)?;
 
-    let row = sqlx::query_as::<_, UserRow>(
+    let row = sqlx::query_as::<_, UserRow>(&format!(
         "SELECT u.id, u.email, u.username, u.password_hash, u.role, u.language, u.timezone,
-            u.locale, u.date_format, u.time_format, u.week_start, u.is_active
+            u.locale, u.date_format, u.time_format, u.week_start, u.theme, u.is_active, u.must_change_password
          FROM user_sessions s
          JOIN users u ON u.id = s.user_id
-         WHERE s.token = ? AND s.expires_at > NOW()
+         WHERE {SESSION_TOKEN_MATCH_SQL} AND s.expires_at > NOW()
            AND u.is_active = 1 AND u.deleted_at IS NULL
-         LIMIT 1",
-    )
+         LIMIT 1"
+    ))
+    .bind(hash_token(&token))
     .bind(&token)
     .fetch_optional(pool).await?
     .ok_or(AppError::Unauthorized)?;
@@ -675,13 +902,17 @@ async fn change_password(
 
     verify_password(&body.current_password, &row.password_hash)?;
 
-    if config.app_env != "development" {
+    if row.must_change_password && body.password == body.current_password {
+        return Err(AppError::BadRequest("New password must differ from the current one".into()));
+    }
+
+    if config.password_policy_enforced() {
         validate_password_strength(&body.password)?;
     }
 
     let hash = hash_password(&body.password)?;
     sqlx::query(
-        "UPDATE users SET password_hash = ?, updated_at = NOW() WHERE id = ?",
+        "UPDATE users SET password_hash = ?, must_change_password = 0, updated_at = NOW() WHERE id = ?",
     )
     .bind(hash)
     .bind(&row.id)
@@ -693,20 +924,20 @@ async fn change_password(
 
 // ── Internal helpers ──────────────────────────────────────────
 
-async fn create_session(pool: &Db, user_id: &str, days: i64) -> AppResult<String> {
+async fn create_session(conn: &mut sqlx::MySqlConnection, user_id: &str, days: i64) -> AppResult<String> {
     let token = generate_token();
     let id    = Uuid::new_v4().to_string();
     let expires_at =
         (Utc::now() + chrono::Duration::days(days)).naive_utc();
 
     sqlx::query(
-        "INSERT INTO user_sessions (id, user_id, token, expires_at) VALUES (?, ?, ?, ?)",
+        "INSERT INTO user_sessions (id, user_id, token_hash, expires_at) VALUES (?, ?, ?, ?)",
     )
     .bind(id)
     .bind(user_id)
-    .bind(&token)
+    .bind(hash_token(&token))
     .bind(expires_at)
-    .execute(pool)
+    .execute(conn)
     .await?;
 
     Ok(token)
@@ -731,11 +962,11 @@ async fn issue_email_token(
         (Utc::now() + chrono::Duration::hours(hours)).naive_utc();
 
     sqlx::query(
-        "INSERT INTO email_tokens (id, user_id, token, kind, expires_at) VALUES (?, ?, ?, ?, ?)",
+        "INSERT INTO email_tokens (id, user_id, token_hash, kind, expires_at) VALUES (?, ?, ?, ?, ?)",
     )
     .bind(id)
     .bind(user_id)
-    .bind(&token)
+    .bind(hash_token(&token))
     .bind(kind)
     .bind(expires_at)
     .execute(pool)
@@ -744,60 +975,50 @@ async fn issue_email_token(
     Ok(token)
 }
 
-fn set_session_cookie(cookies: &Cookies, app_env: &str, token: &str, days: i64) {
-    let is_prod = app_env != "development";
+fn set_session_cookie(cookies: &Cookies, secure: bool, token: &str, days: i64) {
     let cookie = Cookie::build((SESSION_COOKIE, token.to_owned()))
         .http_only(true)
         .same_site(SameSite::Strict)
-        .secure(is_prod)
+        .secure(secure)
         .path("/")
         .max_age(CookieDuration::days(days))
         .build();
     cookies.add(cookie);
 }
 
-fn clear_session_cookie(cookies: &Cookies, app_env: &str) {
-    let is_prod = app_env != "development";
+fn clear_session_cookie(cookies: &Cookies, secure: bool) {
     let cookie = Cookie::build((SESSION_COOKIE, ""))
         .http_only(true)
         .same_site(SameSite::Strict)
-        .secure(is_prod)
+        .secure(secure)
         .path("/")
         .max_age(CookieDuration::ZERO)
         .build();
     cookies.add(cookie);
 }
 
-fn set_child_session_cookie(cookies: &Cookies, app_env: &str, token: &str) {
-    let is_prod = app_env != "development";
+fn set_child_session_cookie(cookies: &Cookies, secure: bool, token: &str) {
     let cookie = Cookie::build((CHILD_SESSION_COOKIE, token.to_owned()))
         .http_only(true)
         .same_site(SameSite::Strict)
-        .secure(is_prod)
+        .secure(secure)
         .path("/")
         .max_age(CookieDuration::days(180))
         .build();
     cookies.add(cookie);
 }
 
-fn clear_child_session_cookie(cookies: &Cookies, app_env: &str) {
-    let is_prod = app_env != "development";
+fn clear_child_session_cookie(cookies: &Cookies, secure: bool) {
     let cookie = Cookie::build((CHILD_SESSION_COOKIE, ""))
         .http_only(true)
         .same_site(SameSite::Strict)
-        .secure(is_prod)
+        .secure(secure)
         .path("/")
         .max_age(CookieDuration::ZERO)
         .build();
     cookies.add(cookie);
 }
 
-fn hash_token(raw: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(raw.as_bytes());
-    format!("{:x}", hasher.finalize())
-}
-
 fn normalize_timezone(input: Option<&str>) -> AppResult<String> {
     let tz = input.unwrap_or("UTC").trim();
     if tz.is_empty() {
@@ -855,3 +1076,107 @@ fn normalize_week_start(input: Option<u8>) -> AppResult<u8> {
     }
     Ok(week_start)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, models::UserRole, test_support};
+
+    async fn try_login(state: &AppState, email: &str, password: &str) -> AppResult<()> {
+        let body = LoginRequest { email: Some(email.into()), username: None, password: password.into() };
+        login(State(state.clone()), Cookies::default(), Json(body)).await.map(|_| ())
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn unknown_email_and_wrong_password_fail_alike(pool: Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let email = format!("{}@example.test", parent.user_id);
+        sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+            .bind(hash_password("Correct1horse").unwrap())
+            .bind(&parent.user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let state = test_support::state(pool);
+
+        let err = test_support::expect_err(try_login(&state, "nobody@example.test", "Correct1horse").await);
+        assert!(matches!(err, AppError::Unauthorized), "{err:?}");
+        let err = test_support::expect_err(try_login(&state, &email, "Wrong1horse").await);
+        assert!(matches!(err, AppError::Unauthorized), "{err:?}");
+        assert!(try_login(&state, &email, "Correct1horse").await.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn successful_login_records_last_login_at(pool: Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let email = format!("{}@example.test", parent.user_id);
+        sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+            .bind(hash_password("Correct1horse").unwrap())
+            .bind(&parent.user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let state = test_support::state(pool.clone());
+        let last_login_at = || {
+            sqlx::query_scalar::<_, Option<chrono::NaiveDateTime>>("SELECT last_login_at FROM users WHERE id = ?")
+                .bind(&parent.user_id)
+                .fetch_one(&pool)
+        };
+
+        assert!(try_login(&state, &email, "Wrong1horse").await.is_err());
+        assert_eq!(last_login_at().await.unwrap(), None);
+
+        let before = Utc::now().naive_utc() - chrono::Duration::seconds(1);
+        assert!(try_login(&state, &email, "Correct1horse").await.is_ok());
+        let recorded = last_login_at().await.unwrap().expect("last_login_at set");
+        assert!(recorded >= before && recorded <= Utc::now().naive_utc(), "{recorded}");
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn pairing_past_the_device_limit_lists_revocable_devices(pool: Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let qr_token = generate_token();
+        sqlx::query("INSERT INTO qr_tokens (id, child_id, token, is_active) VALUES (UUID(), ?, ?, 1)")
+            .bind(&child)
+            .bind(&qr_token)
+            .execute(&pool)
+            .await
+            .unwrap();
+        for n in 0..MAX_ACTIVE_CHILD_DEVICES {
+            sqlx::query(
+                "INSERT INTO child_device_tokens (id, parent_user_id, child_id, label, token_hash)
+                 VALUES (UUID(), ?, ?, ?, ?)",
+            )
+            .bind(&parent.user_id)
+            .bind(&child)
+            .bind(format!("Tablet {n}"))
+            .bind(hash_token(&generate_token()))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let state = test_support::state(pool);
+        let body = ChildPairRequest { token: qr_token };
+        let result = child_pair(State(state), Cookies::default(), axum::http::HeaderMap::new(), Json(body)).await;
+        let AppError::ConflictDetails { message, code, details } = test_support::expect_err(result) else {
+            panic!("expected a structured conflict");
+        };
+        assert!(message.contains("Maximum number of active devices"));
+        assert_eq!(code, "child_device_limit_reached");
+        assert_eq!(details["limit"], MAX_ACTIVE_CHILD_DEVICES);
+        let mut labels: Vec<&str> = details["devices"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| d["label"].as_str().unwrap())
+            .collect();
+        labels.sort_unstable();
+        assert_eq!(labels, ["Tablet 0", "Tablet 1", "Tablet 2"]);
+        assert!(details["devices"][0]["id"].is_string());
+    }
+}
@@ -1,41 +1,63 @@
 //! `/calendar` routes — weekly view and schedule day-assignment management.
 //!
 //! * `GET  /calendar/:child_id/week/:iso_week` — fetch the week's activity cards for a child
-//!   `:iso_week` format: `YYYY-Wnn`  (e.g. `2025-W07`)
+//!   `:iso_week` format: `YYYY-Wnn`  (e.g. `2025-W07`); `?labels=true` adds a
+//!   localized `label` per day
+//! * `GET  /calendar/:child_id/range?from=&to=` — the same per-day resolution for
+//!   up to [`MAX_RANGE_DAYS`] arbitrary dates
+//! * `GET  /child/:child_id/week/:iso_week`     — the same week for a paired device;
+//!   `?expand=pictograms` inlines each card's cached pictogram
 //! * `POST /calendar/:child_id/assign`          — assign a schedule to a weekday
 //! * `DELETE /calendar/:child_id/assign/:id`    — remove an assignment
+//! * `PUT  /child/:child_id/progress`           — device-authenticated "where am I" pointer for today
+//! * `GET  /child/:child_id/now-next`           — current and next card from that pointer
+
+use std::collections::HashMap;
 
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Json, Router,
 };
-use chrono::{NaiveDate, Weekday};
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use tower_cookies::Cookies;
 use uuid::Uuid;
 
+use super::schedule_sql;
 use crate::{
+    config::{resolve_public_asset, Config},
     errors::{AppError, AppResult},
     middleware::auth_guard::AuthUser,
-    models::UserRole,
+    models::{DayOfWeek, UserRole},
     state::AppState,
 };
 
 const WEEKLY_TYPE: &str = "WEEKLY_SCHEDULE";
 
+/// OpenAPI paths for this module, merged in `super::openapi`.
+#[derive(utoipa::OpenApi)]
+#[openapi(paths(get_week, get_range, assign, create_override, unassign, get_week_child, set_child_progress, get_child_now_next, get_child_day_options, select_child_day_schedule))]
+pub(super) struct ApiDoc;
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/calendar/{child_id}/week/{iso_week}",        get(get_week))
+        .route("/calendar/{child_id}/range",                 get(get_range))
         .route("/calendar/{child_id}/assign",                post(assign))
+        .route("/calendar/{child_id}/override",              post(create_override))
         .route("/calendar/{child_id}/assign/{assignment_id}", delete(unassign))
 }
 
 pub fn public_router() -> Router<AppState> {
     Router::new()
     .route("/child/{child_id}/week/{iso_week}", get(get_week_child))
+    .route("/child/{child_id}/progress", put(set_child_progress))
+    .route("/child/{child_id}/now-next", get(get_child_now_next))
+    .route("/child/{child_id}/day/{date}/options", get(get_child_day_options))
+    .route("/child/{child_id}/day/{date}/select", post(select_child_day_schedule))
 }
 
 // ── Row types ────────────────────────────────────────────────
@@ -44,57 +66,207 @@ pub fn public_router() -> Router<AppState> {
 struct AssignmentRow {
     id:          String,
     schedule_id: String,
-    day_of_week: i8,   // MariaDB TINYINT is signed
+    /// Raw `CAST(... AS SIGNED)` value; see [`DayOfWeek`].
+    day_of_week: i64,
+    persistent:  Option<bool>,
     start_date:  Option<String>,
     end_date:    Option<String>,
+    /// Set on one-off overrides created via `/calendar/{child_id}/override`.
+    is_override: bool,
 }
 
-#[derive(sqlx::FromRow, Serialize, Clone)]
+#[derive(sqlx::FromRow, Serialize, Clone, utoipa::ToSchema)]
 struct ActivityCardRow {
     id:           String,
     activity_card_id: Option<String>,
-    title:        String,
+    title:        Option<String>, // NULL when the library card was deleted
     description:  Option<String>,
     picture_path: Option<String>,
+    #[sqlx(skip)]
+    picture_url:  Option<String>,
     start_time:   String,
     end_time:     Option<String>,
     sort_order:   i32,
+    /// Only filled with `?expand=pictograms`.
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pictogram:    Option<CardPictogram>,
+}
+
+#[derive(sqlx::FromRow, Serialize, Clone, utoipa::ToSchema)]
+struct CardPictogram {
+    #[serde(skip)]
+    card_id:         String,
+    arasaac_id:      i32,
+    image_url:       Option<String>,
+    local_file_path: Option<String>,
+    #[sqlx(skip)]
+    thumb_url:       String,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, utoipa::ToSchema)]
 struct DayView {
     date:          String, // "YYYY-MM-DD"
     day_of_week:   u8,     // 1=Mon … 7=Sun
     assignment_id: Option<String>,
     schedule_id:   Option<String>,
     schedule_name: Option<String>,
+    schedule_color: Option<String>, // "#rrggbb" display color, when set
+    schedule_icon:  Option<String>,
+    persistent:    Option<bool>,
+    start_date:    Option<String>, // "YYYY-MM-DD" when date-bounded
+    end_date:      Option<String>,
+    /// True when a single-date override replaces the weekday assignment.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    is_override:   bool,
     activity_cards: Vec<ActivityCardRow>,
+    /// Index of the card the child is on, as set from their device. Equal to
+    /// the card count once the day is finished; `None` until first set.
+    current_index: Option<u32>,
+    /// Weekday + date formatted per the parent's `locale`/`date_format`;
+    /// only present when requested with `?labels=true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
 }
 
-#[derive(Serialize)]
-struct WeekResponse {
-    year:   i32,
-    week:   u32,
-    monday: String,
-    days:   Vec<DayView>,
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct WeekResponse {
+    year:     i32,
+    week:     u32,
+    monday:   String,
+    /// First day shown, per the parent's `week_start` (equals `monday` by default).
+    start_date: String,
+    week_start: u8,
+    today:    String, // "YYYY-MM-DD" in `timezone`
+    timezone: String,
+    days:     Vec<DayView>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+struct RangeResponse {
+    from:     String,
+    to:       String,
+    today:    String, // "YYYY-MM-DD" in `timezone`
+    timezone: String,
+    days:     Vec<DayView>,
 }
 
 // ── Request bodies ───────────────────────────────────────────
 
-#[derive(Deserialize)]
+/// Longest span `GET /calendar/{child_id}/range` resolves in one request.
+const MAX_RANGE_DAYS: i64 = 31;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+struct RangeQuery {
+    /// First date, `YYYY-MM-DD`.
+    from: String,
+    /// Last date (inclusive), `YYYY-MM-DD`.
+    to: String,
+    #[serde(default)]
+    labels: bool,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+struct WeekQuery {
+    #[serde(default)]
+    labels: bool,
+    /// Comma-separated; only `pictograms` is supported.
+    expand: Option<String>,
+}
+
+impl WeekQuery {
+    fn expand_pictograms(&self) -> AppResult<bool> {
+        let mut pictograms = false;
+        for part in self.expand.as_deref().unwrap_or("").split(',').map(str::trim) {
+            match part {
+                "" => {}
+                "pictograms" => pictograms = true,
+                other => return Err(AppError::BadRequest(format!("Unsupported expand value: {other}"))),
+            }
+        }
+        Ok(pictograms)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
 struct AssignBody {
     schedule_id: String,
-    day_of_week: u8,
+    /// 1 = Monday … 7 = Sunday.
+    #[schema(minimum = 1, maximum = 7)]
+    day_of_week: i64,
     #[serde(default = "default_true")]
     persistent: bool,
     start_date: Option<String>,
     end_date: Option<String>,
 }
 
+#[derive(Deserialize, utoipa::ToSchema)]
+struct OverrideBody {
+    schedule_id: String,
+    /// "YYYY-MM-DD"; the weekday is derived from it.
+    date: String,
+}
+
 fn default_true() -> bool {
     true
 }
 
+#[derive(Deserialize, utoipa::ToSchema)]
+struct ProgressBody {
+    current_index: u32,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+struct ProgressResponse {
+    date: String,
+    current_index: u32,
+    card_count: usize,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+struct NowNextResponse {
+    date: String,
+    current_index: u32,
+    current: Option<ActivityCardRow>,
+    next: Option<ActivityCardRow>,
+    /// Set when `next` is tomorrow's first card (`end_behavior = next_day`).
+    next_date: Option<String>,
+    /// True once every card of today has been completed (or there are none).
+    done: bool,
+    /// `done` or `next_day`, from today's schedule.
+    end_behavior: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+struct DayOption {
+    schedule_id: String,
+    schedule_name: String,
+    schedule_color: Option<String>,
+    schedule_icon: Option<String>,
+    /// The assignment (or override) that makes the schedule apply.
+    assignment_id: String,
+    is_override: bool,
+    /// Shown when nothing is selected for the date.
+    is_default: bool,
+    /// Shown on the date right now.
+    selected: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+struct DayOptionsResponse {
+    date: String,
+    /// Schedule picked from a device for this date, if any and still valid.
+    selected_schedule_id: Option<String>,
+    /// Highest priority first.
+    options: Vec<DayOption>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+struct SelectDayBody {
+    /// One of the date's options; `null` returns the date to the default.
+    schedule_id: Option<String>,
+}
+
 // ── Auth helper ──────────────────────────────────────────────
 
 /// Verify the caller may access this child's calendar.
@@ -113,48 +285,111 @@ async fn assert_calendar_access(
 
     // Parents own child profiles
     let ok: bool = sqlx::query_scalar(
-        "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ?)",
+        "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ? AND deleted_at IS NULL)",
     )
     .bind(child_profile_id)
     .bind(&caller.user_id)
     .fetch_one(pool)
     .await?;
     if !ok {
-        return Err(AppError::Forbidden);
+        return Err(AppError::NotOwned);
     }
     Ok(())
 }
 
 // ── Handlers ─────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/calendar/{child_id}/week/{iso_week}",
+    tag = "calendar",
+    params(("child_id" = String, Path, description = "Child id"), ("iso_week" = String, Path, description = "YYYY-Wnn"), WeekQuery),
+    responses((status = 200, description = "Resolved week", body = WeekResponse), (status = 400, description = "Bad ISO week", body = super::openapi::ErrorResponse)),
+)]
 async fn get_week(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((child_profile_id, iso_week)): Path<(String, String)>,
+    Query(q): Query<WeekQuery>,
 ) -> AppResult<Json<WeekResponse>> {
     let pool = &state.pool;
     assert_calendar_access(pool, &child_profile_id, &user).await?;
 
-    let week_data = load_week_for_child(pool, &child_profile_id, &iso_week).await?;
+    let mut week_data = load_week_for_child(
+        pool,
+        &child_profile_id,
+        &iso_week,
+        &state.config,
+        false,
+    )
+    .await?;
+    if q.labels {
+        apply_day_labels(pool, &child_profile_id, &mut week_data.days).await?;
+    }
     Ok(Json(week_data))
 }
 
-async fn get_week_child(
+#[utoipa::path(
+    get,
+    path = "/calendar/{child_id}/range",
+    tag = "calendar",
+    params(("child_id" = String, Path, description = "Child id"), RangeQuery),
+    responses((status = 200, description = "Resolved dates", body = RangeResponse), (status = 400, description = "Bad or too long range", body = super::openapi::ErrorResponse)),
+)]
+async fn get_range(
     State(state): State<AppState>,
-    cookies: Cookies,
-    Path((child_id, iso_week)): Path<(String, String)>,
-) -> AppResult<Json<WeekResponse>> {
-    const CHILD_SESSION_COOKIE: &str = "child_session";
+    Extension(user): Extension<AuthUser>,
+    Path(child_profile_id): Path<String>,
+    Query(q): Query<RangeQuery>,
+) -> AppResult<Json<RangeResponse>> {
+    let parse = |field: &str, raw: &str| {
+        NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+            .map_err(|_| AppError::BadRequest(format!("{field} must be in YYYY-MM-DD format")))
+    };
+    let from = parse("from", &q.from)?;
+    let to = parse("to", &q.to)?;
+    if to < from {
+        return Err(AppError::BadRequest("to must be on or after from".into()));
+    }
+    let count = (to - from).num_days() + 1;
+    if count > MAX_RANGE_DAYS {
+        return Err(AppError::BadRequest(format!("Range is limited to {MAX_RANGE_DAYS} days")));
+    }
 
     let pool = &state.pool;
+    assert_calendar_access(pool, &child_profile_id, &user).await?;
+
+    let tz = parent_timezone(pool, &child_profile_id).await?.unwrap_or(state.config.app_default_timezone);
+    let today = local_date(Utc::now(), tz);
+    let mut days = load_days(pool, &child_profile_id, from, count, &state.config, false).await?;
+    if q.labels {
+        apply_day_labels(pool, &child_profile_id, &mut days).await?;
+    }
+
+    Ok(Json(RangeResponse {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+        today: today.format("%Y-%m-%d").to_string(),
+        timezone: tz.name().to_string(),
+        days,
+    }))
+}
+
+/// Validate the `child_session` device cookie for `child_id`, touch its
+/// `last_used_at` and return the device id.
+async fn authenticate_child_device(
+    pool: &crate::db::Db,
+    cookies: &Cookies,
+    child_id: &str,
+) -> AppResult<String> {
+    const CHILD_SESSION_COOKIE: &str = "child_session";
+
     let raw = cookies
         .get(CHILD_SESSION_COOKIE)
         .map(|c| c.value().to_owned())
         .ok_or(AppError::Unauthorized)?;
 
-    let mut hasher = Sha256::new();
-    hasher.update(raw.as_bytes());
-    let token_hash = format!("{:x}", hasher.finalize());
+    let token_hash = crate::auth::hash_token(&raw);
 
     #[derive(sqlx::FromRow)]
     struct ChildAccessRow {
@@ -163,9 +398,10 @@ async fn get_week_child(
     }
 
     let access = sqlx::query_as::<_, ChildAccessRow>(
-        "SELECT id, child_id
-         FROM child_device_tokens
-         WHERE token_hash = ? AND revoked_at IS NULL
+        "SELECT d.id, d.child_id
+         FROM child_device_tokens d
+         JOIN child_profiles cp ON cp.id = d.child_id AND cp.deleted_at IS NULL
+         WHERE d.token_hash = ? AND d.revoked_at IS NULL
          LIMIT 1",
     )
     .bind(&token_hash)
@@ -182,14 +418,418 @@ async fn get_week_child(
         .execute(pool)
         .await?;
 
-    let week_data = load_week_for_child(pool, &child_id, &iso_week).await?;
+    Ok(access.id)
+}
+
+#[utoipa::path(
+    get,
+    path = "/child/{child_id}/week/{iso_week}",
+    tag = "child",
+    params(("child_id" = String, Path, description = "Child id"), ("iso_week" = String, Path, description = "YYYY-Wnn"), WeekQuery),
+    responses((status = 200, description = "Week as the child sees it", body = WeekResponse), (status = 401, description = "No child session", body = super::openapi::ErrorResponse)),
+)]
+async fn get_week_child(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Path((child_id, iso_week)): Path<(String, String)>,
+    Query(q): Query<WeekQuery>,
+) -> AppResult<Json<WeekResponse>> {
+    let pool = &state.pool;
+    authenticate_child_device(pool, &cookies, &child_id).await?;
+    let expand_pictograms = q.expand_pictograms()?;
+
+    let mut week_data = load_week_for_child(
+        pool,
+        &child_id,
+        &iso_week,
+        &state.config,
+        true,
+    )
+    .await?;
+    if q.labels {
+        apply_day_labels(pool, &child_id, &mut week_data.days).await?;
+    }
+    if expand_pictograms {
+        expand_card_pictograms(pool, &mut week_data).await?;
+    }
     Ok(Json(week_data))
 }
 
-async fn load_week_for_child(
+/// PUT /child/{child_id}/progress — move today's "where am I" pointer.
+///
+/// `current_index` is validated against the cards the child sees today
+/// (`0..=card_count`, where `card_count` marks the day as finished).
+#[utoipa::path(
+    put,
+    path = "/child/{child_id}/progress",
+    tag = "child",
+    params(("child_id" = String, Path, description = "Child id")),
+    request_body = ProgressBody,
+    responses((status = 200, description = "Progress stored", body = ProgressResponse)),
+)]
+async fn set_child_progress(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Path(child_id): Path<String>,
+    Json(body): Json<ProgressBody>,
+) -> AppResult<Json<ProgressResponse>> {
+    let pool = &state.pool;
+    let device_id = authenticate_child_device(pool, &cookies, &child_id).await?;
+
+    let tz = parent_timezone(pool, &child_id).await?.unwrap_or(state.config.app_default_timezone);
+    let today = local_date(Utc::now(), tz);
+    let week = load_week_containing(pool, &child_id, today, &state.config, true).await?;
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let card_count = week
+        .days
+        .iter()
+        .find(|d| d.date == today_str)
+        .map(|d| d.activity_cards.len())
+        .unwrap_or(0);
+
+    if card_count == 0 {
+        return Err(AppError::BadRequest("No activity cards scheduled for today".into()));
+    }
+    if body.current_index as usize > card_count {
+        return Err(AppError::BadRequest(format!(
+            "current_index must be between 0 and {card_count}"
+        )));
+    }
+
+    sqlx::query(
+        "INSERT INTO child_day_progress (child_id, progress_date, current_index, device_id)
+         VALUES (?, ?, ?, ?)
+         ON DUPLICATE KEY UPDATE current_index = VALUES(current_index), device_id = VALUES(device_id)",
+    )
+    .bind(&child_id)
+    .bind(today)
+    .bind(body.current_index)
+    .bind(&device_id)
+    .execute(pool)
+    .await?;
+
+    Ok(Json(ProgressResponse {
+        date: today_str,
+        current_index: body.current_index,
+        card_count,
+    }))
+}
+
+/// GET /child/{child_id}/now-next — what the child is on and what follows,
+/// based on today's progress pointer. After the last card the schedule's
+/// `end_behavior` decides whether `next` is empty (`done`) or tomorrow's first
+/// card (`next_day`).
+#[utoipa::path(
+    get,
+    path = "/child/{child_id}/now-next",
+    tag = "child",
+    params(("child_id" = String, Path, description = "Child id")),
+    responses((status = 200, description = "Current and next card", body = NowNextResponse)),
+)]
+async fn get_child_now_next(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Path(child_id): Path<String>,
+) -> AppResult<Json<NowNextResponse>> {
+    let pool = &state.pool;
+    authenticate_child_device(pool, &cookies, &child_id).await?;
+
+    let tz = parent_timezone(pool, &child_id).await?.unwrap_or(state.config.app_default_timezone);
+    let today = local_date(Utc::now(), tz);
+    let week = load_week_containing(pool, &child_id, today, &state.config, true).await?;
+
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let day = week.days.iter().find(|d| d.date == today_str);
+    let cards: &[ActivityCardRow] = day.map(|d| d.activity_cards.as_slice()).unwrap_or(&[]);
+    let current_index = day.and_then(|d| d.current_index).unwrap_or(0);
+    let idx = current_index as usize;
+
+    let end_behavior = match day.and_then(|d| d.schedule_id.as_deref()) {
+        Some(schedule_id) => sqlx::query_scalar::<_, String>(&format!(
+            "SELECT {} FROM visual_support_documents_templates t WHERE t.id = ?",
+            schedule_sql::END_BEHAVIOR
+        ))
+        .bind(schedule_id)
+        .fetch_optional(pool)
+        .await?
+        .unwrap_or_else(|| "done".to_string()),
+        None => "done".to_string(),
+    };
+
+    let current = cards.get(idx).cloned();
+    let done = current.is_none();
+    let mut next = if done { None } else { cards.get(idx + 1).cloned() };
+    let mut next_date = None;
+
+    if next.is_none() && end_behavior == "next_day" {
+        let tomorrow = today + chrono::Duration::days(1);
+        let tomorrow_str = tomorrow.format("%Y-%m-%d").to_string();
+        let tomorrow_week = if week.days.iter().any(|d| d.date == tomorrow_str) {
+            None
+        } else {
+            Some(load_week_containing(pool, &child_id, tomorrow, &state.config, true).await?)
+        };
+        next = tomorrow_week
+            .as_ref()
+            .unwrap_or(&week)
+            .days
+            .iter()
+            .find(|d| d.date == tomorrow_str)
+            .and_then(|d| d.activity_cards.first().cloned());
+        if next.is_some() {
+            next_date = Some(tomorrow_str);
+        }
+    }
+
+    Ok(Json(NowNextResponse {
+        date: today_str,
+        current_index,
+        current,
+        next,
+        next_date,
+        done,
+        end_behavior,
+    }))
+}
+
+fn parse_path_date(raw: &str, config: &Config) -> AppResult<NaiveDate> {
+    let date = NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::BadRequest("date must be in YYYY-MM-DD format".into()))?;
+    check_date_in_range("date", date, config)?;
+    Ok(date)
+}
+
+/// Every schedule that applies to `date` for the child, one entry per
+/// schedule, flagged with the priority default and what is shown now.
+/// Archived schedules are left out since they are never shown to the child.
+async fn day_options(pool: &crate::db::Db, child_profile_id: &str, date: NaiveDate) -> AppResult<DayOptionsResponse> {
+    let assignments = load_assignments(pool, child_profile_id).await?;
+    let mut candidates: Vec<&AssignmentRow> = applicable_assignments(&assignments, date).collect();
+    candidates.sort_by_key(|a| std::cmp::Reverse(assignment_priority(a)));
+
+    let stored = load_day_selections(pool, child_profile_id, date, 1).await?.pop().map(|(_, id)| id);
+
+    let default_id = pick_assignment(candidates.iter().copied(), None).map(|a| a.schedule_id.clone());
+    let shown_id = pick_assignment(candidates.iter().copied(), stored.as_deref()).map(|a| a.schedule_id.clone());
+
+    // Several assignments of one schedule may apply; describe the one that
+    // wins when it is selected.
+    let mut winners: Vec<&AssignmentRow> = Vec::new();
+    for a in &candidates {
+        if winners.iter().any(|w| w.schedule_id == a.schedule_id) {
+            continue;
+        }
+        if let Some(a) = pick_assignment(candidates.iter().copied(), Some(&a.schedule_id)) {
+            winners.push(a);
+        }
+    }
+
+    #[derive(sqlx::FromRow)]
+    struct NameRow {
+        id: String,
+        name: String,
+        color: Option<String>,
+        icon: Option<String>,
+    }
+    let mut info: HashMap<String, NameRow> = HashMap::new();
+    if !winners.is_empty() {
+        let placeholders = vec!["?"; winners.len()].join(", ");
+        let sql = format!(
+            "SELECT t.id, t.name, {} AS color, {} AS icon
+             FROM visual_support_documents_templates t
+             WHERE t.document_type = ?
+               AND t.id IN ({placeholders})
+               AND {} <> 'archived'",
+            schedule_sql::COLOR,
+            schedule_sql::ICON,
+            schedule_sql::STATUS
+        );
+        let mut query = sqlx::query_as::<_, NameRow>(&sql).bind(WEEKLY_TYPE);
+        for a in &winners {
+            query = query.bind(&a.schedule_id);
+        }
+        info = query.fetch_all(pool).await?.into_iter().map(|r| (r.id.clone(), r)).collect();
+    }
+
+    let options: Vec<DayOption> = winners
+        .into_iter()
+        .filter_map(|a| {
+            let info = info.remove(&a.schedule_id)?;
+            Some(DayOption {
+                schedule_id: a.schedule_id.clone(),
+                schedule_name: info.name,
+                schedule_color: info.color,
+                schedule_icon: info.icon,
+                assignment_id: a.id.clone(),
+                is_override: a.is_override,
+                is_default: default_id.as_ref() == Some(&a.schedule_id),
+                selected: shown_id.as_ref() == Some(&a.schedule_id),
+            })
+        })
+        .collect();
+
+    let selected_schedule_id = stored.filter(|id| options.iter().any(|o| &o.schedule_id == id));
+    Ok(DayOptionsResponse {
+        date: date.format("%Y-%m-%d").to_string(),
+        selected_schedule_id,
+        options,
+    })
+}
+
+/// GET /child/{child_id}/day/{date}/options — the schedules a child can
+/// choose between on `date` when several assignments overlap.
+#[utoipa::path(
+    get,
+    path = "/child/{child_id}/day/{date}/options",
+    tag = "child",
+    params(("child_id" = String, Path, description = "Child id"), ("date" = String, Path, description = "YYYY-MM-DD")),
+    responses((status = 200, description = "Applicable schedules", body = DayOptionsResponse), (status = 401, description = "No child session", body = super::openapi::ErrorResponse)),
+)]
+async fn get_child_day_options(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Path((child_id, date)): Path<(String, String)>,
+) -> AppResult<Json<DayOptionsResponse>> {
+    let pool = &state.pool;
+    authenticate_child_device(pool, &cookies, &child_id).await?;
+    let date = parse_path_date(&date, &state.config)?;
+    Ok(Json(day_options(pool, &child_id, date).await?))
+}
+
+/// POST /child/{child_id}/day/{date}/select — choose which applicable
+/// schedule the child sees on `date`. Switching to another schedule resets
+/// that date's progress pointer, since it indexed the previous cards.
+#[utoipa::path(
+    post,
+    path = "/child/{child_id}/day/{date}/select",
+    tag = "child",
+    params(("child_id" = String, Path, description = "Child id"), ("date" = String, Path, description = "YYYY-MM-DD")),
+    request_body = SelectDayBody,
+    responses(
+        (status = 200, description = "Selection stored", body = DayOptionsResponse),
+        (status = 400, description = "Schedule does not apply to the date", body = super::openapi::ErrorResponse),
+        (status = 401, description = "No child session", body = super::openapi::ErrorResponse),
+    ),
+)]
+async fn select_child_day_schedule(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Path((child_id, date)): Path<(String, String)>,
+    Json(body): Json<SelectDayBody>,
+) -> AppResult<Json<DayOptionsResponse>> {
+    let pool = &state.pool;
+    let device_id = authenticate_child_device(pool, &cookies, &child_id).await?;
+    let date = parse_path_date(&date, &state.config)?;
+
+    let before = day_options(pool, &child_id, date).await?;
+    let shown_before = before.options.iter().find(|o| o.selected).map(|o| o.schedule_id.clone());
+
+    match body.schedule_id.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(schedule_id) => {
+            if !before.options.iter().any(|o| o.schedule_id == schedule_id) {
+                return Err(AppError::BadRequest("schedule_id does not apply to this date".into()));
+            }
+            sqlx::query(
+                "INSERT INTO child_day_selection (child_id, selection_date, schedule_id, device_id)
+                 VALUES (?, ?, ?, ?)
+                 ON DUPLICATE KEY UPDATE schedule_id = VALUES(schedule_id), device_id = VALUES(device_id)",
+            )
+            .bind(&child_id)
+            .bind(date)
+            .bind(schedule_id)
+            .bind(&device_id)
+            .execute(pool)
+            .await?;
+        }
+        None => {
+            sqlx::query("DELETE FROM child_day_selection WHERE child_id = ? AND selection_date = ?")
+                .bind(&child_id)
+                .bind(date)
+                .execute(pool)
+                .await?;
+        }
+    }
+
+    let after = day_options(pool, &child_id, date).await?;
+    let shown_after = after.options.iter().find(|o| o.selected).map(|o| o.schedule_id.clone());
+    if shown_after != shown_before {
+        sqlx::query("DELETE FROM child_day_progress WHERE child_id = ? AND progress_date = ?")
+            .bind(&child_id)
+            .bind(date)
+            .execute(pool)
+            .await?;
+    }
+    Ok(Json(after))
+}
+
+fn iso_week_of(date: NaiveDate) -> String {
+    let iso = date.iso_week();
+    format!("{}-W{:02}", iso.year(), iso.week())
+}
+
+/// Days a week starting on `week_start` (1 = Monday … 7 = Sunday) begins
+/// before the ISO Monday. Weeks always contain their ISO Monday, so the
+/// `YYYY-Wnn` key keeps its meaning: a Sunday-start W10 runs from the Sunday
+/// before W10's Monday through the Saturday after it.
+fn days_before_monday(week_start: u8) -> i64 {
+    (8 - week_start as i64) % 7
+}
+
+/// Load the displayed week that contains `date`, which is not always
+/// `date`'s own ISO week when the parent starts weeks on another day.
+async fn load_week_containing(
+    pool: &crate::db::Db,
+    child_profile_id: &str,
+    date: NaiveDate,
+    config: &Config,
+    child_view: bool,
+) -> AppResult<WeekResponse> {
+    let week_start = parent_week_start(pool, child_profile_id).await?;
+    let key_date = date + chrono::Duration::days(days_before_monday(week_start));
+    load_week_for_child(pool, child_profile_id, &iso_week_of(key_date), config, child_view).await
+}
+
+/// What a child's calendar shows on one date, for reminder emails.
+pub(crate) struct DayPlan {
+    pub schedule_id: String,
+    pub schedule_name: String,
+    /// `(start_time, title)` in display order.
+    pub cards: Vec<(String, String)>,
+}
+
+/// Resolve `date` the same way the child's week view does. `None` when no
+/// schedule is shown that day.
+pub(crate) async fn day_plan(
+    pool: &crate::db::Db,
+    child_profile_id: &str,
+    date: NaiveDate,
+    config: &Config,
+) -> AppResult<Option<DayPlan>> {
+    let week = load_week_containing(pool, child_profile_id, date, config, true).await?;
+    let key = date.format("%Y-%m-%d").to_string();
+    let Some(day) = week.days.into_iter().find(|d| d.date == key) else {
+        return Ok(None);
+    };
+    let (Some(schedule_id), Some(schedule_name)) = (day.schedule_id, day.schedule_name) else {
+        return Ok(None);
+    };
+    let cards = day
+        .activity_cards
+        .into_iter()
+        .map(|c| (c.start_time, c.title.unwrap_or_default()))
+        .collect();
+    Ok(Some(DayPlan { schedule_id, schedule_name, cards }))
+}
+
+/// Build the week view for a child. With `child_view` set, activity cards that
+/// resolve to no title and no picture are dropped so the child never sees a
+/// blank card; parent/admin reads keep them so they can be repaired.
+pub(super) async fn load_week_for_child(
     pool: &crate::db::Db,
     child_profile_id: &str,
     iso_week: &str,
+    config: &Config,
+    child_view: bool,
 ) -> AppResult<WeekResponse> {
 
     // Parse "YYYY-Wnn"
@@ -199,106 +839,475 @@ async fn load_week_for_child(
     // Monday of that ISO week
     let monday = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
         .ok_or_else(|| AppError::BadRequest("Invalid ISO week".into()))?;
+    let week_start = parent_week_start(pool, child_profile_id).await?;
+    let start = monday - chrono::Duration::days(days_before_monday(week_start));
 
-    // Fetch assignments for this child and weekday.
-        let assignments: Vec<AssignmentRow> = sqlx::query_as::<_, AssignmentRow>(
-                "SELECT
-                        d.id,
-                        d.template_id AS schedule_id,
-                        CAST(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.day_of_week')) AS SIGNED) AS day_of_week,
-                    CAST(NULLIF(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.start_date')), ''), 'null') AS CHAR(10)) AS start_date,
-                    CAST(NULLIF(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.end_date')), ''), 'null') AS CHAR(10)) AS end_date
-                 FROM visual_support_documents d
-                 WHERE d.child_id = ?
-                     AND d.document_type = ?
-                     AND d.template_id IS NOT NULL
-                 ORDER BY d.created_at DESC",
-        )
-        .bind(child_profile_id)
-        .bind(WEEKLY_TYPE)
-    .fetch_all(pool).await?;
+    let tz = parent_timezone(pool, child_profile_id).await?.unwrap_or(config.app_default_timezone);
+    let today = local_date(Utc::now(), tz);
+    let days = load_days(pool, child_profile_id, start, 7, config, child_view).await?;
+
+    Ok(WeekResponse {
+        year,
+        week,
+        monday: monday.format("%Y-%m-%d").to_string(),
+        start_date: start.format("%Y-%m-%d").to_string(),
+        week_start,
+        today: today.format("%Y-%m-%d").to_string(),
+        timezone: tz.name().to_string(),
+        days,
+    })
+}
+
+/// Resolve `count` consecutive days from `start`: for each date the winning
+/// assignment (see [`pick_assignment`]), its schedule's cards and the child's
+/// progress pointer.
+async fn load_days(
+    pool: &crate::db::Db,
+    child_profile_id: &str,
+    start: NaiveDate,
+    count: i64,
+    config: &Config,
+    child_view: bool,
+) -> AppResult<Vec<DayView>> {
+    let assignments = load_assignments(pool, child_profile_id).await?;
+
+    #[derive(sqlx::FromRow)]
+    struct ProgressRow {
+        progress_date: NaiveDate,
+        current_index: i32,
+    }
+    let progress: Vec<ProgressRow> = sqlx::query_as::<_, ProgressRow>(
+        "SELECT progress_date, current_index
+         FROM child_day_progress
+         WHERE child_id = ? AND progress_date BETWEEN ? AND ?",
+    )
+    .bind(child_profile_id)
+    .bind(start)
+    .bind(start + chrono::Duration::days(count - 1))
+    .fetch_all(pool)
+    .await?;
+    let selections = load_day_selections(pool, child_profile_id, start, count).await?;
 
     let mut days: Vec<DayView> = Vec::new();
-    for dow in 1u8..=7 {
-        let date = monday + chrono::Duration::days((dow - 1) as i64);
-        let date_s = date.format("%Y-%m-%d").to_string();
-        let assignment = assignments
-            .iter()
-            .filter(|a| a.day_of_week == dow as i8)
-            .filter(|a| assignment_applies_to_date(a, &date_s))
-            .max_by(|a, b| assignment_priority(a).cmp(&assignment_priority(b)));
+    for offset in 0..count {
+        let date = start + chrono::Duration::days(offset);
+        let dow = DayOfWeek::of(date);
+        let selected = selections.iter().find(|(d, _)| *d == date).map(|(_, s)| s.as_str());
+        let assignment = pick_assignment(applicable_assignments(&assignments, date), selected);
 
-        let (assignment_id, schedule_id, schedule_name, activity_cards) = if let Some(a) = assignment {
+        let (assignment_id, schedule_id, schedule_name, schedule_color, schedule_icon, activity_cards) = if let Some(a) = assignment {
             #[derive(sqlx::FromRow)]
             struct NameRow {
                 name: String,
+                color: Option<String>,
+                icon: Option<String>,
             }
             let s: Option<NameRow> = sqlx::query_as::<_, NameRow>(
-                "SELECT t.name
-                 FROM visual_support_documents_templates t
-                 WHERE t.id = ?
-                   AND t.document_type = ?
-                   AND COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') <> 'archived'",
+                &format!(
+                    "SELECT t.name, {} AS color, {} AS icon
+                     FROM visual_support_documents_templates t
+                     WHERE t.id = ?
+                       AND t.document_type = ?
+                       AND {} <> 'archived'",
+                    schedule_sql::COLOR,
+                    schedule_sql::ICON,
+                    schedule_sql::STATUS
+                ),
             )
             .bind(&a.schedule_id)
             .bind(WEEKLY_TYPE)
             .fetch_optional(pool).await?;
 
             if let Some(s) = s {
-                let activity_cards: Vec<ActivityCardRow> = sqlx::query_as::<_, ActivityCardRow>(
-                    "SELECT
-                        vta.id,
-                        vta.activity_card_id,
-                        COALESCE(NULLIF(vta.text_label, ''), vsa.label_text) AS title,
-                        vta.optional_notes AS description,
-                        CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.picture_path')), ''), vsa.local_image_path) AS CHAR(500)) AS picture_path,
-                        CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.start_time')), ''), '08:00') AS CHAR(5)) AS start_time,
-                        CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.end_time')), '') AS CHAR(5)) AS end_time,
-                        vta.activity_order AS sort_order
-                     FROM visual_support_template_activities vta
-                     LEFT JOIN visual_support_activity_library vsa ON vsa.id = vta.activity_card_id
-                     WHERE vta.template_id = ?
-                     ORDER BY vta.activity_order",
+                let mut activity_cards: Vec<ActivityCardRow> = sqlx::query_as::<_, ActivityCardRow>(
+                    &schedule_sql::select_activity_cards(false, &config.default_activity_start_time, "vta.template_id = ? ORDER BY vta.activity_order"),
                 )
                  .bind(&a.schedule_id)
                 .fetch_all(pool).await?;
+                for card in &mut activity_cards {
+                    card.picture_url = resolve_public_asset(config, card.picture_path.as_deref());
+                }
 
-                (Some(a.id.clone()), Some(a.schedule_id.clone()), Some(s.name), activity_cards)
+                let activity_cards = if child_view {
+                    activity_cards.into_iter().filter(|c| !is_unresolved_card(c)).collect()
+                } else {
+                    activity_cards
+                };
+
+                (Some(a.id.clone()), Some(a.schedule_id.clone()), Some(s.name), s.color, s.icon, activity_cards)
             } else {
                 // Archived or missing schedules are hidden from child-facing reads.
-                (None, None, None, vec![])
+                (None, None, None, None, None, vec![])
             }
         } else {
-            (None, None, None, vec![])
+            (None, None, None, None, None, vec![])
         };
 
+        // Only describe the assignment when it is actually shown for this day.
+        let shown = assignment.filter(|_| assignment_id.is_some());
+        let bound = |raw: Option<&str>| parse_bound(raw).ok().flatten();
+        let shown_start = shown.and_then(|a| bound(a.start_date.as_deref()));
+        let shown_end = shown.and_then(|a| bound(a.end_date.as_deref()));
+
         days.push(DayView {
             date: date.format("%Y-%m-%d").to_string(),
-            day_of_week: dow,
+            day_of_week: dow.get(),
             assignment_id,
             schedule_id,
             schedule_name,
+            schedule_color,
+            schedule_icon,
+            persistent: shown.map(|a| a.persistent.unwrap_or(shown_start.is_none() && shown_end.is_none())),
+            start_date: shown_start.map(|d| d.format("%Y-%m-%d").to_string()),
+            end_date: shown_end.map(|d| d.format("%Y-%m-%d").to_string()),
+            is_override: shown.is_some_and(|a| a.is_override),
             activity_cards,
+            current_index: progress
+                .iter()
+                .find(|p| p.progress_date == date)
+                .map(|p| p.current_index.max(0) as u32),
+            label: None,
         });
     }
 
-    Ok(WeekResponse {
-        year,
-        week,
-        monday: monday.format("%Y-%m-%d").to_string(),
-        days,
-    })
+    Ok(days)
+}
+
+/// `(date, schedule_id)` picked from the child's device for `count` days
+/// from `start`. Schedules archived since then are skipped so those days fall
+/// back to the default instead of going blank.
+async fn load_day_selections(
+    pool: &crate::db::Db,
+    child_profile_id: &str,
+    start: NaiveDate,
+    count: i64,
+) -> AppResult<Vec<(NaiveDate, String)>> {
+    let rows = sqlx::query_as::<_, (NaiveDate, String)>(&format!(
+        "SELECT s.selection_date, s.schedule_id
+         FROM child_day_selection s
+         JOIN visual_support_documents_templates t ON t.id = s.schedule_id
+         WHERE s.child_id = ? AND s.selection_date BETWEEN ? AND ?
+           AND {} <> 'archived'",
+        schedule_sql::STATUS
+    ))
+    .bind(child_profile_id)
+    .bind(start)
+    .bind(start + chrono::Duration::days(count - 1))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Assignments whose weekday and date bounds cover `date`.
+fn applicable_assignments(assignments: &[AssignmentRow], date: NaiveDate) -> impl Iterator<Item = &AssignmentRow> {
+    let dow = DayOfWeek::of(date);
+    assignments
+        .iter()
+        .filter(move |a| DayOfWeek::try_from(a.day_of_week).ok() == Some(dow))
+        .filter(move |a| assignment_applies_to_date(a, date))
+}
+
+/// The assignment shown for a day: the one for the schedule picked on the
+/// child's device when it still applies, otherwise the highest
+/// [`assignment_priority`].
+fn pick_assignment<'a>(
+    candidates: impl Iterator<Item = &'a AssignmentRow>,
+    selected_schedule_id: Option<&str>,
+) -> Option<&'a AssignmentRow> {
+    let mut best: Option<&AssignmentRow> = None;
+    let mut best_selected: Option<&AssignmentRow> = None;
+    for a in candidates {
+        let slot = if selected_schedule_id == Some(a.schedule_id.as_str()) { &mut best_selected } else { &mut best };
+        if slot.is_none_or(|b| assignment_priority(a) >= assignment_priority(b)) {
+            *slot = Some(a);
+        }
+    }
+    best_selected.or(best)
+}
+
+/// Every weekday assignment and override of a child, newest first.
+async fn load_assignments(pool: &crate::db::Db, child_profile_id: &str) -> AppResult<Vec<AssignmentRow>> {
+    let assignments = sqlx::query_as::<_, AssignmentRow>(
+        "SELECT
+            d.id,
+            d.template_id AS schedule_id,
+            CAST(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.day_of_week')) AS SIGNED) AS day_of_week,
+            IF(JSON_EXTRACT(d.content_json, '$.assignment.persistent') IS NULL, NULL,
+               IF(JSON_EXTRACT(d.content_json, '$.assignment.persistent') = true, 1, 0)) AS persistent,
+            CAST(NULLIF(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.start_date')), ''), 'null') AS CHAR(10)) AS start_date,
+            CAST(NULLIF(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.end_date')), ''), 'null') AS CHAR(10)) AS end_date,
+            IFNULL(JSON_EXTRACT(d.content_json, '$.assignment.override') = true, 0) AS is_override
+         FROM visual_support_documents d
+         WHERE d.child_id = ?
+           AND d.document_type = ?
+           AND d.template_id IS NOT NULL
+         ORDER BY d.created_at DESC",
+    )
+    .bind(child_profile_id)
+    .bind(WEEKLY_TYPE)
+    .fetch_all(pool)
+    .await?;
+    Ok(assignments)
+}
+
+/// One day assignment of a schedule, as listed by [`child_schedules`].
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ChildScheduleAssignment {
+    assignment_id: String,
+    day_of_week: u8, // 1=Mon … 7=Sun
+    start_date: Option<String>,
+    end_date: Option<String>,
+    is_override: bool,
+}
+
+/// A schedule on a child's calendar with the days it is assigned to.
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ChildSchedule {
+    schedule_id: String,
+    name: String,
+    /// `active`, `inactive` or `archived`; archived schedules are not shown
+    /// to the child.
+    status: String,
+    color: Option<String>,
+    icon: Option<String>,
+    assignments: Vec<ChildScheduleAssignment>,
+}
+
+/// Schedules assigned to a child that still apply today or later (in the
+/// parent's timezone), ordered by name. Access is checked by the caller.
+pub(super) async fn child_schedules(
+    pool: &crate::db::Db,
+    child_profile_id: &str,
+    config: &Config,
+) -> AppResult<Vec<ChildSchedule>> {
+    let tz = parent_timezone(pool, child_profile_id).await?.unwrap_or(config.app_default_timezone);
+    let today = local_date(Utc::now(), tz);
+
+    let mut by_schedule: std::collections::BTreeMap<String, Vec<ChildScheduleAssignment>> = Default::default();
+    for a in load_assignments(pool, child_profile_id).await? {
+        let (Ok(start), Ok(end)) = (parse_bound(a.start_date.as_deref()), parse_bound(a.end_date.as_deref())) else {
+            continue;
+        };
+        let Ok(day) = DayOfWeek::try_from(a.day_of_week) else { continue };
+        if end.is_some_and(|e| e < today) {
+            continue;
+        }
+        by_schedule.entry(a.schedule_id).or_default().push(ChildScheduleAssignment {
+            assignment_id: a.id,
+            day_of_week: day.get(),
+            start_date: start.map(|d| d.format("%Y-%m-%d").to_string()),
+            end_date: end.map(|d| d.format("%Y-%m-%d").to_string()),
+            is_override: a.is_override,
+        });
+    }
+    if by_schedule.is_empty() {
+        return Ok(vec![]);
+    }
+
+    #[derive(sqlx::FromRow)]
+    struct ScheduleInfo {
+        id: String,
+        name: String,
+        status: String,
+        color: Option<String>,
+        icon: Option<String>,
+    }
+    let placeholders = vec!["?"; by_schedule.len()].join(", ");
+    let sql = format!(
+        "SELECT t.id, t.name, {} AS status, {} AS color, {} AS icon
+         FROM visual_support_documents_templates t
+         WHERE t.document_type = ? AND t.id IN ({placeholders})
+         ORDER BY t.name",
+        schedule_sql::STATUS,
+        schedule_sql::COLOR,
+        schedule_sql::ICON,
+    );
+    let mut query = sqlx::query_as::<_, ScheduleInfo>(&sql).bind(WEEKLY_TYPE);
+    for id in by_schedule.keys() {
+        query = query.bind(id);
+    }
+    let schedules = query.fetch_all(pool).await?;
+
+    Ok(schedules
+        .into_iter()
+        .map(|s| {
+            let mut assignments = by_schedule.remove(&s.id).unwrap_or_default();
+            assignments.sort_by(|a, b| (a.day_of_week, &a.start_date).cmp(&(b.day_of_week, &b.start_date)));
+            ChildSchedule {
+                schedule_id: s.id,
+                name: s.name,
+                status: s.status,
+                color: s.color,
+                icon: s.icon,
+                assignments,
+            }
+        })
+        .collect())
 }
 
+/// The timezone configured by the child's parent, if it is a known IANA name.
+async fn parent_timezone(pool: &crate::db::Db, child_profile_id: &str) -> AppResult<Option<Tz>> {
+    let tz: Option<String> = sqlx::query_scalar(
+        "SELECT u.timezone
+         FROM child_profiles cp
+         JOIN users u ON u.id = cp.parent_id
+         WHERE cp.id = ?",
+    )
+    .bind(child_profile_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(tz.and_then(|raw| raw.trim().parse::<Tz>().ok()))
+}
+
+/// The parent's preferred first day of the week (1 = Monday … 7 = Sunday).
+async fn parent_week_start(pool: &crate::db::Db, child_profile_id: &str) -> AppResult<u8> {
+    let week_start: Option<i16> = sqlx::query_scalar(
+        "SELECT u.week_start
+         FROM child_profiles cp
+         JOIN users u ON u.id = cp.parent_id
+         WHERE cp.id = ?",
+    )
+    .bind(child_profile_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(week_start
+        .filter(|d| (1..=7).contains(d))
+        .map(|d| d as u8)
+        .unwrap_or(1))
+}
+
+/// Attach the locally cached pictogram behind each card's library entry, in
+/// one query for the whole week. Cards without a cached pictogram are left
+/// unexpanded.
+async fn expand_card_pictograms(pool: &crate::db::Db, week: &mut WeekResponse) -> AppResult<()> {
+    // The same schedule is often assigned to several days.
+    let mut card_ids: Vec<&str> = week
+        .days
+        .iter()
+        .flat_map(|d| d.activity_cards.iter().map(|c| c.id.as_str()))
+        .collect();
+    card_ids.sort_unstable();
+    card_ids.dedup();
+    if card_ids.is_empty() {
+        return Ok(());
+    }
+
+    let placeholders = vec!["?"; card_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT vta.id AS card_id, p.arasaac_id, p.image_url, p.local_file_path
+         FROM visual_support_template_activities vta
+         JOIN visual_support_activity_library vsa ON vsa.id = vta.activity_card_id
+         JOIN pictograms p ON p.arasaac_id = vsa.arasaac_id
+         WHERE vta.id IN ({placeholders})"
+    );
+    let mut query = sqlx::query_as::<_, CardPictogram>(&sql);
+    for id in &card_ids {
+        query = query.bind(*id);
+    }
+    let found: HashMap<String, CardPictogram> = query
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|mut p| {
+            p.thumb_url = crate::services::pictograms::build_remote_thumb_url(p.arasaac_id);
+            (p.card_id.clone(), p)
+        })
+        .collect();
+
+    for card in week.days.iter_mut().flat_map(|d| d.activity_cards.iter_mut()) {
+        card.pictogram = found.get(&card.id).cloned();
+    }
+    Ok(())
+}
+
+/// Fill `DayView::label` using the owning parent's `locale` and `date_format`
+/// (the values normalized by `PATCH /users/me`).
+async fn apply_day_labels(
+    pool: &crate::db::Db,
+    child_profile_id: &str,
+    days: &mut [DayView],
+) -> AppResult<()> {
+    #[derive(sqlx::FromRow)]
+    struct DatePrefsRow {
+        locale: String,
+        date_format: String,
+    }
+    let prefs = sqlx::query_as::<_, DatePrefsRow>(
+        "SELECT u.locale, u.date_format
+         FROM child_profiles cp
+         JOIN users u ON u.id = cp.parent_id
+         WHERE cp.id = ?",
+    )
+    .bind(child_profile_id)
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or(DatePrefsRow { locale: "en-GB".into(), date_format: "locale".into() });
+
+    for day in days.iter_mut() {
+        if let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") {
+            day.label = Some(day_label(date, &prefs.locale, &prefs.date_format));
+        }
+    }
+    Ok(())
+}
+
+const WEEKDAYS_EN: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+const WEEKDAYS_DA: [&str; 7] = ["mandag", "tirsdag", "onsdag", "torsdag", "fredag", "lørdag", "søndag"];
+const MONTHS_EN: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+const MONTHS_DA: [&str; 12] = [
+    "januar", "februar", "marts", "april", "maj", "juni",
+    "juli", "august", "september", "oktober", "november", "december",
+];
+
+/// "Monday 05-10-2026" style label. Only the UI languages we ship (`en`,
+/// `da`) have name tables; other locales fall back to English names.
+fn day_label(date: NaiveDate, locale: &str, date_format: &str) -> String {
+    let language = locale.split(['-', '_']).next().unwrap_or("en").to_ascii_lowercase();
+    let (weekdays, months) = if language == "da" {
+        (WEEKDAYS_DA, MONTHS_DA)
+    } else {
+        (WEEKDAYS_EN, MONTHS_EN)
+    };
+    let weekday = weekdays[date.weekday().num_days_from_monday() as usize];
+    let month = months[date.month0() as usize];
+
+    let formatted = match date_format {
+        "dd-mm-yyyy" => date.format("%d-%m-%Y").to_string(),
+        "mm/dd/yyyy" => date.format("%m/%d/%Y").to_string(),
+        "dd_month_yyyy" => format!("{:02} {month} {}", date.day(), date.year()),
+        // "locale": long date in the locale's conventional order.
+        _ if language == "da" => format!("{}. {month} {}", date.day(), date.year()),
+        _ if locale.eq_ignore_ascii_case("en-US") => format!("{month} {}, {}", date.day(), date.year()),
+        _ => format!("{} {month} {}", date.day(), date.year()),
+    };
+
+    if language == "da" {
+        format!("{weekday} d. {formatted}")
+    } else {
+        format!("{weekday} {formatted}")
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/calendar/{child_id}/assign",
+    tag = "calendar",
+    params(("child_id" = String, Path, description = "Child id")),
+    request_body = AssignBody,
+    responses((status = 204, description = "Assigned"), (status = 409, description = "Schedule archived", body = super::openapi::ErrorResponse)),
+)]
 async fn assign(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(child_profile_id): Path<String>,
     Json(body): Json<AssignBody>,
 ) -> AppResult<StatusCode> {
-    if !(1..=7).contains(&body.day_of_week) {
-        return Err(AppError::BadRequest("day_of_week must be 1–7".into()));
-    }
+    let day_of_week = DayOfWeek::parse("day_of_week", body.day_of_week)?;
 
     let start_raw = body.start_date.as_deref().map(str::trim).filter(|s| !s.is_empty());
     let end_raw = body.end_date.as_deref().map(str::trim).filter(|s| !s.is_empty());
@@ -323,6 +1332,8 @@ async fn assign(
         if end < start {
             return Err(AppError::BadRequest("end_date must be on or after start_date".into()));
         }
+        check_date_in_range("start_date", start, &state.config)?;
+        check_date_in_range("end_date", end, &state.config)?;
 
         (Some(start), Some(end))
     };
@@ -334,83 +1345,29 @@ async fn assign(
         return Err(AppError::Forbidden);
     }
     assert_calendar_access(pool, &child_profile_id, &user).await?;
+    let schedule_row = assignable_schedule(pool, &body.schedule_id, &user).await?;
 
-    #[derive(sqlx::FromRow)]
-    struct ScheduleAccessRow {
-        owner_id: Option<String>,
-        name: String,
-    }
-
-    let schedule_row: Option<ScheduleAccessRow> = if user.role == UserRole::Admin {
-        sqlx::query_as::<_, ScheduleAccessRow>(
-            "SELECT owner_id, name
-             FROM visual_support_documents_templates
-             WHERE id = ?
-               AND document_type = ?
-               AND COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.schedule.status')), ''), 'inactive') <> 'archived'",
-        )
-        .bind(&body.schedule_id)
-        .bind(WEEKLY_TYPE)
-        .fetch_optional(pool)
-        .await?
-    } else {
-        sqlx::query_as::<_, ScheduleAccessRow>(
-            "SELECT owner_id, name
-             FROM visual_support_documents_templates
-             WHERE id = ?
-               AND owner_id = ?
-               AND document_type = ?
-               AND COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.schedule.status')), ''), 'inactive') <> 'archived'",
-        )
-        .bind(&body.schedule_id)
-        .bind(&user.user_id)
-        .bind(WEEKLY_TYPE)
-        .fetch_optional(pool)
-        .await?
-    };
-
-    let Some(schedule_row) = schedule_row else {
-        return Err(AppError::Forbidden);
-    };
-
-    let has_activity_cards: bool = sqlx::query_scalar(
-        "SELECT EXISTS(SELECT 1 FROM visual_support_template_activities WHERE template_id = ?)",
-    )
-    .bind(&body.schedule_id)
-    .fetch_one(pool)
-    .await?;
-
-    if !has_activity_cards {
-        return Err(AppError::BadRequest(
-            "Cannot assign a schedule with no activity cards. Add at least one activity card first.".into(),
-        ));
-    }
-
+    // Replace the weekday's recurring assignment; one-off overrides stay.
     sqlx::query(
         "DELETE FROM visual_support_documents
          WHERE child_id = ?
            AND document_type = ?
-           AND CAST(JSON_UNQUOTE(JSON_EXTRACT(content_json, '$.assignment.day_of_week')) AS SIGNED) = ?",
+           AND CAST(JSON_UNQUOTE(JSON_EXTRACT(content_json, '$.assignment.day_of_week')) AS SIGNED) = ?
+           AND IFNULL(JSON_EXTRACT(content_json, '$.assignment.override') = true, 0) = 0",
     )
     .bind(&child_profile_id)
     .bind(WEEKLY_TYPE)
-    .bind(body.day_of_week as i32)
+    .bind(day_of_week.get())
     .execute(pool)
     .await?;
 
-    let assignment_owner = if user.role == UserRole::Admin {
-        schedule_row.owner_id.unwrap_or_else(|| user.user_id.clone())
-    } else {
-        user.user_id.clone()
-    };
-
     // Note: For persistent assignments we intentionally *omit* start_date/end_date.
     // Some MariaDB JSON functions can otherwise surface JSON null as the string "null",
     // which breaks date comparisons when reading assignments back.
     let mut assignment = serde_json::Map::new();
     assignment.insert(
         "day_of_week".to_string(),
-        serde_json::json!(body.day_of_week),
+        serde_json::json!(day_of_week),
     );
     assignment.insert(
         "persistent".to_string(),
@@ -429,6 +1386,145 @@ async fn assign(
         );
     }
 
+    insert_assignment(pool, &user, &child_profile_id, &body.schedule_id, schedule_row, assignment).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /calendar/{child_id}/override — show `schedule_id` on one date only,
+/// replacing whatever the weekday assignment would show. Removed like any
+/// other assignment via `DELETE /calendar/{child_id}/assign/{id}`.
+#[utoipa::path(
+    post,
+    path = "/calendar/{child_id}/override",
+    tag = "calendar",
+    params(("child_id" = String, Path, description = "Child id")),
+    request_body = OverrideBody,
+    responses((status = 204, description = "Override stored"), (status = 409, description = "Schedule archived", body = super::openapi::ErrorResponse)),
+)]
+async fn create_override(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(child_profile_id): Path<String>,
+    Json(body): Json<OverrideBody>,
+) -> AppResult<StatusCode> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+    let date = NaiveDate::parse_from_str(body.date.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::BadRequest("date must be in YYYY-MM-DD format".into()))?;
+    check_date_in_range("date", date, &state.config)?;
+    let day_of_week = DayOfWeek::of(date);
+    let date_str = date.format("%Y-%m-%d").to_string();
+
+    let pool = &state.pool;
+    assert_calendar_access(pool, &child_profile_id, &user).await?;
+    let schedule_row = assignable_schedule(pool, &body.schedule_id, &user).await?;
+
+    // At most one override per child and date.
+    sqlx::query(
+        "DELETE FROM visual_support_documents
+         WHERE child_id = ?
+           AND document_type = ?
+           AND JSON_EXTRACT(content_json, '$.assignment.override') = true
+           AND JSON_UNQUOTE(JSON_EXTRACT(content_json, '$.assignment.start_date')) = ?",
+    )
+    .bind(&child_profile_id)
+    .bind(WEEKLY_TYPE)
+    .bind(&date_str)
+    .execute(pool)
+    .await?;
+
+    // Stored as a one-day bounded assignment so every reader that only knows
+    // about `start_date`/`end_date` still limits it to that date.
+    let mut assignment = serde_json::Map::new();
+    assignment.insert("day_of_week".to_string(), serde_json::json!(day_of_week));
+    assignment.insert("persistent".to_string(), serde_json::json!(false));
+    assignment.insert("override".to_string(), serde_json::json!(true));
+    assignment.insert("start_date".to_string(), serde_json::Value::String(date_str.clone()));
+    assignment.insert("end_date".to_string(), serde_json::Value::String(date_str));
+
+    insert_assignment(pool, &user, &child_profile_id, &body.schedule_id, schedule_row, assignment).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+struct ScheduleAccessRow {
+    owner_id: Option<String>,
+    name: String,
+}
+
+/// Load a schedule the caller may assign: it must exist, be theirs (or the
+/// caller is admin), not be archived and have at least one activity card.
+async fn assignable_schedule(
+    pool: &crate::db::Db,
+    schedule_id: &str,
+    user: &AuthUser,
+) -> AppResult<ScheduleAccessRow> {
+    #[derive(sqlx::FromRow)]
+    struct ScheduleStatusRow {
+        owner_id: Option<String>,
+        name: String,
+        status: String,
+    }
+
+    // Check existence, ownership and status separately so each failure
+    // surfaces a distinct, meaningful error.
+    let schedule_row: ScheduleStatusRow = sqlx::query_as::<_, ScheduleStatusRow>(
+        &format!(
+            "SELECT
+                t.owner_id,
+                t.name,
+                {} AS status
+             FROM visual_support_documents_templates t
+             WHERE t.id = ?
+               AND t.document_type = ?",
+            schedule_sql::STATUS
+        ),
+    )
+    .bind(schedule_id)
+    .bind(WEEKLY_TYPE)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    if user.role != UserRole::Admin && schedule_row.owner_id.as_deref() != Some(user.user_id.as_str()) {
+        return Err(AppError::NotOwned);
+    }
+
+    if schedule_row.status == "archived" {
+        return Err(AppError::Conflict("Schedule is archived".into()));
+    }
+
+    let has_activity_cards: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM visual_support_template_activities WHERE template_id = ?)",
+    )
+    .bind(schedule_id)
+    .fetch_one(pool)
+    .await?;
+
+    if !has_activity_cards {
+        return Err(AppError::BadRequest(
+            "Cannot assign a schedule with no activity cards. Add at least one activity card first.".into(),
+        ));
+    }
+
+    Ok(ScheduleAccessRow { owner_id: schedule_row.owner_id, name: schedule_row.name })
+}
+
+/// Store an assignment document with `content_json = {"assignment": ...}`.
+async fn insert_assignment(
+    pool: &crate::db::Db,
+    user: &AuthUser,
+    child_profile_id: &str,
+    schedule_id: &str,
+    schedule_row: ScheduleAccessRow,
+    assignment: serde_json::Map<String, serde_json::Value>,
+) -> AppResult<()> {
+    let assignment_owner = if user.role == UserRole::Admin {
+        schedule_row.owner_id.unwrap_or_else(|| user.user_id.clone())
+    } else {
+        user.user_id.clone()
+    };
+
     let assignment_content = serde_json::json!({ "assignment": assignment });
 
     let assignment_content_json = serde_json::to_string(&assignment_content)
@@ -442,17 +1538,24 @@ async fn assign(
     )
     .bind(&id)
     .bind(&assignment_owner)
-    .bind(&child_profile_id)
-    .bind(&body.schedule_id)
+    .bind(child_profile_id)
+    .bind(schedule_id)
     .bind(&schedule_row.name)
     .bind(WEEKLY_TYPE)
     .bind(assignment_content_json)
     .execute(pool)
     .await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(())
 }
 
+#[utoipa::path(
+    delete,
+    path = "/calendar/{child_id}/assign/{assignment_id}",
+    tag = "calendar",
+    params(("child_id" = String, Path, description = "Child id"), ("assignment_id" = String, Path, description = "Assignment id")),
+    responses((status = 204, description = "Removed")),
+)]
 async fn unassign(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -489,16 +1592,329 @@ fn parse_iso_week(s: &str) -> Option<(i32, u32)> {
     Some((year, week))
 }
 
-fn assignment_applies_to_date(a: &AssignmentRow, date: &str) -> bool {
-    match (&a.start_date, &a.end_date) {
-        (Some(start), Some(end)) => date >= start.as_str() && date <= end.as_str(),
-        (Some(start), None) => date >= start.as_str(),
-        (None, Some(end)) => date <= end.as_str(),
-        (None, None) => true,
+/// Reject dates more than `assignment_date_max_years` from today; typos such
+/// as `0202-01-01` would otherwise make an assignment silently never apply.
+fn check_date_in_range(field: &str, date: NaiveDate, config: &Config) -> AppResult<()> {
+    let today = Utc::now().with_timezone(&config.app_default_timezone).date_naive();
+    let years = config.assignment_date_max_years;
+    let span = chrono::Months::new(years * 12);
+    let earliest = today.checked_sub_months(span).unwrap_or(NaiveDate::MIN);
+    let latest = today.checked_add_months(span).unwrap_or(NaiveDate::MAX);
+    if date < earliest || date > latest {
+        return Err(AppError::BadRequest(format!(
+            "{field} must be within {years} years of today"
+        )));
     }
+    Ok(())
 }
 
-fn assignment_priority(a: &AssignmentRow) -> (i8, Option<&str>) {
-    let bounded = if a.start_date.is_some() || a.end_date.is_some() { 1 } else { 0 };
-    (bounded, a.start_date.as_deref())
+/// Parse a stored `YYYY-MM-DD` bound. `Err` means the value is present but
+/// malformed, so the assignment should not match any date. A JSON null that
+/// MariaDB surfaced as the string `"null"` counts as no bound.
+/// The calendar date `now` falls on in `tz`; what "today" means for a child.
+fn local_date(now: DateTime<Utc>, tz: Tz) -> NaiveDate {
+    now.with_timezone(&tz).date_naive()
+}
+
+fn parse_bound(raw: Option<&str>) -> Result<Option<NaiveDate>, ()> {
+    match raw.map(str::trim).filter(|s| !s.is_empty() && !s.eq_ignore_ascii_case("null")) {
+        None => Ok(None),
+        Some(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d").map(Some).map_err(|_| ()),
+    }
+}
+
+fn assignment_applies_to_date(a: &AssignmentRow, date: NaiveDate) -> bool {
+    let (Ok(start), Ok(end)) = (parse_bound(a.start_date.as_deref()), parse_bound(a.end_date.as_deref())) else {
+        tracing::warn!(assignment_id = %a.id, "Ignoring assignment with malformed date bounds");
+        return false;
+    };
+    start.is_none_or(|s| date >= s) && end.is_none_or(|e| date <= e)
+}
+
+fn is_unresolved_card(card: &ActivityCardRow) -> bool {
+    let blank = |v: &Option<String>| v.as_deref().map(str::trim).is_none_or(str::is_empty);
+    blank(&card.title) && blank(&card.picture_path)
+}
+
+/// Higher wins: single-date overrides, then date-bounded assignments (the
+/// most recently starting first), then persistent ones.
+fn assignment_priority(a: &AssignmentRow) -> (i8, Option<NaiveDate>) {
+    let start = parse_bound(a.start_date.as_deref()).ok().flatten();
+    let end = parse_bound(a.end_date.as_deref()).ok().flatten();
+    let tier = if a.is_override {
+        2
+    } else if start.is_some() || end.is_some() {
+        1
+    } else {
+        0
+    };
+    (tier, start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, test_support};
+
+    fn assignment(id: &str, schedule_id: &str, start: Option<&str>, is_override: bool) -> AssignmentRow {
+        AssignmentRow {
+            id: id.into(),
+            schedule_id: schedule_id.into(),
+            day_of_week: 1,
+            persistent: Some(start.is_none()),
+            start_date: start.map(Into::into),
+            end_date: start.map(Into::into),
+            is_override,
+        }
+    }
+
+    #[test]
+    fn pick_prefers_override_then_dated_then_persistent() {
+        let persistent = assignment("p", "s1", None, false);
+        let dated = assignment("d", "s2", Some("2026-03-02"), false);
+        let override_ = assignment("o", "s3", Some("2026-03-02"), true);
+
+        let all = [&persistent, &dated, &override_];
+        assert_eq!(pick_assignment(all.into_iter(), None).unwrap().id, "o");
+        assert_eq!(pick_assignment([&persistent, &dated].into_iter(), None).unwrap().id, "d");
+        assert!(pick_assignment(std::iter::empty(), None).is_none());
+    }
+
+    #[test]
+    fn pick_honours_a_selection_that_still_applies() {
+        let persistent = assignment("p", "s1", None, false);
+        let override_ = assignment("o", "s3", Some("2026-03-02"), true);
+        let all = [&persistent, &override_];
+
+        assert_eq!(pick_assignment(all.into_iter(), Some("s1")).unwrap().id, "p");
+        // A selection for a schedule that does not apply falls back to priority.
+        assert_eq!(pick_assignment(all.into_iter(), Some("gone")).unwrap().id, "o");
+    }
+
+    #[test]
+    fn pick_takes_the_best_assignment_of_the_selected_schedule() {
+        let older = assignment("older", "s1", Some("2026-03-01"), false);
+        let newer = assignment("newer", "s1", Some("2026-03-02"), false);
+        let other = assignment("other", "s2", Some("2026-03-02"), true);
+
+        let all = [&older, &newer, &other];
+        assert_eq!(pick_assignment(all.into_iter(), Some("s1")).unwrap().id, "newer");
+    }
+
+    /// An active schedule of `parent`'s with one card per `(text_label, library id)`.
+    async fn schedule_with_cards(pool: &Db, parent: &AuthUser, cards: &[(&str, Option<&str>)]) -> String {
+        let schedule_id = test_support::schedule(pool, parent, "Week", serde_json::json!({ "schedule": { "status": "active" } })).await;
+
+        // Library ids may be dangling, so write the cards with FK checks off.
+        let mut conn = pool.acquire().await.unwrap();
+        sqlx::query("SET FOREIGN_KEY_CHECKS = 0").execute(&mut *conn).await.unwrap();
+        for (order, (label, library_id)) in cards.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO visual_support_template_activities (id, template_id, activity_order, activity_card_id, text_label)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&schedule_id)
+            .bind(order as i32)
+            .bind(library_id)
+            .bind(label)
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        }
+        sqlx::query("SET FOREIGN_KEY_CHECKS = 1").execute(&mut *conn).await.unwrap();
+        schedule_id
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn child_view_hides_cards_whose_library_card_is_missing(pool: Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let missing = Uuid::new_v4().to_string();
+        let schedule_id = schedule_with_cards(&pool, &parent, &[("Breakfast", None), ("", Some(&missing))]).await;
+
+        let state = test_support::state(pool.clone());
+        let body = AssignBody {
+            schedule_id,
+            day_of_week: 1,
+            persistent: true,
+            start_date: None,
+            end_date: None,
+        };
+        assert!(assign(State(state.clone()), Extension(parent), Path(child.clone()), Json(body)).await.is_ok());
+
+        // 2026-W10 starts on Monday 2026-03-02.
+        let monday_cards = |week: WeekResponse| {
+            let day = week.days.into_iter().find(|d| d.date == "2026-03-02").unwrap();
+            day.activity_cards.into_iter().map(|c| c.title).collect::<Vec<_>>()
+        };
+        let child_week = load_week_for_child(&pool, &child, "2026-W10", &state.config, true).await.unwrap();
+        assert_eq!(monday_cards(child_week), [Some("Breakfast".to_string())]);
+
+        let parent_week = load_week_for_child(&pool, &child, "2026-W10", &state.config, false).await.unwrap();
+        assert_eq!(monday_cards(parent_week), [Some("Breakfast".to_string()), None]);
+    }
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn date_bounds_are_inclusive_and_compared_as_dates() {
+        let mut a = assignment("a", "s1", Some("2026-03-02"), false);
+        a.end_date = Some("2026-03-10".into());
+        assert!(!assignment_applies_to_date(&a, date("2026-03-01")));
+        assert!(assignment_applies_to_date(&a, date("2026-03-02")));
+        assert!(assignment_applies_to_date(&a, date("2026-03-10")));
+        assert!(!assignment_applies_to_date(&a, date("2026-03-11")));
+
+        // Unpadded or stringly "null" bounds from older rows still parse.
+        a.start_date = Some("2026-3-2".into());
+        a.end_date = Some("null".into());
+        assert!(assignment_applies_to_date(&a, date("2027-01-01")));
+
+        a.start_date = Some("next week".into());
+        assert!(!assignment_applies_to_date(&a, date("2026-03-02")));
+    }
+
+    #[test]
+    fn today_follows_the_parents_timezone_across_midnight() {
+        let mut a = assignment("a", "s1", None, false);
+        a.end_date = Some("2026-03-01".into());
+
+        // 23:30 UTC on Sunday is already Monday in Copenhagen and still
+        // Sunday afternoon in New York.
+        let now = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 3, 1, 23, 30, 0).unwrap();
+        let copenhagen = local_date(now, chrono_tz::Europe::Copenhagen);
+        let new_york = local_date(now, chrono_tz::America::New_York);
+        assert_eq!(copenhagen, date("2026-03-02"));
+        assert_eq!(new_york, date("2026-03-01"));
+        assert!(!assignment_applies_to_date(&a, copenhagen));
+        assert!(assignment_applies_to_date(&a, new_york));
+        assert!(assignment_applies_to_date(&a, local_date(now, Tz::UTC)));
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn assign_tells_missing_foreign_and_archived_schedules_apart(pool: Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let foreign = schedule_with_cards(&pool, &other, &[("Breakfast", None)]).await;
+        let archived = schedule_with_cards(&pool, &parent, &[("Breakfast", None)]).await;
+        sqlx::query(
+            "UPDATE visual_support_documents_templates
+             SET metadata_json = JSON_SET(metadata_json, '$.schedule.status', 'archived')
+             WHERE id = ?",
+        )
+        .bind(&archived)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let state = test_support::state(pool.clone());
+        let assign_to_monday = |schedule_id: String| {
+            let body = AssignBody { schedule_id, day_of_week: 1, persistent: true, start_date: None, end_date: None };
+            assign(State(state.clone()), Extension(parent.clone()), Path(child.clone()), Json(body))
+        };
+
+        let err = assign_to_monday(Uuid::new_v4().to_string()).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound), "{err:?}");
+        let err = assign_to_monday(foreign).await.unwrap_err();
+        assert!(matches!(err, AppError::NotOwned), "{err:?}");
+        let err = assign_to_monday(archived).await.unwrap_err();
+        assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+    }
+
+    #[test]
+    fn expand_accepts_only_pictograms() {
+        let query = |expand: Option<&str>| WeekQuery { labels: false, expand: expand.map(Into::into) };
+        assert!(!query(None).expand_pictograms().unwrap());
+        assert!(!query(Some("")).expand_pictograms().unwrap());
+        assert!(query(Some("pictograms")).expand_pictograms().unwrap());
+        assert!(query(Some(" pictograms ,")).expand_pictograms().unwrap());
+        assert!(matches!(query(Some("pictograms,cards")).expand_pictograms(), Err(AppError::BadRequest(_))));
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn expanded_week_inlines_cached_pictograms(pool: Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let library_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO visual_support_activity_library (id, owner_id, label_text, arasaac_id)
+             VALUES (?, ?, 'Teeth', 2345)",
+        )
+        .bind(&library_id)
+        .bind(&parent.user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO pictograms (arasaac_id, keywords_json, keywords_text, image_url, local_file_path)
+             VALUES (2345, JSON_ARRAY(), 'teeth', 'https://example.test/2345.png', '/assets/pictograms/2345.png')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let schedule_id = schedule_with_cards(&pool, &parent, &[("Teeth", Some(&library_id)), ("Breakfast", None)]).await;
+
+        let state = test_support::state(pool.clone());
+        let body = AssignBody { schedule_id, day_of_week: 1, persistent: true, start_date: None, end_date: None };
+        assert!(assign(State(state.clone()), Extension(parent), Path(child.clone()), Json(body)).await.is_ok());
+
+        let monday = |week: &WeekResponse| {
+            let day = week.days.iter().find(|d| d.date == "2026-03-02").unwrap();
+            serde_json::to_value(&day.activity_cards).unwrap()
+        };
+        let mut week = load_week_for_child(&pool, &child, "2026-W10", &state.config, true).await.unwrap();
+        let lean = monday(&week);
+        assert!(lean.as_array().unwrap().iter().all(|c| c.get("pictogram").is_none()));
+
+        expand_card_pictograms(&pool, &mut week).await.unwrap();
+        let expanded = monday(&week);
+        let teeth = &expanded[0]["pictogram"];
+        assert_eq!(teeth["arasaac_id"], 2345);
+        assert_eq!(teeth["image_url"], "https://example.test/2345.png");
+        assert_eq!(teeth["local_file_path"], "/assets/pictograms/2345.png");
+        assert!(teeth["thumb_url"].as_str().unwrap().contains("2345"));
+        assert!(teeth.get("card_id").is_none());
+        assert!(expanded[1].get("pictogram").is_none());
+    }
+
+    #[test]
+    fn week_start_offsets_from_the_iso_monday() {
+        let offsets: Vec<i64> = (1..=7).map(days_before_monday).collect();
+        // Mon, Tue, Wed, Thu, Fri, Sat, Sun.
+        assert_eq!(offsets, [0, 6, 5, 4, 3, 2, 1]);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn week_days_follow_the_parents_week_start(pool: Db) {
+        let config = Config::for_tests();
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let days = |week: &WeekResponse| -> Vec<(String, u8)> {
+            week.days.iter().map(|d| (d.date.clone(), d.day_of_week)).collect()
+        };
+
+        let monday_start = load_week_for_child(&pool, &child, "2026-W10", &config, true).await.unwrap();
+        assert_eq!((monday_start.week_start, monday_start.start_date.as_str()), (1, "2026-03-02"));
+        let expected: Vec<(String, u8)> = (0..7).map(|i| (format!("2026-03-{:02}", 2 + i), i as u8 + 1)).collect();
+        assert_eq!(days(&monday_start), expected);
+
+        sqlx::query("UPDATE users SET week_start = 7 WHERE id = ?")
+            .bind(&parent.user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let sunday_start = load_week_for_child(&pool, &child, "2026-W10", &config, true).await.unwrap();
+        assert_eq!((sunday_start.week_start, sunday_start.monday.as_str()), (7, "2026-03-02"));
+        let mut expected = vec![("2026-03-01".to_string(), 7)];
+        expected.extend((0..6).map(|i| (format!("2026-03-{:02}", 2 + i), i as u8 + 1)));
+        assert_eq!(days(&sunday_start), expected);
+    }
 }
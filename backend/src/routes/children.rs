@@ -1,34 +1,53 @@
 //! `/children` routes — CRUD for child profiles and QR tokens.
 
 use axum::{
-    extract::{Extension, Path, State},
-    http::StatusCode,
-    routing::get,
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
     Json, Router,
 };
 use serde::Serializer;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::validate;
 use crate::{
     errors::{AppError, AppResult},
     middleware::auth_guard::AuthUser,
     models::UserRole,
+    services::{idempotency, qr},
     state::AppState,
 };
 
+/// OpenAPI paths for this module, merged in `super::openapi`.
+#[derive(utoipa::OpenApi)]
+#[openapi(paths(list_children, create_child, get_child, update_child, delete_child, list_deleted_children, restore_child, get_qr, regenerate_qr, get_qr_png, list_child_schedules, export_child, list_child_devices, child_device_slots, prune_child_devices, revoke_child_device, revoke_all_child_devices))]
+pub(super) struct ApiDoc;
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/children",         get(list_children).post(create_child))
+        .route("/children/deleted",  get(list_deleted_children))
         .route("/children/{id}",     get(get_child).put(update_child).delete(delete_child))
+        .route("/children/{id}/restore", post(restore_child))
+        .route("/children/{id}/schedules", get(list_child_schedules))
+        .route("/children/{id}/export", get(export_child))
     .route("/children/{id}/devices", get(list_child_devices).delete(revoke_all_child_devices))
+    .route("/children/{id}/devices/slots", get(child_device_slots))
+    .route("/children/{id}/devices/prune", post(prune_child_devices))
     .route("/children/{id}/devices/{device_id}", axum::routing::delete(revoke_child_device))
         .route("/children/{id}/qr",  get(get_qr).post(regenerate_qr))
+        .route("/children/{id}/qr.png", get(get_qr_png))
 }
 
+/// Active (unrevoked) devices a child may have paired at once.
+pub(super) const MAX_ACTIVE_CHILD_DEVICES: i64 = 3;
+const DEFAULT_STALE_DEVICE_DAYS: i64 = 90;
+
 // ── Row / payload types ──────────────────────────────────────
 
-#[derive(sqlx::FromRow, Serialize)]
+#[derive(sqlx::FromRow, Serialize, utoipa::ToSchema)]
 struct ChildRow {
     id:           String,
     parent_id:    Option<String>,
@@ -36,29 +55,77 @@ struct ChildRow {
     avatar_path:  Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
 struct CreateChildBody {
     display_name: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+struct PruneDevicesQuery {
+    inactive_days: Option<i64>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+struct PruneDevicesResponse {
+    revoked: u64,
+    inactive_days: i64,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+struct DeleteChildQuery {
+    /// Skip the restore window and remove the profile and everything tied
+    /// to it immediately (data subject erasure).
+    #[serde(default)]
+    erase: bool,
+}
+
+#[derive(sqlx::FromRow, Serialize, utoipa::ToSchema)]
+struct DeletedChildRow {
+    id:           String,
+    parent_id:    Option<String>,
+    display_name: String,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    deleted_at:   chrono::NaiveDateTime,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
 struct UpdateChildBody {
     display_name: Option<String>,
     avatar_path:  Option<String>,
 }
 
-#[derive(sqlx::FromRow, Serialize)]
+#[derive(sqlx::FromRow, Serialize, utoipa::ToSchema)]
 struct QrRow {
     id:        String,
     token:     String,
     is_active: bool,
 }
 
-#[derive(sqlx::FromRow, Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
+struct QrResponse {
+    #[serde(flatten)]
+    qr:          QrRow,
+    pairing_url: String,
+}
+
+impl QrResponse {
+    fn new(config: &crate::config::Config, qr: QrRow) -> Self {
+        let pairing_url = pairing_url(config, &qr.token);
+        Self { qr, pairing_url }
+    }
+}
+
+/// URL a scanned QR code opens; the frontend pairs the device from `?token=`.
+fn pairing_url(config: &crate::config::Config, token: &str) -> String {
+    format!("{}/qr-login?token={}", config.qr_pairing_base_url, urlencoding::encode(token))
+}
+
+#[derive(sqlx::FromRow, Serialize, utoipa::ToSchema)]
 struct ChildDeviceRow {
     id: String,
     parent_user_id: String,
     child_id: String,
+    label: Option<String>,
     #[serde(serialize_with = "serialize_naive_datetime_utc")]
     created_at: chrono::NaiveDateTime,
     #[serde(serialize_with = "serialize_option_naive_datetime_utc")]
@@ -90,7 +157,7 @@ where
 
 // ── Auth helper ───────────────────────────────────────────────
 
-/// Verify the caller owns the child profile. Admins bypass.
+/// Verify the caller owns the (not soft-deleted) child profile. Admins bypass.
 async fn assert_owns_child(
     pool: &crate::db::Db,
     child_id: &str,
@@ -100,20 +167,26 @@ async fn assert_owns_child(
         return Ok(());
     }
     let is_mine: bool = sqlx::query_scalar(
-        "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ?)",
+        "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ? AND deleted_at IS NULL)",
     )
     .bind(child_id)
     .bind(&caller.user_id)
     .fetch_one(pool)
     .await?;
     if !is_mine {
-        return Err(AppError::Forbidden);
+        return Err(AppError::NotOwned);
     }
     Ok(())
 }
 
 // ── Handlers ─────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/children",
+    tag = "children",
+    responses((status = 200, description = "Children visible to the caller", body = Vec<ChildRow>)),
+)]
 async fn list_children(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -126,6 +199,7 @@ async fn list_children(
         UserRole::Admin => sqlx::query_as::<_, ChildRow>(
             "SELECT cp.id, cp.parent_id, cp.display_name, cp.avatar_path
              FROM child_profiles cp
+             WHERE cp.deleted_at IS NULL
              ORDER BY cp.display_name",
         )
         .fetch_all(pool)
@@ -133,7 +207,7 @@ async fn list_children(
         _ => sqlx::query_as::<_, ChildRow>(
             "SELECT cp.id, cp.parent_id, cp.display_name, cp.avatar_path
              FROM child_profiles cp
-             WHERE cp.parent_id = ?
+             WHERE cp.parent_id = ? AND cp.deleted_at IS NULL
              ORDER BY cp.display_name",
         )
         .bind(&user.user_id)
@@ -143,44 +217,92 @@ async fn list_children(
     Ok(Json(rows))
 }
 
+#[utoipa::path(
+    post,
+    path = "/children",
+    tag = "children",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replay-safe create")),
+    request_body = CreateChildBody,
+    responses((status = 201, description = "Created", body = ChildRow), (status = 400, description = "Invalid display_name", body = super::openapi::ErrorResponse), (status = 409, description = "Child limit reached (`child_limit_reached`)", body = super::openapi::ErrorResponse), (status = 422, description = "Idempotency-Key reused with a different body", body = super::openapi::ErrorResponse)),
+)]
 async fn create_child(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
+    headers: HeaderMap,
     Json(body): Json<CreateChildBody>,
 ) -> AppResult<(StatusCode, Json<ChildRow>)> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
-    if body.display_name.trim().is_empty() {
-        return Err(AppError::BadRequest("display_name is required".into()));
-    }
+    let display_name = validate::title("display_name", &body.display_name)?;
     let pool = &state.pool;
 
-    let profile_id = Uuid::new_v4().to_string();
     let parent_id: Option<String> = if user.role == UserRole::Admin {
         None
     } else {
         Some(user.user_id.clone())
     };
 
-    sqlx::query(
-        "INSERT INTO child_profiles (id, parent_id, display_name) VALUES (?, ?, ?)",
-    )
-    .bind(&profile_id).bind(&parent_id).bind(&body.display_name)
-    .execute(pool).await?;
+    let idempotency_key = idempotency::key_from_headers(&headers)?;
+    let profile_id = idempotency::run_once(pool, &user.user_id, "child", idempotency_key.as_deref(), &body, async {
+        let mut tx = pool.begin().await?;
+        if let Some(parent_id) = &parent_id {
+            ensure_child_capacity(&mut tx, state.config.max_children_per_parent, parent_id).await?;
+        }
+        let profile_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO child_profiles (id, parent_id, display_name) VALUES (?, ?, ?)",
+        )
+        .bind(&profile_id).bind(&parent_id).bind(&display_name)
+        .execute(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(profile_id)
+    })
+    .await?;
 
     let row: ChildRow = sqlx::query_as::<_, ChildRow>(
         "SELECT cp.id, cp.parent_id, cp.display_name, cp.avatar_path
          FROM child_profiles cp
-         WHERE cp.id = ?",
+         WHERE cp.id = ? AND cp.deleted_at IS NULL",
     )
     .bind(&profile_id)
-    .fetch_one(pool)
-    .await?;
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
 
     Ok((StatusCode::CREATED, Json(row)))
 }
 
+/// 409 `child_limit_reached` when the parent already has `limit` children.
+/// Locks the parent's user row so concurrent creates cannot both pass.
+async fn ensure_child_capacity(conn: &mut sqlx::MySqlConnection, limit: u32, parent_id: &str) -> AppResult<()> {
+    sqlx::query("SELECT id FROM users WHERE id = ? FOR UPDATE")
+        .bind(parent_id)
+        .execute(&mut *conn)
+        .await?;
+    let children: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM child_profiles WHERE parent_id = ? AND deleted_at IS NULL",
+    )
+    .bind(parent_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    if children >= i64::from(limit) {
+        return Err(AppError::ConflictDetails {
+            message: format!("Maximum number of children reached ({limit})"),
+            code: "child_limit_reached",
+            details: serde_json::json!({ "limit": limit, "children": children }),
+        });
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/children/{id}",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses((status = 200, description = "Child", body = ChildRow), (status = 404, description = "Not found", body = super::openapi::ErrorResponse)),
+)]
 async fn get_child(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -193,7 +315,7 @@ async fn get_child(
     let row: ChildRow = sqlx::query_as::<_, ChildRow>(
         "SELECT cp.id, cp.parent_id, cp.display_name, cp.avatar_path
          FROM child_profiles cp
-         WHERE cp.id = ?",
+         WHERE cp.id = ? AND cp.deleted_at IS NULL",
     )
     .bind(&id)
     .fetch_optional(pool).await?
@@ -207,6 +329,14 @@ async fn get_child(
     Ok(Json(row))
 }
 
+#[utoipa::path(
+    put,
+    path = "/children/{id}",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    request_body = UpdateChildBody,
+    responses((status = 200, description = "Updated child", body = ChildRow), (status = 404, description = "Not found", body = super::openapi::ErrorResponse)),
+)]
 async fn update_child(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -216,10 +346,11 @@ async fn update_child(
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
+    let display_name = validate::optional_title("display_name", body.display_name.as_deref())?;
     let pool = &state.pool;
     assert_owns_child(pool, &id, &user).await?;
 
-    if let Some(name) = &body.display_name {
+    if let Some(name) = &display_name {
         sqlx::query("UPDATE child_profiles SET display_name = ? WHERE id = ?")
             .bind(name).bind(&id).execute(pool).await?;
     }
@@ -237,83 +368,383 @@ async fn update_child(
     Ok(Json(row))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/children/{id}",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id"), DeleteChildQuery),
+    responses((status = 204, description = "Soft-deleted, or erased with `erase=true`"), (status = 404, description = "Not found", body = super::openapi::ErrorResponse)),
+)]
 async fn delete_child(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(id): Path<String>,
+    Query(query): Query<DeleteChildQuery>,
 ) -> AppResult<StatusCode> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
     let pool = &state.pool;
+
+    if query.erase {
+        // Erasure also applies to profiles already in the restore window.
+        if user.role != UserRole::Admin {
+            let is_mine: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ?)",
+            )
+            .bind(&id)
+            .bind(&user.user_id)
+            .fetch_one(pool)
+            .await?;
+            if !is_mine {
+                return Err(AppError::NotOwned);
+            }
+        }
+        let affected = sqlx::query("DELETE FROM child_profiles WHERE id = ?")
+            .bind(&id).execute(pool).await?
+            .rows_affected();
+        if affected == 0 {
+            return Err(AppError::NotFound);
+        }
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
     assert_owns_child(pool, &id, &user).await?;
 
-    let affected = sqlx::query("DELETE FROM child_profiles WHERE id = ?")
-        .bind(&id).execute(pool).await?
-        .rows_affected();
+    // Soft delete: devices and QR logins stop working (they join on
+    // `deleted_at IS NULL`) but nothing is removed until the retention rule
+    // for `child_profiles.deleted_at` runs.
+    let affected = sqlx::query(
+        "UPDATE child_profiles SET deleted_at = NOW() WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(&id).execute(pool).await?
+    .rows_affected();
     if affected == 0 {
         return Err(AppError::NotFound);
     }
     Ok(StatusCode::NO_CONTENT)
 }
 
-async fn get_qr(
+/// Soft-deleted children still inside the restore window.
+#[utoipa::path(
+    get,
+    path = "/children/deleted",
+    tag = "children",
+    responses((status = 200, description = "Soft-deleted children that can be restored", body = Vec<DeletedChildRow>)),
+)]
+async fn list_deleted_children(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<Vec<DeletedChildRow>>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+    let pool = &state.pool;
+    let owner_filter = if user.role == UserRole::Admin { "" } else { " AND cp.parent_id = ?" };
+    let sql = format!(
+        "SELECT cp.id, cp.parent_id, cp.display_name, cp.deleted_at
+         FROM child_profiles cp
+         WHERE cp.deleted_at IS NOT NULL{owner_filter}
+         ORDER BY cp.deleted_at DESC"
+    );
+    let mut query = sqlx::query_as::<_, DeletedChildRow>(&sql);
+    if user.role != UserRole::Admin {
+        query = query.bind(&user.user_id);
+    }
+    Ok(Json(query.fetch_all(pool).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/children/{id}/restore",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses((status = 200, description = "Restored child", body = ChildRow), (status = 404, description = "Not found or not deleted", body = super::openapi::ErrorResponse), (status = 409, description = "Child limit reached (`child_limit_reached`)", body = super::openapi::ErrorResponse)),
+)]
+async fn restore_child(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(id): Path<String>,
-) -> AppResult<Json<QrRow>> {
-    if user.role != UserRole::Parent {
+) -> AppResult<Json<ChildRow>> {
+    if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
     let pool = &state.pool;
-    assert_owns_child(pool, &id, &user).await?;
 
-    // Return existing active token if present, otherwise generate one
+    let mut tx = pool.begin().await?;
+    let result = if user.role == UserRole::Admin {
+        sqlx::query("UPDATE child_profiles SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL")
+            .bind(&id)
+            .execute(&mut *tx)
+            .await?
+    } else {
+        // A missing, foreign or live child is 404 whether or not the parent
+        // is at the limit.
+        let restorable: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ? AND deleted_at IS NOT NULL)",
+        )
+        .bind(&id)
+        .bind(&user.user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !restorable {
+            return Err(AppError::NotFound);
+        }
+        ensure_child_capacity(&mut tx, state.config.max_children_per_parent, &user.user_id).await?;
+        sqlx::query(
+            "UPDATE child_profiles SET deleted_at = NULL
+             WHERE id = ? AND parent_id = ? AND deleted_at IS NOT NULL",
+        )
+        .bind(&id)
+        .bind(&user.user_id)
+        .execute(&mut *tx)
+        .await?
+    };
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    tx.commit().await?;
+
+    let row: ChildRow = sqlx::query_as::<_, ChildRow>(
+        "SELECT cp.id, cp.parent_id, cp.display_name, cp.avatar_path
+         FROM child_profiles cp
+         WHERE cp.id = ?",
+    )
+    .bind(&id).fetch_one(pool).await?;
+    Ok(Json(row))
+}
+
+/// Return the child's active QR token, creating one if none exists.
+///
+/// The child row is locked for the duration of the transaction so concurrent
+/// get/regenerate calls serialize; `uniq_qr_active_child` backs this up at
+/// the schema level.
+async fn get_or_create_active_qr(pool: &crate::db::Db, child_id: &str) -> AppResult<QrRow> {
+    let mut tx = pool.begin().await?;
+    lock_child_for_qr(&mut tx, child_id).await?;
+
     if let Some(row) = sqlx::query_as::<_, QrRow>(
         "SELECT id, token, is_active FROM qr_tokens
          WHERE child_id = ? AND is_active = 1 LIMIT 1",
     )
-    .bind(&id)
-    .fetch_optional(pool).await? {
-        return Ok(Json(row));
+    .bind(child_id)
+    .fetch_optional(&mut *tx).await? {
+        tx.commit().await?;
+        return Ok(row);
     }
 
+    let row = insert_active_qr(&mut tx, child_id).await?;
+    tx.commit().await?;
+    Ok(row)
+}
+
+async fn lock_child_for_qr(
+    tx: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    child_id: &str,
+) -> AppResult<()> {
+    sqlx::query_scalar::<_, String>("SELECT id FROM child_profiles WHERE id = ? FOR UPDATE")
+        .bind(child_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(())
+}
+
+async fn insert_active_qr(
+    tx: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    child_id: &str,
+) -> AppResult<QrRow> {
     let qr_id = Uuid::new_v4().to_string();
     let token = crate::auth::generate_token();
     sqlx::query(
         "INSERT INTO qr_tokens (id, child_id, token, is_active) VALUES (?, ?, ?, 1)",
     )
-    .bind(&qr_id).bind(&id).bind(&token)
-    .execute(pool).await?;
+    .bind(&qr_id).bind(child_id).bind(&token)
+    .execute(&mut **tx).await?;
+
+    Ok(QrRow { id: qr_id, token, is_active: true })
+}
+
+#[utoipa::path(
+    get,
+    path = "/children/{id}/qr",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses((status = 200, description = "Active QR token", body = QrResponse)),
+)]
+async fn get_qr(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<QrResponse>> {
+    if user.role != UserRole::Parent {
+        return Err(AppError::Forbidden);
+    }
+    let pool = &state.pool;
+    assert_owns_child(pool, &id, &user).await?;
 
-    Ok(Json(QrRow { id: qr_id, token, is_active: true }))
+    let row = get_or_create_active_qr(pool, &id).await?;
+    Ok(Json(QrResponse::new(&state.config, row)))
 }
 
+/// Printable PNG of the pairing QR code. Never cached: regenerating the token
+/// invalidates any previously rendered image.
+#[utoipa::path(
+    get,
+    path = "/children/{id}/qr.png",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses((status = 200, description = "Printable QR code", content_type = "image/png")),
+)]
+async fn get_qr_png(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    if user.role != UserRole::Parent {
+        return Err(AppError::Forbidden);
+    }
+    let pool = &state.pool;
+    assert_owns_child(pool, &id, &user).await?;
+
+    let row = get_or_create_active_qr(pool, &id).await?;
+    let png = qr::render_png(&pairing_url(&state.config, &row.token))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "no-store, no-cache, must-revalidate"),
+            (header::PRAGMA, "no-cache"),
+        ],
+        png,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/children/{id}/qr",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses((status = 200, description = "New active QR token", body = QrResponse)),
+)]
 async fn regenerate_qr(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(id): Path<String>,
-) -> AppResult<Json<QrRow>> {
+) -> AppResult<Json<QrResponse>> {
     if user.role != UserRole::Parent {
         return Err(AppError::Forbidden);
     }
     let pool = &state.pool;
     assert_owns_child(pool, &id, &user).await?;
 
-    sqlx::query("UPDATE qr_tokens SET is_active = 0 WHERE child_id = ?")
-        .bind(&id).execute(pool).await?;
+    // Deactivate + insert atomically; concurrent regenerations queue on the
+    // child row lock and each leaves exactly one active token behind.
+    let mut tx = pool.begin().await?;
+    lock_child_for_qr(&mut tx, &id).await?;
+
+    sqlx::query("UPDATE qr_tokens SET is_active = 0 WHERE child_id = ? AND is_active = 1")
+        .bind(&id).execute(&mut *tx).await?;
+
+    let row = insert_active_qr(&mut tx, &id).await?;
+    tx.commit().await?;
+
+    Ok(Json(QrResponse::new(&state.config, row)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/children/{id}/schedules",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses(
+        (status = 200, description = "Schedules assigned to the child that still apply today or later", body = Vec<super::calendar::ChildSchedule>),
+        (status = 404, description = "Child not found", body = super::openapi::ErrorResponse),
+    ),
+)]
+async fn list_child_schedules(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<Vec<super::calendar::ChildSchedule>>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let pool = &state.pool;
+    assert_owns_child(pool, &id, &user).await?;
+    if user.role == UserRole::Admin {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND deleted_at IS NULL)",
+        )
+        .bind(&id)
+        .fetch_one(pool)
+        .await?;
+        if !exists {
+            return Err(AppError::NotFound);
+        }
+    }
+
+    Ok(Json(super::calendar::child_schedules(pool, &id, &state.config).await?))
+}
+
+/// Export what is stored about one child, for a data-subject request scoped
+/// to that child. Only the owning parent may download it; each download is
+/// logged in `dsr_audit_logs`.
+#[utoipa::path(
+    get,
+    path = "/children/{id}/export",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses(
+        (status = 200, description = "Data export for one child (profile, assigned schedules, assignments, day progress and schedule picks, devices, reminders)", body = Object),
+        (status = 403, description = "Only the child's parent may export it", body = super::openapi::ErrorResponse),
+    ),
+)]
+async fn export_child(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<(HeaderMap, Json<serde_json::Value>)> {
+    if user.role != UserRole::Parent {
+        return Err(AppError::Forbidden);
+    }
+
+    let pool = &state.pool;
+    assert_owns_child(pool, &id, &user).await?;
+    let export = super::users::build_child_export(pool, &state.config, &id).await?;
 
-    let qr_id = Uuid::new_v4().to_string();
-    let token = crate::auth::generate_token();
     sqlx::query(
-        "INSERT INTO qr_tokens (id, child_id, token, is_active) VALUES (?, ?, ?, 1)",
+        "INSERT INTO dsr_audit_logs
+            (id, request_id, user_id, action, status, requested_at, completed_at, actor_user_id, metadata)
+         VALUES (?, ?, ?, 'export', 'completed', NOW(), NOW(), ?,
+                 JSON_OBJECT('endpoint', '/children/{id}/export', 'child_id', ?))",
     )
-    .bind(&qr_id).bind(&id).bind(&token)
-    .execute(pool).await?;
+    .bind(Uuid::new_v4().to_string())
+    .bind(Uuid::new_v4().to_string())
+    .bind(&user.user_id)
+    .bind(&user.user_id)
+    .bind(&id)
+    .execute(pool)
+    .await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"carlscalendar-child-export.json\""),
+    );
 
-    Ok(Json(QrRow { id: qr_id, token, is_active: true }))
+    Ok((headers, Json(export)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/children/{id}/devices",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses((status = 200, description = "Active devices", body = Vec<ChildDeviceRow>)),
+)]
 async fn list_child_devices(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -328,7 +759,7 @@ async fn list_child_devices(
 
     let rows: Vec<ChildDeviceRow> = if user.role == UserRole::Admin {
         sqlx::query_as::<_, ChildDeviceRow>(
-            "SELECT id, parent_user_id, child_id, created_at, last_used_at, user_agent_hash, ip_range
+            "SELECT id, parent_user_id, child_id, label, created_at, last_used_at, user_agent_hash, ip_range
              FROM child_device_tokens
              WHERE child_id = ? AND revoked_at IS NULL
              ORDER BY created_at DESC",
@@ -338,7 +769,7 @@ async fn list_child_devices(
         .await?
     } else {
         sqlx::query_as::<_, ChildDeviceRow>(
-            "SELECT id, parent_user_id, child_id, created_at, last_used_at, user_agent_hash, ip_range
+            "SELECT id, parent_user_id, child_id, label, created_at, last_used_at, user_agent_hash, ip_range
              FROM child_device_tokens
              WHERE child_id = ? AND parent_user_id = ? AND revoked_at IS NULL
              ORDER BY created_at DESC",
@@ -352,6 +783,84 @@ async fn list_child_devices(
     Ok(Json(rows))
 }
 
+#[derive(Serialize, utoipa::ToSchema)]
+struct DeviceSlotsResponse {
+    limit: i64,
+    active: i64,
+    remaining_slots: i64,
+}
+
+/// Active device count for a child, as checked by `/auth/child/pair`.
+pub(super) async fn active_child_device_count(pool: &crate::db::Db, child_id: &str) -> AppResult<i64> {
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM child_device_tokens WHERE child_id = ? AND revoked_at IS NULL",
+    )
+    .bind(child_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
+#[derive(sqlx::FromRow, Serialize)]
+pub(super) struct RevocableDevice {
+    id: String,
+    label: Option<String>,
+    #[serde(serialize_with = "serialize_option_naive_datetime_utc")]
+    last_used_at: Option<chrono::NaiveDateTime>,
+}
+
+/// Active devices a parent could revoke to free a slot, least recently used first.
+pub(super) async fn revocable_child_devices(
+    pool: &crate::db::Db,
+    child_id: &str,
+) -> AppResult<Vec<RevocableDevice>> {
+    let rows = sqlx::query_as::<_, RevocableDevice>(
+        "SELECT id, label, last_used_at
+         FROM child_device_tokens
+         WHERE child_id = ? AND revoked_at IS NULL
+         ORDER BY COALESCE(last_used_at, created_at) ASC",
+    )
+    .bind(child_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// GET /children/{id}/devices/slots — how many more devices can be paired.
+#[utoipa::path(
+    get,
+    path = "/children/{id}/devices/slots",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses((status = 200, description = "Device limit usage", body = DeviceSlotsResponse)),
+)]
+async fn child_device_slots(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<DeviceSlotsResponse>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let pool = &state.pool;
+    assert_owns_child(pool, &id, &user).await?;
+
+    let active = active_child_device_count(pool, &id).await?;
+    Ok(Json(DeviceSlotsResponse {
+        limit: MAX_ACTIVE_CHILD_DEVICES,
+        active,
+        remaining_slots: (MAX_ACTIVE_CHILD_DEVICES - active).max(0),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/children/{id}/devices/{device_id}",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id"), ("device_id" = String, Path, description = "Device id")),
+    responses((status = 204, description = "Revoked"), (status = 404, description = "Not found", body = super::openapi::ErrorResponse)),
+)]
 async fn revoke_child_device(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -396,6 +905,69 @@ async fn revoke_child_device(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// POST /children/{id}/devices/prune — revoke devices unused for
+/// `inactive_days` (default 90). Never-used devices count from pairing.
+#[utoipa::path(
+    post,
+    path = "/children/{id}/devices/prune",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id"), PruneDevicesQuery),
+    responses((status = 200, description = "Stale devices revoked", body = PruneDevicesResponse), (status = 400, description = "inactive_days not positive", body = super::openapi::ErrorResponse)),
+)]
+async fn prune_child_devices(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Query(query): Query<PruneDevicesQuery>,
+) -> AppResult<Json<PruneDevicesResponse>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+    let inactive_days = query.inactive_days.unwrap_or(DEFAULT_STALE_DEVICE_DAYS);
+    if inactive_days <= 0 {
+        return Err(AppError::BadRequest("inactive_days must be a positive number".into()));
+    }
+
+    let pool = &state.pool;
+    assert_owns_child(pool, &id, &user).await?;
+
+    let revoked = if user.role == UserRole::Admin {
+        sqlx::query(
+            "UPDATE child_device_tokens
+             SET revoked_at = NOW()
+             WHERE child_id = ? AND revoked_at IS NULL
+               AND COALESCE(last_used_at, created_at) < NOW() - INTERVAL ? DAY",
+        )
+        .bind(&id)
+        .bind(inactive_days)
+        .execute(pool)
+        .await?
+        .rows_affected()
+    } else {
+        sqlx::query(
+            "UPDATE child_device_tokens
+             SET revoked_at = NOW()
+             WHERE child_id = ? AND parent_user_id = ? AND revoked_at IS NULL
+               AND COALESCE(last_used_at, created_at) < NOW() - INTERVAL ? DAY",
+        )
+        .bind(&id)
+        .bind(&user.user_id)
+        .bind(inactive_days)
+        .execute(pool)
+        .await?
+        .rows_affected()
+    };
+
+    Ok(Json(PruneDevicesResponse { revoked, inactive_days }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/children/{id}/devices",
+    tag = "children",
+    params(("id" = String, Path, description = "Child id")),
+    responses((status = 204, description = "All devices revoked")),
+)]
 async fn revoke_all_child_devices(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -429,3 +1001,139 @@ async fn revoke_all_child_devices(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, test_support};
+
+    async fn active_tokens(pool: &Db, child_id: &str) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM qr_tokens WHERE child_id = ? AND is_active = 1")
+            .bind(child_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn concurrent_regenerations_leave_one_active_token(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+
+        let regenerate = || regenerate_qr(State(state.clone()), Extension(parent.clone()), Path(child.clone()));
+        let get = get_qr(State(state.clone()), Extension(parent.clone()), Path(child.clone()));
+        let (a, b, c) = tokio::join!(regenerate(), regenerate(), get);
+        assert!(a.is_ok() && b.is_ok() && c.is_ok());
+        assert_eq!(active_tokens(&pool, &child).await, 1);
+
+        // The schema rejects a second active token even without the lock.
+        let second = sqlx::query("INSERT INTO qr_tokens (id, child_id, token, is_active) VALUES (?, ?, ?, 1)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(&child)
+            .bind(crate::auth::generate_token())
+            .execute(&pool)
+            .await;
+        assert!(second.is_err());
+        assert_eq!(active_tokens(&pool, &child).await, 1);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn creating_or_restoring_past_the_child_limit_conflicts(pool: Db) {
+        let mut state = test_support::state(pool.clone());
+        state.config.max_children_per_parent = 2;
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let admin = test_support::user(&pool, UserRole::Admin).await;
+        let create = |user: &AuthUser| {
+            let body = CreateChildBody { display_name: "Kid".into() };
+            create_child(State(state.clone()), Extension(user.clone()), HeaderMap::new(), Json(body))
+        };
+
+        let (_, Json(first)) = create(&parent).await.unwrap();
+        assert!(create(&parent).await.is_ok());
+        let AppError::ConflictDetails { code, details, .. } = test_support::expect_err(create(&parent).await) else {
+            panic!("expected a structured conflict");
+        };
+        assert_eq!(code, "child_limit_reached");
+        assert_eq!(details, serde_json::json!({ "limit": 2, "children": 2 }));
+
+        // Soft-deleted children free a slot until they are restored.
+        sqlx::query("UPDATE child_profiles SET deleted_at = NOW() WHERE id = ?")
+            .bind(&first.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(create(&parent).await.is_ok());
+        let restore = |id: &str| restore_child(State(state.clone()), Extension(parent.clone()), Path(id.into()));
+        assert!(matches!(test_support::expect_err(restore(&first.id).await), AppError::ConflictDetails { code: "child_limit_reached", .. }));
+
+        // Ids the parent cannot restore are 404 even at the limit.
+        let live = test_support::child(&pool, Some(&parent.user_id)).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let foreign = test_support::child(&pool, Some(&other.user_id)).await;
+        sqlx::query("UPDATE child_profiles SET deleted_at = NOW() WHERE id = ?")
+            .bind(&foreign)
+            .execute(&pool)
+            .await
+            .unwrap();
+        for id in [Uuid::new_v4().to_string(), foreign, live] {
+            assert!(matches!(test_support::expect_err(restore(&id).await), AppError::NotFound));
+        }
+
+        // Admins are exempt.
+        for _ in 0..3 {
+            assert!(create(&admin).await.is_ok());
+        }
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn child_schedules_lists_current_assignments_for_parent_and_admin(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let admin = test_support::user(&pool, UserRole::Admin).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let status = |status: &str| serde_json::json!({ "schedule": { "status": status } });
+        let morning = test_support::schedule(&pool, &parent, "Morning", status("active")).await;
+        let holiday = test_support::schedule(&pool, &parent, "Holiday", status("archived")).await;
+        let old = test_support::schedule(&pool, &parent, "Old", status("active")).await;
+        test_support::assign(&pool, &parent, &child, &morning, serde_json::json!({ "day_of_week": 3, "persistent": true })).await;
+        test_support::assign(&pool, &parent, &child, &morning, serde_json::json!({ "day_of_week": 1, "persistent": true })).await;
+        test_support::assign(
+            &pool,
+            &parent,
+            &child,
+            &holiday,
+            serde_json::json!({ "day_of_week": 5, "override": true, "start_date": "2999-07-03", "end_date": "2999-07-03" }),
+        )
+        .await;
+        test_support::assign(&pool, &parent, &child, &old, serde_json::json!({ "day_of_week": 2, "end_date": "2020-01-01" })).await;
+        let list = |user: &AuthUser, id: &str| {
+            list_child_schedules(State(state.clone()), Extension(user.clone()), Path(id.into()))
+        };
+
+        let Json(schedules) = list(&parent, &child).await.unwrap();
+        let listed = serde_json::to_value(&schedules).unwrap();
+        // Ordered by name; ended assignments are left out.
+        assert_eq!(listed.as_array().unwrap().len(), 2, "{listed}");
+        assert_eq!(listed[0]["schedule_id"], holiday.as_str());
+        assert_eq!(listed[0]["status"], "archived");
+        assert_eq!(listed[0]["assignments"][0]["is_override"], true);
+        assert_eq!(listed[0]["assignments"][0]["start_date"], "2999-07-03");
+        assert_eq!(listed[1]["schedule_id"], morning.as_str());
+        assert_eq!(listed[1]["status"], "active");
+        let days: Vec<_> = listed[1]["assignments"].as_array().unwrap().iter().map(|a| a["day_of_week"].clone()).collect();
+        assert_eq!(days, [1, 3]);
+
+        let Json(as_admin) = list(&admin, &child).await.unwrap();
+        assert_eq!(serde_json::to_value(&as_admin).unwrap(), listed);
+
+        let err = test_support::expect_err(list(&other, &child).await);
+        assert!(matches!(err, AppError::NotOwned), "{err:?}");
+        let err = test_support::expect_err(list(&admin, &Uuid::new_v4().to_string()).await);
+        assert!(matches!(err, AppError::NotFound), "{err:?}");
+    }
+}
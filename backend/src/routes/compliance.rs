@@ -1,6 +1,6 @@
 use axum::{
-    extract::{Extension, Path, State},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, Extension, Multipart, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     routing::{get, post, put},
     Json, Router,
 };
@@ -9,7 +9,9 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
+    auth::sweep,
     compliance,
+    events::{self, AdminEvent},
     errors::{AppError, AppResult},
     middleware::{auth_guard::AuthUser, role_guard::require_admin},
     services::pictograms,
@@ -21,16 +23,31 @@ pub fn router() -> Router<AppState> {
     let admin_guard = middleware::from_fn(require_admin);
 
     Router::new()
+        .route("/admin/compliance/export", get(export_compliance_registers))
         .route("/admin/compliance/dsr", get(list_dsr_logs))
         .route("/admin/compliance/deletions", get(list_deletion_logs))
         .route("/admin/compliance/retention-rules", get(list_retention_rules).post(create_retention_rule))
         .route("/admin/compliance/retention-rules/{id}", put(update_retention_rule))
         .route("/admin/compliance/retention/cleanup", post(run_retention_cleanup_now))
+        .route("/admin/compliance/retention/status", get(get_retention_status))
+        .route("/admin/compliance/token-sweep/status", get(get_token_sweep_status))
         .route("/admin/compliance/pictogram-prefetch", get(get_pictogram_prefetch_settings).put(update_pictogram_prefetch_settings))
         .route("/admin/compliance/pictogram-prefetch/run", post(run_pictogram_prefetch_now))
+        .route("/admin/compliance/pictograms/reindex", post(reindex_pictograms))
+        .route("/admin/compliance/pictograms/keywords/refresh", post(refresh_pictogram_keywords))
         .route("/admin/compliance/breach-logs", get(list_breach_logs).post(create_breach_log))
-        .route("/admin/compliance/breach-logs/{id}", put(update_breach_log))
+        .route("/admin/compliance/breach-logs/{id}", put(update_breach_log).delete(delete_breach_log))
+        .route(
+            "/admin/compliance/breach-logs/{id}/attachments",
+            get(list_breach_attachments).post(upload_breach_attachment)
+                .layer(DefaultBodyLimit::max(BREACH_ATTACHMENT_MAX_BYTES + 64 * 1024)),
+        )
+        .route(
+            "/admin/compliance/breach-logs/{id}/attachments/{attachment_id}",
+            get(download_breach_attachment).delete(delete_breach_attachment),
+        )
         .route("/admin/compliance/subprocessors", get(list_subprocessors).post(create_subprocessor))
+        .route("/admin/compliance/subprocessors/due", get(list_subprocessors_due))
         .route("/admin/compliance/subprocessors/{id}", put(update_subprocessor).delete(delete_subprocessor))
         .route_layer(admin_guard)
 }
@@ -77,6 +94,64 @@ struct RetentionRuleRow {
     updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Shared query string for audit log listings. `from`/`to` are inclusive
+/// `YYYY-MM-DD` dates; `page` is 1-based.
+#[derive(Deserialize)]
+struct LogListQuery {
+    from: Option<String>,
+    to: Option<String>,
+    action: Option<String>,
+    reason: Option<String>,
+    page: Option<u32>,
+    page_size: Option<u32>,
+}
+
+const LOG_PAGE_SIZE_MAX: u32 = 500;
+
+struct LogFilters {
+    from: Option<chrono::NaiveDateTime>,
+    to_exclusive: Option<chrono::NaiveDateTime>,
+    limit: i64,
+    offset: i64,
+}
+
+fn parse_log_filters(q: &LogListQuery) -> AppResult<LogFilters> {
+    fn parse_day(raw: Option<&str>, field: &str) -> AppResult<Option<chrono::NaiveDate>> {
+        match raw.map(str::trim).filter(|s| !s.is_empty()) {
+            None => Ok(None),
+            Some(s) => chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| AppError::BadRequest(format!("{field} must be in YYYY-MM-DD format"))),
+        }
+    }
+
+    let from = parse_day(q.from.as_deref(), "from")?;
+    let to = parse_day(q.to.as_deref(), "to")?;
+    if let (Some(f), Some(t)) = (from, to) {
+        if t < f {
+            return Err(AppError::BadRequest("to must be on or after from".into()));
+        }
+    }
+
+    let page_size = q.page_size.unwrap_or(LOG_PAGE_SIZE_MAX).clamp(1, LOG_PAGE_SIZE_MAX);
+    let page = q.page.unwrap_or(1).max(1);
+
+    Ok(LogFilters {
+        from: from.and_then(|d| d.and_hms_opt(0, 0, 0)),
+        to_exclusive: to
+            .and_then(|d| d.succ_opt())
+            .and_then(|d| d.and_hms_opt(0, 0, 0)),
+        limit: page_size as i64,
+        offset: (page as i64 - 1) * page_size as i64,
+    })
+}
+
+fn total_count_headers(total: i64) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-total-count", HeaderValue::from(total));
+    headers
+}
+
 #[derive(Deserialize)]
 struct CreateRetentionRuleBody {
     name: String,
@@ -98,6 +173,9 @@ struct UpdatePictogramPrefetchBody {
     enabled: Option<bool>,
     idle_minutes: Option<u64>,
     batch_size: Option<u64>,
+    /// RFC 3339 time until which the worker ignores `idle_minutes`; an empty
+    /// string clears it.
+    ignore_idle_until: Option<String>,
 }
 
 #[derive(sqlx::FromRow, Serialize)]
@@ -143,6 +221,25 @@ struct UpdateBreachLogBody {
     reported_at: Option<String>,
 }
 
+/// Breach evidence is kept outside `uploads/` and `assets/` so it is never
+/// served statically; downloads go through the admin-only handler.
+const BREACH_EVIDENCE_DIR: &str = "private/breach-evidence";
+const BREACH_ATTACHMENT_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(sqlx::FromRow, Serialize)]
+struct BreachAttachmentRow {
+    id: String,
+    breach_log_id: String,
+    original_filename: String,
+    #[serde(skip_serializing)]
+    stored_filename: String,
+    content_type: String,
+    size_bytes: i64,
+    uploaded_by: Option<String>,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(sqlx::FromRow, Serialize)]
 struct SubprocessorRow {
     id: String,
@@ -150,6 +247,7 @@ struct SubprocessorRow {
     purpose: String,
     location: String,
     dpa_signed_date: Option<chrono::NaiveDate>,
+    dpa_review_date: Option<chrono::NaiveDate>,
     transfer_basis: String,
     notes: Option<String>,
     is_active: bool,
@@ -159,7 +257,7 @@ struct SubprocessorRow {
     updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-fn serialize_naive_datetime_utc<S>(value: &chrono::DateTime<chrono::Utc>, serializer: S) -> Result<S::Ok, S::Error>
+pub(super) fn serialize_naive_datetime_utc<S>(value: &chrono::DateTime<chrono::Utc>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -185,6 +283,7 @@ struct CreateSubprocessorBody {
     purpose: String,
     location: String,
     dpa_signed_date: Option<String>,
+    dpa_review_date: Option<String>,
     transfer_basis: String,
     notes: Option<String>,
     is_active: Option<bool>,
@@ -196,43 +295,235 @@ struct UpdateSubprocessorBody {
     purpose: Option<String>,
     location: Option<String>,
     dpa_signed_date: Option<String>,
+    dpa_review_date: Option<String>,
     transfer_basis: Option<String>,
     notes: Option<String>,
     is_active: Option<bool>,
 }
 
+#[derive(Deserialize)]
+struct SubprocessorDueQuery {
+    /// Include reviews falling due within this many days (default 30).
+    within_days: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct SubprocessorDueItem {
+    #[serde(flatten)]
+    subprocessor: SubprocessorRow,
+    /// Days until `dpa_review_date`; negative once the review is overdue.
+    days_until_review: i64,
+    overdue: bool,
+}
+
+/// List DSR audit events, newest first. The unpaged total is returned in the
+/// `X-Total-Count` header.
 async fn list_dsr_logs(
     State(state): State<AppState>,
     Extension(_admin): Extension<AuthUser>,
-) -> AppResult<Json<Vec<DsrAuditRow>>> {
-    let rows = sqlx::query_as::<_, DsrAuditRow>(
+    Query(q): Query<LogListQuery>,
+) -> AppResult<(HeaderMap, Json<Vec<DsrAuditRow>>)> {
+    let filters = parse_log_filters(&q)?;
+    let action = q.action.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    if let Some(a) = action {
+        if !matches!(a, "export" | "delete") {
+            return Err(AppError::BadRequest("action must be export or delete".into()));
+        }
+    }
+
+    const FILTER: &str = "WHERE (? IS NULL OR requested_at >= ?)
+           AND (? IS NULL OR requested_at < ?)
+           AND (? IS NULL OR action = ?)";
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM dsr_audit_logs {FILTER}"))
+        .bind(filters.from).bind(filters.from)
+        .bind(filters.to_exclusive).bind(filters.to_exclusive)
+        .bind(action).bind(action)
+        .fetch_one(&state.pool)
+        .await?;
+
+    let rows = sqlx::query_as::<_, DsrAuditRow>(&format!(
         "SELECT id, request_id, user_id, action, status, requested_at, completed_at,
                 error_message, actor_user_id, CAST(metadata AS CHAR) AS metadata
          FROM dsr_audit_logs
+         {FILTER}
          ORDER BY requested_at DESC
-         LIMIT 500",
-    )
+         LIMIT ? OFFSET ?"
+    ))
+    .bind(filters.from).bind(filters.from)
+    .bind(filters.to_exclusive).bind(filters.to_exclusive)
+    .bind(action).bind(action)
+    .bind(filters.limit)
+    .bind(filters.offset)
     .fetch_all(&state.pool)
     .await?;
 
-    Ok(Json(rows))
+    Ok((total_count_headers(total), Json(rows)))
 }
 
+/// List deletion log entries, newest first. The unpaged total is returned in
+/// the `X-Total-Count` header.
 async fn list_deletion_logs(
     State(state): State<AppState>,
     Extension(_admin): Extension<AuthUser>,
-) -> AppResult<Json<Vec<DeletionLogRow>>> {
-    let rows = sqlx::query_as::<_, DeletionLogRow>(
+    Query(q): Query<LogListQuery>,
+) -> AppResult<(HeaderMap, Json<Vec<DeletionLogRow>>)> {
+    let filters = parse_log_filters(&q)?;
+    let reason = q.reason.as_deref().map(str::trim).filter(|s| !s.is_empty());
+
+    const FILTER: &str = "WHERE (? IS NULL OR deleted_at >= ?)
+           AND (? IS NULL OR deleted_at < ?)
+           AND (? IS NULL OR reason = ?)";
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM deletion_logs {FILTER}"))
+        .bind(filters.from).bind(filters.from)
+        .bind(filters.to_exclusive).bind(filters.to_exclusive)
+        .bind(reason).bind(reason)
+        .fetch_one(&state.pool)
+        .await?;
+
+    let rows = sqlx::query_as::<_, DeletionLogRow>(&format!(
         "SELECT id, table_name, record_id, deleted_at, reason,
                 CAST(details AS CHAR) AS details, actor_user_id
          FROM deletion_logs
+         {FILTER}
          ORDER BY deleted_at DESC
-         LIMIT 500",
-    )
+         LIMIT ? OFFSET ?"
+    ))
+    .bind(filters.from).bind(filters.from)
+    .bind(filters.to_exclusive).bind(filters.to_exclusive)
+    .bind(reason).bind(reason)
+    .bind(filters.limit)
+    .bind(filters.offset)
     .fetch_all(&state.pool)
     .await?;
 
-    Ok(Json(rows))
+    Ok((total_count_headers(total), Json(rows)))
+}
+
+/// Rows per log section in the compliance export; newer rows win.
+const EXPORT_SECTION_LIMIT: i64 = 5000;
+
+/// Keep at most [`EXPORT_SECTION_LIMIT`] rows, reporting whether any were
+/// dropped. Callers fetch one extra row so truncation is detectable.
+fn cap_section<T>(mut rows: Vec<T>) -> (Vec<T>, bool) {
+    let truncated = rows.len() as i64 > EXPORT_SECTION_LIMIT;
+    rows.truncate(EXPORT_SECTION_LIMIT as usize);
+    (rows, truncated)
+}
+
+/// GET /admin/compliance/export — retention rules, subprocessors, breach
+/// logs and the most recent DSR and deletion log entries in one document.
+/// The log sections are capped; `truncated` says which ones hit the cap.
+async fn export_compliance_registers(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthUser>,
+) -> AppResult<(HeaderMap, Json<serde_json::Value>)> {
+    let pool = &state.pool;
+    let fetch_limit = EXPORT_SECTION_LIMIT + 1;
+
+    let retention_rules = sqlx::query_as::<_, RetentionRuleRow>(
+        "SELECT id, name, table_name, timestamp_column, retention_days, enabled, created_at, updated_at
+         FROM retention_rules
+         ORDER BY table_name, timestamp_column",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let subprocessors = sqlx::query_as::<_, SubprocessorRow>(
+        "SELECT id, provider, purpose, location, dpa_signed_date, dpa_review_date, transfer_basis,
+                notes, is_active, created_at, updated_at
+         FROM subprocessor_register
+         ORDER BY provider",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let (breach_logs, breach_logs_truncated) = cap_section(
+        sqlx::query_as::<_, BreachLogRow>(
+            "SELECT id, detected_at, reported_at, severity, status, title, description,
+                    affected_records, authority_notified, data_subjects_notified,
+                    created_by, created_at, updated_at
+             FROM breach_logs
+             ORDER BY detected_at DESC
+             LIMIT ?",
+        )
+        .bind(fetch_limit)
+        .fetch_all(pool)
+        .await?,
+    );
+
+    let (dsr_logs, dsr_logs_truncated) = cap_section(
+        sqlx::query_as::<_, DsrAuditRow>(
+            "SELECT id, request_id, user_id, action, status, requested_at, completed_at,
+                    error_message, actor_user_id, CAST(metadata AS CHAR) AS metadata
+             FROM dsr_audit_logs
+             ORDER BY requested_at DESC
+             LIMIT ?",
+        )
+        .bind(fetch_limit)
+        .fetch_all(pool)
+        .await?,
+    );
+
+    let (deletion_logs, deletion_logs_truncated) = cap_section(
+        sqlx::query_as::<_, DeletionLogRow>(
+            "SELECT id, table_name, record_id, deleted_at, reason,
+                    CAST(details AS CHAR) AS details, actor_user_id
+             FROM deletion_logs
+             ORDER BY deleted_at DESC
+             LIMIT ?",
+        )
+        .bind(fetch_limit)
+        .fetch_all(pool)
+        .await?,
+    );
+
+    let counts = serde_json::json!({
+        "retention_rules": retention_rules.len(),
+        "subprocessors": subprocessors.len(),
+        "breach_logs": breach_logs.len(),
+        "dsr_logs": dsr_logs.len(),
+        "deletion_logs": deletion_logs.len(),
+    });
+
+    sqlx::query(
+        "INSERT INTO admin_audit_logs (id, actor_user_id, action, target_table, target_id, details)
+         VALUES (?, ?, 'compliance_export', 'compliance', NULL, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&admin.user_id)
+    .bind(serde_json::json!({ "counts": counts }).to_string())
+    .execute(pool)
+    .await?;
+
+    let exported_at = chrono::Utc::now();
+    let mut headers = HeaderMap::new();
+    let disposition = format!(
+        "attachment; filename=\"compliance-export-{}.json\"",
+        exported_at.format("%Y%m%d-%H%M%S")
+    );
+    if let Ok(value) = HeaderValue::from_str(&disposition) {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+
+    Ok((
+        headers,
+        Json(serde_json::json!({
+            "exported_at": exported_at.to_rfc3339(),
+            "section_limit": EXPORT_SECTION_LIMIT,
+            "truncated": {
+                "breach_logs": breach_logs_truncated,
+                "dsr_logs": dsr_logs_truncated,
+                "deletion_logs": deletion_logs_truncated,
+            },
+            "retention_rules": retention_rules,
+            "subprocessors": subprocessors,
+            "breach_logs": breach_logs,
+            "dsr_logs": dsr_logs,
+            "deletion_logs": deletion_logs,
+        })),
+    ))
 }
 
 async fn list_retention_rules(
@@ -332,10 +623,33 @@ async fn run_retention_cleanup_now(
     State(state): State<AppState>,
     Extension(_admin): Extension<AuthUser>,
 ) -> AppResult<StatusCode> {
-    compliance::run_retention_cleanup(&state.pool)
+    let outcome = compliance::run_retention_cleanup(&state.pool).await;
+    if let Err(err) = compliance::record_retention_run(&state.pool, "manual", &outcome, None).await {
+        tracing::warn!(error = %err, "Unable to record retention run status");
+    }
+    compliance::publish_retention_outcome(&state.events, "manual", &outcome);
+    outcome.map_err(AppError::Internal)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_retention_status(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<AuthUser>,
+) -> AppResult<Json<compliance::RetentionStatusDto>> {
+    let status = compliance::get_retention_status(&state.pool, &state.config)
         .await
         .map_err(AppError::Internal)?;
-    Ok(StatusCode::NO_CONTENT)
+    Ok(Json(status))
+}
+
+async fn get_token_sweep_status(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<AuthUser>,
+) -> AppResult<Json<sweep::TokenSweepStatusDto>> {
+    let status = sweep::get_sweep_status(&state.pool, &state.config)
+        .await
+        .map_err(AppError::Internal)?;
+    Ok(Json(status))
 }
 
 async fn get_pictogram_prefetch_settings(
@@ -351,12 +665,27 @@ async fn update_pictogram_prefetch_settings(
     Extension(_admin): Extension<AuthUser>,
     Json(body): Json<UpdatePictogramPrefetchBody>,
 ) -> AppResult<Json<pictograms::PictogramPrefetchSettingsDto>> {
+    let ignore_idle_until = match body.ignore_idle_until.as_deref().map(str::trim) {
+        None => None,
+        Some("") => Some(None),
+        Some(raw) => {
+            let until = chrono::DateTime::parse_from_rfc3339(raw)
+                .map_err(|_| AppError::BadRequest("ignore_idle_until must be an RFC 3339 timestamp".into()))?
+                .with_timezone(&chrono::Utc);
+            if until <= chrono::Utc::now() {
+                return Err(AppError::BadRequest("ignore_idle_until must be in the future".into()));
+            }
+            Some(Some(until.naive_utc()))
+        }
+    };
+
     let settings = pictograms::update_prefetch_settings(
         &state.pool,
         &state.config,
         body.enabled,
         body.idle_minutes,
         body.batch_size,
+        ignore_idle_until,
     )
     .await?;
     Ok(Json(settings))
@@ -366,10 +695,33 @@ async fn run_pictogram_prefetch_now(
     State(state): State<AppState>,
     Extension(_admin): Extension<AuthUser>,
 ) -> AppResult<Json<pictograms::PictogramPrefetchRunResultDto>> {
-    let result = pictograms::run_prefetch_now(&state.pool, &state.config).await?;
+    let result = pictograms::run_prefetch_now(&state.pool, &state.config, &state.events).await?;
+    Ok(Json(result))
+}
+
+async fn reindex_pictograms(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<AuthUser>,
+) -> AppResult<Json<pictograms::PictogramReindexResultDto>> {
+    let result = pictograms::reindex_fulltext(&state.pool).await?;
     Ok(Json(result))
 }
 
+#[derive(Deserialize)]
+struct KeywordsRefreshQuery {
+    /// Language to refresh; every cached language when omitted.
+    lang: Option<String>,
+}
+
+async fn refresh_pictogram_keywords(
+    Extension(_admin): Extension<AuthUser>,
+    Query(q): Query<KeywordsRefreshQuery>,
+) -> AppResult<Json<Vec<pictograms::KeywordsRefreshDto>>> {
+    let lang = q.lang.as_deref().map(str::trim).filter(|l| !l.is_empty());
+    let refreshed = pictograms::refresh_keywords_cache(lang).await?;
+    Ok(Json(refreshed))
+}
+
 async fn list_breach_logs(
     State(state): State<AppState>,
     Extension(_admin): Extension<AuthUser>,
@@ -415,6 +767,12 @@ async fn create_breach_log(
     .execute(&state.pool)
     .await?;
 
+    events::publish(&state.events, AdminEvent::BreachLogCreated {
+        id: id.clone(),
+        severity: body.severity.clone(),
+        title: body.title.clone(),
+    });
+
     let row = sqlx::query_as::<_, BreachLogRow>(
         "SELECT id, detected_at, reported_at, severity, status, title, description,
                 affected_records, authority_notified, data_subjects_notified,
@@ -511,12 +869,239 @@ async fn update_breach_log(
     Ok(Json(row))
 }
 
+async fn delete_breach_log(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<StatusCode> {
+    // Attachment rows go with the FK cascade; files are removed afterwards.
+    let affected = sqlx::query("DELETE FROM breach_logs WHERE id = ?")
+        .bind(&id)
+        .execute(&state.pool)
+        .await?
+        .rows_affected();
+    if affected == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    sqlx::query(
+        "INSERT INTO deletion_logs (id, table_name, record_id, deleted_at, reason, details, actor_user_id)
+         VALUES (?, 'breach_logs', ?, NOW(), 'manual', JSON_OBJECT('source', '/admin/compliance/breach-logs'), ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&id)
+    .bind(&admin.user_id)
+    .execute(&state.pool)
+    .await?;
+
+    // Best-effort disk cleanup
+    let _ = tokio::fs::remove_dir_all(format!("{BREACH_EVIDENCE_DIR}/{id}")).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn ensure_breach_log_exists(pool: &crate::db::Db, id: &str) -> AppResult<()> {
+    let exists: Option<String> = sqlx::query_scalar("SELECT id FROM breach_logs WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    exists.map(|_| ()).ok_or(AppError::NotFound)
+}
+
+/// Map an allowed evidence extension to its content type. Binary formats are
+/// additionally checked against their magic bytes.
+fn breach_attachment_content_type(ext: &str, bytes: &[u8]) -> AppResult<&'static str> {
+    let (content_type, magic): (&str, Option<&[u8]>) = match ext {
+        "png" => ("image/png", Some(b"\x89PNG")),
+        "jpg" | "jpeg" => ("image/jpeg", Some(b"\xFF\xD8\xFF")),
+        "pdf" => ("application/pdf", Some(b"%PDF")),
+        "txt" | "log" => ("text/plain; charset=utf-8", None),
+        "csv" => ("text/csv; charset=utf-8", None),
+        "json" => ("application/json", None),
+        _ => {
+            return Err(AppError::BadRequest(
+                "Unsupported file type. Allowed: png, jpg, pdf, txt, log, csv, json".into(),
+            ))
+        }
+    };
+
+    let valid = match magic {
+        Some(prefix) => bytes.starts_with(prefix),
+        None => std::str::from_utf8(bytes).is_ok(),
+    };
+    if !valid {
+        return Err(AppError::BadRequest("File content does not match its extension".into()));
+    }
+    Ok(content_type)
+}
+
+async fn list_breach_attachments(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<Vec<BreachAttachmentRow>>> {
+    ensure_breach_log_exists(&state.pool, &id).await?;
+
+    let rows = sqlx::query_as::<_, BreachAttachmentRow>(
+        "SELECT id, breach_log_id, original_filename, stored_filename, content_type,
+                size_bytes, uploaded_by, created_at
+         FROM breach_log_attachments
+         WHERE breach_log_id = ?
+         ORDER BY created_at",
+    )
+    .bind(&id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// Attach an evidence file via `multipart/form-data` (field `file`).
+async fn upload_breach_attachment(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthUser>,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> AppResult<(StatusCode, Json<BreachAttachmentRow>)> {
+    ensure_breach_log_exists(&state.pool, &id).await?;
+
+    let mut file_data: Option<(String, Vec<u8>)> = None;
+    while let Some(field) = multipart.next_field().await
+        .map_err(|e| AppError::BadRequest(e.to_string()))? {
+        if field.name() == Some("file") {
+            let orig_name = field.file_name()
+                .map(|s| s.to_owned())
+                .unwrap_or_else(|| "evidence".into());
+            let bytes = field.bytes().await
+                .map_err(|e| AppError::BadRequest(e.to_string()))?;
+            file_data = Some((orig_name, bytes.to_vec()));
+        }
+    }
+
+    let (orig_name, bytes) = file_data.ok_or_else(|| AppError::BadRequest("Missing file field".into()))?;
+    if bytes.is_empty() {
+        return Err(AppError::BadRequest("File is empty".into()));
+    }
+    if bytes.len() > BREACH_ATTACHMENT_MAX_BYTES {
+        return Err(AppError::BadRequest("File exceeds the 10 MB limit".into()));
+    }
+
+    // Keep only the final path component of the client-supplied name.
+    let orig_name: String = std::path::Path::new(&orig_name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("evidence")
+        .chars()
+        .take(255)
+        .collect();
+    let ext = std::path::Path::new(&orig_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let content_type = breach_attachment_content_type(&ext, &bytes)?;
+
+    let dir = format!("{BREACH_EVIDENCE_DIR}/{id}");
+    tokio::fs::create_dir_all(&dir).await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Could not create evidence dir: {e}")))?;
+
+    let attachment_id = Uuid::new_v4().to_string();
+    let stored_filename = format!("{attachment_id}.{ext}");
+    tokio::fs::write(format!("{dir}/{stored_filename}"), &bytes).await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Write failed: {e}")))?;
+
+    sqlx::query(
+        "INSERT INTO breach_log_attachments
+            (id, breach_log_id, original_filename, stored_filename, content_type, size_bytes, uploaded_by)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&attachment_id)
+    .bind(&id)
+    .bind(&orig_name)
+    .bind(&stored_filename)
+    .bind(content_type)
+    .bind(bytes.len() as i64)
+    .bind(&admin.user_id)
+    .execute(&state.pool)
+    .await?;
+
+    let row = fetch_breach_attachment(&state.pool, &id, &attachment_id).await?;
+    Ok((StatusCode::CREATED, Json(row)))
+}
+
+async fn fetch_breach_attachment(
+    pool: &crate::db::Db,
+    breach_log_id: &str,
+    attachment_id: &str,
+) -> AppResult<BreachAttachmentRow> {
+    sqlx::query_as::<_, BreachAttachmentRow>(
+        "SELECT id, breach_log_id, original_filename, stored_filename, content_type,
+                size_bytes, uploaded_by, created_at
+         FROM breach_log_attachments
+         WHERE id = ? AND breach_log_id = ?",
+    )
+    .bind(attachment_id)
+    .bind(breach_log_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)
+}
+
+async fn download_breach_attachment(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<AuthUser>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> AppResult<(HeaderMap, Vec<u8>)> {
+    let row = fetch_breach_attachment(&state.pool, &id, &attachment_id).await?;
+    let bytes = tokio::fs::read(format!("{BREACH_EVIDENCE_DIR}/{id}/{}", row.stored_filename))
+        .await
+        .map_err(|_| AppError::NotFound)?;
+
+    let safe_name: String = row
+        .original_filename
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && *c != '"' && *c != '\\')
+        .collect();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_str(&row.content_type)
+            .unwrap_or_else(|_| HeaderValue::from_static("application/octet-stream")),
+    );
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    if let Ok(v) = HeaderValue::from_str(&format!("attachment; filename=\"{safe_name}\"")) {
+        headers.insert(header::CONTENT_DISPOSITION, v);
+    }
+
+    Ok((headers, bytes))
+}
+
+async fn delete_breach_attachment(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<AuthUser>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    let row = fetch_breach_attachment(&state.pool, &id, &attachment_id).await?;
+
+    sqlx::query("DELETE FROM breach_log_attachments WHERE id = ?")
+        .bind(&row.id)
+        .execute(&state.pool)
+        .await?;
+
+    // Best-effort disk cleanup
+    let _ = tokio::fs::remove_file(format!("{BREACH_EVIDENCE_DIR}/{id}/{}", row.stored_filename)).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn list_subprocessors(
     State(state): State<AppState>,
     Extension(_admin): Extension<AuthUser>,
 ) -> AppResult<Json<Vec<SubprocessorRow>>> {
     let rows = sqlx::query_as::<_, SubprocessorRow>(
-        "SELECT id, provider, purpose, location, dpa_signed_date, transfer_basis,
+        "SELECT id, provider, purpose, location, dpa_signed_date, dpa_review_date, transfer_basis,
                 notes, is_active, created_at, updated_at
          FROM subprocessor_register
          ORDER BY provider",
@@ -527,6 +1112,48 @@ async fn list_subprocessors(
     Ok(Json(rows))
 }
 
+/// Active subprocessors whose DPA review date has passed or falls within
+/// `within_days` of today (UTC), most urgent first.
+async fn list_subprocessors_due(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<AuthUser>,
+    Query(q): Query<SubprocessorDueQuery>,
+) -> AppResult<Json<Vec<SubprocessorDueItem>>> {
+    let within_days = q.within_days.unwrap_or(30);
+    if !(0..=3650).contains(&within_days) {
+        return Err(AppError::BadRequest("within_days must be in range 0..3650".into()));
+    }
+
+    let rows = sqlx::query_as::<_, SubprocessorRow>(
+        "SELECT id, provider, purpose, location, dpa_signed_date, dpa_review_date, transfer_basis,
+                notes, is_active, created_at, updated_at
+         FROM subprocessor_register
+         WHERE is_active = 1 AND dpa_review_date IS NOT NULL",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let today = chrono::Utc::now().date_naive();
+    let mut due: Vec<SubprocessorDueItem> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let days = (row.dpa_review_date? - today).num_days();
+            (days <= within_days).then_some(SubprocessorDueItem {
+                subprocessor: row,
+                days_until_review: days,
+                overdue: days < 0,
+            })
+        })
+        .collect();
+    due.sort_by(|a, b| {
+        a.days_until_review
+            .cmp(&b.days_until_review)
+            .then_with(|| a.subprocessor.provider.cmp(&b.subprocessor.provider))
+    });
+
+    Ok(Json(due))
+}
+
 async fn create_subprocessor(
     State(state): State<AppState>,
     Extension(_admin): Extension<AuthUser>,
@@ -535,14 +1162,15 @@ async fn create_subprocessor(
     let id = Uuid::new_v4().to_string();
     sqlx::query(
         "INSERT INTO subprocessor_register
-            (id, provider, purpose, location, dpa_signed_date, transfer_basis, notes, is_active)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            (id, provider, purpose, location, dpa_signed_date, dpa_review_date, transfer_basis, notes, is_active)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&id)
     .bind(&body.provider)
     .bind(&body.purpose)
     .bind(&body.location)
     .bind(&body.dpa_signed_date)
+    .bind(&body.dpa_review_date)
     .bind(&body.transfer_basis)
     .bind(&body.notes)
     .bind(body.is_active.unwrap_or(true))
@@ -550,7 +1178,7 @@ async fn create_subprocessor(
     .await?;
 
     let row = sqlx::query_as::<_, SubprocessorRow>(
-        "SELECT id, provider, purpose, location, dpa_signed_date, transfer_basis,
+        "SELECT id, provider, purpose, location, dpa_signed_date, dpa_review_date, transfer_basis,
                 notes, is_active, created_at, updated_at
          FROM subprocessor_register WHERE id = ?",
     )
@@ -595,6 +1223,13 @@ async fn update_subprocessor(
             .execute(&state.pool)
             .await?;
     }
+    if let Some(v) = &body.dpa_review_date {
+        sqlx::query("UPDATE subprocessor_register SET dpa_review_date = ? WHERE id = ?")
+            .bind(v)
+            .bind(&id)
+            .execute(&state.pool)
+            .await?;
+    }
     if let Some(v) = &body.transfer_basis {
         sqlx::query("UPDATE subprocessor_register SET transfer_basis = ? WHERE id = ?")
             .bind(v)
@@ -618,7 +1253,7 @@ async fn update_subprocessor(
     }
 
     let row = sqlx::query_as::<_, SubprocessorRow>(
-        "SELECT id, provider, purpose, location, dpa_signed_date, transfer_basis,
+        "SELECT id, provider, purpose, location, dpa_signed_date, dpa_review_date, transfer_basis,
                 notes, is_active, created_at, updated_at
          FROM subprocessor_register WHERE id = ?",
     )
@@ -6,11 +6,18 @@
 //! The cookie is intentionally **not** HttpOnly so that the frontend
 //! JavaScript can read it directly from `document.cookie` without an
 //! extra round-trip on every page load.
+//!
+//! These routes need no session, so they are rate limited per client IP
+//! (`CONSENT_RATE_LIMIT_PER_MINUTE`), take small bodies only, and a repeated
+//! choice from the same subject is not recorded twice.
+
+use std::time::Duration;
 
 use axum::{
+    extract::{DefaultBodyLimit, Extension, State},
     http::HeaderMap,
-    extract::State,
     http::StatusCode,
+    middleware,
     routing::get,
     Json, Router,
 };
@@ -23,18 +30,27 @@ use tower_cookies::{
 use uuid::Uuid;
 
 use crate::{
+    auth::{hash_token, SESSION_TOKEN_MATCH_SQL},
     errors::{AppError, AppResult},
+    middleware::rate_limit::{limit_by_ip, ClientIp, RateLimiter},
     state::AppState,
 };
 
 const CONSENT_COOKIE: &str = "cookie_consent";
 const SESSION_COOKIE: &str = "session";
 const DEFAULT_COOKIE_POLICY_VERSION: &str = "cookie-policy-v1";
+const SOURCES: [&str; 3] = ["banner", "settings", "api"];
+const MAX_POLICY_VERSION_LEN: usize = 64;
+/// A consent body is a few short strings; anything larger is rejected unread.
+const MAX_BODY_BYTES: usize = 1024;
 
-pub fn router() -> Router<AppState> {
+pub fn router(rate_limit_per_minute: u32, trusted_proxy_hops: usize) -> Router<AppState> {
+    let limiter = RateLimiter::new("consent", rate_limit_per_minute, Duration::from_secs(60), trusted_proxy_hops);
     Router::new()
         .route("/consent", get(get_consent).post(set_consent).delete(clear_consent))
     .route("/consent/policies", get(list_policy_versions))
+    .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+    .route_layer(middleware::from_fn_with_state(limiter, limit_by_ip))
 }
 
 // ── Response / request types ──────────────────────────────────
@@ -51,7 +67,7 @@ struct SetConsentBody {
     choice: String,
     /// Optional legal policy version identifier for auditability.
     policy_version: Option<String>,
-    /// Optional source marker (`banner`, `settings`, `api`).
+    /// Optional source marker (`banner`, `settings`, `api`); defaults to `banner`.
     source: Option<String>,
 }
 
@@ -96,6 +112,7 @@ async fn set_consent(
     State(state): State<AppState>,
     cookies: Cookies,
     headers: HeaderMap,
+    client_ip: Option<Extension<ClientIp>>,
     Json(body): Json<SetConsentBody>,
 ) -> AppResult<StatusCode> {
     if !matches!(body.choice.as_str(), "accepted" | "declined") {
@@ -103,27 +120,52 @@ async fn set_consent(
             "choice must be 'accepted' or 'declined'".into(),
         ));
     }
+    let source = body.source.unwrap_or_else(|| "banner".to_owned());
+    if !SOURCES.contains(&source.as_str()) {
+        return Err(AppError::BadRequest(format!("source must be one of: {}", SOURCES.join(", "))));
+    }
+    let policy_version = body
+        .policy_version
+        .unwrap_or_else(|| DEFAULT_COOKIE_POLICY_VERSION.to_owned());
+    let well_formed = policy_version
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if policy_version.is_empty() || policy_version.len() > MAX_POLICY_VERSION_LEN || !well_formed {
+        return Err(AppError::BadRequest(format!(
+            "policy_version must be 1-{MAX_POLICY_VERSION_LEN} letters, digits, '-', '_' or '.'"
+        )));
+    }
+    ensure_policy_version_is_active(&state, "cookies", &policy_version).await?;
 
-    let is_prod = state.config.app_env != "development";
+    let secure = state.config.secure_cookies();
 
     let choice = body.choice;
+    let previous = cookies.get(CONSENT_COOKIE).map(|c| c.value().to_owned());
     let cookie = Cookie::build((CONSENT_COOKIE, choice.clone()))
         .http_only(false)          // JS must be able to read it
         .same_site(SameSite::Lax)  // Lax allows cookie on top-level navigations
         .path("/")
-        .secure(is_prod)
+        .secure(secure)
         .max_age(CookieDuration::days(365))
         .build();
 
     cookies.add(cookie);
 
     let user_id = resolve_user_id_from_session_cookie(&state, &cookies).await?;
-    let source = body.source.unwrap_or_else(|| "banner".to_owned());
-    let policy_version = body
-        .policy_version
-        .unwrap_or_else(|| DEFAULT_COOKIE_POLICY_VERSION.to_owned());
-
-    ensure_policy_version_is_active(&state, "cookies", &policy_version).await?;
+    let (user_agent_hash, ip_hash) = request_hashes(&headers, client_ip.as_ref().map(|Extension(ip)| ip));
+    let repeat = is_repeat_consent(
+        &state,
+        user_id.as_deref(),
+        (ip_hash.as_deref(), user_agent_hash.as_deref()),
+        "cookies",
+        &choice,
+        &policy_version,
+        previous.as_deref(),
+    )
+    .await?;
+    if repeat {
+        return Ok(StatusCode::NO_CONTENT);
+    }
 
     persist_consent_event(
         &state,
@@ -132,7 +174,7 @@ async fn set_consent(
         choice.as_str(),
         Some(policy_version),
         source,
-        &headers,
+        (user_agent_hash, ip_hash),
         None,
     )
     .await?;
@@ -145,13 +187,13 @@ async fn clear_consent(
     State(state): State<AppState>,
     cookies: Cookies,
 ) -> AppResult<StatusCode> {
-    let is_prod = state.config.app_env != "development";
+    let secure = state.config.secure_cookies();
 
     let cookie = Cookie::build((CONSENT_COOKIE, ""))
         .http_only(false)
         .same_site(SameSite::Lax)
         .path("/")
-        .secure(is_prod)
+        .secure(secure)
         .max_age(CookieDuration::ZERO)
         .build();
 
@@ -166,7 +208,7 @@ async fn clear_consent(
         "withdrawn",
         Some(DEFAULT_COOKIE_POLICY_VERSION.to_owned()),
         "api".to_owned(),
-        &HeaderMap::new(),
+        (None, None),
         None,
     )
     .await?;
@@ -187,16 +229,17 @@ async fn resolve_user_id_from_session_cookie(
         user_id: String,
     }
 
-    let row = sqlx::query_as::<_, SessionUserRow>(
+    let row = sqlx::query_as::<_, SessionUserRow>(&format!(
         "SELECT u.id AS user_id
          FROM user_sessions s
          JOIN users u ON u.id = s.user_id
-         WHERE s.token = ?
+         WHERE {SESSION_TOKEN_MATCH_SQL}
            AND s.expires_at > NOW()
            AND u.is_active = 1
            AND u.deleted_at IS NULL
-         LIMIT 1",
-    )
+         LIMIT 1"
+    ))
+    .bind(hash_token(&token))
     .bind(&token)
     .fetch_optional(&state.pool)
     .await?;
@@ -204,6 +247,7 @@ async fn resolve_user_id_from_session_cookie(
     Ok(row.map(|r| r.user_id))
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn persist_consent_event(
     state: &AppState,
     user_id: Option<&str>,
@@ -211,25 +255,10 @@ async fn persist_consent_event(
     choice: &str,
     policy_version: Option<String>,
     source: String,
-    headers: &HeaderMap,
+    (user_agent_hash, ip_hash): (Option<String>, Option<String>),
     evidence_json: Option<serde_json::Value>,
 ) -> AppResult<()> {
     let id = Uuid::new_v4().to_string();
-    let user_agent_hash = headers
-        .get(axum::http::header::USER_AGENT)
-        .and_then(|v| v.to_str().ok())
-        .map(hash_value);
-    let ip_hash = headers
-        .get("x-forwarded-for")
-        .and_then(|v| v.to_str().ok())
-        .and_then(|v| v.split(',').next())
-        .map(|v| hash_value(v.trim()))
-        .or_else(|| {
-            headers
-                .get("x-real-ip")
-                .and_then(|v| v.to_str().ok())
-                .map(|v| hash_value(v.trim()))
-        });
 
     sqlx::query(
         "INSERT INTO consent_events
@@ -251,6 +280,58 @@ async fn persist_consent_event(
     Ok(())
 }
 
+/// `(user_agent_hash, ip_hash)` recorded with a consent event. The IP is the
+/// one the rate limiter resolved, so forwarding headers are only used when
+/// the proxy is trusted.
+fn request_hashes(headers: &HeaderMap, client_ip: Option<&ClientIp>) -> (Option<String>, Option<String>) {
+    let user_agent_hash = headers
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(hash_value);
+    let ip_hash = client_ip.map(|ClientIp(ip)| hash_value(&ip.to_string()));
+    (user_agent_hash, ip_hash)
+}
+
+/// Whether `choice` is already on record as the subject's current choice, so
+/// a re-shown banner, a retried request or a script adds no row. The subject
+/// is the signed-in user, otherwise the `(ip_hash, user_agent_hash)` pair.
+/// Events carry no ordering we rely on, so "current" means the visitor's
+/// cookie still holds `choice` or the subject never recorded another choice
+/// for this policy version.
+async fn is_repeat_consent(
+    state: &AppState,
+    user_id: Option<&str>,
+    (ip_hash, user_agent_hash): (Option<&str>, Option<&str>),
+    consent_scope: &str,
+    choice: &str,
+    policy_version: &str,
+    previous_cookie: Option<&str>,
+) -> AppResult<bool> {
+    let subject = match user_id {
+        Some(_) => "user_id = ?",
+        None if ip_hash.is_none() && user_agent_hash.is_none() => return Ok(false),
+        None => "user_id IS NULL AND ip_hash <=> ? AND user_agent_hash <=> ?",
+    };
+    let sql = format!(
+        "SELECT CAST(COALESCE(SUM(choice = ?), 0) AS SIGNED),
+                CAST(COALESCE(SUM(choice <> ?), 0) AS SIGNED)
+         FROM consent_events
+         WHERE {subject} AND consent_scope = ? AND policy_version = ?"
+    );
+    let mut query = sqlx::query_as::<_, (i64, i64)>(&sql).bind(choice).bind(choice);
+    query = match user_id {
+        Some(user_id) => query.bind(user_id),
+        None => query.bind(ip_hash).bind(user_agent_hash),
+    };
+    let (same, other) = query
+        .bind(consent_scope)
+        .bind(policy_version)
+        .fetch_one(&state.pool)
+        .await?;
+
+    Ok(same > 0 && (previous_cookie == Some(choice) || other == 0))
+}
+
 fn hash_value(raw: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(raw.as_bytes());
@@ -286,3 +367,156 @@ async fn ensure_policy_version_is_active(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::{
+        body::Body,
+        extract::ConnectInfo,
+        http::{header, Request},
+    };
+    use tower::ServiceExt;
+    use tower_cookies::CookieManagerLayer;
+
+    use super::*;
+    use crate::test_support;
+
+    fn app(state: AppState, per_minute: u32) -> Router {
+        router(per_minute, 0).layer(CookieManagerLayer::new()).with_state(state)
+    }
+
+    fn request(method: &str, peer: &str, body: Option<&str>, extra: &[(header::HeaderName, &str)]) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri("/consent")
+            .header(header::USER_AGENT, "test-agent");
+        for (name, value) in extra {
+            builder = builder.header(name, *value);
+        }
+        let mut req = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_owned()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        };
+        let addr: SocketAddr = peer.parse().unwrap();
+        req.extensions_mut().insert(ConnectInfo(addr));
+        req
+    }
+
+    #[tokio::test]
+    async fn over_limit_gets_429_even_with_rotating_forwarded_for() {
+        // GET /consent never touches the database.
+        let pool = sqlx::mysql::MySqlPoolOptions::new().connect_lazy("mysql://unused@localhost/unused").unwrap();
+        let app = app(test_support::state(pool), 2);
+
+        for i in 0..2 {
+            let forwarded = format!("198.51.100.{i}");
+            let res = app
+                .clone()
+                .oneshot(request("GET", "192.0.2.1:1000", None, &[(header::HeaderName::from_static("x-forwarded-for"), &forwarded)]))
+                .await
+                .unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+        }
+
+        let spoofed = [(header::HeaderName::from_static("x-forwarded-for"), "198.51.100.99")];
+        let res = app.clone().oneshot(request("GET", "192.0.2.1:1000", None, &spoofed)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(res.headers().contains_key(header::RETRY_AFTER));
+
+        let res = app.oneshot(request("GET", "192.0.2.2:1000", None, &[])).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn oversized_body_is_rejected() {
+        let pool = sqlx::mysql::MySqlPoolOptions::new().connect_lazy("mysql://unused@localhost/unused").unwrap();
+        let app = app(test_support::state(pool), 100);
+        let body = format!(r#"{{"choice":"accepted","source":"{}"}}"#, "x".repeat(MAX_BODY_BYTES));
+        let res = app.oneshot(request("POST", "192.0.2.1:1000", Some(&body), &[])).await.unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    /// The consent tables are not created by `database/migrations`; this is
+    /// the shape the handlers read and write.
+    async fn create_consent_tables(pool: &crate::db::Db) {
+        for sql in [
+            "CREATE TABLE IF NOT EXISTS policy_versions (
+                policy_scope VARCHAR(32) NOT NULL,
+                version      VARCHAR(64) NOT NULL,
+                title        VARCHAR(255) NULL,
+                published_at DATETIME NOT NULL,
+                retired_at   DATETIME NULL,
+                PRIMARY KEY (policy_scope, version)
+            )",
+            "CREATE TABLE IF NOT EXISTS consent_events (
+                id              CHAR(36) NOT NULL PRIMARY KEY,
+                user_id         CHAR(36) NULL,
+                consent_scope   VARCHAR(32) NOT NULL,
+                choice          VARCHAR(16) NOT NULL,
+                policy_version  VARCHAR(64) NULL,
+                source          VARCHAR(32) NOT NULL,
+                user_agent_hash CHAR(64) NULL,
+                ip_hash         CHAR(64) NULL,
+                evidence_json   JSON NULL,
+                created_at      DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+            "INSERT INTO policy_versions (policy_scope, version, published_at)
+             VALUES ('cookies', 'cookie-policy-v1', NOW() - INTERVAL 1 DAY)",
+        ] {
+            sqlx::query(sql).execute(pool).await.unwrap();
+        }
+    }
+
+    async fn event_count(pool: &crate::db::Db) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM consent_events").fetch_one(pool).await.unwrap()
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn repeated_accept_is_recorded_once(pool: crate::db::Db) {
+        create_consent_tables(&pool).await;
+        let app = app(test_support::state(pool.clone()), 100);
+        let accept = r#"{"choice":"accepted"}"#;
+        let peer = "192.0.2.1:1000";
+
+        let res = app.clone().oneshot(request("POST", peer, Some(accept), &[])).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        let res = app.clone().oneshot(request("POST", peer, Some(accept), &[])).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        let cookie = [(header::COOKIE, "cookie_consent=accepted")];
+        app.clone().oneshot(request("POST", peer, Some(accept), &cookie)).await.unwrap();
+        assert_eq!(event_count(&pool).await, 1);
+
+        // A real change is still recorded, and so is switching back.
+        app.clone().oneshot(request("POST", peer, Some(r#"{"choice":"declined"}"#), &cookie)).await.unwrap();
+        assert_eq!(event_count(&pool).await, 2);
+        let declined = [(header::COOKIE, "cookie_consent=declined")];
+        app.clone().oneshot(request("POST", peer, Some(accept), &declined)).await.unwrap();
+        assert_eq!(event_count(&pool).await, 3);
+
+        // Another client is another subject.
+        app.oneshot(request("POST", "192.0.2.2:1000", Some(accept), &[])).await.unwrap();
+        assert_eq!(event_count(&pool).await, 4);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn unknown_source_and_policy_version_are_rejected(pool: crate::db::Db) {
+        create_consent_tables(&pool).await;
+        let app = app(test_support::state(pool.clone()), 100);
+        for body in [
+            r#"{"choice":"accepted","source":"spam"}"#,
+            r#"{"choice":"accepted","policy_version":"cookie-policy-v9"}"#,
+            r#"{"choice":"accepted","policy_version":"bad version!"}"#,
+        ] {
+            let res = app.clone().oneshot(request("POST", "192.0.2.1:1000", Some(body), &[])).await.unwrap();
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{body}");
+        }
+        assert_eq!(event_count(&pool).await, 0);
+    }
+}
@@ -7,7 +7,6 @@ use axum::{
     Json, Router,
 };
 use serde::Serialize;
-use std::path::PathBuf;
 use tokio::fs;
 use uuid::Uuid;
 
@@ -15,11 +14,12 @@ use crate::{
     errors::{AppError, AppResult},
     middleware::auth_guard::AuthUser,
     models::UserRole,
+    services::{image_upload, storage},
     state::AppState,
 };
 
 /// Directory where uploaded images are stored (relative to the binary's cwd).
-const UPLOAD_DIR: &str = "uploads/images";
+const UPLOAD_DIR: &str = storage::IMAGE_UPLOAD_DIR;
 
 pub fn router() -> Router<AppState> {
     Router::new()
@@ -69,6 +69,9 @@ async fn list_images(
 
 /// Upload an image via `multipart/form-data`.
 ///
+/// JPEG, PNG, GIF and WebP are re-encoded (GIF and WebP to PNG) with metadata
+/// stripped; SVGs are rejected.
+///
 /// Fields:
 /// * `file`     — the image file (required)
 /// * `alt_text` — optional description
@@ -105,39 +108,52 @@ async fn upload_image(
         }
     }
 
-    let (orig_name, bytes) = file_data.ok_or_else(|| AppError::BadRequest("Missing file field".into()))?;
+    let (_orig_name, bytes) = file_data.ok_or_else(|| AppError::BadRequest("Missing file field".into()))?;
 
-    // Derive extension from original filename
-    let ext = PathBuf::from(&orig_name)
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("bin")
-        .to_lowercase();
+    // The type comes from the content, never the client's filename, and what
+    // is stored is a re-encoded copy.
+    let image = tokio::task::spawn_blocking(move || image_upload::sanitize(&bytes))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Image processing task failed: {e}")))??;
 
-    // Validate extension
-    if !matches!(ext.as_str(), "jpg" | "jpeg" | "png" | "gif" | "webp" | "svg") {
-        return Err(AppError::BadRequest("Unsupported image type".into()));
+    // Admin uploads are system images and do not count against any quota.
+    let owner: Option<String> = if user.role == UserRole::Admin { None } else { Some(user.user_id.clone()) };
+    let size_bytes = image.bytes.len() as u64;
+    if let Some(owner) = &owner {
+        storage::reserve(pool, &state.config, owner, size_bytes).await?;
     }
 
-    // Ensure upload directory exists
-    fs::create_dir_all(UPLOAD_DIR).await
-        .map_err(|e| AppError::Internal(anyhow::anyhow!("Could not create upload dir: {e}")))?;
-
+    // One directory per owner; `filename` is relative to `UPLOAD_DIR`.
     let image_id  = Uuid::new_v4().to_string();
-    let filename  = format!("{}.{}", image_id, ext);
+    let owner_dir = owner.as_deref().unwrap_or("system");
+    let filename  = format!("{}/{}.{}", owner_dir, image_id, image.ext);
     let disk_path = format!("{}/{}", UPLOAD_DIR, filename);
     let url_path  = format!("/uploads/images/{}", filename);
 
-    fs::write(&disk_path, &bytes).await
-        .map_err(|e| AppError::Internal(anyhow::anyhow!("Write failed: {e}")))?;
+    let stored: AppResult<()> = async {
+        // Ensure upload directory exists
+        fs::create_dir_all(format!("{}/{}", UPLOAD_DIR, owner_dir)).await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Could not create upload dir: {e}")))?;
 
-    let owner: Option<String> = if user.role == UserRole::Admin { None } else { Some(user.user_id.clone()) };
+        fs::write(&disk_path, &image.bytes).await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Write failed: {e}")))?;
 
-    sqlx::query(
-        "INSERT INTO image_library (id, owner_id, filename, path, alt_text) VALUES (?, ?, ?, ?, ?)",
-    )
-    .bind(&image_id).bind(&owner).bind(&filename).bind(&url_path).bind(&alt_text)
-    .execute(pool).await?;
+        sqlx::query(
+            "INSERT INTO image_library (id, owner_id, filename, path, alt_text, size_bytes) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&image_id).bind(&owner).bind(&filename).bind(&url_path).bind(&alt_text).bind(size_bytes)
+        .execute(pool).await?;
+        Ok(())
+    }
+    .await;
+    if let Err(err) = stored {
+        // Release the reservation.
+        let _ = fs::remove_file(&disk_path).await;
+        if let Some(owner) = &owner {
+            storage::release(pool, owner, size_bytes).await?;
+        }
+        return Err(err);
+    }
 
     let row: ImageRow = sqlx::query_as::<_, ImageRow>(
         "SELECT id, owner_id, filename, path, alt_text FROM image_library WHERE id = ?",
@@ -173,9 +189,16 @@ async fn delete_image(
         _ => {}
     }
 
-    sqlx::query("DELETE FROM image_library WHERE id = ?")
+    let size_bytes: u64 = sqlx::query_scalar("SELECT size_bytes FROM image_library WHERE id = ?")
+        .bind(&id).fetch_one(pool).await?;
+    let deleted = sqlx::query("DELETE FROM image_library WHERE id = ?")
         .bind(&id).execute(pool).await?;
 
+    // Only the request that actually removed the row gives its bytes back.
+    if let (Some(owner), 1) = (&row.owner_id, deleted.rows_affected()) {
+        storage::release(pool, owner, size_bytes).await?;
+    }
+
     // Best-effort disk cleanup
     let disk_path = format!("{}/{}", UPLOAD_DIR, row.filename);
     let _ = fs::remove_file(&disk_path).await;
@@ -11,21 +11,32 @@ mod children;
 mod compliance;
 mod consent;
 mod images;
+mod openapi;
 mod pictograms;
+mod reminders;
+mod schedule_sql;
 mod schedules;
+mod search;
 mod users;
+mod validate;
 mod visual_documents;
 
+pub(crate) use calendar::{day_plan, DayPlan};
+
 /// Build the full `/api/v1` router.
 ///
 /// Public auth routes are left unprotected; every other route is wrapped in
 /// the session-based [`require_auth`] middleware.
 pub fn all_routes(state: AppState) -> Router<AppState> {
+    let consent_rate_limit = state.config.consent_rate_limit_per_minute;
+    let trusted_proxy_hops = state.config.trusted_proxy_hops;
     let auth_mw = middleware::from_fn_with_state(state, require_auth);
     Router::new()
         .merge(auth::router())
-        .merge(consent::router())   // public — no auth required
+        .merge(consent::router(consent_rate_limit, trusted_proxy_hops))   // public — no auth required
         .merge(calendar::public_router())
+        .merge(users::public_router())
+        .merge(openapi::router())   // public — API description
         .merge(
             Router::new()
                 .merge(children::router())
@@ -33,8 +44,10 @@ pub fn all_routes(state: AppState) -> Router<AppState> {
                 .merge(calendar::router())
                 .merge(images::router())
                 .merge(pictograms::router())
+                .merge(reminders::router())
                 .merge(admin::router())
                 .merge(compliance::router())
+                .merge(search::router())
                 .merge(users::router())
                 .merge(visual_documents::router())
                 .route_layer(auth_mw),
@@ -0,0 +1,130 @@
+//! `/openapi.json` and `/docs` — machine-readable API description.
+//!
+//! The document is generated from the handler annotations and DTO types in
+//! the `auth`, `children`, `schedules` and `calendar` modules, so it cannot
+//! drift from what those handlers actually accept and return. Other modules
+//! are not described yet.
+
+use axum::{
+    http::header,
+    response::{Html, IntoResponse},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use utoipa::OpenApi;
+
+use crate::state::AppState;
+
+/// Body of endpoints that only confirm an action (`{"message": "..."}`).
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct MessageResponse {
+    pub message: String,
+}
+
+/// Error body shared by every endpoint. `code` is only set for validation
+/// failures, resolvable conflicts and rate limiting (`rate_limited`), and
+/// `details` for the first two; `error_id` only on 5xx responses, matching the
+/// server log entry for that failure.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_id: Option<String>,
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "CarlsCalendar API", description = "Session-cookie authenticated JSON API."),
+    servers((url = "/api/v1")),
+    components(schemas(MessageResponse, ErrorResponse)),
+    tags(
+        (name = "auth", description = "Parent and child-device sessions"),
+        (name = "children", description = "Child profiles, QR codes and paired devices"),
+        (name = "schedules", description = "Weekly schedules, templates and activity cards"),
+        (name = "calendar", description = "Week view and weekday assignments"),
+        (name = "child", description = "Endpoints used by paired child devices"),
+    )
+)]
+struct ApiDoc;
+
+fn document() -> utoipa::openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    doc.merge(super::auth::ApiDoc::openapi());
+    doc.merge(super::children::ApiDoc::openapi());
+    doc.merge(super::schedules::ApiDoc::openapi());
+    doc.merge(super::calendar::ApiDoc::openapi());
+    doc
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/openapi.json", get(openapi_json))
+        .route("/docs", get(docs))
+}
+
+async fn openapi_json() -> impl IntoResponse {
+    (
+        [(header::CACHE_CONTROL, "no-cache")],
+        Json(document()),
+    )
+}
+
+/// Plain operation index; the full schemas are in `openapi.json`.
+async fn docs() -> Html<String> {
+    let doc = document();
+    let mut rows = String::new();
+    for (path, item) in &doc.paths.paths {
+        let ops = [
+            ("GET", &item.get),
+            ("POST", &item.post),
+            ("PUT", &item.put),
+            ("PATCH", &item.patch),
+            ("DELETE", &item.delete),
+        ];
+        for (method, op) in ops {
+            let Some(op) = op else { continue };
+            let tag = op.tags.as_ref().and_then(|t| t.first()).map(String::as_str).unwrap_or("");
+            let summary = op.summary.as_deref().or(op.description.as_deref()).unwrap_or("");
+            rows.push_str(&format!(
+                "<tr><td>{}</td><td><code>{method}</code></td><td><code>/api/v1{}</code></td><td>{}</td></tr>\n",
+                escape(tag),
+                escape(path),
+                escape(summary.lines().next().unwrap_or("")),
+            ));
+        }
+    }
+
+    Html(format!(
+        "<!doctype html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{title} {version}</title>
+<style>
+body {{ font-family: system-ui, sans-serif; margin: 2rem; }}
+table {{ border-collapse: collapse; }}
+td, th {{ border-bottom: 1px solid #ddd; padding: .3rem .8rem; text-align: left; vertical-align: top; }}
+</style>
+</head>
+<body>
+<h1>{title} {version}</h1>
+<p>Machine-readable description: <a href=\"openapi.json\">openapi.json</a></p>
+<table>
+<tr><th>Tag</th><th>Method</th><th>Path</th><th>Summary</th></tr>
+{rows}</table>
+</body>
+</html>",
+        title = escape(&doc.info.title),
+        version = escape(&doc.info.version),
+    ))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
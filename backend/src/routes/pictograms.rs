@@ -1,5 +1,6 @@
 use axum::{
     extract::{Extension, Path, Query, State},
+    http::{HeaderMap, HeaderValue},
     routing::{delete, get, post},
     Json, Router,
 };
@@ -21,25 +22,34 @@ pub fn router() -> Router<AppState> {
         // Browse: latest from ARASAAC + keyword autocomplete list
         .route("/pictograms/new",      get(new_pictograms))
         .route("/pictograms/keywords", get(get_keywords))
+        .route("/pictograms/attribution", get(get_attribution))
         // Saved pictogram library
-        .route("/pictograms/saved",              get(list_saved).post(save_pictogram))
+        .route("/pictograms/saved",              get(list_saved).post(save_pictogram).delete(clear_saved))
+        .route("/pictograms/unsave-bulk",        post(unsave_bulk))
         .route("/pictograms/saved/ids",          get(saved_ids))
         .route("/pictograms/saved/{id}",         delete(unsave_pictogram))
         .route("/pictograms/saved/{id}/use",     post(record_use))
+        .route("/pictograms/{id}/saved-state",   get(saved_state))
 }
 
 async fn search_pictograms(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((language, query)): Path<(String, String)>,
+    Query(options): Query<pictograms::PictogramOptions>,
 ) -> AppResult<Json<Vec<pictograms::PictogramDto>>> {
     pictograms::mark_activity();
 
     if user.role == UserRole::Child {
         return Ok(Json(vec![]));
     }
+    let options = options.normalized()?;
 
-    match pictograms::search_local_first(&state.pool, &language, &query).await {
+    let result = match pictograms::search_local_first(&state.pool, &language, &query, Some(&user.user_id)).await {
+        Ok(mut list) => pictograms::apply_variant_options(&mut list, &options).await.map(|_| list),
+        Err(err) => Err(err),
+    };
+    match result {
         Ok(list) => Ok(Json(list)),
         Err(err) => {
             tracing::warn!(error = ?err, language, query, "Pictogram search failed; returning empty result set");
@@ -52,6 +62,7 @@ async fn get_pictogram_by_id(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((language, arasaac_id)): Path<(String, i32)>,
+    Query(options): Query<pictograms::PictogramOptions>,
 ) -> AppResult<Json<pictograms::PictogramDto>> {
     pictograms::mark_activity();
 
@@ -59,7 +70,10 @@ async fn get_pictogram_by_id(
         return Err(crate::errors::AppError::Forbidden);
     }
 
-    match pictograms::get_or_fetch_by_id(&state.pool, &language, arasaac_id).await {
+    // Reject unknown option values up front rather than masking them as 404.
+    let options = options.normalized()?;
+
+    match pictograms::get_or_fetch_variant_by_id(&state.pool, &language, arasaac_id, &options).await {
         Ok(item) => Ok(Json(item)),
         Err(err) => {
             tracing::warn!(error = ?err, language, arasaac_id, "Pictogram fetch failed");
@@ -75,19 +89,60 @@ struct LangQuery {
     lang: Option<String>,
 }
 
+/// `?lang=` when given, otherwise the caller's profile language (`en` if unset).
+async fn request_language(
+    pool: &crate::db::Db,
+    user: &AuthUser,
+    lang: Option<&str>,
+) -> AppResult<String> {
+    if let Some(lang) = lang.map(str::trim).filter(|l| !l.is_empty()) {
+        return Ok(lang.to_string());
+    }
+    let language: Option<String> = sqlx::query_scalar("SELECT language FROM users WHERE id = ?")
+        .bind(&user.user_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(language.filter(|l| !l.trim().is_empty()).unwrap_or_else(|| "en".to_string()))
+}
+
+#[derive(Deserialize)]
+struct SavedListQuery {
+    lang: Option<String>,
+    /// Substring of the saved label or the pictogram keywords.
+    q: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+/// Saved pictograms, most used first. The unpaged total matching `q` is
+/// returned in the `X-Total-Count` header.
 async fn list_saved(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
-    Query(q): Query<LangQuery>,
-) -> AppResult<Json<Vec<pictograms::SavedPictogramDto>>> {
+    Query(q): Query<SavedListQuery>,
+) -> AppResult<(HeaderMap, Json<Vec<pictograms::SavedPictogramDto>>)> {
     pictograms::mark_activity();
 
     if user.role == UserRole::Child {
-        return Ok(Json(vec![]));
+        return Ok((total_count_headers(0), Json(vec![])));
     }
-    let lang = q.lang.as_deref().unwrap_or("en");
-    let items = pictograms::list_saved_pictograms(&state.pool, &user.user_id, lang).await?;
-    Ok(Json(items))
+    let lang = request_language(&state.pool, &user, q.lang.as_deref()).await?;
+    let (total, items) = pictograms::list_saved_pictograms(
+        &state.pool,
+        &user.user_id,
+        &lang,
+        q.q.as_deref(),
+        q.limit.unwrap_or(pictograms::SAVED_PAGE_DEFAULT),
+        q.offset.unwrap_or(0),
+    )
+    .await?;
+    Ok((total_count_headers(total), Json(items)))
+}
+
+fn total_count_headers(total: i64) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-total-count", HeaderValue::from(total));
+    headers
 }
 
 async fn saved_ids(
@@ -103,6 +158,26 @@ async fn saved_ids(
     Ok(Json(ids))
 }
 
+/// Star state for one search result without fetching the whole library.
+async fn saved_state(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(arasaac_id): Path<i32>,
+) -> AppResult<Json<pictograms::SavedStateDto>> {
+    pictograms::mark_activity();
+
+    if user.role == UserRole::Child {
+        return Ok(Json(pictograms::SavedStateDto {
+            arasaac_id,
+            saved: false,
+            used_count: 0,
+            saved_at: None,
+        }));
+    }
+    let saved = pictograms::saved_state_for_user(&state.pool, &user.user_id, arasaac_id).await?;
+    Ok(Json(saved))
+}
+
 #[derive(Deserialize)]
 struct SaveBody {
     arasaac_id: i32,
@@ -137,6 +212,62 @@ async fn unsave_pictogram(
     Ok(Json(json!({ "ok": true })))
 }
 
+/// Most ids accepted by one `POST /pictograms/unsave-bulk`.
+const MAX_BULK_UNSAVE: usize = 500;
+
+#[derive(Deserialize)]
+struct UnsaveBulkBody {
+    arasaac_ids: Vec<i32>,
+}
+
+async fn unsave_bulk(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(body): Json<UnsaveBulkBody>,
+) -> AppResult<Json<serde_json::Value>> {
+    pictograms::mark_activity();
+
+    if user.role == UserRole::Child {
+        return Err(crate::errors::AppError::Forbidden);
+    }
+    if body.arasaac_ids.len() > MAX_BULK_UNSAVE {
+        return Err(crate::errors::AppError::BadRequest(format!(
+            "At most {MAX_BULK_UNSAVE} ids per request"
+        )));
+    }
+    let mut ids = body.arasaac_ids;
+    ids.sort_unstable();
+    ids.dedup();
+    let removed = pictograms::unsave_pictograms(&state.pool, &user.user_id, &ids).await?;
+    Ok(Json(json!({ "removed": removed })))
+}
+
+#[derive(Deserialize)]
+struct ClearSavedQuery {
+    #[serde(default)]
+    confirm: bool,
+}
+
+/// Clear the whole saved library; requires `?confirm=true`.
+async fn clear_saved(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Query(q): Query<ClearSavedQuery>,
+) -> AppResult<Json<serde_json::Value>> {
+    pictograms::mark_activity();
+
+    if user.role == UserRole::Child {
+        return Err(crate::errors::AppError::Forbidden);
+    }
+    if !q.confirm {
+        return Err(crate::errors::AppError::BadRequest(
+            "Clearing all saved pictograms requires confirm=true".into(),
+        ));
+    }
+    let removed = pictograms::clear_saved_pictograms(&state.pool, &user.user_id).await?;
+    Ok(Json(json!({ "removed": removed })))
+}
+
 async fn record_use(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -148,6 +279,18 @@ async fn record_use(
     Ok(Json(json!({ "ok": true })))
 }
 
+/// CC BY-NC-SA attribution line for print and export footers, in `?lang=` or
+/// the caller's profile language.
+async fn get_attribution(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Query(q): Query<LangQuery>,
+) -> AppResult<Json<serde_json::Value>> {
+    let lang = request_language(&state.pool, &user, q.lang.as_deref()).await?;
+    let license = pictograms::attribution(&lang);
+    Ok(Json(json!({ "language": lang, "license": license })))
+}
+
 // ── Browse handlers ──────────────────────────────────────────────────────────
 
 #[derive(Deserialize)]
@@ -160,15 +303,21 @@ async fn new_pictograms(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Query(q): Query<NewQuery>,
+    Query(options): Query<pictograms::PictogramOptions>,
 ) -> AppResult<Json<Vec<pictograms::PictogramDto>>> {
     pictograms::mark_activity();
 
     if user.role == UserRole::Child {
         return Ok(Json(vec![]));
     }
-    let lang = q.lang.as_deref().unwrap_or("en");
+    let options = options.normalized()?;
+    let lang = request_language(&state.pool, &user, q.lang.as_deref()).await?;
     let n = q.n.unwrap_or(30);
-    match pictograms::get_new_pictograms(&state.pool, lang, n).await {
+    let result = match pictograms::get_new_pictograms(&state.pool, &lang, n).await {
+        Ok(mut list) => pictograms::apply_variant_options(&mut list, &options).await.map(|_| list),
+        Err(err) => Err(err),
+    };
+    match result {
         Ok(list) => Ok(Json(list)),
         Err(err) => {
             tracing::warn!(error = ?err, lang, "Fetching new pictograms failed");
@@ -178,6 +327,7 @@ async fn new_pictograms(
 }
 
 async fn get_keywords(
+    State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Query(q): Query<LangQuery>,
 ) -> AppResult<Json<Vec<String>>> {
@@ -186,12 +336,108 @@ async fn get_keywords(
     if user.role == UserRole::Child {
         return Ok(Json(vec![]));
     }
-    let lang = q.lang.as_deref().unwrap_or("en");
-    match pictograms::get_keywords(lang).await {
-        Ok(words) => Ok(Json(words)),
+    let lang = request_language(&state.pool, &user, q.lang.as_deref()).await?;
+    let ttl = std::time::Duration::from_secs(state.config.pictogram_keywords_cache_ttl_seconds);
+    match pictograms::get_keywords(&lang, ttl).await {
+        Ok(words) => Ok(Json(words.to_vec())),
         Err(err) => {
             tracing::warn!(error = ?err, lang, "Fetching ARASAAC keywords failed");
             Ok(Json(vec![]))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, errors::AppError, test_support};
+
+    async fn save(pool: &Db, user: &AuthUser, arasaac_ids: &[i32]) {
+        for id in arasaac_ids {
+            sqlx::query("INSERT INTO saved_pictograms (user_id, arasaac_id) VALUES (?, ?)")
+                .bind(&user.user_id)
+                .bind(id)
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn saved_ids(pool: &Db, user: &AuthUser) -> Vec<i32> {
+        sqlx::query_scalar("SELECT arasaac_id FROM saved_pictograms WHERE user_id = ? ORDER BY arasaac_id")
+            .bind(&user.user_id)
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn bulk_unsave_removes_only_the_callers_listed_ids(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        save(&pool, &parent, &[1, 2, 3]).await;
+        save(&pool, &other, &[1, 2]).await;
+
+        let body = UnsaveBulkBody { arasaac_ids: vec![2, 1, 2, 99] };
+        let Json(result) = unsave_bulk(State(state), Extension(parent.clone()), Json(body)).await.unwrap();
+        assert_eq!(result, json!({ "removed": 2 }));
+        assert_eq!(saved_ids(&pool, &parent).await, [3]);
+        assert_eq!(saved_ids(&pool, &other).await, [1, 2]);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn clear_saved_needs_confirmation_and_stays_scoped(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        save(&pool, &parent, &[1, 2, 3]).await;
+        save(&pool, &other, &[1]).await;
+        let clear = |confirm: bool| clear_saved(State(state.clone()), Extension(parent.clone()), Query(ClearSavedQuery { confirm }));
+
+        let err = test_support::expect_err(clear(false).await);
+        assert!(matches!(err, AppError::BadRequest(_)), "{err:?}");
+        assert_eq!(saved_ids(&pool, &parent).await, [1, 2, 3]);
+
+        let Json(result) = clear(true).await.unwrap();
+        assert_eq!(result, json!({ "removed": 3 }));
+        assert!(saved_ids(&pool, &parent).await.is_empty());
+        assert_eq!(saved_ids(&pool, &other).await, [1]);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn saved_list_pages_and_searches_labels(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        for (id, label, used_count) in [(1, "Brush teeth", 3), (2, "Breakfast", 2), (3, "Bath", 1), (4, "100% done", 0)] {
+            sqlx::query("INSERT INTO saved_pictograms (user_id, arasaac_id, label, used_count) VALUES (?, ?, ?, ?)")
+                .bind(&parent.user_id)
+                .bind(id)
+                .bind(label)
+                .bind(used_count)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        let list = |q: Option<&str>, limit: Option<i64>, offset: Option<i64>| {
+            let query = SavedListQuery { lang: Some("en".into()), q: q.map(Into::into), limit, offset };
+            list_saved(State(state.clone()), Extension(parent.clone()), Query(query))
+        };
+        let page = |result: AppResult<(HeaderMap, Json<Vec<pictograms::SavedPictogramDto>>)>| {
+            let (headers, Json(items)) = result.unwrap();
+            let total = headers["x-total-count"].to_str().unwrap().to_string();
+            (total, items.iter().map(|p| p.arasaac_id).collect::<Vec<_>>())
+        };
+
+        assert_eq!(page(list(None, None, None).await), ("4".into(), vec![1, 2, 3, 4]));
+        assert_eq!(page(list(None, Some(2), Some(1)).await), ("4".into(), vec![2, 3]));
+        // Case-insensitive substring of the label; the total ignores paging.
+        assert_eq!(page(list(Some(" BR "), Some(1), Some(1)).await), ("2".into(), vec![2]));
+        // LIKE wildcards in `q` match literally.
+        assert_eq!(page(list(Some("%"), None, None).await), ("1".into(), vec![4]));
+        assert_eq!(page(list(Some("zzz"), None, None).await), ("0".into(), vec![]));
+    }
+}
@@ -0,0 +1,284 @@
+//! `/children/{id}/reminders` — opt-in reminder emails for a child's parent.
+//!
+//! * `daily_summary`   — today's routine, sent at `send_at` parent-local time
+//! * `upcoming_change` — sent at `send_at` when tomorrow's schedule differs
+//!   from today's
+//!
+//! An optional `schedule_id` limits a reminder to days that involve that
+//! schedule. Nothing is sent unless the parent also enabled the
+//! `schedule_reminders` notification preference and the server runs with
+//! `REMINDERS_ENABLED=true`; see `crate::reminders`.
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::{get, put},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::schedule_sql;
+use crate::{
+    errors::{AppError, AppResult},
+    middleware::auth_guard::AuthUser,
+    models::UserRole,
+    state::AppState,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/children/{id}/reminders", get(list_reminders).post(create_reminder))
+        .route("/children/{id}/reminders/{reminder_id}", put(update_reminder).delete(delete_reminder))
+}
+
+const KINDS: [&str; 2] = ["daily_summary", "upcoming_change"];
+const DEFAULT_SEND_AT: &str = "07:00";
+
+#[derive(sqlx::FromRow, Serialize)]
+struct ReminderRow {
+    id: String,
+    child_id: String,
+    schedule_id: Option<String>,
+    kind: String,
+    send_at: String,
+    is_enabled: bool,
+    last_sent_on: Option<chrono::NaiveDate>,
+}
+
+#[derive(Deserialize)]
+struct CreateReminderBody {
+    kind: String,
+    schedule_id: Option<String>,
+    send_at: Option<String>,
+    is_enabled: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct UpdateReminderBody {
+    send_at: Option<String>,
+    is_enabled: Option<bool>,
+}
+
+const SELECT_REMINDERS: &str = "SELECT id, child_id, schedule_id, CAST(kind AS CHAR) AS kind, send_at,
+        is_enabled, last_sent_on
+     FROM child_reminders";
+
+/// The child's parent, after checking the caller may manage the child.
+/// Reminders always go to the parent, also when an admin sets them up.
+async fn child_parent(pool: &crate::db::Db, child_id: &str, caller: &AuthUser) -> AppResult<String> {
+    if caller.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+    let parent_id: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT parent_id FROM child_profiles WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(child_id)
+    .fetch_optional(pool)
+    .await?;
+    let parent_id = parent_id.ok_or(AppError::NotFound)?;
+    match parent_id {
+        Some(p) if caller.role == UserRole::Admin || p == caller.user_id => Ok(p),
+        Some(_) => Err(AppError::NotOwned),
+        None => Err(AppError::BadRequest("Child has no parent account to remind".into())),
+    }
+}
+
+fn validate_send_at(value: &str) -> AppResult<String> {
+    let value = value.trim();
+    if !schedule_sql::is_valid_start_time(value) {
+        return Err(AppError::BadRequest("send_at must be HH:MM (24-hour)".into()));
+    }
+    Ok(value.to_string())
+}
+
+async fn fetch_reminder(pool: &crate::db::Db, child_id: &str, reminder_id: &str) -> AppResult<ReminderRow> {
+    sqlx::query_as::<_, ReminderRow>(&format!("{SELECT_REMINDERS} WHERE id = ? AND child_id = ?"))
+        .bind(reminder_id)
+        .bind(child_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(AppError::NotFound)
+}
+
+async fn list_reminders(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(child_id): Path<String>,
+) -> AppResult<Json<Vec<ReminderRow>>> {
+    child_parent(&state.pool, &child_id, &user).await?;
+    let rows = sqlx::query_as::<_, ReminderRow>(&format!(
+        "{SELECT_REMINDERS} WHERE child_id = ? ORDER BY created_at"
+    ))
+    .bind(&child_id)
+    .fetch_all(&state.pool)
+    .await?;
+    Ok(Json(rows))
+}
+
+async fn create_reminder(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(child_id): Path<String>,
+    Json(body): Json<CreateReminderBody>,
+) -> AppResult<(StatusCode, Json<ReminderRow>)> {
+    let pool = &state.pool;
+    let parent_id = child_parent(pool, &child_id, &user).await?;
+
+    let kind = body.kind.trim();
+    if !KINDS.contains(&kind) {
+        return Err(AppError::BadRequest(format!("kind must be one of: {}", KINDS.join(", "))));
+    }
+    let send_at = validate_send_at(body.send_at.as_deref().unwrap_or(DEFAULT_SEND_AT))?;
+    let schedule_id = body.schedule_id.as_deref().map(str::trim).filter(|s| !s.is_empty());
+
+    if let Some(schedule_id) = schedule_id {
+        let filter = format!("t.id = ? AND t.owner_id = ? AND {}", schedule_sql::regular_schedule_filter());
+        let exists: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS(SELECT 1 FROM visual_support_documents_templates t WHERE {filter})"
+        ))
+        .bind(schedule_id)
+        .bind(&parent_id)
+        .fetch_one(pool)
+        .await?;
+        if !exists {
+            return Err(AppError::BadRequest("schedule_id must be one of the parent's schedules".into()));
+        }
+    }
+
+    // NULL schedule ids are distinct to the unique key, so check explicitly,
+    // under the child's row lock so two requests cannot both pass the check.
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT id FROM child_profiles WHERE id = ? FOR UPDATE")
+        .bind(&child_id)
+        .execute(&mut *tx)
+        .await?;
+    let duplicate: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM child_reminders
+                       WHERE child_id = ? AND kind = ? AND schedule_id <=> ?)",
+    )
+    .bind(&child_id)
+    .bind(kind)
+    .bind(schedule_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if duplicate {
+        return Err(AppError::Conflict("A reminder of this kind already exists for this child and schedule".into()));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO child_reminders (id, child_id, schedule_id, kind, send_at, is_enabled)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&child_id)
+    .bind(schedule_id)
+    .bind(kind)
+    .bind(&send_at)
+    .bind(body.is_enabled.unwrap_or(true))
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let row = fetch_reminder(pool, &child_id, &id).await?;
+    Ok((StatusCode::CREATED, Json(row)))
+}
+
+async fn update_reminder(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((child_id, reminder_id)): Path<(String, String)>,
+    Json(body): Json<UpdateReminderBody>,
+) -> AppResult<Json<ReminderRow>> {
+    let pool = &state.pool;
+    child_parent(pool, &child_id, &user).await?;
+    let current = fetch_reminder(pool, &child_id, &reminder_id).await?;
+
+    let send_at = match body.send_at.as_deref() {
+        Some(v) => validate_send_at(v)?,
+        None => current.send_at,
+    };
+    sqlx::query("UPDATE child_reminders SET send_at = ?, is_enabled = ? WHERE id = ?")
+        .bind(&send_at)
+        .bind(body.is_enabled.unwrap_or(current.is_enabled))
+        .bind(&reminder_id)
+        .execute(pool)
+        .await?;
+
+    Ok(Json(fetch_reminder(pool, &child_id, &reminder_id).await?))
+}
+
+async fn delete_reminder(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((child_id, reminder_id)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    let pool = &state.pool;
+    child_parent(pool, &child_id, &user).await?;
+    let deleted = sqlx::query("DELETE FROM child_reminders WHERE id = ? AND child_id = ?")
+        .bind(&reminder_id)
+        .bind(&child_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, test_support};
+
+    fn body(kind: &str, schedule_id: Option<&str>, send_at: Option<&str>) -> Json<CreateReminderBody> {
+        Json(CreateReminderBody {
+            kind: kind.into(),
+            schedule_id: schedule_id.map(Into::into),
+            send_at: send_at.map(Into::into),
+            is_enabled: None,
+        })
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn create_reminder_validates_and_rejects_duplicates(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let active = serde_json::json!({ "schedule": { "status": "active" } });
+        let foreign_schedule = test_support::schedule(&pool, &other, "Theirs", active).await;
+        let create = |user: &AuthUser, body| {
+            create_reminder(State(state.clone()), Extension(user.clone()), Path(child.clone()), body)
+        };
+
+        let (status, Json(created)) = create(&parent, body("daily_summary", None, None)).await.unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created.send_at, DEFAULT_SEND_AT);
+        assert_eq!(created.schedule_id, None);
+
+        // Child-wide reminders have a NULL schedule id, which the unique key
+        // does not catch.
+        let duplicate = test_support::expect_err(create(&parent, body("daily_summary", None, Some("08:00"))).await);
+        assert!(matches!(duplicate, AppError::Conflict(_)));
+
+        let foreign = create(&parent, body("upcoming_change", Some(&foreign_schedule), None)).await;
+        assert!(matches!(test_support::expect_err(foreign), AppError::BadRequest(_)));
+
+        let bad_time = create(&parent, body("upcoming_change", None, Some("25:00"))).await;
+        assert!(matches!(test_support::expect_err(bad_time), AppError::BadRequest(_)));
+
+        let not_theirs = create(&other, body("upcoming_change", None, None)).await;
+        assert!(matches!(test_support::expect_err(not_theirs), AppError::NotOwned));
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM child_reminders WHERE child_id = ?")
+            .bind(&child)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
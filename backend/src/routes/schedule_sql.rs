@@ -0,0 +1,227 @@
+//! Shared SQL fragments for weekly schedules.
+//!
+//! Schedules are `visual_support_documents_templates` rows with
+//! `document_type = 'WEEKLY_SCHEDULE'`; schedule-only fields (child, status,
+//! template flag) live under `metadata_json.schedule`. All fragments assume
+//! the template table is aliased `t`, schedule activities `vta` and the
+//! activity library `vsa`.
+
+/// Child the schedule is bound to, or NULL.
+pub const CHILD_ID: &str =
+    "CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36))";
+
+/// `active` / `inactive` / `archived`, defaulting to `inactive`.
+pub const STATUS: &str =
+    "CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20))";
+
+/// What a child sees after the last activity: `done` (default) or `next_day`.
+pub const END_BEHAVIOR: &str =
+    "CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.end_behavior')), ''), 'done') AS CHAR(20))";
+
+/// Display color as `#rrggbb`, or NULL.
+pub const COLOR: &str =
+    "CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.color')), '') AS CHAR(7))";
+
+/// Display icon name (one of [`ICONS`]), or NULL.
+pub const ICON: &str =
+    "CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.icon')), '') AS CHAR(32))";
+
+/// Icon names the frontend ships for schedules.
+pub const ICONS: [&str; 14] = [
+    "star", "heart", "sun", "moon", "home", "school", "car", "book",
+    "music", "sport", "food", "bath", "bed", "play",
+];
+
+/// 1 for system rows and user templates, 0 for regular schedules.
+pub const IS_TEMPLATE: &str =
+    "IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0)";
+
+/// The user template flag alone (ignores `is_system`), NULL-safe.
+const TEMPLATE_FLAG: &str = "IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0)";
+
+/// `SELECT` producing the `ScheduleRow` columns (plus `extra_columns`, which
+/// must start with a comma when non-empty) filtered by `filter`.
+pub fn select_schedules(extra_columns: &str, filter: &str) -> String {
+    format!(
+        "SELECT
+            t.id,
+            COALESCE(t.owner_id, '') AS owner_id,
+            {CHILD_ID} AS child_id,
+            t.name,
+            {STATUS} AS status,
+            {IS_TEMPLATE} AS is_template,
+            {END_BEHAVIOR} AS end_behavior,
+            {COLOR} AS color,
+            {ICON} AS icon,
+            t.version,
+            t.created_at,
+            t.updated_at{extra_columns}
+         FROM visual_support_documents_templates t
+         WHERE {filter}"
+    )
+}
+
+/// Regular (non-template, non-system) schedules.
+pub fn regular_schedule_filter() -> String {
+    format!("{TEMPLATE_FLAG} = 0 AND t.is_system = 0")
+}
+
+/// Templates visible to the caller. Admins see every template; otherwise the
+/// filter expects one extra bind: the caller's user id.
+pub fn template_filter(is_admin: bool) -> String {
+    if is_admin {
+        format!("(t.is_system = 1 OR {TEMPLATE_FLAG} = 1)")
+    } else {
+        format!("(t.is_system = 1 OR (t.owner_id = ? AND {TEMPLATE_FLAG} = 1))")
+    }
+}
+
+/// Start time the schedule gives cards without one of their own, or NULL.
+pub const DEFAULT_START_TIME: &str =
+    "CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.default_start_time')), '') AS CHAR(5))";
+
+/// Normalize a `#rgb` / `#rrggbb` hex color to lowercase `#rrggbb`.
+pub fn normalize_color(value: &str) -> Option<String> {
+    let hex = value.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let full = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+    Some(format!("#{}", full.to_ascii_lowercase()))
+}
+
+/// Whether `value` is a 24-hour `HH:MM` time.
+pub fn is_valid_start_time(value: &str) -> bool {
+    value.len() == 5 && chrono::NaiveTime::parse_from_str(value, "%H:%M").is_ok()
+}
+
+/// A card's `start_time`: its own, else the schedule's `default_start_time`,
+/// else `fallback` (`Config::default_activity_start_time`, validated as
+/// `HH:MM` at startup so it is safe to inline).
+pub fn start_time_column(fallback: &str) -> String {
+    format!(
+        "CAST(COALESCE(
+                NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.start_time')), ''),
+                (SELECT {DEFAULT_START_TIME} FROM visual_support_documents_templates t WHERE t.id = vta.template_id),
+                '{fallback}'
+            ) AS CHAR(5))"
+    )
+}
+
+/// `SELECT` for schedule activity cards. With `with_schedule_id` the first
+/// bind is echoed back as `schedule_id`; `filter` binds follow.
+pub fn select_activity_cards(with_schedule_id: bool, default_start_time: &str, filter: &str) -> String {
+    let schedule_id = if with_schedule_id { "\n            ? AS schedule_id," } else { "" };
+    let start_time = start_time_column(default_start_time);
+    format!(
+        "SELECT
+            vta.id,{schedule_id}
+            vta.activity_card_id,
+            COALESCE(NULLIF(vta.text_label, ''), vsa.label_text) AS title,
+            vta.optional_notes AS description,
+            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.picture_path')), ''), vsa.local_image_path) AS CHAR(500)) AS picture_path,
+            {start_time} AS start_time,
+            CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.end_time')), '') AS CHAR(5)) AS end_time,
+            vta.activity_order AS sort_order
+         FROM visual_support_template_activities vta
+         LEFT JOIN visual_support_activity_library vsa ON vsa.id = vta.activity_card_id
+         WHERE {filter}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, models::UserRole, test_support};
+
+    fn binds(sql: &str) -> usize {
+        sql.matches('?').count()
+    }
+
+    #[test]
+    fn filters_and_selects_declare_their_binds() {
+        assert_eq!(binds(&regular_schedule_filter()), 0);
+        assert_eq!(binds(&template_filter(true)), 0);
+        assert_eq!(binds(&template_filter(false)), 1);
+        assert_eq!(binds(&select_activity_cards(false, "08:00", "vta.template_id = ?")), 1);
+        assert_eq!(binds(&select_activity_cards(true, "08:00", "vta.template_id = ?")), 2);
+
+        let sql = select_schedules(", 1 AS extra", "t.id = ?");
+        assert!(sql.contains("t.updated_at, 1 AS extra\n"));
+        assert!(sql.trim_end().ends_with("WHERE t.id = ?"));
+    }
+
+    #[test]
+    fn colors_normalize_to_lowercase_six_digits() {
+        assert_eq!(normalize_color("#ABC").as_deref(), Some("#aabbcc"));
+        assert_eq!(normalize_color(" #12ab9F ").as_deref(), Some("#12ab9f"));
+        assert_eq!(normalize_color("12ab9f"), None);
+        assert_eq!(normalize_color("#12ab9"), None);
+        assert_eq!(normalize_color("#ggg"), None);
+    }
+
+    #[test]
+    fn start_times_must_be_24_hour_hh_mm() {
+        assert!(is_valid_start_time("00:00"));
+        assert!(is_valid_start_time("23:59"));
+        assert!(!is_valid_start_time("24:00"));
+        assert!(!is_valid_start_time("8:00"));
+        assert!(!is_valid_start_time("08:00:00"));
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn schedule_columns_default_and_filters_partition_rows(pool: Db) {
+        let owner = test_support::user(&pool, UserRole::Parent).await;
+        let regular_id = test_support::schedule(&pool, &owner, "Regular", serde_json::json!({})).await;
+        let template_id = test_support::schedule(
+            &pool,
+            &owner,
+            "Template",
+            serde_json::json!({ "schedule": { "is_template": true, "status": "active", "color": "#aabbcc" } }),
+        )
+        .await;
+
+        #[derive(sqlx::FromRow, Debug, PartialEq)]
+        struct Row {
+            id: String,
+            status: String,
+            is_template: bool,
+            end_behavior: String,
+            color: Option<String>,
+        }
+        let fetch = |filter: String, bind_owner: bool| {
+            let sql = select_schedules("", &format!("t.id IN ('{regular_id}', '{template_id}') AND {filter} ORDER BY t.id"));
+            let pool = pool.clone();
+            let owner_id = owner.user_id.clone();
+            async move {
+                let mut query = sqlx::query_as::<_, Row>(&sql);
+                if bind_owner {
+                    query = query.bind(owner_id);
+                }
+                query.fetch_all(&pool).await.unwrap()
+            }
+        };
+
+        let regular = fetch(regular_schedule_filter(), false).await;
+        assert_eq!(
+            regular,
+            [Row { id: regular_id.clone(), status: "inactive".into(), is_template: false, end_behavior: "done".into(), color: None }],
+        );
+        let templates = fetch(template_filter(false), true).await;
+        assert_eq!(
+            templates,
+            [Row {
+                id: template_id.clone(),
+                status: "active".into(),
+                is_template: true,
+                end_behavior: "done".into(),
+                color: Some("#aabbcc".into()),
+            }],
+        );
+    }
+}
@@ -2,8 +2,8 @@
 //! Backed by visual support templates + template activities.
 
 use axum::{
-    extract::{Extension, Path, State},
-    http::StatusCode,
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap, HeaderName, StatusCode},
     routing::{get, patch, post, put},
     Json, Router,
 };
@@ -11,29 +11,41 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use uuid::Uuid;
 
+use super::{compliance::serialize_naive_datetime_utc, schedule_sql, validate};
 use crate::{
+    config::{resolve_public_asset, Config},
     errors::{AppError, AppResult},
     middleware::auth_guard::AuthUser,
     models::UserRole,
+    services::{idempotency, pictograms},
     state::AppState,
 };
 
 const WEEKLY_TYPE: &str = "WEEKLY_SCHEDULE";
 
+/// OpenAPI paths for this module, merged in `super::openapi`.
+#[derive(utoipa::OpenApi)]
+#[openapi(paths(list_schedules, create_schedule, list_templates, get_template, copy_template, get_schedule, preview_schedule, update_schedule, delete_schedule, update_status, duplicate_schedule, import_schedule, list_activity_cards, add_activity_card, reorder_activity_cards, compact_activity_cards, update_activity_card, delete_activity_card))]
+pub(super) struct ApiDoc;
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/schedules", get(list_schedules).post(create_schedule))
+        .route("/schedules/import", post(import_schedule))
         .route("/schedules/templates", get(list_templates))
         .route("/schedules/templates/{id}", get(get_template))
         .route("/schedules/templates/{id}/copy", post(copy_template))
         .route("/schedules/{id}", get(get_schedule).put(update_schedule).delete(delete_schedule))
         .route("/schedules/{id}/status", patch(update_status))
+        .route("/schedules/{id}/duplicate", post(duplicate_schedule))
+        .route("/schedules/{id}/preview", get(preview_schedule))
         .route("/schedules/{id}/activity-cards", get(list_activity_cards).post(add_activity_card))
         .route("/schedules/{id}/activity-cards/reorder", patch(reorder_activity_cards))
+        .route("/schedules/{id}/activity-cards/compact", post(compact_activity_cards))
         .route("/schedules/{id}/activity-cards/{card_id}", put(update_activity_card).delete(delete_activity_card))
 }
 
-#[derive(sqlx::FromRow, Serialize)]
+#[derive(sqlx::FromRow, Serialize, utoipa::ToSchema)]
 struct ScheduleRow {
     id: String,
     owner_id: String,
@@ -41,6 +53,17 @@ struct ScheduleRow {
     name: String,
     status: String,
     is_template: bool,
+    end_behavior: String,
+    /// `#rrggbb` used to tell schedules apart on the calendar.
+    color: Option<String>,
+    /// One of `schedule_sql::ICONS`.
+    icon: Option<String>,
+    /// Bumped on every edit; send as `If-Match` to detect concurrent changes.
+    version: i32,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    updated_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -51,11 +74,17 @@ struct ScheduleListRow {
     name: String,
     status: String,
     is_template: bool,
+    end_behavior: String,
+    color: Option<String>,
+    icon: Option<String>,
+    version: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
     used_by_children: Option<String>,
     activity_card_count: i64,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 struct ScheduleListItem {
     id: String,
     owner_id: String,
@@ -63,59 +92,139 @@ struct ScheduleListItem {
     name: String,
     status: String,
     is_template: bool,
+    end_behavior: String,
+    /// `#rrggbb` used to tell schedules apart on the calendar.
+    color: Option<String>,
+    /// One of `schedule_sql::ICONS`.
+    icon: Option<String>,
+    /// Bumped on every edit; send as `If-Match` to detect concurrent changes.
+    version: i32,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    updated_at: chrono::DateTime<chrono::Utc>,
     used_by_children: Vec<String>,
     activity_card_count: i64,
 }
 
-#[derive(sqlx::FromRow, Serialize, Clone)]
+#[derive(sqlx::FromRow, Serialize, Clone, utoipa::ToSchema)]
 struct ActivityCardRow {
     id: String,
     schedule_id: String,
+    /// Library card the title/picture fall back to. NULL for cards without
+    /// one and after the library card is deleted (its label and picture are
+    /// copied onto the card first).
     activity_card_id: Option<String>,
     title: String,
     description: Option<String>,
     picture_path: Option<String>,
+    #[sqlx(skip)]
+    picture_url: Option<String>,
     start_time: String,
     end_time: Option<String>,
     sort_order: i32,
 }
 
-#[derive(Serialize)]
+impl ActivityCardRow {
+    fn with_picture_url(mut self, config: &Config) -> Self {
+        self.picture_url = resolve_public_asset(config, self.picture_path.as_deref());
+        self
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
 struct ScheduleWithActivityCards {
     #[serde(flatten)]
     schedule: ScheduleRow,
     activity_cards: Vec<ActivityCardRow>,
 }
 
-#[derive(Deserialize)]
+/// One row of the printable grid: the cards starting at `start_time`, which
+/// repeat in every weekday column.
+#[derive(Serialize, utoipa::ToSchema)]
+struct PreviewRow {
+    start_time: String,
+    cards: Vec<ActivityCardRow>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+struct SchedulePreview {
+    #[serde(flatten)]
+    schedule: ScheduleRow,
+    /// Grid columns (1=Mon … 7=Sun), starting on the owner's `week_start`.
+    week_days: Vec<u8>,
+    /// Cards by start time; empty for a schedule without cards.
+    rows: Vec<PreviewRow>,
+}
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
 struct CreateScheduleBody {
     name: String,
     child_id: Option<String>,
+    /// `#rgb` or `#rrggbb`.
+    color: Option<String>,
+    /// One of `schedule_sql::ICONS`.
+    icon: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct UpdateScheduleBody {
     name: Option<String>,
     child_id: Option<String>,
+    /// `done` or `next_day`; see `schedule_sql::END_BEHAVIOR`.
+    end_behavior: Option<String>,
+    /// `HH:MM` start time for cards added without one; empty clears it.
+    default_start_time: Option<String>,
+    /// `#rgb` or `#rrggbb`; empty clears it.
+    color: Option<String>,
+    /// One of `schedule_sql::ICONS`; empty clears it.
+    icon: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct UpdateStatusBody {
     status: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct CreateActivityCardBody {
     activity_card_id: Option<String>,
     title: String,
     description: Option<String>,
     picture_path: Option<String>,
-    start_time: String,
+    /// Defaults to the schedule's `default_start_time`, then the global default.
+    start_time: Option<String>,
     end_time: Option<String>,
     sort_order: Option<i32>,
 }
 
-#[derive(Deserialize)]
+/// A schedule as returned by `GET /schedules/{id}`; ids and other
+/// account-specific fields in it are ignored.
+#[derive(Deserialize, utoipa::ToSchema)]
+struct ImportScheduleBody {
+    name: String,
+    end_behavior: Option<String>,
+    default_start_time: Option<String>,
+    color: Option<String>,
+    icon: Option<String>,
+    #[serde(default)]
+    activity_cards: Vec<ImportActivityCardBody>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+struct ImportActivityCardBody {
+    title: String,
+    description: Option<String>,
+    /// Kept only for shared `/assets/` pictures; uploads stay with their account.
+    picture_path: Option<String>,
+    /// ARASAAC pictogram to use as the picture, fetched if not cached yet.
+    arasaac_id: Option<i32>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    sort_order: Option<i32>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
 struct UpdateActivityCardBody {
     activity_card_id: Option<String>,
     title: Option<String>,
@@ -126,18 +235,64 @@ struct UpdateActivityCardBody {
     sort_order: Option<i32>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 struct ReorderBody {
     activity_card_ids: Vec<String>,
 }
 
+#[derive(Deserialize, utoipa::IntoParams)]
+struct DeleteScheduleQuery {
+    /// Delete even though the schedule is assigned on a child's calendar.
+    #[serde(default)]
+    force: bool,
+}
+
+/// `used_by_children` column: the bound child plus any child with a day
+/// assignment pointing at the schedule, `||`-separated. Binds the weekly
+/// document type once.
+fn used_by_children_column() -> String {
+    format!(
+        "(
+            SELECT GROUP_CONCAT(DISTINCT cp.display_name ORDER BY cp.display_name SEPARATOR '||')
+            FROM child_profiles cp
+            LEFT JOIN visual_support_documents d
+                ON d.child_id = cp.id
+               AND d.template_id = t.id
+               AND d.document_type = ?
+            WHERE cp.parent_id = t.owner_id
+              AND cp.deleted_at IS NULL
+              AND (cp.id = {} OR d.id IS NOT NULL)
+        ) AS used_by_children",
+        schedule_sql::CHILD_ID
+    )
+}
+
+fn split_child_names(raw: Option<String>) -> Vec<String> {
+    raw.unwrap_or_default()
+        .split("||")
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
 fn schedule_metadata_json(
     status: &str,
     is_template: bool,
     child_id: Option<&str>,
     source_template_id: Option<&str>,
 ) -> AppResult<String> {
-    serde_json::to_string(&json!({
+    serde_json::to_string(&schedule_metadata(status, is_template, child_id, source_template_id))
+        .map_err(|_| AppError::BadRequest("Invalid schedule metadata".into()))
+}
+
+fn schedule_metadata(
+    status: &str,
+    is_template: bool,
+    child_id: Option<&str>,
+    source_template_id: Option<&str>,
+) -> Value {
+    json!({
         "layout": {
             "type": WEEKLY_TYPE,
             "columns": 1,
@@ -149,8 +304,108 @@ fn schedule_metadata_json(
             "child_id": child_id,
             "source_template_id": source_template_id
         }
-    }))
-    .map_err(|_| AppError::BadRequest("Invalid schedule metadata".into()))
+    })
+}
+
+/// Validate optional `color` / `icon` input. `Some("")` means "clear".
+fn display_fields(color: Option<&str>, icon: Option<&str>) -> AppResult<(Option<String>, Option<String>)> {
+    let color = match color.map(str::trim) {
+        None => None,
+        Some("") => Some(String::new()),
+        Some(raw) => Some(schedule_sql::normalize_color(raw).ok_or_else(|| {
+            AppError::BadRequest("color must be a hex color like #4a90d9".into())
+        })?),
+    };
+    let icon = match icon.map(str::trim) {
+        None => None,
+        Some("") => Some(String::new()),
+        Some(raw) if schedule_sql::ICONS.contains(&raw) => Some(raw.to_string()),
+        Some(_) => {
+            return Err(AppError::BadRequest(format!(
+                "icon must be one of: {}",
+                schedule_sql::ICONS.join(", ")
+            )))
+        }
+    };
+    Ok((color, icon))
+}
+
+/// Whether `owner_id` already has another non-archived schedule named
+/// `name`, compared trimmed and case-insensitively.
+async fn name_taken(pool: &crate::db::Db, owner_id: &str, name: &str, except_id: Option<&str>) -> AppResult<bool> {
+    let sql = format!(
+        "SELECT EXISTS(
+            SELECT 1 FROM visual_support_documents_templates t
+            WHERE t.document_type = ?
+              AND t.owner_id = ?
+              AND LOWER(TRIM(t.name)) = LOWER(TRIM(?))
+              AND NOT (t.id <=> ?)
+              AND {} <> 'archived'
+              AND {}
+        )",
+        schedule_sql::STATUS,
+        schedule_sql::regular_schedule_filter()
+    );
+    let taken: bool = sqlx::query_scalar(&sql)
+        .bind(WEEKLY_TYPE)
+        .bind(owner_id)
+        .bind(name)
+        .bind(except_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(taken)
+}
+
+/// 409 when [`name_taken`]. Off unless `UNIQUE_SCHEDULE_NAMES` is set.
+async fn ensure_unique_name(
+    pool: &crate::db::Db,
+    config: &Config,
+    owner_id: &str,
+    name: &str,
+    except_id: Option<&str>,
+) -> AppResult<()> {
+    if !config.unique_schedule_names {
+        return Ok(());
+    }
+    let taken = name_taken(pool, owner_id, name, except_id).await?;
+    if taken {
+        return Err(AppError::Conflict(format!("A schedule named \"{}\" already exists", name.trim())));
+    }
+    Ok(())
+}
+
+/// Copies tried before giving up on a free name.
+const MAX_COPY_SUFFIX: u32 = 100;
+
+/// `name` with the `n`th copy suffix: none for 0, then " (copy)",
+/// " (copy 2)", …, shortened to fit [`validate::MAX_TITLE_CHARS`].
+fn copy_name(name: &str, n: u32) -> String {
+    let suffix = match n {
+        0 => String::new(),
+        1 => " (copy)".to_string(),
+        n => format!(" (copy {n})"),
+    };
+    let keep = validate::MAX_TITLE_CHARS - suffix.chars().count();
+    format!("{}{suffix}", name.trim().chars().take(keep).collect::<String>())
+}
+
+/// Name for a copy of `name` owned by `owner_id`, starting at suffix
+/// `first` (see [`copy_name`]) and counting up while the name is taken.
+/// With `UNIQUE_SCHEDULE_NAMES` off the first candidate is used as is.
+async fn free_copy_name(
+    pool: &crate::db::Db,
+    config: &Config,
+    owner_id: &str,
+    name: &str,
+    first: u32,
+) -> AppResult<String> {
+    for n in first..first + MAX_COPY_SUFFIX {
+        let candidate = copy_name(name, n);
+        if !config.unique_schedule_names || !name_taken(pool, owner_id, &candidate, None).await? {
+            return Ok(candidate);
+        }
+    }
+    Err(AppError::Conflict(format!("Too many copies of \"{}\" already exist", name.trim())))
 }
 
 async fn assert_owns_schedule(
@@ -178,12 +433,89 @@ async fn assert_owns_schedule(
     .await?;
 
     if !is_mine {
-        return Err(AppError::Forbidden);
+        return Err(AppError::NotOwned);
     }
 
     Ok(())
 }
 
+/// Archived schedules are read-only: their cards cannot be added, edited,
+/// reordered or removed until the schedule is restored via `PATCH /status`.
+async fn ensure_cards_editable(pool: &crate::db::Db, schedule_id: &str) -> AppResult<()> {
+    let status: Option<String> = sqlx::query_scalar(&format!(
+        "SELECT {} FROM visual_support_documents_templates t WHERE t.id = ?",
+        schedule_sql::STATUS,
+    ))
+    .bind(schedule_id)
+    .fetch_optional(pool)
+    .await?;
+    match status.as_deref() {
+        None => Err(AppError::NotFound),
+        Some("archived") => Err(AppError::Conflict(
+            "Schedule is archived. Restore it before editing its activity cards.".into(),
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+/// `If-Match` as a schedule version: `"3"`, `W/"3"` or `3`. Absent or `*`
+/// means the caller does not ask for a check.
+fn if_match_version(headers: &HeaderMap) -> AppResult<Option<i32>> {
+    let Some(raw) = headers.get(axum::http::header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let raw = raw
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Invalid If-Match header".into()))?
+        .trim();
+    if raw == "*" {
+        return Ok(None);
+    }
+    raw.trim_start_matches("W/")
+        .trim_matches('"')
+        .parse::<i32>()
+        .map(Some)
+        .map_err(|_| AppError::BadRequest("If-Match must be a schedule version".into()))
+}
+
+/// Bump the schedule's version inside the edit's transaction, refusing with
+/// 409 when the request's `If-Match` names a version that is no longer
+/// current. The bump locks the row, so a concurrent edit waits for this one
+/// to commit or roll back. Returns the new version. Same conflict shape as
+/// the documents' `expected_version` check.
+async fn claim_schedule_version(
+    conn: &mut sqlx::MySqlConnection,
+    schedule_id: &str,
+    headers: &HeaderMap,
+) -> AppResult<i32> {
+    let expected = if_match_version(headers)?;
+    let affected = sqlx::query(
+        "UPDATE visual_support_documents_templates
+         SET version = version + 1
+         WHERE id = ? AND (? IS NULL OR version = ?)",
+    )
+    .bind(schedule_id)
+    .bind(expected)
+    .bind(expected)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+    if affected == 0 {
+        return Err(AppError::Conflict("Version conflict. Reload and retry.".into()));
+    }
+    let version = sqlx::query_scalar("SELECT version FROM visual_support_documents_templates WHERE id = ?")
+        .bind(schedule_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok(version)
+}
+
+/// `ETag` carrying the schedule version an edit produced, for the next
+/// request's `If-Match`.
+fn etag(version: i32) -> [(HeaderName, String); 1] {
+    [(header::ETAG, format!("\"{version}\""))]
+}
+
 async fn assert_owns_child_if_set(
     pool: &crate::db::Db,
     child_id: &Option<String>,
@@ -198,13 +530,13 @@ async fn assert_owns_child_if_set(
     }
 
     let exists: bool = if caller.role == UserRole::Admin {
-        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ?)")
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND deleted_at IS NULL)")
             .bind(child_id)
             .fetch_one(pool)
             .await?
     } else {
         sqlx::query_scalar(
-            "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ?)",
+            "SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ? AND deleted_at IS NULL)",
         )
         .bind(child_id)
         .bind(&caller.user_id)
@@ -213,25 +545,65 @@ async fn assert_owns_child_if_set(
     };
 
     if !exists {
-        return Err(AppError::Forbidden);
+        return Err(AppError::NotOwned);
     }
 
     Ok(())
 }
 
-async fn get_schedule_row(pool: &crate::db::Db, id: &str) -> AppResult<ScheduleRow> {
-    sqlx::query_as::<_, ScheduleRow>(
-        "SELECT
-            t.id,
-            COALESCE(t.owner_id, '') AS owner_id,
-                        CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-            t.name,
-                        CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-            IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template
+/// The child must belong to the schedule's owner. `assert_owns_child_if_set`
+/// only checks the caller, which would let an admin link a schedule to
+/// another parent's child. Admins may still link any child to an
+/// admin-owned schedule, and children without a parent account to any
+/// schedule.
+async fn assert_child_matches_schedule_owner(
+    pool: &crate::db::Db,
+    schedule_id: &str,
+    child_id: &Option<String>,
+    user: &AuthUser,
+) -> AppResult<()> {
+    let Some(child_id) = child_id else {
+        return Ok(());
+    };
+
+    #[derive(sqlx::FromRow)]
+    struct LinkRow {
+        owner_id: Option<String>,
+        owner_is_admin: i64,
+        child_parent_id: Option<String>,
+    }
+    let link = sqlx::query_as::<_, LinkRow>(
+        "SELECT t.owner_id,
+                CAST(COALESCE(u.role = 'admin', 0) AS SIGNED) AS owner_is_admin,
+                cp.parent_id AS child_parent_id
          FROM visual_support_documents_templates t
-         WHERE t.id = ?
-           AND t.document_type = ?",
+         JOIN child_profiles cp ON cp.id = ?
+         LEFT JOIN users u ON u.id = t.owner_id
+         WHERE t.id = ?",
     )
+    .bind(child_id)
+    .bind(schedule_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let same_parent = link.child_parent_id.is_some() && link.child_parent_id == link.owner_id;
+    let admin_link = user.role == UserRole::Admin
+        && (link.owner_is_admin != 0 || link.child_parent_id.is_none());
+    if !(same_parent || admin_link) {
+        return Err(AppError::BadRequest(
+            "child_id must belong to the schedule owner".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn get_schedule_row(pool: &crate::db::Db, id: &str) -> AppResult<ScheduleRow> {
+    sqlx::query_as::<_, ScheduleRow>(&schedule_sql::select_schedules(
+        "",
+        "t.id = ? AND t.document_type = ?",
+    ))
     .bind(id)
     .bind(WEEKLY_TYPE)
     .fetch_optional(pool)
@@ -239,111 +611,84 @@ async fn get_schedule_row(pool: &crate::db::Db, id: &str) -> AppResult<ScheduleR
     .ok_or(AppError::NotFound)
 }
 
+/// Fetch a template (system or the caller's own; any for admins).
+async fn get_visible_template_row(
+    pool: &crate::db::Db,
+    id: &str,
+    caller: &AuthUser,
+) -> AppResult<ScheduleRow> {
+    let is_admin = caller.role == UserRole::Admin;
+    let sql = schedule_sql::select_schedules(
+        "",
+        &format!(
+            "t.id = ? AND t.document_type = ? AND {}",
+            schedule_sql::template_filter(is_admin)
+        ),
+    );
+
+    let mut query = sqlx::query_as::<_, ScheduleRow>(&sql).bind(id).bind(WEEKLY_TYPE);
+    if !is_admin {
+        query = query.bind(&caller.user_id);
+    }
+    query.fetch_optional(pool).await?.ok_or(AppError::NotFound)
+}
+
 async fn load_activity_cards_for_schedule(
     pool: &crate::db::Db,
+    config: &Config,
     schedule_id: &str,
 ) -> AppResult<Vec<ActivityCardRow>> {
-    let rows: Vec<ActivityCardRow> = sqlx::query_as::<_, ActivityCardRow>(
-        "SELECT
-            vta.id,
-            ? AS schedule_id,
-            vta.activity_card_id,
-            COALESCE(NULLIF(vta.text_label, ''), vsa.label_text) AS title,
-            vta.optional_notes AS description,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.picture_path')), ''), vsa.local_image_path) AS CHAR(500)) AS picture_path,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.start_time')), ''), '08:00') AS CHAR(5)) AS start_time,
-            CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.end_time')), '') AS CHAR(5)) AS end_time,
-            vta.activity_order AS sort_order
-         FROM visual_support_template_activities vta
-         LEFT JOIN visual_support_activity_library vsa ON vsa.id = vta.activity_card_id
-         WHERE vta.template_id = ?
-         ORDER BY vta.activity_order",
-    )
+    let rows: Vec<ActivityCardRow> = sqlx::query_as::<_, ActivityCardRow>(&schedule_sql::select_activity_cards(
+        true,
+        &config.default_activity_start_time,
+        "vta.template_id = ? ORDER BY vta.activity_order",
+    ))
     .bind(schedule_id)
     .bind(schedule_id)
     .fetch_all(pool)
     .await?;
 
-    Ok(rows)
+    Ok(rows.into_iter().map(|r| r.with_picture_url(config)).collect())
 }
 
+#[utoipa::path(
+    get,
+    path = "/schedules",
+    tag = "schedules",
+    responses((status = 200, description = "Schedules visible to the caller", body = Vec<ScheduleListItem>)),
+)]
 async fn list_schedules(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
 ) -> AppResult<Json<Vec<ScheduleListItem>>> {
     let pool = &state.pool;
 
-    let rows: Vec<ScheduleListRow> = if user.role == UserRole::Admin {
-        sqlx::query_as::<_, ScheduleListRow>(
-            "SELECT
-                t.id,
-                COALESCE(t.owner_id, '') AS owner_id,
-                CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-                t.name,
-                CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-                IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template,
-                (
-                    SELECT COUNT(*)
-                    FROM visual_support_template_activities vta
-                    WHERE vta.template_id = t.id
-                ) AS activity_card_count,
-                (
-                    SELECT GROUP_CONCAT(DISTINCT cp.display_name ORDER BY cp.display_name SEPARATOR '||')
-                    FROM child_profiles cp
-                    LEFT JOIN visual_support_documents d
-                        ON d.child_id = cp.id
-                       AND d.template_id = t.id
-                       AND d.document_type = ?
-                    WHERE cp.parent_id = t.owner_id
-                                            AND (cp.id = CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) OR d.id IS NOT NULL)
-                ) AS used_by_children
-             FROM visual_support_documents_templates t
-             WHERE t.document_type = ?
-               AND IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0) = 0
-               AND t.is_system = 0
-             ORDER BY t.name",
-        )
-        .bind(WEEKLY_TYPE)
+    let extra_columns = format!(
+        ",
+            (
+                SELECT COUNT(*)
+                FROM visual_support_template_activities vta
+                WHERE vta.template_id = t.id
+            ) AS activity_card_count,
+            {}",
+        used_by_children_column()
+    );
+    let owner_filter = if user.role == UserRole::Admin { "" } else { " AND t.owner_id = ?" };
+    let sql = schedule_sql::select_schedules(
+        &extra_columns,
+        &format!(
+            "t.document_type = ?{owner_filter} AND {} ORDER BY t.name",
+            schedule_sql::regular_schedule_filter()
+        ),
+    );
+
+    let mut query = sqlx::query_as::<_, ScheduleListRow>(&sql)
         .bind(WEEKLY_TYPE)
-        .fetch_all(pool)
-        .await?
-    } else {
-        sqlx::query_as::<_, ScheduleListRow>(
-            "SELECT
-                t.id,
-                COALESCE(t.owner_id, '') AS owner_id,
-                CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-                t.name,
-                CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-                IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template,
-                (
-                    SELECT COUNT(*)
-                    FROM visual_support_template_activities vta
-                    WHERE vta.template_id = t.id
-                ) AS activity_card_count,
-                (
-                    SELECT GROUP_CONCAT(DISTINCT cp.display_name ORDER BY cp.display_name SEPARATOR '||')
-                    FROM child_profiles cp
-                    LEFT JOIN visual_support_documents d
-                        ON d.child_id = cp.id
-                       AND d.template_id = t.id
-                       AND d.document_type = ?
-                    WHERE cp.parent_id = t.owner_id
-                                            AND (cp.id = CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) OR d.id IS NOT NULL)
-                ) AS used_by_children
-             FROM visual_support_documents_templates t
-             WHERE t.document_type = ?
-               AND t.owner_id = ?
-               AND IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0) = 0
-               AND t.is_system = 0
-             ORDER BY t.name",
-        )
-        .bind(WEEKLY_TYPE)
-        .bind(WEEKLY_TYPE)
-        .bind(&user.user_id)
-        .fetch_all(pool)
-        .await?
-    };
+        .bind(WEEKLY_TYPE);
+    if user.role != UserRole::Admin {
+        query = query.bind(&user.user_id);
+    }
+    let rows: Vec<ScheduleListRow> = query.fetch_all(pool).await?;
 
     let items = rows
         .into_iter()
@@ -354,102 +699,114 @@ async fn list_schedules(
             name: r.name,
             status: r.status,
             is_template: r.is_template,
+            end_behavior: r.end_behavior,
+            color: r.color,
+            icon: r.icon,
+            version: r.version,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
             activity_card_count: r.activity_card_count,
-            used_by_children: r
-                .used_by_children
-                .unwrap_or_default()
-                .split("||")
-                .map(str::trim)
-                .filter(|s| !s.is_empty())
-                .map(ToString::to_string)
-                .collect(),
+            used_by_children: split_child_names(r.used_by_children),
         })
         .collect();
 
     Ok(Json(items))
 }
 
+#[utoipa::path(
+    post,
+    path = "/schedules",
+    tag = "schedules",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replay-safe create")),
+    request_body = CreateScheduleBody,
+    responses((status = 201, description = "Created", body = ScheduleRow), (status = 400, description = "Invalid name", body = super::openapi::ErrorResponse), (status = 409, description = "Name already used", body = super::openapi::ErrorResponse), (status = 422, description = "Idempotency-Key reused with a different body", body = super::openapi::ErrorResponse)),
+)]
 async fn create_schedule(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
+    headers: HeaderMap,
     Json(body): Json<CreateScheduleBody>,
 ) -> AppResult<(StatusCode, Json<ScheduleRow>)> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
 
+    let name = validate::title("name", &body.name)?;
+    let (color, icon) = display_fields(body.color.as_deref(), body.icon.as_deref())?;
     let pool = &state.pool;
     assert_owns_child_if_set(pool, &body.child_id, &user).await?;
 
-    let id = Uuid::new_v4().to_string();
-    let metadata = schedule_metadata_json("inactive", false, body.child_id.as_deref(), None)?;
+    let idempotency_key = idempotency::key_from_headers(&headers)?;
+    let id = idempotency::run_once(pool, &user.user_id, "schedule", idempotency_key.as_deref(), &body, async {
+        ensure_unique_name(pool, &state.config, &user.user_id, &name, None).await?;
+        let id = Uuid::new_v4().to_string();
+        let mut metadata = schedule_metadata("inactive", false, body.child_id.as_deref(), None);
+        if let Some(color) = color.filter(|c| !c.is_empty()) {
+            metadata["schedule"]["color"] = Value::String(color);
+        }
+        if let Some(icon) = icon.filter(|i| !i.is_empty()) {
+            metadata["schedule"]["icon"] = Value::String(icon);
+        }
+        let metadata = metadata.to_string();
 
-    sqlx::query(
-        "INSERT INTO visual_support_documents_templates
-            (id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json)
-         VALUES (?, ?, ?, NULL, ?, 'CUSTOM', 'en', 0, ?)",
-    )
-    .bind(&id)
-    .bind(&user.user_id)
-    .bind(&body.name)
-    .bind(WEEKLY_TYPE)
-    .bind(metadata)
-    .execute(pool)
+        sqlx::query(
+            "INSERT INTO visual_support_documents_templates
+                (id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json)
+             VALUES (?, ?, ?, NULL, ?, 'CUSTOM', 'en', 0, ?)",
+        )
+        .bind(&id)
+        .bind(&user.user_id)
+        .bind(&name)
+        .bind(WEEKLY_TYPE)
+        .bind(metadata)
+        .execute(pool)
+        .await?;
+
+        Ok(id)
+    })
     .await?;
 
     let row = get_schedule_row(pool, &id).await?;
     Ok((StatusCode::CREATED, Json(row)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/schedules/templates",
+    tag = "schedules",
+    responses((status = 200, description = "System and own templates", body = Vec<ScheduleRow>)),
+)]
 async fn list_templates(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
 ) -> AppResult<Json<Vec<ScheduleRow>>> {
     let pool = &state.pool;
 
-    let rows: Vec<ScheduleRow> = if user.role == UserRole::Admin {
-        sqlx::query_as::<_, ScheduleRow>(
-            "SELECT
-                t.id,
-                COALESCE(t.owner_id, '') AS owner_id,
-                CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-                t.name,
-                CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-                IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template
-             FROM visual_support_documents_templates t
-             WHERE t.document_type = ?
-               AND (t.is_system = 1 OR IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0) = 1)
-             ORDER BY t.name",
-        )
-        .bind(WEEKLY_TYPE)
-        .fetch_all(pool)
-        .await?
-    } else {
-        sqlx::query_as::<_, ScheduleRow>(
-            "SELECT
-                t.id,
-                COALESCE(t.owner_id, '') AS owner_id,
-                CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-                t.name,
-                CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-                IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template
-             FROM visual_support_documents_templates t
-             WHERE t.document_type = ?
-               AND (
-                    t.is_system = 1
-                    OR (t.owner_id = ? AND IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0) = 1)
-               )
-             ORDER BY t.name",
-        )
-        .bind(WEEKLY_TYPE)
-        .bind(&user.user_id)
-        .fetch_all(pool)
-        .await?
-    };
+    let is_admin = user.role == UserRole::Admin;
+    let sql = schedule_sql::select_schedules(
+        "",
+        &format!(
+            "t.document_type = ? AND {} ORDER BY t.name",
+            schedule_sql::template_filter(is_admin)
+        ),
+    );
+
+    let mut query = sqlx::query_as::<_, ScheduleRow>(&sql).bind(WEEKLY_TYPE);
+    if !is_admin {
+        query = query.bind(&user.user_id);
+    }
+    let rows: Vec<ScheduleRow> = query.fetch_all(pool).await?;
 
     Ok(Json(rows))
 }
 
+#[utoipa::path(
+    post,
+    path = "/schedules/templates/{id}/copy",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Template id")),
+    responses((status = 201, description = "New schedule", body = ScheduleRow)),
+)]
 async fn copy_template(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -461,46 +818,8 @@ async fn copy_template(
 
     let pool = &state.pool;
 
-    let tmpl: ScheduleRow = if user.role == UserRole::Admin {
-        sqlx::query_as::<_, ScheduleRow>(
-            "SELECT
-                t.id,
-                COALESCE(t.owner_id, '') AS owner_id,
-                CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-                t.name,
-                CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-                IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template
-             FROM visual_support_documents_templates t
-             WHERE t.id = ?
-               AND t.document_type = ?
-               AND (t.is_system = 1 OR IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0) = 1)",
-        )
-        .bind(&template_id)
-        .bind(WEEKLY_TYPE)
-        .fetch_optional(pool)
-        .await?
-        .ok_or(AppError::NotFound)?
-    } else {
-        sqlx::query_as::<_, ScheduleRow>(
-            "SELECT
-                t.id,
-                COALESCE(t.owner_id, '') AS owner_id,
-                CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-                t.name,
-                CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-                IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template
-             FROM visual_support_documents_templates t
-             WHERE t.id = ?
-               AND t.document_type = ?
-               AND (t.is_system = 1 OR (t.owner_id = ? AND IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0) = 1))",
-        )
-        .bind(&template_id)
-        .bind(WEEKLY_TYPE)
-        .bind(&user.user_id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or(AppError::NotFound)?
-    };
+    let tmpl = get_visible_template_row(pool, &template_id, &user).await?;
+    let name = free_copy_name(pool, &state.config, &user.user_id, &tmpl.name, 0).await?;
 
     let new_id = Uuid::new_v4().to_string();
     let metadata = schedule_metadata_json("inactive", false, None, Some(&template_id))?;
@@ -512,7 +831,7 @@ async fn copy_template(
     )
     .bind(&new_id)
     .bind(&user.user_id)
-    .bind(&tmpl.name)
+    .bind(&name)
     .bind(WEEKLY_TYPE)
     .bind(metadata)
     .execute(pool)
@@ -543,58 +862,240 @@ async fn copy_template(
     Ok((StatusCode::CREATED, Json(row)))
 }
 
-async fn get_template(
+/// Copy one of the caller's own schedules, activity cards included. The copy
+/// starts inactive and unbound; day assignments are not copied.
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/duplicate",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses((status = 201, description = "Copy", body = ScheduleRow)),
+)]
+async fn duplicate_schedule(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(id): Path<String>,
-) -> AppResult<Json<ScheduleWithActivityCards>> {
+) -> AppResult<(StatusCode, Json<ScheduleRow>)> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
     let pool = &state.pool;
+    assert_owns_schedule(pool, &id, &user).await?;
 
-    let sched: ScheduleRow = if user.role == UserRole::Admin {
-        sqlx::query_as::<_, ScheduleRow>(
-            "SELECT
-                t.id,
-                COALESCE(t.owner_id, '') AS owner_id,
-                CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-                t.name,
-                CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-                IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template
-             FROM visual_support_documents_templates t
-             WHERE t.id = ?
-               AND t.document_type = ?
-               AND (t.is_system = 1 OR IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0) = 1)",
-        )
-        .bind(&id)
-        .bind(WEEKLY_TYPE)
-        .fetch_optional(pool)
-        .await?
-        .ok_or(AppError::NotFound)?
-    } else {
-        sqlx::query_as::<_, ScheduleRow>(
-            "SELECT
-                t.id,
-                COALESCE(t.owner_id, '') AS owner_id,
-                CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.child_id')), '') AS CHAR(36)) AS child_id,
-                t.name,
-                CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(t.metadata_json, '$.schedule.status')), ''), 'inactive') AS CHAR(20)) AS status,
-                IF(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true OR t.is_system = 1, 1, 0) AS is_template
-             FROM visual_support_documents_templates t
-             WHERE t.id = ?
-               AND t.document_type = ?
-               AND (t.is_system = 1 OR (t.owner_id = ? AND IFNULL(JSON_EXTRACT(t.metadata_json, '$.schedule.is_template') = true, 0) = 1))",
+    let source = get_schedule_row(pool, &id).await?;
+    if source.is_template {
+        return Err(AppError::BadRequest(
+            "Templates are copied with POST /schedules/templates/{id}/copy".into(),
+        ));
+    }
+    let name = free_copy_name(pool, &state.config, &user.user_id, &source.name, 1).await?;
+
+    let new_id = Uuid::new_v4().to_string();
+    let metadata = schedule_metadata_json("inactive", false, None, None)?;
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "INSERT INTO visual_support_documents_templates
+            (id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json)
+         SELECT ?, ?, ?, description, document_type, scenario_type, language, 0, ?
+         FROM visual_support_documents_templates
+         WHERE id = ?",
+    )
+    .bind(&new_id)
+    .bind(&user.user_id)
+    .bind(&name)
+    .bind(metadata)
+    .bind(&id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO visual_support_template_activities
+            (id, template_id, activity_order, activity_card_id, pictogram_id, text_label, optional_notes, metadata_json)
+         SELECT
+            UUID(),
+            ?,
+            activity_order,
+            activity_card_id,
+            pictogram_id,
+            text_label,
+            optional_notes,
+            metadata_json
+         FROM visual_support_template_activities
+         WHERE template_id = ?
+         ORDER BY activity_order",
+    )
+    .bind(&new_id)
+    .bind(&id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let row = get_schedule_row(pool, &new_id).await?;
+    Ok((StatusCode::CREATED, Json(row)))
+}
+
+/// Most activity cards accepted in one imported schedule.
+const MAX_IMPORT_CARDS: usize = 200;
+
+/// Recreate an exported schedule under the caller with fresh ids. The import
+/// starts inactive and unbound; cards are not linked to library cards, which
+/// belong to the exporting account.
+#[utoipa::path(
+    post,
+    path = "/schedules/import",
+    tag = "schedules",
+    request_body = ImportScheduleBody,
+    responses((status = 201, description = "Imported schedule", body = ScheduleRow), (status = 400, description = "Invalid schedule or card", body = super::openapi::ErrorResponse), (status = 409, description = "Name already used", body = super::openapi::ErrorResponse)),
+)]
+async fn import_schedule(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(body): Json<ImportScheduleBody>,
+) -> AppResult<(StatusCode, Json<ScheduleRow>)> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let name = validate::title("name", &body.name)?;
+    let (color, icon) = display_fields(body.color.as_deref(), body.icon.as_deref())?;
+    if let Some(end_behavior) = &body.end_behavior {
+        if !matches!(end_behavior.as_str(), "done" | "next_day") {
+            return Err(AppError::BadRequest("end_behavior must be done or next_day".into()));
+        }
+    }
+    let default_start_time = body.default_start_time.as_deref().map(str::trim).filter(|t| !t.is_empty());
+    if default_start_time.is_some_and(|t| !schedule_sql::is_valid_start_time(t)) {
+        return Err(AppError::BadRequest("default_start_time must be HH:MM".into()));
+    }
+    if body.activity_cards.len() > MAX_IMPORT_CARDS {
+        return Err(AppError::BadRequest(format!(
+            "A schedule can import at most {MAX_IMPORT_CARDS} activity cards"
+        )));
+    }
+
+    let pool = &state.pool;
+    let card_start_default = default_start_time.unwrap_or(&state.config.default_activity_start_time);
+    let mut cards = body.activity_cards.iter().enumerate().collect::<Vec<_>>();
+    cards.sort_by_key(|(index, card)| (card.sort_order.unwrap_or(i32::MAX), *index));
+
+    // Validate and resolve every card before writing anything.
+    let mut prepared = Vec::with_capacity(cards.len());
+    for (index, card) in cards {
+        let title = validate::title(&format!("activity_cards[{index}].title"), &card.title)?;
+        let start_time = card.start_time.as_deref().map(str::trim).filter(|t| !t.is_empty());
+        let end_time = card.end_time.as_deref().map(str::trim).filter(|t| !t.is_empty());
+        for (field, value) in [("start_time", start_time), ("end_time", end_time)] {
+            if value.is_some_and(|t| !schedule_sql::is_valid_start_time(t)) {
+                return Err(AppError::BadRequest(format!("activity_cards[{index}].{field} must be HH:MM")));
+            }
+        }
+
+        let mut picture_path = card
+            .picture_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| p.starts_with("/assets/") && !p.contains(".."))
+            .map(str::to_string);
+        if let Some(arasaac_id) = card.arasaac_id {
+            match pictograms::get_or_fetch_by_id(pool, "en", arasaac_id).await {
+                Ok(pictogram) => picture_path = pictogram.local_file_path.or(picture_path),
+                Err(err) => tracing::warn!(error = ?err, arasaac_id, "Skipping pictogram for imported card"),
+            }
+        }
+
+        let mut metadata = json!({ "start_time": start_time.unwrap_or(card_start_default) });
+        if let Some(end_time) = end_time {
+            metadata["end_time"] = Value::String(end_time.to_string());
+        }
+        if let Some(picture_path) = picture_path {
+            metadata["picture_path"] = Value::String(picture_path);
+        }
+        prepared.push((title, card.description.clone(), metadata.to_string()));
+    }
+
+    ensure_unique_name(pool, &state.config, &user.user_id, &name, None).await?;
+
+    let new_id = Uuid::new_v4().to_string();
+    let mut metadata = schedule_metadata("inactive", false, None, None);
+    if let Some(end_behavior) = &body.end_behavior {
+        metadata["schedule"]["end_behavior"] = Value::String(end_behavior.clone());
+    }
+    if let Some(default_start_time) = default_start_time {
+        metadata["schedule"]["default_start_time"] = Value::String(default_start_time.to_string());
+    }
+    if let Some(color) = color.filter(|c| !c.is_empty()) {
+        metadata["schedule"]["color"] = Value::String(color);
+    }
+    if let Some(icon) = icon.filter(|i| !i.is_empty()) {
+        metadata["schedule"]["icon"] = Value::String(icon);
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "INSERT INTO visual_support_documents_templates
+            (id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json)
+         VALUES (?, ?, ?, NULL, ?, 'CUSTOM', 'en', 0, ?)",
+    )
+    .bind(&new_id)
+    .bind(&user.user_id)
+    .bind(&name)
+    .bind(WEEKLY_TYPE)
+    .bind(metadata.to_string())
+    .execute(&mut *tx)
+    .await?;
+
+    for (order, (title, description, metadata_json)) in prepared.into_iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO visual_support_template_activities
+                (id, template_id, activity_order, activity_card_id, pictogram_id, text_label, optional_notes, metadata_json)
+             VALUES (?, ?, ?, NULL, NULL, ?, ?, ?)",
         )
-        .bind(&id)
-        .bind(WEEKLY_TYPE)
-        .bind(&user.user_id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or(AppError::NotFound)?
-    };
+        .bind(Uuid::new_v4().to_string())
+        .bind(&new_id)
+        .bind(order as i32)
+        .bind(title)
+        .bind(description)
+        .bind(metadata_json)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    let row = get_schedule_row(pool, &new_id).await?;
+    Ok((StatusCode::CREATED, Json(row)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/schedules/templates/{id}",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Template id")),
+    responses((status = 200, description = "Template with cards", body = ScheduleWithActivityCards)),
+)]
+async fn get_template(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<ScheduleWithActivityCards>> {
+    let pool = &state.pool;
+
+    let sched = get_visible_template_row(pool, &id, &user).await?;
 
-    let activity_cards = load_activity_cards_for_schedule(pool, &id).await?;
+    let activity_cards = load_activity_cards_for_schedule(pool, &state.config, &id).await?;
     Ok(Json(ScheduleWithActivityCards { schedule: sched, activity_cards }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses((status = 200, description = "Schedule with cards", body = ScheduleWithActivityCards), (status = 404, description = "Not found", body = super::openapi::ErrorResponse)),
+)]
 async fn get_schedule(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -604,32 +1105,108 @@ async fn get_schedule(
     let sched = get_schedule_row(pool, &id).await?;
 
     if user.role != UserRole::Admin && sched.owner_id != user.user_id {
-        return Err(AppError::Forbidden);
+        return Err(AppError::NotOwned);
     }
 
-    let activity_cards = load_activity_cards_for_schedule(pool, &id).await?;
+    let activity_cards = load_activity_cards_for_schedule(pool, &state.config, &id).await?;
     Ok(Json(ScheduleWithActivityCards { schedule: sched, activity_cards }))
 }
 
+/// The schedule laid out as a 7-day grid for printing: its cards in time
+/// order, grouped by start time, with weekday columns in the owner's week
+/// order. The backend has no PDF exporter, so the client prints this grid
+/// (or saves it as PDF) itself.
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/preview",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses((status = 200, description = "Printable weekly grid", body = SchedulePreview), (status = 404, description = "Not found", body = super::openapi::ErrorResponse)),
+)]
+async fn preview_schedule(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<SchedulePreview>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let pool = &state.pool;
+    assert_owns_schedule(pool, &id, &user).await?;
+    let sched = get_schedule_row(pool, &id).await?;
+
+    let week_start: Option<i16> = sqlx::query_scalar("SELECT week_start FROM users WHERE id = ?")
+        .bind(&sched.owner_id)
+        .fetch_optional(pool)
+        .await?;
+    let week_start = week_start.and_then(|d| u8::try_from(d).ok()).filter(|d| (1..=7).contains(d)).unwrap_or(1);
+    let week_days = (0..7).map(|i| (week_start - 1 + i) % 7 + 1).collect();
+
+    let mut cards = load_activity_cards_for_schedule(pool, &state.config, &id).await?;
+    cards.sort_by(|a, b| (&a.start_time, a.sort_order).cmp(&(&b.start_time, b.sort_order)));
+    let mut rows: Vec<PreviewRow> = Vec::new();
+    for card in cards {
+        match rows.last_mut() {
+            Some(row) if row.start_time == card.start_time => row.cards.push(card),
+            _ => rows.push(PreviewRow { start_time: card.start_time.clone(), cards: vec![card] }),
+        }
+    }
+
+    Ok(Json(SchedulePreview { schedule: sched, week_days, rows }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/schedules/{id}",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id"), ("If-Match" = Option<String>, Header, description = "Expected schedule `version`; 409 when stale")),
+    request_body = UpdateScheduleBody,
+    responses((status = 200, description = "Updated schedule", body = ScheduleRow, headers(("ETag" = String, description = "New schedule `version`"))), (status = 409, description = "Version conflict or name already used", body = super::openapi::ErrorResponse)),
+)]
 async fn update_schedule(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(body): Json<UpdateScheduleBody>,
-) -> AppResult<Json<ScheduleRow>> {
+) -> AppResult<([(HeaderName, String); 1], Json<ScheduleRow>)> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
 
+    let name = validate::optional_title("name", body.name.as_deref())?;
     let pool = &state.pool;
     assert_owns_schedule(pool, &id, &user).await?;
     assert_owns_child_if_set(pool, &body.child_id, &user).await?;
+    assert_child_matches_schedule_owner(pool, &id, &body.child_id, &user).await?;
+    if let Some(end_behavior) = &body.end_behavior {
+        if !matches!(end_behavior.as_str(), "done" | "next_day") {
+            return Err(AppError::BadRequest("end_behavior must be done or next_day".into()));
+        }
+    }
+    if let Some(default_start_time) = &body.default_start_time {
+        if !default_start_time.is_empty() && !schedule_sql::is_valid_start_time(default_start_time) {
+            return Err(AppError::BadRequest("default_start_time must be HH:MM".into()));
+        }
+    }
+
+    let (color, icon) = display_fields(body.color.as_deref(), body.icon.as_deref())?;
+    if let Some(name) = &name {
+        let current = get_schedule_row(pool, &id).await?;
+        if current.status != "archived" {
+            ensure_unique_name(pool, &state.config, &current.owner_id, name, Some(&id)).await?;
+        }
+    }
 
-    if let Some(name) = &body.name {
+    let mut tx = pool.begin().await?;
+    let version = claim_schedule_version(&mut tx, &id, &headers).await?;
+
+    if let Some(name) = &name {
         sqlx::query("UPDATE visual_support_documents_templates SET name = ? WHERE id = ?")
             .bind(name)
             .bind(&id)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
     }
 
@@ -641,18 +1218,121 @@ async fn update_schedule(
         )
         .bind(child_id)
         .bind(&id)
-        .execute(pool)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(end_behavior) = &body.end_behavior {
+        sqlx::query(
+            "UPDATE visual_support_documents_templates
+             SET metadata_json = JSON_SET(COALESCE(metadata_json, JSON_OBJECT()), '$.schedule.end_behavior', ?)
+             WHERE id = ?",
+        )
+        .bind(end_behavior)
+        .bind(&id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(default_start_time) = &body.default_start_time {
+        sqlx::query(
+            "UPDATE visual_support_documents_templates
+             SET metadata_json = JSON_SET(COALESCE(metadata_json, JSON_OBJECT()), '$.schedule.default_start_time', ?)
+             WHERE id = ?",
+        )
+        .bind(default_start_time)
+        .bind(&id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for (path, value) in [("$.schedule.color", color), ("$.schedule.icon", icon)] {
+        let Some(value) = value else { continue };
+        sqlx::query(
+            "UPDATE visual_support_documents_templates
+             SET metadata_json = JSON_SET(COALESCE(metadata_json, JSON_OBJECT()), ?, ?)
+             WHERE id = ?",
+        )
+        .bind(path)
+        .bind(value)
+        .bind(&id)
+        .execute(&mut *tx)
         .await?;
     }
 
+    tx.commit().await?;
+
     let row = get_schedule_row(pool, &id).await?;
-    Ok(Json(row))
+    Ok((etag(version), Json(row)))
+}
+
+/// 409 `schedule_in_use` listing where the schedule is currently assigned,
+/// so the parent can confirm before their child's routine disappears.
+async fn ensure_not_assigned(pool: &crate::db::Db, schedule_id: &str) -> AppResult<()> {
+    #[derive(sqlx::FromRow, Serialize)]
+    struct AssignedDay {
+        child_id: String,
+        child_name: String,
+        day_of_week: i64,
+        /// Set for single-date overrides and date-limited assignments.
+        start_date: Option<String>,
+        end_date: Option<String>,
+    }
+
+    let assignments = sqlx::query_as::<_, AssignedDay>(
+        "SELECT d.child_id,
+                cp.display_name AS child_name,
+                CAST(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.day_of_week')) AS SIGNED) AS day_of_week,
+                CAST(NULLIF(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.start_date')), ''), 'null') AS CHAR(10)) AS start_date,
+                CAST(NULLIF(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(d.content_json, '$.assignment.end_date')), ''), 'null') AS CHAR(10)) AS end_date
+         FROM visual_support_documents d
+         JOIN child_profiles cp ON cp.id = d.child_id AND cp.deleted_at IS NULL
+         WHERE d.template_id = ?
+           AND d.document_type = ?
+         ORDER BY cp.display_name, day_of_week, start_date",
+    )
+    .bind(schedule_id)
+    .bind(WEEKLY_TYPE)
+    .fetch_all(pool)
+    .await?;
+    if assignments.is_empty() {
+        return Ok(());
+    }
+
+    let used_by: Option<String> = sqlx::query_scalar(&format!(
+        "SELECT {} FROM visual_support_documents_templates t WHERE t.id = ?",
+        used_by_children_column()
+    ))
+    .bind(WEEKLY_TYPE)
+    .bind(schedule_id)
+    .fetch_one(pool)
+    .await?;
+
+    Err(AppError::ConflictDetails {
+        message: "Schedule is assigned on a child's calendar. Delete with force=true to remove it anyway.".into(),
+        code: "schedule_in_use",
+        details: json!({
+            "used_by_children": split_child_names(used_by),
+            "assignments": assignments,
+        }),
+    })
 }
 
+#[utoipa::path(
+    delete,
+    path = "/schedules/{id}",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id"), DeleteScheduleQuery),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 409, description = "Assigned on a child's calendar (`schedule_in_use`); retry with `force=true`", body = super::openapi::ErrorResponse),
+    ),
+)]
 async fn delete_schedule(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(id): Path<String>,
+    Query(query): Query<DeleteScheduleQuery>,
 ) -> AppResult<StatusCode> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
@@ -661,6 +1341,10 @@ async fn delete_schedule(
     let pool = &state.pool;
     assert_owns_schedule(pool, &id, &user).await?;
 
+    if !query.force {
+        ensure_not_assigned(pool, &id).await?;
+    }
+
     let mut tx = pool.begin().await?;
 
     // Remove any child-day assignment documents pointing to this schedule.
@@ -711,12 +1395,21 @@ async fn delete_schedule(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    patch,
+    path = "/schedules/{id}/status",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id"), ("If-Match" = Option<String>, Header, description = "Expected schedule `version`; 409 when stale")),
+    request_body = UpdateStatusBody,
+    responses((status = 200, description = "Updated schedule", body = ScheduleRow, headers(("ETag" = String, description = "New schedule `version`"))), (status = 409, description = "Version conflict", body = super::openapi::ErrorResponse)),
+)]
 async fn update_status(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(body): Json<UpdateStatusBody>,
-) -> AppResult<Json<ScheduleRow>> {
+) -> AppResult<([(HeaderName, String); 1], Json<ScheduleRow>)> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
@@ -728,6 +1421,15 @@ async fn update_status(
 
     let pool = &state.pool;
     assert_owns_schedule(pool, &id, &user).await?;
+    if body.status != "archived" {
+        // Unarchiving brings the name back into the owner's namespace.
+        let current = get_schedule_row(pool, &id).await?;
+        if current.status == "archived" {
+            ensure_unique_name(pool, &state.config, &current.owner_id, &current.name, Some(&id)).await?;
+        }
+    }
+    let mut tx = pool.begin().await?;
+    let version = claim_schedule_version(&mut tx, &id, &headers).await?;
 
     sqlx::query(
         "UPDATE visual_support_documents_templates
@@ -736,13 +1438,22 @@ async fn update_status(
     )
     .bind(&body.status)
     .bind(&id)
-    .execute(pool)
+    .execute(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
     let row = get_schedule_row(pool, &id).await?;
-    Ok(Json(row))
+    Ok((etag(version), Json(row)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/activity-cards",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses((status = 200, description = "Cards in order", body = Vec<ActivityCardRow>)),
+)]
 async fn list_activity_cards(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -752,25 +1463,35 @@ async fn list_activity_cards(
     let sched = get_schedule_row(pool, &id).await?;
 
     if user.role != UserRole::Admin && sched.owner_id != user.user_id {
-        return Err(AppError::Forbidden);
+        return Err(AppError::NotOwned);
     }
 
-    let activity_cards = load_activity_cards_for_schedule(pool, &id).await?;
+    let activity_cards = load_activity_cards_for_schedule(pool, &state.config, &id).await?;
     Ok(Json(activity_cards))
 }
 
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/activity-cards",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id"), ("If-Match" = Option<String>, Header, description = "Expected schedule `version`; 409 when stale")),
+    request_body = CreateActivityCardBody,
+    responses((status = 201, description = "Created card", body = ActivityCardRow, headers(("ETag" = String, description = "New schedule `version`"))), (status = 409, description = "Version conflict or schedule archived", body = super::openapi::ErrorResponse)),
+)]
 async fn add_activity_card(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(schedule_id): Path<String>,
+    headers: HeaderMap,
     Json(body): Json<CreateActivityCardBody>,
-) -> AppResult<(StatusCode, Json<ActivityCardRow>)> {
+) -> AppResult<(StatusCode, [(HeaderName, String); 1], Json<ActivityCardRow>)> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
 
     let pool = &state.pool;
     assert_owns_schedule(pool, &schedule_id, &user).await?;
+    ensure_cards_editable(pool, &schedule_id).await?;
 
     let sort_order = if let Some(o) = body.sort_order {
         o
@@ -784,30 +1505,30 @@ async fn add_activity_card(
         max.unwrap_or(-1) + 1
     };
 
-    let owner_id: String = sqlx::query_scalar(
-        "SELECT owner_id FROM visual_support_documents_templates WHERE id = ?",
-    )
+    #[derive(sqlx::FromRow)]
+    struct ScheduleDefaultsRow {
+        owner_id: String,
+        default_start_time: Option<String>,
+    }
+    let schedule = sqlx::query_as::<_, ScheduleDefaultsRow>(&format!(
+        "SELECT t.owner_id, {} AS default_start_time
+         FROM visual_support_documents_templates t
+         WHERE t.id = ?",
+        schedule_sql::DEFAULT_START_TIME
+    ))
     .bind(&schedule_id)
     .fetch_one(pool)
     .await?;
+    let owner_id = schedule.owner_id;
+    let start_time = body
+        .start_time
+        .clone()
+        .filter(|t| !t.trim().is_empty())
+        .or(schedule.default_start_time)
+        .unwrap_or_else(|| state.config.default_activity_start_time.clone());
 
     let resolved_activity_card_id = if let Some(activity_card_id) = body.activity_card_id.clone() {
-        let allowed: bool = sqlx::query_scalar(
-            "SELECT EXISTS(
-                SELECT 1
-                FROM visual_support_activity_library
-                WHERE id = ?
-                  AND (is_system = 1 OR owner_id = ?)
-            )",
-        )
-        .bind(&activity_card_id)
-        .bind(&owner_id)
-        .fetch_one(pool)
-        .await?;
-
-        if !allowed {
-            return Err(AppError::Forbidden);
-        }
+        ensure_library_card_usable(pool, &activity_card_id, &owner_id).await?;
         Some(activity_card_id)
     } else {
         let label = body.title.trim();
@@ -848,7 +1569,7 @@ async fn add_activity_card(
     };
 
     let mut metadata: Value = json!({
-        "start_time": body.start_time,
+        "start_time": start_time,
     });
     if let Some(end_time) = &body.end_time {
         metadata["end_time"] = Value::String(end_time.clone());
@@ -859,6 +1580,8 @@ async fn add_activity_card(
     let metadata_json = serde_json::to_string(&metadata)
         .map_err(|_| AppError::BadRequest("Invalid metadata JSON".into()))?;
 
+    let mut tx = pool.begin().await?;
+    let version = claim_schedule_version(&mut tx, &schedule_id, &headers).await?;
     let id = Uuid::new_v4().to_string();
     sqlx::query(
         "INSERT INTO visual_support_template_activities
@@ -872,44 +1595,46 @@ async fn add_activity_card(
     .bind(&body.title)
     .bind(&body.description)
     .bind(metadata_json)
-    .execute(pool)
+    .execute(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
     let card: ActivityCardRow = sqlx::query_as::<_, ActivityCardRow>(
-        "SELECT
-            vta.id,
-            ? AS schedule_id,
-            vta.activity_card_id,
-            COALESCE(NULLIF(vta.text_label, ''), vsa.label_text) AS title,
-            vta.optional_notes AS description,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.picture_path')), ''), vsa.local_image_path) AS CHAR(500)) AS picture_path,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.start_time')), ''), '08:00') AS CHAR(5)) AS start_time,
-            CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.end_time')), '') AS CHAR(5)) AS end_time,
-            vta.activity_order AS sort_order
-         FROM visual_support_template_activities vta
-         LEFT JOIN visual_support_activity_library vsa ON vsa.id = vta.activity_card_id
-         WHERE vta.id = ?",
+        &schedule_sql::select_activity_cards(true, &state.config.default_activity_start_time, "vta.id = ?"),
     )
     .bind(&schedule_id)
     .bind(&id)
     .fetch_one(pool)
     .await?;
 
-    Ok((StatusCode::CREATED, Json(card)))
+    Ok((StatusCode::CREATED, etag(version), Json(card.with_picture_url(&state.config))))
 }
 
+#[utoipa::path(
+    patch,
+    path = "/schedules/{id}/activity-cards/reorder",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id"), ("If-Match" = Option<String>, Header, description = "Expected schedule `version`; 409 when stale")),
+    request_body = ReorderBody,
+    responses((status = 204, description = "Reordered", headers(("ETag" = String, description = "New schedule `version`"))), (status = 409, description = "Version conflict or schedule archived", body = super::openapi::ErrorResponse)),
+)]
 async fn reorder_activity_cards(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(schedule_id): Path<String>,
+    headers: HeaderMap,
     Json(body): Json<ReorderBody>,
-) -> AppResult<StatusCode> {
+) -> AppResult<(StatusCode, [(HeaderName, String); 1])> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
 
     let pool = &state.pool;
     assert_owns_schedule(pool, &schedule_id, &user).await?;
+    ensure_cards_editable(pool, &schedule_id).await?;
+    let mut tx = pool.begin().await?;
+    let version = claim_schedule_version(&mut tx, &schedule_id, &headers).await?;
 
     for (i, card_id) in body.activity_card_ids.iter().enumerate() {
         sqlx::query(
@@ -920,25 +1645,75 @@ async fn reorder_activity_cards(
         .bind(i as i32)
         .bind(card_id)
         .bind(&schedule_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
-    Ok(StatusCode::NO_CONTENT)
+    tx.commit().await?;
+
+    Ok((StatusCode::NO_CONTENT, etag(version)))
+}
+
+/// A schedule may only reference system library cards or cards owned by the
+/// schedule owner.
+async fn ensure_library_card_usable(
+    pool: &crate::db::Db,
+    activity_card_id: &str,
+    owner_id: &str,
+) -> AppResult<()> {
+    let allowed: bool = sqlx::query_scalar(
+        "SELECT EXISTS(
+            SELECT 1
+            FROM visual_support_activity_library
+            WHERE id = ?
+              AND (is_system = 1 OR owner_id = ?)
+        )",
+    )
+    .bind(activity_card_id)
+    .bind(owner_id)
+    .fetch_one(pool)
+    .await?;
+
+    if !allowed {
+        return Err(AppError::Forbidden);
+    }
+    Ok(())
 }
 
+#[utoipa::path(
+    put,
+    path = "/schedules/{id}/activity-cards/{card_id}",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id"), ("card_id" = String, Path, description = "Card id"), ("If-Match" = Option<String>, Header, description = "Expected schedule `version`; 409 when stale")),
+    request_body = UpdateActivityCardBody,
+    responses((status = 200, description = "Updated card", body = ActivityCardRow, headers(("ETag" = String, description = "New schedule `version`"))), (status = 409, description = "Version conflict or schedule archived", body = super::openapi::ErrorResponse)),
+)]
 async fn update_activity_card(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((schedule_id, card_id)): Path<(String, String)>,
+    headers: HeaderMap,
     Json(body): Json<UpdateActivityCardBody>,
-) -> AppResult<Json<ActivityCardRow>> {
+) -> AppResult<([(HeaderName, String); 1], Json<ActivityCardRow>)> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
 
     let pool = &state.pool;
     assert_owns_schedule(pool, &schedule_id, &user).await?;
+    ensure_cards_editable(pool, &schedule_id).await?;
+
+    // Validate before any field is written so a rejected card id leaves the
+    // row untouched.
+    if let Some(activity_card_id) = &body.activity_card_id {
+        let owner_id: String = sqlx::query_scalar(
+            "SELECT owner_id FROM visual_support_documents_templates WHERE id = ?",
+        )
+        .bind(&schedule_id)
+        .fetch_one(pool)
+        .await?;
+        ensure_library_card_usable(pool, activity_card_id, &owner_id).await?;
+    }
 
     let existing_metadata: Option<String> = sqlx::query_scalar(
         "SELECT CAST(metadata_json AS CHAR)
@@ -968,6 +1743,9 @@ async fn update_activity_card(
     let metadata_json = serde_json::to_string(&metadata)
         .map_err(|_| AppError::BadRequest("Invalid metadata JSON".into()))?;
 
+    let mut tx = pool.begin().await?;
+    let version = claim_schedule_version(&mut tx, &schedule_id, &headers).await?;
+
     if let Some(v) = &body.title {
         sqlx::query(
             "UPDATE visual_support_template_activities
@@ -977,7 +1755,7 @@ async fn update_activity_card(
         .bind(v)
         .bind(&card_id)
         .bind(&schedule_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -990,7 +1768,7 @@ async fn update_activity_card(
         .bind(v)
         .bind(&card_id)
         .bind(&schedule_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -1003,7 +1781,7 @@ async fn update_activity_card(
         .bind(metadata_json)
         .bind(&card_id)
         .bind(&schedule_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -1016,7 +1794,7 @@ async fn update_activity_card(
         .bind(v)
         .bind(&card_id)
         .bind(&schedule_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -1029,24 +1807,18 @@ async fn update_activity_card(
         .bind(v)
         .bind(&card_id)
         .bind(&schedule_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
+    tx.commit().await?;
+
     let card: ActivityCardRow = sqlx::query_as::<_, ActivityCardRow>(
-        "SELECT
-            vta.id,
-            ? AS schedule_id,
-            vta.activity_card_id,
-            COALESCE(NULLIF(vta.text_label, ''), vsa.label_text) AS title,
-            vta.optional_notes AS description,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.picture_path')), ''), vsa.local_image_path) AS CHAR(500)) AS picture_path,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.start_time')), ''), '08:00') AS CHAR(5)) AS start_time,
-            CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.end_time')), '') AS CHAR(5)) AS end_time,
-            vta.activity_order AS sort_order
-         FROM visual_support_template_activities vta
-         LEFT JOIN visual_support_activity_library vsa ON vsa.id = vta.activity_card_id
-         WHERE vta.id = ? AND vta.template_id = ?",
+        &schedule_sql::select_activity_cards(
+            true,
+            &state.config.default_activity_start_time,
+            "vta.id = ? AND vta.template_id = ?",
+        ),
     )
     .bind(&schedule_id)
     .bind(&card_id)
@@ -1055,26 +1827,570 @@ async fn update_activity_card(
     .await?
     .ok_or(AppError::NotFound)?;
 
-    Ok(Json(card))
+    Ok((etag(version), Json(card.with_picture_url(&state.config))))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/schedules/{id}/activity-cards/{card_id}",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id"), ("card_id" = String, Path, description = "Card id"), ("If-Match" = Option<String>, Header, description = "Expected schedule `version`; 409 when stale")),
+    responses((status = 204, description = "Deleted", headers(("ETag" = String, description = "New schedule `version`"))), (status = 409, description = "Version conflict or schedule archived", body = super::openapi::ErrorResponse)),
+)]
 async fn delete_activity_card(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((schedule_id, card_id)): Path<(String, String)>,
-) -> AppResult<StatusCode> {
+    headers: HeaderMap,
+) -> AppResult<(StatusCode, [(HeaderName, String); 1])> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
 
     let pool = &state.pool;
     assert_owns_schedule(pool, &schedule_id, &user).await?;
+    ensure_cards_editable(pool, &schedule_id).await?;
+    let mut tx = pool.begin().await?;
+    let version = claim_schedule_version(&mut tx, &schedule_id, &headers).await?;
 
     sqlx::query("DELETE FROM visual_support_template_activities WHERE id = ? AND template_id = ?")
         .bind(&card_id)
         .bind(&schedule_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    compact_activity_order(&mut tx, &schedule_id).await?;
+    tx.commit().await?;
+
+    Ok((StatusCode::NO_CONTENT, etag(version)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/activity-cards/compact",
+    tag = "schedules",
+    params(("id" = String, Path, description = "Schedule id"), ("If-Match" = Option<String>, Header, description = "Expected schedule `version`; 409 when stale")),
+    responses((status = 200, description = "Cards renumbered", body = Vec<ActivityCardRow>, headers(("ETag" = String, description = "New schedule `version`"))), (status = 409, description = "Version conflict or schedule archived", body = super::openapi::ErrorResponse)),
+)]
+async fn compact_activity_cards(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(schedule_id): Path<String>,
+    headers: HeaderMap,
+) -> AppResult<([(HeaderName, String); 1], Json<Vec<ActivityCardRow>>)> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let pool = &state.pool;
+    assert_owns_schedule(pool, &schedule_id, &user).await?;
+    ensure_cards_editable(pool, &schedule_id).await?;
+
+    let mut tx = pool.begin().await?;
+    let version = claim_schedule_version(&mut tx, &schedule_id, &headers).await?;
+    compact_activity_order(&mut tx, &schedule_id).await?;
+    tx.commit().await?;
+
+    let activity_cards = load_activity_cards_for_schedule(pool, &state.config, &schedule_id).await?;
+    Ok((etag(version), Json(activity_cards)))
+}
+
+/// Renumber a schedule's cards to a contiguous `0..n` in their current order.
+/// Cards are first shifted out of the way so the unique
+/// `(template_id, activity_order)` key never collides mid-update. Runs in the
+/// caller's transaction.
+async fn compact_activity_order(conn: &mut sqlx::MySqlConnection, schedule_id: &str) -> AppResult<()> {
+    const SHIFT: i32 = 1_000_000;
+
+    let ids: Vec<String> = sqlx::query_scalar(
+        "SELECT id
+         FROM visual_support_template_activities
+         WHERE template_id = ?
+         ORDER BY activity_order, id
+         FOR UPDATE",
+    )
+    .bind(schedule_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "UPDATE visual_support_template_activities
+         SET activity_order = activity_order + ?
+         WHERE template_id = ?",
+    )
+    .bind(SHIFT)
+    .bind(schedule_id)
+    .execute(&mut *conn)
+    .await?;
+
+    for (i, id) in ids.iter().enumerate() {
+        sqlx::query(
+            "UPDATE visual_support_template_activities
+             SET activity_order = ?
+             WHERE id = ? AND template_id = ?",
+        )
+        .bind(i as i32)
+        .bind(id)
+        .bind(schedule_id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, test_support};
+
+    async fn new_schedule(state: &AppState, owner: &AuthUser, name: &str) -> String {
+        let body = CreateScheduleBody { name: name.into(), child_id: None, color: None, icon: None };
+        match create_schedule(State(state.clone()), Extension(owner.clone()), HeaderMap::new(), Json(body)).await {
+            Ok((_, Json(row))) => row.id,
+            Err(err) => panic!("create_schedule: {err:?}"),
+        }
+    }
+
+    async fn link_child(state: &AppState, user: &AuthUser, schedule_id: &str, child_id: &str) -> AppResult<()> {
+        let body = UpdateScheduleBody {
+            name: None,
+            child_id: Some(child_id.into()),
+            end_behavior: None,
+            default_start_time: None,
+            color: None,
+            icon: None,
+        };
+        update_schedule(State(state.clone()), Extension(user.clone()), Path(schedule_id.into()), HeaderMap::new(), Json(body))
+            .await
+            .map(|_| ())
+    }
+
+    /// Rename with `If-Match: "<if_match>"`; the `ETag` of the result.
+    async fn rename(state: &AppState, user: &AuthUser, schedule_id: &str, name: &str, if_match: i32) -> AppResult<String> {
+        let body = UpdateScheduleBody {
+            name: Some(name.into()),
+            child_id: None,
+            end_behavior: None,
+            default_start_time: None,
+            color: None,
+            icon: None,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, format!("\"{if_match}\"").parse().unwrap());
+        let ([(_, etag)], Json(row)) =
+            update_schedule(State(state.clone()), Extension(user.clone()), Path(schedule_id.into()), headers, Json(body))
+                .await?;
+        assert_eq!(etag, format!("\"{}\"", row.version));
+        Ok(etag)
+    }
+
+    /// Append a card titled `title`; its id.
+    async fn add_card(state: &AppState, user: &AuthUser, schedule_id: &str, title: &str, sort_order: Option<i32>) -> AppResult<String> {
+        let body = CreateActivityCardBody {
+            activity_card_id: None,
+            title: title.into(),
+            description: None,
+            picture_path: None,
+            start_time: None,
+            end_time: None,
+            sort_order,
+        };
+        let (_, _, Json(card)) =
+            add_activity_card(State(state.clone()), Extension(user.clone()), Path(schedule_id.into()), HeaderMap::new(), Json(body))
+                .await?;
+        Ok(card.id)
+    }
+
+    /// `(title, activity_order)` of a schedule's cards, in order.
+    async fn card_orders(pool: &Db, schedule_id: &str) -> Vec<(String, i32)> {
+        sqlx::query_as(
+            "SELECT text_label, activity_order
+             FROM visual_support_template_activities
+             WHERE template_id = ?
+             ORDER BY activity_order",
+        )
+        .bind(schedule_id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    fn if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, value.parse().unwrap());
+        headers
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn preview_of_a_schedule_without_cards_is_an_empty_grid(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        sqlx::query("UPDATE users SET week_start = 7 WHERE id = ?")
+            .bind(&parent.user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let id = new_schedule(&state, &parent, "Empty week").await;
+
+        let preview = |user: &AuthUser| preview_schedule(State(state.clone()), Extension(user.clone()), Path(id.clone()));
+        let Json(grid) = preview(&parent).await.unwrap();
+        assert_eq!(grid.schedule.id, id);
+        assert_eq!(grid.week_days, vec![7, 1, 2, 3, 4, 5, 6]);
+        assert!(grid.rows.is_empty());
+
+        let err = test_support::expect_err(preview(&other).await);
+        assert!(matches!(err, AppError::NotOwned), "{err:?}");
+    }
+
+    #[test]
+    fn if_match_accepts_strong_weak_and_bare_versions() {
+        assert_eq!(if_match_version(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(if_match_version(&if_match("*")).unwrap(), None);
+        assert_eq!(if_match_version(&if_match("\"3\"")).unwrap(), Some(3));
+        assert_eq!(if_match_version(&if_match("W/\"3\"")).unwrap(), Some(3));
+        assert_eq!(if_match_version(&if_match(" 7 ")).unwrap(), Some(7));
+        assert!(matches!(if_match_version(&if_match("\"abc\"")), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn copy_names_count_up_and_fit_the_title_limit() {
+        assert_eq!(copy_name(" Week ", 0), "Week");
+        assert_eq!(copy_name("Week", 1), "Week (copy)");
+        assert_eq!(copy_name("Week", 2), "Week (copy 2)");
+        let long = copy_name(&"é".repeat(validate::MAX_TITLE_CHARS), 12);
+        assert_eq!(long.chars().count(), validate::MAX_TITLE_CHARS);
+        assert!(long.ends_with(" (copy 12)"));
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn copies_get_a_free_name(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let id = new_schedule(&state, &parent, "Week").await;
+        let template =
+            test_support::schedule(&pool, &parent, "Morning", serde_json::json!({ "schedule": { "is_template": true } }))
+                .await;
+
+        let mut names = Vec::new();
+        for _ in 0..2 {
+            let (_, Json(copy)) =
+                duplicate_schedule(State(state.clone()), Extension(parent.clone()), Path(id.clone())).await.unwrap();
+            names.push(copy.name);
+        }
+        for _ in 0..2 {
+            let (_, Json(copy)) =
+                copy_template(State(state.clone()), Extension(parent.clone()), Path(template.clone())).await.unwrap();
+            names.push(copy.name);
+        }
+        assert_eq!(names, ["Week (copy)", "Week (copy 2)", "Morning", "Morning (copy)"]);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn second_edit_with_stale_version_conflicts(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let id = new_schedule(&state, &parent, "Week").await;
+        let version = get_schedule_row(&pool, &id).await.unwrap().version;
+
+        // Both clients loaded `version`; the first edit wins.
+        let etag = rename(&state, &parent, &id, "Mine", version).await.unwrap();
+        assert_eq!(etag, format!("\"{}\"", version + 1));
+        let err = test_support::expect_err(rename(&state, &parent, &id, "Theirs", version).await);
+        assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+
+        // The rejected edit wrote nothing and did not bump the version.
+        let row = get_schedule_row(&pool, &id).await.unwrap();
+        assert_eq!((row.name.as_str(), row.version), ("Mine", version + 1));
+        rename(&state, &parent, &id, "Theirs", version + 1).await.unwrap();
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn schedule_cannot_be_linked_to_another_parents_child(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let admin = test_support::user(&pool, UserRole::Admin).await;
+        let parent_a = test_support::user(&pool, UserRole::Parent).await;
+        let parent_b = test_support::user(&pool, UserRole::Parent).await;
+        let child_a = test_support::child(&pool, Some(&parent_a.user_id)).await;
+        let child_b = test_support::child(&pool, Some(&parent_b.user_id)).await;
+        let schedule_a = new_schedule(&state, &parent_a, "A's week").await;
+
+        // Admins pass the caller check but not the owner check.
+        let err = test_support::expect_err(link_child(&state, &admin, &schedule_a, &child_b).await);
+        assert!(matches!(err, AppError::BadRequest(_)), "{err:?}");
+        // Parents are stopped by the caller check first.
+        let err = test_support::expect_err(link_child(&state, &parent_a, &schedule_a, &child_b).await);
+        assert!(matches!(err, AppError::NotOwned), "{err:?}");
+
+        assert!(link_child(&state, &parent_a, &schedule_a, &child_a).await.is_ok());
+        assert!(link_child(&state, &admin, &schedule_a, &child_a).await.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn admin_may_link_admin_schedules_and_parentless_children(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let admin = test_support::user(&pool, UserRole::Admin).await;
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let orphan = test_support::child(&pool, None).await;
+        let admin_schedule = new_schedule(&state, &admin, "Admin week").await;
+        let parent_schedule = new_schedule(&state, &parent, "Parent week").await;
+
+        assert!(link_child(&state, &admin, &admin_schedule, &child).await.is_ok());
+        assert!(link_child(&state, &admin, &parent_schedule, &orphan).await.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn deleting_a_middle_card_leaves_contiguous_order(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let id = new_schedule(&state, &parent, "Week").await;
+        let mut cards = Vec::new();
+        for title in ["Wake up", "Breakfast", "Teeth", "School"] {
+            cards.push(add_card(&state, &parent, &id, title, None).await.unwrap());
+        }
+
+        let deleted = delete_activity_card(
+            State(state.clone()),
+            Extension(parent.clone()),
+            Path((id.clone(), cards[1].clone())),
+            HeaderMap::new(),
+        )
+        .await;
+        assert!(deleted.is_ok());
+
+        let expected = [("Wake up", 0), ("Teeth", 1), ("School", 2)].map(|(t, o)| (t.to_string(), o));
+        assert_eq!(card_orders(&pool, &id).await, expected);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn compact_renumbers_sparse_orders(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let id = new_schedule(&state, &parent, "Week").await;
+        for (title, order) in [("Lunch", 7), ("Wake up", 0), ("Breakfast", 3)] {
+            add_card(&state, &parent, &id, title, Some(order)).await.unwrap();
+        }
+
+        let compact = |user: &AuthUser, headers: HeaderMap| {
+            compact_activity_cards(State(state.clone()), Extension(user.clone()), Path(id.clone()), headers)
+        };
+        let err = test_support::expect_err(compact(&other, HeaderMap::new()).await);
+        assert!(matches!(err, AppError::NotOwned), "{err:?}");
+        let err = test_support::expect_err(compact(&parent, if_match("\"1\"")).await);
+        assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+
+        let ([(_, etag)], Json(cards)) = compact(&parent, HeaderMap::new()).await.unwrap();
+        let version: i32 = sqlx::query_scalar("SELECT version FROM visual_support_documents_templates WHERE id = ?")
+            .bind(&id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(etag, format!("\"{version}\""));
+        let orders: Vec<i32> = cards.iter().map(|c| c.sort_order).collect();
+        assert_eq!(orders, [0, 1, 2]);
+        let expected = [("Wake up", 0), ("Breakfast", 1), ("Lunch", 2)].map(|(t, o)| (t.to_string(), o));
+        assert_eq!(card_orders(&pool, &id).await, expected);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn card_cannot_point_at_another_parents_library_card(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let id = new_schedule(&state, &parent, "Week").await;
+        let card_id = add_card(&state, &parent, &id, "Breakfast", None).await.unwrap();
+        let private = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO visual_support_activity_library (id, owner_id, language, label_text, is_system)
+             VALUES (?, ?, 'en', 'Secret', 0)",
+        )
+        .bind(&private)
+        .bind(&other.user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let body = UpdateActivityCardBody {
+            activity_card_id: Some(private),
+            title: Some("Renamed".into()),
+            description: None,
+            picture_path: None,
+            start_time: None,
+            end_time: None,
+            sort_order: None,
+        };
+        let result = update_activity_card(
+            State(state.clone()),
+            Extension(parent.clone()),
+            Path((id.clone(), card_id)),
+            HeaderMap::new(),
+            Json(body),
+        )
+        .await;
+        let err = test_support::expect_err(result);
+        assert!(matches!(err, AppError::Forbidden), "{err:?}");
+
+        // Nothing was written.
+        let expected = [("Breakfast".to_string(), 0)];
+        assert_eq!(card_orders(&pool, &id).await, expected);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn card_start_time_falls_back_to_schedule_then_config(pool: Db) {
+        let mut state = test_support::state(pool.clone());
+        state.config.default_activity_start_time = "13:30".into();
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let id = new_schedule(&state, &parent, "Afternoon").await;
+        let start_times = |cards: Vec<ActivityCardRow>| cards.into_iter().map(|c| (c.title, c.start_time)).collect::<Vec<_>>();
+
+        add_card(&state, &parent, &id, "Snack", None).await.unwrap();
+
+        let body = UpdateScheduleBody {
+            name: None,
+            child_id: None,
+            end_behavior: None,
+            default_start_time: Some("15:00".into()),
+            color: None,
+            icon: None,
+        };
+        let updated =
+            update_schedule(State(state.clone()), Extension(parent.clone()), Path(id.clone()), HeaderMap::new(), Json(body)).await;
+        assert!(updated.is_ok());
+        add_card(&state, &parent, &id, "Homework", None).await.unwrap();
+
+        // A card stored without a start time of its own reads the current default.
+        sqlx::query(
+            "INSERT INTO visual_support_template_activities (id, template_id, activity_order, text_label)
+             VALUES (UUID(), ?, 9, 'Play')",
+        )
+        .bind(&id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let cards = load_activity_cards_for_schedule(&pool, &state.config, &id).await.unwrap();
+        let expected = [("Snack", "13:30"), ("Homework", "15:00"), ("Play", "15:00")].map(|(t, s)| (t.to_string(), s.to_string()));
+        assert_eq!(start_times(cards), expected);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn deleting_an_assigned_schedule_needs_force(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let id = new_schedule(&state, &parent, "Week").await;
+        test_support::assign(&pool, &parent, &child, &id, json!({ "day_of_week": 1, "persistent": true })).await;
+        let delete = |force: bool| {
+            delete_schedule(State(state.clone()), Extension(parent.clone()), Path(id.clone()), Query(DeleteScheduleQuery { force }))
+        };
+
+        let AppError::ConflictDetails { code, details, .. } = test_support::expect_err(delete(false).await) else {
+            panic!("expected a structured conflict");
+        };
+        assert_eq!(code, "schedule_in_use");
+        assert_eq!(details["used_by_children"], json!(["Test child"]));
+        assert_eq!(details["assignments"][0]["child_id"], child.as_str());
+        assert_eq!(details["assignments"][0]["day_of_week"], 1);
+        assert!(get_schedule_row(&pool, &id).await.is_ok());
+
+        assert_eq!(delete(true).await.unwrap(), StatusCode::NO_CONTENT);
+        assert!(matches!(get_schedule_row(&pool, &id).await, Err(AppError::NotFound)));
+        let assignments: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM visual_support_documents WHERE template_id = ?")
+            .bind(&id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(assignments, 0);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn duplicate_schedule_names_conflict_per_owner(pool: Db) {
+        let mut state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let create = |state: &AppState, user: &AuthUser, name: &str| {
+            let body = CreateScheduleBody { name: name.into(), child_id: None, color: None, icon: None };
+            create_schedule(State(state.clone()), Extension(user.clone()), HeaderMap::new(), Json(body))
+        };
+        let set_status = |id: &str, status: &str| {
+            let body = UpdateStatusBody { status: status.into() };
+            update_status(State(state.clone()), Extension(parent.clone()), Path(id.into()), HeaderMap::new(), Json(body))
+        };
+
+        let morning = new_schedule(&state, &parent, "Morning").await;
+        let err = test_support::expect_err(create(&state, &parent, "  MORNING ").await);
+        assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+        // Names are per owner.
+        new_schedule(&state, &other, "Morning").await;
+
+        let evening = new_schedule(&state, &parent, "Evening").await;
+        let version = get_schedule_row(&pool, &evening).await.unwrap().version;
+        let err = test_support::expect_err(rename(&state, &parent, &evening, "morning", version).await);
+        assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+        rename(&state, &parent, &evening, "Night", version).await.unwrap();
+        // A schedule does not clash with its own name.
+        let version = get_schedule_row(&pool, &morning).await.unwrap().version;
+        rename(&state, &parent, &morning, "morning", version).await.unwrap();
+
+        // Archiving frees the name; unarchiving into a clash is refused.
+        assert!(set_status(&morning, "archived").await.is_ok());
+        new_schedule(&state, &parent, "Morning").await;
+        let err = test_support::expect_err(set_status(&morning, "inactive").await);
+        assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+
+        state.config.unique_schedule_names = false;
+        assert!(create(&state, &parent, "Morning").await.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn archived_schedule_cards_are_read_only_until_restored(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let id = new_schedule(&state, &parent, "Week").await;
+        let card = add_card(&state, &parent, &id, "Breakfast", None).await.unwrap();
+        let set_status = |status: &str| {
+            let body = UpdateStatusBody { status: status.into() };
+            update_status(State(state.clone()), Extension(parent.clone()), Path(id.clone()), HeaderMap::new(), Json(body))
+        };
+        let is_archived_conflict = |err: AppError| matches!(&err, AppError::Conflict(m) if m.contains("archived"));
+
+        assert!(set_status("archived").await.is_ok());
+        assert!(is_archived_conflict(test_support::expect_err(add_card(&state, &parent, &id, "Lunch", None).await)));
+        let reorder = ReorderBody { activity_card_ids: vec![card.clone()] };
+        let result =
+            reorder_activity_cards(State(state.clone()), Extension(parent.clone()), Path(id.clone()), HeaderMap::new(), Json(reorder))
+                .await;
+        assert!(is_archived_conflict(test_support::expect_err(result)));
+        let result = delete_activity_card(
+            State(state.clone()),
+            Extension(parent.clone()),
+            Path((id.clone(), card.clone())),
+            HeaderMap::new(),
+        )
+        .await;
+        assert!(is_archived_conflict(test_support::expect_err(result)));
+        let result =
+            compact_activity_cards(State(state.clone()), Extension(parent.clone()), Path(id.clone()), HeaderMap::new()).await;
+        assert!(is_archived_conflict(test_support::expect_err(result)));
+        assert_eq!(card_orders(&pool, &id).await, [("Breakfast".to_string(), 0)]);
+
+        assert!(set_status("inactive").await.is_ok());
+        add_card(&state, &parent, &id, "Lunch", None).await.unwrap();
+        assert_eq!(card_orders(&pool, &id).await.len(), 2);
+    }
 }
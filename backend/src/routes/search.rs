@@ -0,0 +1,186 @@
+//! `/search` — find the caller's documents and schedules by title.
+//!
+//! Plain `LIKE` matching for now; `services::pictograms` shows the FULLTEXT
+//! pattern to move to once accounts grow large enough to need it.
+
+use axum::{
+    extract::{Extension, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use super::schedule_sql;
+use crate::{
+    errors::{AppError, AppResult},
+    middleware::auth_guard::AuthUser,
+    models::UserRole,
+    state::AppState,
+};
+
+const MAX_QUERY_CHARS: usize = 100;
+const DEFAULT_LIMIT: u32 = 20;
+const MAX_LIMIT: u32 = 50;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/search", get(search))
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: Option<String>,
+    limit: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct SearchResult {
+    /// `document` or `schedule`.
+    #[serde(rename = "type")]
+    kind: &'static str,
+    id: String,
+    title: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct TitleRow {
+    id: String,
+    title: String,
+}
+
+/// Escape `LIKE` wildcards so user input only ever matches literally.
+fn like_contains_pattern(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len() + 2);
+    escaped.push('%');
+    for c in input.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped.push('%');
+    escaped
+}
+
+async fn search(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Query(q): Query<SearchQuery>,
+) -> AppResult<Json<Vec<SearchResult>>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let term: String = q
+        .q
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .chars()
+        .take(MAX_QUERY_CHARS)
+        .collect();
+    if term.is_empty() {
+        return Err(AppError::BadRequest("Query parameter q is required".into()));
+    }
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let pattern = like_contains_pattern(&term.to_lowercase());
+
+    let pool = &state.pool;
+    let is_admin = user.role == UserRole::Admin;
+    let owner_filter = if is_admin { "" } else { " AND owner_id = ?" };
+
+    // Weekly schedules also keep a document row; they are listed once, as
+    // schedules, below.
+    let doc_sql = format!(
+        "SELECT id, title
+         FROM visual_support_documents
+         WHERE document_type <> 'WEEKLY_SCHEDULE'
+           AND LOWER(title) LIKE ?{owner_filter}
+         ORDER BY updated_at DESC
+         LIMIT ?"
+    );
+    let mut doc_query = sqlx::query_as::<_, TitleRow>(&doc_sql).bind(&pattern);
+    if !is_admin {
+        doc_query = doc_query.bind(&user.user_id);
+    }
+    let documents = doc_query.bind(limit).fetch_all(pool).await?;
+
+    let schedule_sql = format!(
+        "SELECT t.id, t.name AS title
+         FROM visual_support_documents_templates t
+         WHERE t.document_type = 'WEEKLY_SCHEDULE'
+           AND {}
+           AND LOWER(t.name) LIKE ?{}
+         ORDER BY t.updated_at DESC
+         LIMIT ?",
+        schedule_sql::regular_schedule_filter(),
+        if is_admin { "" } else { " AND t.owner_id = ?" },
+    );
+    let mut schedule_query = sqlx::query_as::<_, TitleRow>(&schedule_sql).bind(&pattern);
+    if !is_admin {
+        schedule_query = schedule_query.bind(&user.user_id);
+    }
+    let schedules = schedule_query.bind(limit).fetch_all(pool).await?;
+
+    let needle = term.to_lowercase();
+    let mut results: Vec<SearchResult> = documents
+        .into_iter()
+        .map(|r| SearchResult { kind: "document", id: r.id, title: r.title })
+        .chain(
+            schedules
+                .into_iter()
+                .map(|r| SearchResult { kind: "schedule", id: r.id, title: r.title }),
+        )
+        .collect();
+
+    // Prefix matches first, then alphabetical.
+    results.sort_by_cached_key(|r| {
+        let lower = r.title.to_lowercase();
+        (!lower.starts_with(&needle), lower)
+    });
+    results.truncate(limit as usize);
+
+    Ok(Json(results))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{db::Db, test_support};
+
+    async fn document(pool: &Db, owner_id: &str, title: &str, document_type: &str) -> String {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO visual_support_documents
+                (id, owner_id, title, document_type, layout_spec_json, content_json)
+             VALUES (?, ?, ?, ?, '{}', '{}')",
+        )
+        .bind(&id)
+        .bind(owner_id)
+        .bind(title)
+        .bind(document_type)
+        .execute(pool)
+        .await
+        .expect("insert document");
+        id
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn weekly_schedule_documents_are_not_listed_as_documents(pool: Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let board = document(&pool, &parent.user_id, "Morning board", "FIRST_THEN").await;
+        document(&pool, &parent.user_id, "Morning week", "WEEKLY_SCHEDULE").await;
+
+        let state = test_support::state(pool);
+        let query = SearchQuery { q: Some("morning".into()), limit: None };
+        let Json(results) = match search(State(state), Extension(parent), Query(query)).await {
+            Ok(results) => results,
+            Err(err) => panic!("search: {err:?}"),
+        };
+        let documents: Vec<&str> =
+            results.iter().filter(|r| r.kind == "document").map(|r| r.id.as_str()).collect();
+        assert_eq!(documents, [board.as_str()]);
+    }
+}
@@ -2,11 +2,12 @@
 //!
 //! `GET  /users/me`   — returns id, email, username, role, language
 //! `PATCH /users/me`  — update language preference (and future fields)
+//! `GET  /users/me/overview` — profile plus counts for the dashboard
 
 use axum::{
-    extract::{Extension, State},
-    http::StatusCode,
-    routing::get,
+    extract::{Extension, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    routing::{get, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize, Serializer};
@@ -18,9 +19,10 @@ use tower_cookies::{
 use uuid::Uuid;
 
 use crate::{
+    auth::{sign_export_token, verify_export_token},
     errors::{AppError, AppResult},
     middleware::auth_guard::AuthUser,
-    models::UserRole,
+    models::{NotificationPreferences, UserRole},
     state::AppState,
 };
 
@@ -28,8 +30,20 @@ pub fn router() -> Router<AppState> {
     Router::new()
     .route("/users/me", get(get_me).patch(update_me).delete(delete_me))
     .route("/users/me/export", get(export_me))
+    .route("/users/me/export/link", post(create_export_link))
+    .route("/users/me/notifications", get(get_notifications).put(update_notifications))
+    .route("/users/me/storage", get(get_storage))
+    .route("/users/me/overview", get(get_overview))
 }
 
+/// Unauthenticated routes. Access is granted by the signed token itself.
+pub fn public_router() -> Router<AppState> {
+    Router::new().route("/exports/{token}", get(download_export))
+}
+
+/// Lifetime of a signed export download link.
+const EXPORT_LINK_MINUTES: i64 = 60;
+
 // ── Response / request types ──────────────────────────────────
 
 #[derive(Serialize)]
@@ -44,6 +58,7 @@ struct MeResponse {
     date_format: String,
     time_format: String,
     week_start: u8,
+    theme: String,
 }
 
 #[derive(Serialize, FromRow)]
@@ -58,11 +73,12 @@ struct MeRow {
     date_format: String,
     time_format: String,
     week_start: i16,
+    theme: String,
 }
 
 #[derive(Deserialize)]
 struct UpdateMeBody {
-    /// Language code to set. Must be one of: `"en"`, `"da"`.
+    /// UI language code to set. Must be one of `Config::supported_languages`.
     language: Option<String>,
     /// IANA timezone, e.g. "Europe/Copenhagen".
     timezone: Option<String>,
@@ -74,6 +90,8 @@ struct UpdateMeBody {
     time_format: Option<String>,
     /// Week start day (1=Mon ... 7=Sun).
     week_start: Option<u8>,
+    /// Visual theme: "light", "dark", "high_contrast" or "system".
+    theme: Option<String>,
 }
 
 #[derive(Serialize, FromRow)]
@@ -109,6 +127,8 @@ struct ExportActivityCardRow {
     title: String,
     description: Option<String>,
     picture_path: Option<String>,
+    #[sqlx(skip)]
+    picture_url: Option<String>,
     start_time: String,
     end_time: Option<String>,
     sort_order: i32,
@@ -121,7 +141,7 @@ struct ExportAssignmentRow {
     id: String,
     schedule_id: String,
     child_id: String,
-    day_of_week: i8,
+    day_of_week: i64,
     #[serde(serialize_with = "serialize_naive_datetime_utc")]
     created_at: chrono::NaiveDateTime,
 }
@@ -141,6 +161,38 @@ struct ExportDeviceRow {
     ip_range: Option<String>,
 }
 
+#[derive(Serialize, FromRow)]
+struct ExportReminderRow {
+    id: String,
+    child_id: String,
+    schedule_id: Option<String>,
+    kind: String,
+    send_at: String,
+    is_enabled: bool,
+    last_sent_on: Option<chrono::NaiveDate>,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Serialize, FromRow)]
+struct ExportDaySelectionRow {
+    child_id: String,
+    selection_date: chrono::NaiveDate,
+    schedule_id: String,
+    device_id: Option<String>,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    updated_at: chrono::NaiveDateTime,
+}
+
+#[derive(Serialize, FromRow)]
+struct ExportDayProgressRow {
+    progress_date: chrono::NaiveDate,
+    current_index: i32,
+    device_id: Option<String>,
+    #[serde(serialize_with = "serialize_naive_datetime_utc")]
+    updated_at: chrono::NaiveDateTime,
+}
+
 fn serialize_naive_datetime_utc<S>(value: &chrono::NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -162,10 +214,6 @@ where
     }
 }
 
-// ── Accepted language codes ────────────────────────────────────
-
-const ALLOWED_LANGUAGES: &[&str] = &["en", "da"];
-
 // ── Handlers ─────────────────────────────────────────────────
 
 /// Return the authenticated user's profile.
@@ -173,18 +221,21 @@ async fn get_me(
     Extension(auth): Extension<AuthUser>,
     State(state): State<AppState>,
 ) -> AppResult<Json<MeResponse>> {
-    let pool = &state.pool;
+    Ok(Json(load_me(&state.pool, &auth.user_id).await?))
+}
+
+async fn load_me(pool: &crate::db::Db, user_id: &str) -> AppResult<MeResponse> {
     let row: MeRow = sqlx::query_as::<_, MeRow>(
-        "SELECT id, email, username, role, language, timezone, locale, date_format, time_format, week_start
+        "SELECT id, email, username, role, language, timezone, locale, date_format, time_format, week_start, theme
          FROM users
          WHERE id = ? AND deleted_at IS NULL",
     )
-    .bind(&auth.user_id)
+    .bind(user_id)
     .fetch_optional(pool)
     .await?
     .ok_or(AppError::NotFound)?;
 
-    Ok(Json(MeResponse {
+    Ok(MeResponse {
         id:       row.id,
         email:    row.email,
         username: row.username,
@@ -195,7 +246,72 @@ async fn get_me(
         date_format: row.date_format,
         time_format: row.time_format,
         week_start: row.week_start as u8,
-    }))
+        theme: row.theme,
+    })
+}
+
+#[derive(Serialize, FromRow)]
+struct OverviewCounts {
+    children: i64,
+    schedules: i64,
+    /// Visual documents, not counting calendar day assignments.
+    documents: i64,
+    saved_pictograms: i64,
+    active_child_devices: i64,
+}
+
+/// Caps that apply to the account; `null` where there is none (admins).
+#[derive(Serialize)]
+struct OverviewLimits {
+    children: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct OverviewResponse {
+    profile: MeResponse,
+    counts: OverviewCounts,
+    limits: OverviewLimits,
+}
+
+/// Profile plus the counts the settings/dashboard screen shows, in two
+/// queries instead of one request per panel.
+async fn get_overview(
+    Extension(auth): Extension<AuthUser>,
+    State(state): State<AppState>,
+) -> AppResult<Json<OverviewResponse>> {
+    if auth.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+    let pool = &state.pool;
+    let profile = load_me(pool, &auth.user_id).await?;
+
+    let counts = sqlx::query_as::<_, OverviewCounts>(&format!(
+        "SELECT
+            (SELECT COUNT(*) FROM child_profiles
+             WHERE parent_id = ? AND deleted_at IS NULL) AS children,
+            (SELECT COUNT(*) FROM visual_support_documents_templates t
+             WHERE t.owner_id = ? AND t.document_type = 'WEEKLY_SCHEDULE' AND {}) AS schedules,
+            (SELECT COUNT(*) FROM visual_support_documents
+             WHERE owner_id = ? AND document_type <> 'WEEKLY_SCHEDULE') AS documents,
+            (SELECT COUNT(*) FROM saved_pictograms WHERE user_id = ?) AS saved_pictograms,
+            (SELECT COUNT(*) FROM child_device_tokens d
+             JOIN child_profiles cp ON cp.id = d.child_id AND cp.deleted_at IS NULL
+             WHERE cp.parent_id = ? AND d.revoked_at IS NULL) AS active_child_devices",
+        super::schedule_sql::regular_schedule_filter()
+    ))
+    .bind(&auth.user_id)
+    .bind(&auth.user_id)
+    .bind(&auth.user_id)
+    .bind(&auth.user_id)
+    .bind(&auth.user_id)
+    .fetch_one(pool)
+    .await?;
+
+    let limits = OverviewLimits {
+        children: (auth.role != UserRole::Admin).then_some(state.config.max_children_per_parent),
+    };
+
+    Ok(Json(OverviewResponse { profile, counts, limits }))
 }
 
 /// Update mutable profile fields for the authenticated user.
@@ -211,13 +327,14 @@ async fn update_me(
     }
 
     if let Some(lang) = body.language {
-        if !ALLOWED_LANGUAGES.contains(&lang.as_str()) {
+        let allowed = &state.config.supported_languages;
+        let Some(lang) = allowed.iter().find(|l| l.eq_ignore_ascii_case(lang.trim())).cloned() else {
             return Err(AppError::BadRequest(format!(
                 "Unsupported language '{}'. Allowed: {}",
                 lang,
-                ALLOWED_LANGUAGES.join(", ")
+                allowed.join(", ")
             )));
-        }
+        };
 
         sqlx::query("UPDATE users SET language = ?, updated_at = NOW() WHERE id = ?")
             .bind(&lang)
@@ -271,9 +388,76 @@ async fn update_me(
             .await?;
     }
 
+    if let Some(theme) = body.theme {
+        let normalized = normalize_theme(Some(&theme))?;
+        sqlx::query("UPDATE users SET theme = ?, updated_at = NOW() WHERE id = ?")
+            .bind(&normalized)
+            .bind(&auth.user_id)
+            .execute(&state.pool)
+            .await?;
+    }
+
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn load_notification_preferences(
+    pool: &crate::db::Db,
+    user_id: &str,
+) -> AppResult<NotificationPreferences> {
+    let raw: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT CAST(notification_preferences AS CHAR)
+         FROM users
+         WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let raw = raw.ok_or(AppError::NotFound)?;
+    Ok(NotificationPreferences::from_json(raw.as_deref()))
+}
+
+/// Return the caller's optional-email preferences.
+async fn get_notifications(
+    Extension(auth): Extension<AuthUser>,
+    State(state): State<AppState>,
+) -> AppResult<Json<NotificationPreferences>> {
+    if auth.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let prefs = load_notification_preferences(&state.pool, &auth.user_id).await?;
+    Ok(Json(prefs))
+}
+
+/// Partially update optional-email preferences. Unknown keys are rejected.
+async fn update_notifications(
+    Extension(auth): Extension<AuthUser>,
+    State(state): State<AppState>,
+    Json(body): Json<serde_json::Value>,
+) -> AppResult<Json<NotificationPreferences>> {
+    if auth.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let patch = body
+        .as_object()
+        .ok_or_else(|| AppError::BadRequest("Expected a JSON object of preferences".into()))?;
+
+    let mut prefs = load_notification_preferences(&state.pool, &auth.user_id).await?;
+    prefs.apply_patch(patch).map_err(AppError::BadRequest)?;
+
+    let json = serde_json::to_string(&prefs)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to serialize preferences: {e}")))?;
+    sqlx::query("UPDATE users SET notification_preferences = ?, updated_at = NOW() WHERE id = ?")
+        .bind(json)
+        .bind(&auth.user_id)
+        .execute(&state.pool)
+        .await?;
+
+    Ok(Json(prefs))
+}
+
 /// Export parent-owned data as JSON for GDPR portability.
 async fn export_me(
     Extension(auth): Extension<AuthUser>,
@@ -285,13 +469,131 @@ async fn export_me(
 
     let pool = &state.pool;
     let dsr_request_id = Uuid::new_v4().to_string();
+    let export = build_user_export(pool, &state.config, &auth.user_id).await?;
+
+    sqlx::query(
+        "INSERT INTO dsr_audit_logs
+            (id, request_id, user_id, action, status, requested_at, completed_at, actor_user_id, metadata)
+         VALUES (?, ?, ?, 'export', 'completed', NOW(), NOW(), ?, JSON_OBJECT('endpoint', '/users/me/export'))",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&dsr_request_id)
+    .bind(&auth.user_id)
+    .bind(&auth.user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(Json(export))
+}
+
+#[derive(Serialize)]
+struct ExportLinkResponse {
+    token: String,
+    url: String,
+    expires_at: String,
+}
+
+/// Mint a single-use, signed download link for the caller's data export.
+/// The link works without a session so it can be delivered by email.
+async fn create_export_link(
+    Extension(auth): Extension<AuthUser>,
+    State(state): State<AppState>,
+) -> AppResult<(StatusCode, Json<ExportLinkResponse>)> {
+    if auth.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let expires_at = chrono::Utc::now() + chrono::Duration::minutes(EXPORT_LINK_MINUTES);
+
+    sqlx::query(
+        "INSERT INTO export_download_tokens (id, user_id, expires_at) VALUES (?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&auth.user_id)
+    .bind(expires_at.naive_utc())
+    .execute(&state.pool)
+    .await?;
+
+    let token = sign_export_token(
+        &state.config.session_secret,
+        &id,
+        &auth.user_id,
+        expires_at.timestamp(),
+    );
+    let url = format!(
+        "{}/api/v1/exports/{}",
+        state.config.app_base_url.trim_end_matches('/'),
+        token
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ExportLinkResponse { token, url, expires_at: expires_at.to_rfc3339() }),
+    ))
+}
+
+/// `GET /exports/{token}` — public, single-use export download.
+async fn download_export(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> AppResult<(HeaderMap, Json<serde_json::Value>)> {
+    let claims = verify_export_token(&state.config.session_secret, &token)?;
+    let pool = &state.pool;
+
+    // Consume the token atomically so concurrent requests cannot both succeed.
+    let consumed = sqlx::query(
+        "UPDATE export_download_tokens
+         SET used_at = NOW()
+         WHERE id = ? AND user_id = ? AND used_at IS NULL AND expires_at > UTC_TIMESTAMP()",
+    )
+    .bind(&claims.id)
+    .bind(&claims.user_id)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if consumed == 0 {
+        return Err(AppError::Unauthorized);
+    }
 
+    let export = build_user_export(pool, &state.config, &claims.user_id).await?;
+
+    sqlx::query(
+        "INSERT INTO dsr_audit_logs
+            (id, request_id, user_id, action, status, requested_at, completed_at, actor_user_id, metadata)
+         VALUES (?, ?, ?, 'export', 'completed', NOW(), NOW(), NULL,
+                 JSON_OBJECT('endpoint', '/exports/{token}', 'download_token_id', ?))",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(Uuid::new_v4().to_string())
+    .bind(&claims.user_id)
+    .bind(&claims.id)
+    .execute(pool)
+    .await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"carlscalendar-export.json\""),
+    );
+
+    Ok((headers, Json(export)))
+}
+
+/// Collect everything stored about a parent account for a DSR export.
+async fn build_user_export(
+    pool: &crate::db::Db,
+    config: &crate::config::Config,
+    user_id: &str,
+) -> AppResult<serde_json::Value> {
     let user: MeRow = sqlx::query_as::<_, MeRow>(
-        "SELECT id, email, username, role, language, timezone, locale, date_format, time_format, week_start
+        "SELECT id, email, username, role, language, timezone, locale, date_format, time_format, week_start, theme
          FROM users
          WHERE id = ? AND deleted_at IS NULL",
     )
-    .bind(&auth.user_id)
+    .bind(user_id)
     .fetch_optional(pool)
     .await?
     .ok_or(AppError::NotFound)?;
@@ -302,11 +604,97 @@ async fn export_me(
          WHERE parent_id = ?
          ORDER BY created_at",
     )
-    .bind(&auth.user_id)
+    .bind(user_id)
     .fetch_all(pool)
     .await?;
 
-    let schedules: Vec<ExportScheduleRow> = sqlx::query_as::<_, ExportScheduleRow>(
+    let schedules = export_schedules(pool, "t.owner_id = ?", user_id).await?;
+    let schedule_activity_cards = export_activity_cards(pool, config, "t.owner_id = ?", user_id).await?;
+    let assignments = export_assignments(pool, "cp.parent_id = ?", user_id).await?;
+    let devices = export_devices(pool, "parent_user_id = ?", user_id).await?;
+    let reminders = export_reminders(
+        pool,
+        "child_id IN (SELECT id FROM child_profiles WHERE parent_id = ?)",
+        user_id,
+    )
+    .await?;
+    let day_selections = export_day_selections(
+        pool,
+        "child_id IN (SELECT id FROM child_profiles WHERE parent_id = ?)",
+        user_id,
+    )
+    .await?;
+
+    Ok(serde_json::json!({
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "user": user,
+        "children": children,
+        "schedules": schedules,
+        "schedule_activity_cards": schedule_activity_cards,
+        "assignments": assignments,
+        "child_devices": devices,
+        "reminders": reminders,
+        "day_selections": day_selections
+    }))
+}
+
+/// Schedules assigned to a child, as a `t.*` filter bound to the child id.
+const CHILD_SCHEDULES_FILTER: &str = "t.id IN (
+    SELECT d.template_id FROM visual_support_documents d
+    WHERE d.child_id = ? AND d.document_type = 'WEEKLY_SCHEDULE' AND d.template_id IS NOT NULL)";
+
+/// Collect what is stored about one child for a DSR scoped to that child:
+/// the profile, the schedules assigned to them (with their cards), the
+/// assignments, day progress and schedule picks, paired devices and
+/// reminders. Access is checked by the caller.
+pub(super) async fn build_child_export(
+    pool: &crate::db::Db,
+    config: &crate::config::Config,
+    child_id: &str,
+) -> AppResult<serde_json::Value> {
+    let child: ExportChildRow = sqlx::query_as::<_, ExportChildRow>(
+        "SELECT id, parent_id, display_name, avatar_path, created_at
+         FROM child_profiles
+         WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(child_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let schedules = export_schedules(pool, CHILD_SCHEDULES_FILTER, child_id).await?;
+    let schedule_activity_cards = export_activity_cards(pool, config, CHILD_SCHEDULES_FILTER, child_id).await?;
+    let assignments = export_assignments(pool, "d.child_id = ?", child_id).await?;
+    let devices = export_devices(pool, "child_id = ?", child_id).await?;
+    let reminders = export_reminders(pool, "child_id = ?", child_id).await?;
+    let day_selections = export_day_selections(pool, "child_id = ?", child_id).await?;
+
+    let day_progress: Vec<ExportDayProgressRow> = sqlx::query_as::<_, ExportDayProgressRow>(
+        "SELECT progress_date, current_index, device_id, updated_at
+         FROM child_day_progress
+         WHERE child_id = ?
+         ORDER BY progress_date",
+    )
+    .bind(child_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(serde_json::json!({
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "child": child,
+        "schedules": schedules,
+        "schedule_activity_cards": schedule_activity_cards,
+        "assignments": assignments,
+        "day_progress": day_progress,
+        "day_selections": day_selections,
+        "child_devices": devices,
+        "reminders": reminders
+    }))
+}
+
+/// Weekly schedules matching `filter` (on `t`, one placeholder bound to `value`).
+async fn export_schedules(pool: &crate::db::Db, filter: &str, value: &str) -> AppResult<Vec<ExportScheduleRow>> {
+    let rows = sqlx::query_as::<_, ExportScheduleRow>(&format!(
                 "SELECT
                         t.id,
                         t.owner_id,
@@ -318,15 +706,24 @@ async fn export_me(
                         t.created_at,
                         t.updated_at
                  FROM visual_support_documents_templates t
-                 WHERE t.owner_id = ?
+                 WHERE {filter}
                      AND t.document_type = 'WEEKLY_SCHEDULE'
                  ORDER BY t.created_at",
-    )
-    .bind(&auth.user_id)
+    ))
+    .bind(value)
     .fetch_all(pool)
     .await?;
+    Ok(rows)
+}
 
-    let schedule_activity_cards: Vec<ExportActivityCardRow> = sqlx::query_as::<_, ExportActivityCardRow>(
+/// Activity cards of the schedules matching `filter`, as in [`export_schedules`].
+async fn export_activity_cards(
+    pool: &crate::db::Db,
+    config: &crate::config::Config,
+    filter: &str,
+    value: &str,
+) -> AppResult<Vec<ExportActivityCardRow>> {
+    let mut cards: Vec<ExportActivityCardRow> = sqlx::query_as::<_, ExportActivityCardRow>(&format!(
         "SELECT
             vta.id,
                         t.id AS schedule_id,
@@ -334,22 +731,31 @@ async fn export_me(
             COALESCE(NULLIF(vta.text_label, ''), vsa.label_text) AS title,
             vta.optional_notes AS description,
             CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.picture_path')), ''), vsa.local_image_path) AS CHAR(500)) AS picture_path,
-            CAST(COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.start_time')), ''), '08:00') AS CHAR(5)) AS start_time,
+            {start_time} AS start_time,
             CAST(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(vta.metadata_json, '$.end_time')), '') AS CHAR(5)) AS end_time,
             vta.activity_order AS sort_order,
             vta.created_at
          FROM visual_support_template_activities vta
          LEFT JOIN visual_support_activity_library vsa ON vsa.id = vta.activity_card_id
                  JOIN visual_support_documents_templates t ON t.id = vta.template_id
-                 WHERE t.owner_id = ?
+                 WHERE {filter}
                      AND t.document_type = 'WEEKLY_SCHEDULE'
                  ORDER BY t.id, vta.activity_order",
-    )
-    .bind(&auth.user_id)
+        start_time = super::schedule_sql::start_time_column(&config.default_activity_start_time),
+    ))
+    .bind(value)
     .fetch_all(pool)
     .await?;
 
-    let assignments: Vec<ExportAssignmentRow> = sqlx::query_as::<_, ExportAssignmentRow>(
+    for card in &mut cards {
+        card.picture_url = crate::config::resolve_public_asset(config, card.picture_path.as_deref());
+    }
+    Ok(cards)
+}
+
+/// Weekday assignments matching `filter` (on `d` or `cp`).
+async fn export_assignments(pool: &crate::db::Db, filter: &str, value: &str) -> AppResult<Vec<ExportAssignmentRow>> {
+    let rows = sqlx::query_as::<_, ExportAssignmentRow>(&format!(
                 "SELECT
                         d.id,
                         d.template_id AS schedule_id,
@@ -358,46 +764,62 @@ async fn export_me(
                         d.created_at
                  FROM visual_support_documents d
                  JOIN child_profiles cp ON cp.id = d.child_id
-                 WHERE cp.parent_id = ?
+                 WHERE {filter}
                      AND d.document_type = 'WEEKLY_SCHEDULE'
                      AND d.template_id IS NOT NULL
                  ORDER BY d.child_id, day_of_week",
-    )
-    .bind(&auth.user_id)
+    ))
+    .bind(value)
     .fetch_all(pool)
     .await?;
+    Ok(rows)
+}
 
-    let devices: Vec<ExportDeviceRow> = sqlx::query_as::<_, ExportDeviceRow>(
+/// Paired child devices matching `filter`, newest first.
+async fn export_devices(pool: &crate::db::Db, filter: &str, value: &str) -> AppResult<Vec<ExportDeviceRow>> {
+    let rows = sqlx::query_as::<_, ExportDeviceRow>(&format!(
         "SELECT id, parent_user_id, child_id, created_at, last_used_at, revoked_at, user_agent_hash, ip_range
          FROM child_device_tokens
-         WHERE parent_user_id = ?
+         WHERE {filter}
          ORDER BY created_at DESC",
-    )
-    .bind(&auth.user_id)
+    ))
+    .bind(value)
     .fetch_all(pool)
     .await?;
+    Ok(rows)
+}
 
-    sqlx::query(
-        "INSERT INTO dsr_audit_logs
-            (id, request_id, user_id, action, status, requested_at, completed_at, actor_user_id, metadata)
-         VALUES (?, ?, ?, 'export', 'completed', NOW(), NOW(), ?, JSON_OBJECT('endpoint', '/users/me/export'))",
-    )
-    .bind(Uuid::new_v4().to_string())
-    .bind(&dsr_request_id)
-    .bind(&auth.user_id)
-    .bind(&auth.user_id)
-    .execute(pool)
+/// Reminder settings matching `filter` (on `child_reminders`).
+async fn export_reminders(pool: &crate::db::Db, filter: &str, value: &str) -> AppResult<Vec<ExportReminderRow>> {
+    let rows = sqlx::query_as::<_, ExportReminderRow>(&format!(
+        "SELECT id, child_id, schedule_id, CAST(kind AS CHAR) AS kind, send_at, is_enabled, last_sent_on, created_at
+         FROM child_reminders
+         WHERE {filter}
+         ORDER BY child_id, created_at",
+    ))
+    .bind(value)
+    .fetch_all(pool)
     .await?;
+    Ok(rows)
+}
 
-    Ok(Json(serde_json::json!({
-        "exported_at": chrono::Utc::now().to_rfc3339(),
-        "user": user,
-        "children": children,
-        "schedules": schedules,
-        "schedule_activity_cards": schedule_activity_cards,
-        "assignments": assignments,
-        "child_devices": devices
-    })))
+/// Schedules picked on a child's device per date, matching `filter` (on
+/// `child_day_selection`).
+async fn export_day_selections(
+    pool: &crate::db::Db,
+    filter: &str,
+    value: &str,
+) -> AppResult<Vec<ExportDaySelectionRow>> {
+    let rows = sqlx::query_as::<_, ExportDaySelectionRow>(&format!(
+        "SELECT child_id, selection_date, schedule_id, device_id, updated_at
+         FROM child_day_selection
+         WHERE {filter}
+         ORDER BY child_id, selection_date",
+    ))
+    .bind(value)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
 }
 
 fn normalize_timezone(input: Option<&str>) -> AppResult<String> {
@@ -458,6 +880,16 @@ fn normalize_week_start(input: Option<u8>) -> AppResult<u8> {
     Ok(week_start)
 }
 
+fn normalize_theme(input: Option<&str>) -> AppResult<String> {
+    let raw = input.unwrap_or("system").trim().to_ascii_lowercase().replace('-', "_");
+    match raw.as_str() {
+        "light" | "dark" | "high_contrast" | "system" => Ok(raw),
+        _ => Err(AppError::BadRequest(
+            "Invalid theme. Allowed: light, dark, high_contrast, system".into(),
+        )),
+    }
+}
+
 /// Delete current parent account and associated data.
 async fn delete_me(
     Extension(auth): Extension<AuthUser>,
@@ -509,12 +941,12 @@ async fn delete_me(
     .execute(pool)
     .await?;
 
-    let is_prod = state.config.app_env != "development";
+    let secure = state.config.secure_cookies();
 
     let clear_parent = Cookie::build(("session", ""))
         .http_only(true)
         .same_site(tower_cookies::cookie::SameSite::Strict)
-        .secure(is_prod)
+        .secure(secure)
         .path("/")
         .max_age(CookieDuration::ZERO)
         .build();
@@ -523,7 +955,7 @@ async fn delete_me(
     let clear_child = Cookie::build(("child_session", ""))
         .http_only(true)
         .same_site(tower_cookies::cookie::SameSite::Strict)
-        .secure(is_prod)
+        .secure(secure)
         .path("/")
         .max_age(CookieDuration::ZERO)
         .build();
@@ -531,3 +963,74 @@ async fn delete_me(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// Upload storage used by the caller against `USER_STORAGE_QUOTA_BYTES`.
+async fn get_storage(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<crate::services::storage::StorageUsageDto>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+    let usage = crate::services::storage::get_usage(&state.pool, &state.config, &user.user_id).await?;
+    Ok(Json(usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::Db, test_support};
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn exports_include_child_reminders(pool: Db) {
+        let config = crate::config::Config::for_tests();
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        sqlx::query(
+            "INSERT INTO child_reminders (id, child_id, kind, send_at)
+             VALUES (?, ?, 'daily_summary', '06:45')",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&child)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for export in [
+            build_user_export(&pool, &config, &parent.user_id).await.unwrap(),
+            build_child_export(&pool, &config, &child).await.unwrap(),
+        ] {
+            let reminders = export["reminders"].as_array().unwrap();
+            assert_eq!(reminders.len(), 1);
+            assert_eq!(reminders[0]["child_id"], child.as_str());
+            assert_eq!(reminders[0]["kind"], "daily_summary");
+            assert_eq!(reminders[0]["send_at"], "06:45");
+        }
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn exports_include_day_selections(pool: Db) {
+        let config = crate::config::Config::for_tests();
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let schedule_id = test_support::schedule(&pool, &parent, "Week", serde_json::json!({})).await;
+        sqlx::query("INSERT INTO child_day_selection (child_id, selection_date, schedule_id) VALUES (?, '2026-03-02', ?)")
+            .bind(&child)
+            .bind(&schedule_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for export in [
+            build_user_export(&pool, &config, &parent.user_id).await.unwrap(),
+            build_child_export(&pool, &config, &child).await.unwrap(),
+        ] {
+            let selections = export["day_selections"].as_array().unwrap();
+            assert_eq!(selections.len(), 1);
+            assert_eq!(selections[0]["selection_date"], "2026-03-02");
+            assert_eq!(selections[0]["schedule_id"], schedule_id.as_str());
+        }
+    }
+}
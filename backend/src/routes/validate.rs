@@ -0,0 +1,53 @@
+//! Input checks shared across route modules.
+
+use crate::errors::{AppError, AppResult};
+
+/// Longest accepted title or name, in characters.
+pub const MAX_TITLE_CHARS: usize = 120;
+
+/// Trim a user-facing title/name and require it to be non-empty and at most
+/// [`MAX_TITLE_CHARS`] characters. `field` names the input in the error.
+pub fn title(field: &str, value: &str) -> AppResult<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::BadRequest(format!("{field} must not be empty")));
+    }
+    if trimmed.chars().count() > MAX_TITLE_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "{field} must be at most {MAX_TITLE_CHARS} characters"
+        )));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// [`title`] for optional update fields.
+pub fn optional_title(field: &str, value: Option<&str>) -> AppResult<Option<String>> {
+    value.map(|v| title(field, v)).transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn titles_are_trimmed_and_bounded_in_characters() {
+        assert_eq!(title("name", "  Morning  ").unwrap(), "Morning");
+
+        let longest = "é".repeat(MAX_TITLE_CHARS);
+        assert_eq!(title("name", &format!(" {longest} ")).unwrap(), longest);
+        let err = title("name", &format!("{longest}a")).unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(msg) if msg == "name must be at most 120 characters"));
+
+        for blank in ["", "   ", "\t\n"] {
+            let err = title("title", blank).unwrap_err();
+            assert!(matches!(err, AppError::BadRequest(msg) if msg == "title must not be empty"));
+        }
+    }
+
+    #[test]
+    fn optional_titles_pass_none_through() {
+        assert_eq!(optional_title("name", None).unwrap(), None);
+        assert_eq!(optional_title("name", Some(" x ")).unwrap().as_deref(), Some("x"));
+        assert!(optional_title("name", Some(" ")).is_err());
+    }
+}
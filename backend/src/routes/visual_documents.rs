@@ -1,29 +1,37 @@
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     routing::{get, post, put},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::validate;
 use crate::{
+    config::Config,
     errors::{AppError, AppResult},
     middleware::auth_guard::AuthUser,
     models::UserRole,
+    services::idempotency,
     state::AppState,
 };
 
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/visual-documents/templates", get(list_templates).post(create_template))
+        .route("/visual-documents/scenario-types", get(list_scenario_types))
         .route("/visual-documents/templates/{id}", put(update_template).delete(delete_template))
         .route("/visual-documents/templates/{id}/preview", get(preview_template_document))
         .route("/visual-documents/templates/{id}/copy", post(copy_template_to_document))
         .route("/visual-documents/activity-cards", get(list_activity_cards).post(create_activity_card))
         .route("/visual-documents/activity-cards/{id}", put(update_activity_card).delete(delete_activity_card))
         .route("/visual-documents", get(list_documents).post(create_document))
-        .route("/visual-documents/{id}", get(get_document).put(update_document).delete(delete_document))
+        .route("/visual-documents/import", post(import_document))
+        .route("/visual-documents/{id}", get(get_document).put(update_document).patch(patch_document).delete(delete_document))
+        .route("/visual-documents/{id}/reset-to-template", post(reset_document_to_template))
+        .route("/visual-documents/{id}/slots/{index}", put(update_document_slot))
+        .route("/visual-documents/{id}/export.json", get(export_document))
 }
 
 const ALLOWED_DOCUMENT_TYPES: &[&str] = &[
@@ -36,6 +44,22 @@ const ALLOWED_DOCUMENT_TYPES: &[&str] = &[
     "REWARD_TRACKER",
 ];
 
+/// Template `scenario_type` values. The first entry is the default; the
+/// grouped ones are what the seeded system templates use.
+const ALLOWED_SCENARIO_TYPES: &[&str] = &[
+    "CUSTOM",
+    "MORNING",
+    "BEDTIME",
+    "MEALTIME",
+    "TRANSITION",
+    "THERAPY",
+    "DAILY_LIFE",
+    "SCHOOL_ROUTINE",
+    "BEHAVIOR_SUPPORT",
+    "SOCIAL_EMOTIONAL",
+    "COMMUNICATION",
+];
+
 #[derive(sqlx::FromRow)]
 struct TemplateRow {
     id: String,
@@ -47,6 +71,8 @@ struct TemplateRow {
     language: String,
     is_system: bool,
     metadata_json: Vec<u8>,
+    created_by: Option<String>,
+    updated_by: Option<String>,
     created_at: chrono::NaiveDateTime,
     updated_at: chrono::NaiveDateTime,
 }
@@ -71,6 +97,7 @@ struct DocumentRow {
     layout_spec_json: String,
     content_json: String,
     version: i32,
+    updated_by: Option<String>,
     created_at: chrono::NaiveDateTime,
     updated_at: chrono::NaiveDateTime,
 }
@@ -101,6 +128,9 @@ struct TemplateDto {
     locale: String,
     is_system: bool,
     layout_spec: serde_json::Value,
+    /// User who created / last changed the template; null for older rows.
+    created_by: Option<String>,
+    updated_by: Option<String>,
     created_at: String,
     updated_at: String,
 }
@@ -117,10 +147,41 @@ struct DocumentDto {
     layout_spec: serde_json::Value,
     content: serde_json::Value,
     version: i32,
+    /// User who last changed the document; null for older rows.
+    updated_by: Option<String>,
     created_at: String,
     updated_at: String,
 }
 
+/// `format` marker of single-document backups.
+const DOCUMENT_EXPORT_FORMAT: &str = "carlscalendar.visual-document";
+/// Bumped whenever the shape of [`ExportedDocument`] changes incompatibly.
+const DOCUMENT_EXPORT_VERSION: u32 = 1;
+
+/// Versioned envelope of `GET /visual-documents/{id}/export.json`, accepted
+/// back by `POST /visual-documents/import`.
+#[derive(Serialize, Deserialize)]
+struct DocumentExport {
+    format: String,
+    version: u32,
+    /// Informational; ignored on import.
+    #[serde(default)]
+    exported_at: Option<String>,
+    document: ExportedDocument,
+}
+
+/// The editable definition of a document. Owner, child and source template
+/// are left out: they are ids that need not exist where the backup is
+/// restored.
+#[derive(Serialize, Deserialize)]
+struct ExportedDocument {
+    title: String,
+    document_type: String,
+    locale: String,
+    layout_spec: serde_json::Value,
+    content: serde_json::Value,
+}
+
 #[derive(Serialize)]
 struct TemplatePreviewDto {
     template_id: String,
@@ -158,6 +219,8 @@ struct ListDocumentsQuery {
     #[serde(rename = "type")]
     document_type: Option<String>,
     child_id: Option<String>,
+    /// Only documents created from this template.
+    template_id: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -191,7 +254,7 @@ struct CopyTemplateBody {
     child_id: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 struct CreateDocumentBody {
     title: String,
     document_type: String,
@@ -212,6 +275,24 @@ struct UpdateDocumentBody {
     expected_version: Option<i32>,
 }
 
+/// `PATCH` body: `content` is an RFC 7396 merge patch applied to the stored
+/// content; the layout is never changed here.
+#[derive(Deserialize)]
+struct PatchDocumentBody {
+    title: Option<String>,
+    locale: Option<String>,
+    child_id: Option<String>,
+    content: Option<serde_json::Value>,
+    expected_version: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct UpdateSlotBody {
+    /// The new slot; `null` empties it.
+    slot: serde_json::Value,
+    expected_version: Option<i32>,
+}
+
 #[derive(Deserialize)]
 struct CreateActivityCardBody {
     label: String,
@@ -232,6 +313,19 @@ fn is_valid_document_type(value: &str) -> bool {
     ALLOWED_DOCUMENT_TYPES.contains(&value)
 }
 
+/// Uppercase and check `scenario_type` against [`ALLOWED_SCENARIO_TYPES`].
+fn normalize_scenario_type(value: &str) -> AppResult<String> {
+    let normalized = value.trim().to_ascii_uppercase();
+    if ALLOWED_SCENARIO_TYPES.contains(&normalized.as_str()) {
+        Ok(normalized)
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Invalid scenario_type. Allowed: {}",
+            ALLOWED_SCENARIO_TYPES.join(", ")
+        )))
+    }
+}
+
 fn parse_json_safe(raw: &str) -> serde_json::Value {
     serde_json::from_str(raw).unwrap_or_else(|_| serde_json::json!({}))
 }
@@ -257,6 +351,8 @@ fn to_template_dto(row: TemplateRow) -> TemplateDto {
         locale: row.language,
         is_system: row.is_system,
         layout_spec: extract_layout_spec(&metadata),
+        created_by: row.created_by,
+        updated_by: row.updated_by,
         created_at: fmt_dt(row.created_at),
         updated_at: fmt_dt(row.updated_at),
     }
@@ -274,6 +370,7 @@ fn to_document_dto(row: DocumentRow) -> DocumentDto {
         layout_spec: parse_json_safe(&row.layout_spec_json),
         content: parse_json_safe(&row.content_json),
         version: row.version,
+        updated_by: row.updated_by,
         created_at: fmt_dt(row.created_at),
         updated_at: fmt_dt(row.updated_at),
     }
@@ -324,16 +421,30 @@ fn metadata_with_layout(layout_spec: &serde_json::Value) -> serde_json::Value {
     serde_json::json!({ "layout": layout_spec })
 }
 
-fn content_slot_len(content: &serde_json::Value) -> Option<usize> {
+fn content_slots(content: &serde_json::Value) -> Option<&Vec<serde_json::Value>> {
     if let Some(arr) = content.as_array() {
-        return Some(arr.len());
+        return Some(arr);
     }
 
     content
         .as_object()
         .and_then(|m| m.get("slots"))
         .and_then(|v| v.as_array())
-        .map(|arr| arr.len())
+}
+
+/// Mutable slots array of `content`, creating `{"slots": []}` when the
+/// content has none yet.
+fn content_slots_mut(content: &mut serde_json::Value) -> &mut Vec<serde_json::Value> {
+    if !content.is_array() && !content.get("slots").is_some_and(|v| v.is_array()) {
+        match content.as_object_mut() {
+            Some(map) => {
+                map.insert("slots".into(), serde_json::json!([]));
+            }
+            None => *content = serde_json::json!({ "slots": [] }),
+        }
+    }
+    let slots = if content.is_array() { content } else { &mut content["slots"] };
+    slots.as_array_mut().expect("slots normalized to an array above")
 }
 
 fn validate_layout_for_type(document_type: &str, layout_spec: &serde_json::Value) -> AppResult<()> {
@@ -379,20 +490,91 @@ fn validate_layout_for_type(document_type: &str, layout_spec: &serde_json::Value
     Ok(())
 }
 
+fn json_depth(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Array(items) => 1 + items.iter().map(json_depth).max().unwrap_or(0),
+        serde_json::Value::Object(map) => 1 + map.values().map(json_depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Serialize a client-supplied JSON field for storage, rejecting values nested
+/// deeper than `DOCUMENT_JSON_MAX_DEPTH` or larger than `DOCUMENT_JSON_MAX_BYTES`.
+fn bounded_json_string(field: &str, value: &serde_json::Value, config: &Config) -> AppResult<String> {
+    if json_depth(value) > config.document_json_max_depth {
+        return Err(AppError::BadRequest(format!(
+            "{field} is nested too deeply (max depth {})",
+            config.document_json_max_depth
+        )));
+    }
+    let json = serde_json::to_string(value)
+        .map_err(|_| AppError::BadRequest(format!("Invalid {field} JSON")))?;
+    if json.len() > config.document_json_max_bytes {
+        return Err(AppError::BadRequest(format!(
+            "{field} is too large (max {} bytes)",
+            config.document_json_max_bytes
+        )));
+    }
+    Ok(json)
+}
+
+/// RFC 7396 JSON merge patch: objects merge recursively, `null` removes a key,
+/// anything else (arrays included) replaces the target value.
+fn json_merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch_map) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    if let serde_json::Value::Object(target_map) = target {
+        for (key, value) in patch_map {
+            if value.is_null() {
+                target_map.remove(key);
+            } else {
+                json_merge_patch(target_map.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Slot count must match the layout. Empty (`null`) slots are fine on their
+/// own (drafts and fresh template copies have them); on a mismatch every
+/// missing or extra slot is listed in the error details (1-based).
 fn validate_content_matches_layout(content: &serde_json::Value, layout_spec: &serde_json::Value) -> AppResult<()> {
     let Some(expected_slots) = extract_slot_count(layout_spec) else {
         return Ok(());
     };
+    let expected = expected_slots as usize;
+
+    let Some(slots) = content_slots(content) else {
+        return Ok(());
+    };
+    if slots.len() == expected {
+        return Ok(());
+    }
 
-    if let Some(actual_len) = content_slot_len(content) {
-        if actual_len != expected_slots as usize {
-            return Err(AppError::BadRequest(format!(
-                "content slots ({actual_len}) does not match layout slotCount ({expected_slots})"
-            )));
+    let mut details = vec![format!("content has {} slots, layout expects {expected}", slots.len())];
+    for n in slots.len() + 1..=expected {
+        details.push(format!("slot {n} is missing"));
+    }
+    for (i, slot) in slots.iter().enumerate().skip(expected) {
+        let n = i + 1;
+        if slot.is_null() {
+            details.push(format!("slot {n} is extra (empty)"));
+        } else {
+            details.push(format!("slot {n} is extra"));
         }
     }
 
-    Ok(())
+    Err(AppError::Validation {
+        message: format!(
+            "content slots ({}) does not match layout slotCount ({expected_slots})",
+            slots.len()
+        ),
+        details,
+    })
 }
 
 async fn assert_child_access(pool: &crate::db::Db, child_id: &Option<String>, user: &AuthUser) -> AppResult<()> {
@@ -405,12 +587,12 @@ async fn assert_child_access(pool: &crate::db::Db, child_id: &Option<String>, us
     }
 
     let exists: bool = if user.role == UserRole::Admin {
-        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ?)")
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND deleted_at IS NULL)")
             .bind(child_id)
             .fetch_one(pool)
             .await?
     } else {
-        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ?)")
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM child_profiles WHERE id = ? AND parent_id = ? AND deleted_at IS NULL)")
             .bind(child_id)
             .bind(&user.user_id)
             .fetch_one(pool)
@@ -418,7 +600,7 @@ async fn assert_child_access(pool: &crate::db::Db, child_id: &Option<String>, us
     };
 
     if !exists {
-        return Err(AppError::Forbidden);
+        return Err(AppError::NotOwned);
     }
 
     Ok(())
@@ -426,7 +608,7 @@ async fn assert_child_access(pool: &crate::db::Db, child_id: &Option<String>, us
 
 async fn get_document_row_for_user(pool: &crate::db::Db, id: &str, user: &AuthUser) -> AppResult<DocumentRow> {
     let row: Option<DocumentRow> = sqlx::query_as::<_, DocumentRow>(
-        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
+        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by, created_at, updated_at
          FROM visual_support_documents
          WHERE id = ?",
     )
@@ -436,7 +618,7 @@ async fn get_document_row_for_user(pool: &crate::db::Db, id: &str, user: &AuthUs
 
     let row = row.ok_or(AppError::NotFound)?;
     if user.role != UserRole::Admin && row.owner_id != user.user_id {
-        return Err(AppError::Forbidden);
+        return Err(AppError::NotOwned);
     }
     Ok(row)
 }
@@ -462,7 +644,7 @@ async fn list_templates(
         match (&q.document_type, &q.locale) {
             (Some(t), Some(locale)) => {
                 sqlx::query_as::<_, TemplateRow>(
-                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
                      FROM visual_support_documents_templates
                      WHERE document_type = ? AND language = ?
                      ORDER BY is_system DESC, name",
@@ -474,7 +656,7 @@ async fn list_templates(
             }
             (Some(t), None) => {
                 sqlx::query_as::<_, TemplateRow>(
-                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
                      FROM visual_support_documents_templates
                      WHERE document_type = ?
                      ORDER BY is_system DESC, name",
@@ -485,7 +667,7 @@ async fn list_templates(
             }
             (None, Some(locale)) => {
                 sqlx::query_as::<_, TemplateRow>(
-                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
                      FROM visual_support_documents_templates
                      WHERE language = ?
                      ORDER BY is_system DESC, name",
@@ -496,7 +678,7 @@ async fn list_templates(
             }
             (None, None) => {
                 sqlx::query_as::<_, TemplateRow>(
-                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
                      FROM visual_support_documents_templates
                      ORDER BY is_system DESC, name",
                 )
@@ -508,7 +690,7 @@ async fn list_templates(
         match (&q.document_type, &q.locale) {
             (Some(t), Some(locale)) => {
                 sqlx::query_as::<_, TemplateRow>(
-                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
                      FROM visual_support_documents_templates
                      WHERE (is_system = 1 OR owner_id = ?) AND document_type = ? AND language = ?
                      ORDER BY is_system DESC, name",
@@ -521,7 +703,7 @@ async fn list_templates(
             }
             (Some(t), None) => {
                 sqlx::query_as::<_, TemplateRow>(
-                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
                      FROM visual_support_documents_templates
                      WHERE (is_system = 1 OR owner_id = ?) AND document_type = ?
                      ORDER BY is_system DESC, name",
@@ -533,7 +715,7 @@ async fn list_templates(
             }
             (None, Some(locale)) => {
                 sqlx::query_as::<_, TemplateRow>(
-                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
                      FROM visual_support_documents_templates
                      WHERE (is_system = 1 OR owner_id = ?) AND language = ?
                      ORDER BY is_system DESC, name",
@@ -545,7 +727,7 @@ async fn list_templates(
             }
             (None, None) => {
                 sqlx::query_as::<_, TemplateRow>(
-                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+                    "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
                      FROM visual_support_documents_templates
                      WHERE is_system = 1 OR owner_id = ?
                      ORDER BY is_system DESC, name",
@@ -560,6 +742,10 @@ async fn list_templates(
     Ok(Json(rows.into_iter().map(to_template_dto).collect()))
 }
 
+async fn list_scenario_types() -> Json<&'static [&'static str]> {
+    Json(ALLOWED_SCENARIO_TYPES)
+}
+
 async fn create_template(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -568,6 +754,7 @@ async fn create_template(
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
+    let name = validate::title("name", &body.name)?;
 
     if !is_valid_document_type(&body.document_type) {
         return Err(AppError::BadRequest("Invalid document type".into()));
@@ -591,30 +778,37 @@ async fn create_template(
     };
 
     let locale = body.locale.unwrap_or_else(|| "en".to_string());
-    let scenario_type = body.scenario_type.unwrap_or_else(|| "CUSTOM".to_string());
+    let scenario_type = body
+        .scenario_type
+        .as_deref()
+        .map(normalize_scenario_type)
+        .transpose()?
+        .unwrap_or_else(|| ALLOWED_SCENARIO_TYPES[0].to_string());
     let id = Uuid::new_v4().to_string();
     let metadata_json = serde_json::to_string(&metadata_with_layout(&body.layout_spec))
         .map_err(|_| AppError::BadRequest("Invalid layout_spec JSON".into()))?;
 
     sqlx::query(
         "INSERT INTO visual_support_documents_templates
-         (id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+         (id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&id)
     .bind(owner_id)
-    .bind(&body.name)
+    .bind(&name)
     .bind(body.description)
     .bind(&body.document_type)
     .bind(&scenario_type)
     .bind(&locale)
     .bind(is_system)
     .bind(&metadata_json)
+    .bind(&user.user_id)
+    .bind(&user.user_id)
     .execute(&state.pool)
     .await?;
 
     let row: TemplateRow = sqlx::query_as::<_, TemplateRow>(
-        "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+        "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
          FROM visual_support_documents_templates
          WHERE id = ?",
     )
@@ -634,9 +828,10 @@ async fn update_template(
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
+    let name = validate::optional_title("name", body.name.as_deref())?;
 
     let existing: TemplateRow = sqlx::query_as::<_, TemplateRow>(
-           "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+           "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
             FROM visual_support_documents_templates
          WHERE id = ?",
     )
@@ -652,7 +847,9 @@ async fn update_template(
         }
     }
 
-    if let Some(name) = body.name {
+    let scenario_type = body.scenario_type.as_deref().map(normalize_scenario_type).transpose()?;
+
+    if let Some(name) = name {
         sqlx::query("UPDATE visual_support_documents_templates SET name = ? WHERE id = ?")
             .bind(name)
             .bind(&id)
@@ -668,7 +865,7 @@ async fn update_template(
             .await?;
     }
 
-    if let Some(scenario_type) = body.scenario_type {
+    if let Some(scenario_type) = scenario_type {
         sqlx::query("UPDATE visual_support_documents_templates SET scenario_type = ? WHERE id = ?")
             .bind(scenario_type)
             .bind(&id)
@@ -695,8 +892,14 @@ async fn update_template(
             .await?;
     }
 
+    sqlx::query("UPDATE visual_support_documents_templates SET updated_by = ? WHERE id = ?")
+        .bind(&user.user_id)
+        .bind(&id)
+        .execute(&state.pool)
+        .await?;
+
     let row: TemplateRow = sqlx::query_as::<_, TemplateRow>(
-        "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+        "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
          FROM visual_support_documents_templates
          WHERE id = ?",
     )
@@ -707,6 +910,10 @@ async fn update_template(
     Ok(Json(to_template_dto(row)))
 }
 
+/// Blocking documents listed in a `template_in_use` conflict; the full list
+/// is available through `GET /visual-documents?template_id=`.
+const TEMPLATE_IN_USE_SAMPLE: i64 = 10;
+
 async fn delete_template(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -717,7 +924,7 @@ async fn delete_template(
     }
 
     let _existing: TemplateRow = sqlx::query_as::<_, TemplateRow>(
-           "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+           "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
             FROM visual_support_documents_templates
          WHERE id = ?",
     )
@@ -726,13 +933,39 @@ async fn delete_template(
     .await?
     .ok_or(AppError::NotFound)?;
 
-    let in_use: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM visual_support_documents WHERE template_id = ?)")
+    let in_use: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM visual_support_documents WHERE template_id = ?")
         .bind(&id)
         .fetch_one(&state.pool)
         .await?;
 
-    if in_use {
-        return Err(AppError::Conflict("Template is in use by existing documents".into()));
+    if in_use > 0 {
+        #[derive(sqlx::FromRow, Serialize)]
+        struct BlockingDocument {
+            id: String,
+            title: String,
+            owner_id: String,
+        }
+        let sample: Vec<BlockingDocument> = sqlx::query_as::<_, BlockingDocument>(
+            "SELECT id, title, owner_id
+             FROM visual_support_documents
+             WHERE template_id = ?
+             ORDER BY updated_at DESC
+             LIMIT ?",
+        )
+        .bind(&id)
+        .bind(TEMPLATE_IN_USE_SAMPLE)
+        .fetch_all(&state.pool)
+        .await?;
+
+        return Err(AppError::ConflictDetails {
+            message: format!("Template is in use by {in_use} existing document(s)"),
+            code: "template_in_use",
+            details: serde_json::json!({
+                "documents": in_use,
+                "sample": sample,
+                "list": format!("/visual-documents?template_id={id}"),
+            }),
+        });
     }
 
     sqlx::query("DELETE FROM visual_support_documents_templates WHERE id = ?")
@@ -753,13 +986,19 @@ async fn copy_template_to_document(
         return Err(AppError::Forbidden);
     }
 
+    // The template row and its activities are share-locked until the document
+    // is inserted, so a concurrent template edit cannot interleave with the
+    // copy and produce content that matches neither version.
+    let mut tx = state.pool.begin().await?;
+
     let template: TemplateRow = sqlx::query_as::<_, TemplateRow>(
-           "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+           "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
             FROM visual_support_documents_templates
-         WHERE id = ?",
+         WHERE id = ?
+         LOCK IN SHARE MODE",
     )
     .bind(&id)
-    .fetch_optional(&state.pool)
+    .fetch_optional(&mut *tx)
     .await?
     .ok_or(AppError::NotFound)?;
 
@@ -778,7 +1017,7 @@ async fn copy_template_to_document(
     let template_layout = extract_layout_spec(&template_metadata);
     validate_layout_for_type(&template.document_type, &template_layout)?;
 
-    let initial_content = build_template_initial_content(&state.pool, &id, &template_layout).await?;
+    let initial_content = build_template_initial_content(&mut tx, &id, &template_layout).await?;
     let initial_content_json = serde_json::to_string(&initial_content)
         .map_err(|_| AppError::BadRequest("Invalid initial content JSON".into()))?;
 
@@ -786,12 +1025,15 @@ async fn copy_template_to_document(
         .map_err(|_| AppError::BadRequest("Invalid template layout JSON".into()))?;
 
     let doc_id = Uuid::new_v4().to_string();
-    let title = body.title.unwrap_or_else(|| template.name.clone());
+    let title = match body.title.as_deref() {
+        Some(title) => validate::title("title", title)?,
+        None => template.name.clone(),
+    };
 
     sqlx::query(
         "INSERT INTO visual_support_documents
-         (id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 1)",
+         (id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?)",
     )
     .bind(&doc_id)
     .bind(&user.user_id)
@@ -802,11 +1044,14 @@ async fn copy_template_to_document(
         .bind(&template.language)
         .bind(layout_spec_json)
     .bind(initial_content_json)
-    .execute(&state.pool)
+    .bind(&user.user_id)
+    .execute(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
     let row: DocumentRow = sqlx::query_as::<_, DocumentRow>(
-        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
+        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by, created_at, updated_at
             FROM visual_support_documents
          WHERE id = ?",
     )
@@ -817,8 +1062,10 @@ async fn copy_template_to_document(
     Ok((StatusCode::CREATED, Json(to_document_dto(row))))
 }
 
+/// Activities are read with `LOCK IN SHARE MODE`; inside a transaction that
+/// keeps them stable until commit, elsewhere the lock is released immediately.
 async fn build_template_initial_content(
-    pool: &crate::db::Db,
+    conn: &mut sqlx::MySqlConnection,
     template_id: &str,
     template_layout: &serde_json::Value,
 ) -> AppResult<serde_json::Value> {
@@ -831,10 +1078,11 @@ async fn build_template_initial_content(
          FROM visual_support_template_activities ta
          LEFT JOIN visual_support_activity_library al ON al.id = ta.activity_card_id
          WHERE ta.template_id = ?
-         ORDER BY ta.activity_order ASC",
+         ORDER BY ta.activity_order ASC
+         LOCK IN SHARE MODE",
     )
     .bind(template_id)
-    .fetch_all(pool)
+    .fetch_all(conn)
     .await?;
 
     let expected_slots = extract_slot_count(template_layout).unwrap_or(0);
@@ -870,7 +1118,7 @@ async fn preview_template_document(
     }
 
     let template: TemplateRow = sqlx::query_as::<_, TemplateRow>(
-        "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_at, updated_at
+        "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
          FROM visual_support_documents_templates
          WHERE id = ?",
     )
@@ -892,7 +1140,8 @@ async fn preview_template_document(
     let template_layout = extract_layout_spec(&template_metadata);
     validate_layout_for_type(&template.document_type, &template_layout)?;
 
-    let content = build_template_initial_content(&state.pool, &id, &template_layout).await?;
+    let mut conn = state.pool.acquire().await?;
+    let content = build_template_initial_content(&mut conn, &id, &template_layout).await?;
 
     Ok(Json(TemplatePreviewDto {
         template_id: template.id,
@@ -1033,11 +1282,42 @@ async fn delete_activity_card(
         return Err(AppError::Forbidden);
     }
 
+    let mut tx = state.pool.begin().await?;
+
+    // Schedule cards referencing this library card fall back to its label and
+    // picture when they have none of their own. Copy those onto the cards and
+    // drop the reference explicitly rather than relying on the FK's
+    // ON DELETE SET NULL, which would leave such cards blank.
+    if let Some(image) = row.local_image_path.as_deref().filter(|p| !p.trim().is_empty()) {
+        sqlx::query(
+            "UPDATE visual_support_template_activities
+             SET metadata_json = JSON_SET(COALESCE(metadata_json, JSON_OBJECT()), '$.picture_path', ?)
+             WHERE activity_card_id = ?
+               AND COALESCE(NULLIF(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.picture_path')), ''), 'null') = 'null'",
+        )
+        .bind(image)
+        .bind(&id)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query(
+        "UPDATE visual_support_template_activities
+         SET text_label = IF(text_label = '', ?, text_label),
+             activity_card_id = NULL
+         WHERE activity_card_id = ?",
+    )
+    .bind(&row.label_text)
+    .bind(&id)
+    .execute(&mut *tx)
+    .await?;
+
     sqlx::query("DELETE FROM visual_support_activity_library WHERE id = ?")
         .bind(&id)
-        .execute(&state.pool)
+        .execute(&mut *tx)
         .await?;
 
+    tx.commit().await?;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -1157,105 +1437,45 @@ async fn list_documents(
 
     assert_child_access(&state.pool, &q.child_id, &user).await?;
 
-    let pool = &state.pool;
-    let rows: Vec<DocumentRow> = if user.role == UserRole::Admin {
-        match (&q.document_type, &q.child_id) {
-            (Some(t), Some(child_id)) => {
-                sqlx::query_as::<_, DocumentRow>(
-                    "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
-                     FROM visual_support_documents
-                     WHERE document_type = ? AND child_id = ?
-                     ORDER BY updated_at DESC",
-                )
-                .bind(t)
-                .bind(child_id)
-                .fetch_all(pool)
-                .await?
-            }
-            (Some(t), None) => {
-                sqlx::query_as::<_, DocumentRow>(
-                    "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
-                     FROM visual_support_documents
-                     WHERE document_type = ?
-                     ORDER BY updated_at DESC",
-                )
-                .bind(t)
-                .fetch_all(pool)
-                .await?
-            }
-            (None, Some(child_id)) => {
-                sqlx::query_as::<_, DocumentRow>(
-                    "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
-                     FROM visual_support_documents
-                     WHERE child_id = ?
-                     ORDER BY updated_at DESC",
-                )
-                .bind(child_id)
-                .fetch_all(pool)
-                .await?
-            }
-            (None, None) => {
-                sqlx::query_as::<_, DocumentRow>(
-                    "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
-                     FROM visual_support_documents
-                     ORDER BY updated_at DESC",
-                )
-                .fetch_all(pool)
-                .await?
-            }
+    let template_id = q.template_id.as_deref().map(str::trim).filter(|t| !t.is_empty());
+    if let Some(t) = template_id {
+        if Uuid::parse_str(t).is_err() {
+            return Err(AppError::BadRequest("template_id must be a UUID".into()));
         }
+    }
+
+    // Filters are AND-ed; admins see every owner's documents.
+    let mut filters: Vec<(&str, &str)> = Vec::new();
+    if user.role != UserRole::Admin {
+        filters.push(("owner_id = ?", &user.user_id));
+    }
+    if let Some(t) = q.document_type.as_deref() {
+        filters.push(("document_type = ?", t));
+    }
+    if let Some(child_id) = q.child_id.as_deref() {
+        filters.push(("child_id = ?", child_id));
+    }
+    if let Some(t) = template_id {
+        filters.push(("template_id = ?", t));
+    }
+
+    let where_clause = if filters.is_empty() {
+        String::new()
     } else {
-        match (&q.document_type, &q.child_id) {
-            (Some(t), Some(child_id)) => {
-                sqlx::query_as::<_, DocumentRow>(
-                    "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
-                     FROM visual_support_documents
-                     WHERE owner_id = ? AND document_type = ? AND child_id = ?
-                     ORDER BY updated_at DESC",
-                )
-                .bind(&user.user_id)
-                .bind(t)
-                .bind(child_id)
-                .fetch_all(pool)
-                .await?
-            }
-            (Some(t), None) => {
-                sqlx::query_as::<_, DocumentRow>(
-                    "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
-                     FROM visual_support_documents
-                     WHERE owner_id = ? AND document_type = ?
-                     ORDER BY updated_at DESC",
-                )
-                .bind(&user.user_id)
-                .bind(t)
-                .fetch_all(pool)
-                .await?
-            }
-            (None, Some(child_id)) => {
-                sqlx::query_as::<_, DocumentRow>(
-                    "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
-                     FROM visual_support_documents
-                     WHERE owner_id = ? AND child_id = ?
-                     ORDER BY updated_at DESC",
-                )
-                .bind(&user.user_id)
-                .bind(child_id)
-                .fetch_all(pool)
-                .await?
-            }
-            (None, None) => {
-                sqlx::query_as::<_, DocumentRow>(
-                    "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
-                     FROM visual_support_documents
-                     WHERE owner_id = ?
-                     ORDER BY updated_at DESC",
-                )
-                .bind(&user.user_id)
-                .fetch_all(pool)
-                .await?
-            }
-        }
+        let conditions: Vec<&str> = filters.iter().map(|(c, _)| *c).collect();
+        format!("WHERE {}", conditions.join(" AND "))
     };
+    let sql = format!(
+        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by, created_at, updated_at
+         FROM visual_support_documents
+         {where_clause}
+         ORDER BY updated_at DESC"
+    );
+    let mut query = sqlx::query_as::<_, DocumentRow>(&sql);
+    for (_, value) in &filters {
+        query = query.bind(*value);
+    }
+    let rows: Vec<DocumentRow> = query.fetch_all(&state.pool).await?;
 
     Ok(Json(rows.into_iter().map(to_document_dto).collect()))
 }
@@ -1263,64 +1483,83 @@ async fn list_documents(
 async fn create_document(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
+    headers: HeaderMap,
     Json(body): Json<CreateDocumentBody>,
 ) -> AppResult<(StatusCode, Json<DocumentDto>)> {
     if user.role == UserRole::Child {
         return Err(AppError::Forbidden);
     }
+    let title = validate::title("title", &body.title)?;
 
     if !is_valid_document_type(&body.document_type) {
         return Err(AppError::BadRequest("Invalid document type".into()));
     }
 
+    let layout_spec_json = bounded_json_string("layout_spec", &body.layout_spec, &state.config)?;
+    let content_json = bounded_json_string("content", &body.content, &state.config)?;
     validate_layout_for_type(&body.document_type, &body.layout_spec)?;
     validate_content_matches_layout(&body.content, &body.layout_spec)?;
 
     assert_child_access(&state.pool, &body.child_id, &user).await?;
 
     if let Some(template_id) = &body.template_id {
-        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM visual_support_documents_templates WHERE id = ?)")
-            .bind(template_id)
-            .fetch_one(&state.pool)
-            .await?;
-        if !exists {
-            return Err(AppError::BadRequest("template_id does not exist".into()));
+        #[derive(sqlx::FromRow)]
+        struct TemplateAccessRow {
+            owner_id: Option<String>,
+            is_system: bool,
         }
-    }
+        let template = sqlx::query_as::<_, TemplateAccessRow>(
+            "SELECT owner_id, is_system FROM visual_support_documents_templates WHERE id = ?",
+        )
+        .bind(template_id)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("template_id does not exist".into()))?;
 
-    let layout_spec_json = serde_json::to_string(&body.layout_spec)
-        .map_err(|_| AppError::BadRequest("Invalid layout_spec JSON".into()))?;
-    let content_json = serde_json::to_string(&body.content)
-        .map_err(|_| AppError::BadRequest("Invalid content JSON".into()))?;
+        // Same rule as copy_template_to_document: system or own templates only.
+        let can_use = user.role == UserRole::Admin
+            || template.is_system
+            || template.owner_id.as_deref() == Some(&user.user_id);
+        if !can_use {
+            return Err(AppError::Forbidden);
+        }
+    }
 
-    let id = Uuid::new_v4().to_string();
-    let locale = body.locale.unwrap_or_else(|| "en".to_string());
+    let locale = body.locale.as_deref().unwrap_or("en");
 
-    sqlx::query(
-        "INSERT INTO visual_support_documents
-         (id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 1)",
-    )
-    .bind(&id)
-    .bind(&user.user_id)
-    .bind(&body.child_id)
-    .bind(&body.template_id)
-    .bind(&body.title)
-    .bind(&body.document_type)
-    .bind(&locale)
-    .bind(&layout_spec_json)
-    .bind(&content_json)
-    .execute(&state.pool)
+    let idempotency_key = idempotency::key_from_headers(&headers)?;
+    let id = idempotency::run_once(&state.pool, &user.user_id, "document", idempotency_key.as_deref(), &body, async {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO visual_support_documents
+             (id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?)",
+        )
+        .bind(&id)
+        .bind(&user.user_id)
+        .bind(&body.child_id)
+        .bind(&body.template_id)
+        .bind(&title)
+        .bind(&body.document_type)
+        .bind(locale)
+        .bind(&layout_spec_json)
+        .bind(&content_json)
+        .bind(&user.user_id)
+        .execute(&state.pool)
+        .await?;
+        Ok(id)
+    })
     .await?;
 
     let row: DocumentRow = sqlx::query_as::<_, DocumentRow>(
-        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
+        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by, created_at, updated_at
          FROM visual_support_documents
          WHERE id = ?",
     )
     .bind(&id)
-    .fetch_one(&state.pool)
-    .await?;
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
 
     Ok((StatusCode::CREATED, Json(to_document_dto(row))))
 }
@@ -1339,6 +1578,95 @@ async fn get_document(
     Ok(Json(to_document_dto(row)))
 }
 
+async fn export_document(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<(HeaderMap, Json<DocumentExport>)> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let row = get_document_row_for_user(&state.pool, &id, &user).await?;
+    let export = DocumentExport {
+        format: DOCUMENT_EXPORT_FORMAT.to_string(),
+        version: DOCUMENT_EXPORT_VERSION,
+        exported_at: Some(chrono::Utc::now().to_rfc3339()),
+        document: ExportedDocument {
+            title: row.title,
+            document_type: row.document_type,
+            locale: row.locale,
+            layout_spec: parse_json_safe(&row.layout_spec_json),
+            content: parse_json_safe(&row.content_json),
+        },
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    let disposition = format!("attachment; filename=\"visual-document-{}.json\"", row.id);
+    if let Ok(value) = HeaderValue::from_str(&disposition) {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+
+    Ok((headers, Json(export)))
+}
+
+/// Recreate a document from [`export_document`]'s envelope as a new document
+/// of the caller, validated like `POST /visual-documents`.
+async fn import_document(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(body): Json<DocumentExport>,
+) -> AppResult<(StatusCode, Json<DocumentDto>)> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+    if body.format != DOCUMENT_EXPORT_FORMAT {
+        return Err(AppError::BadRequest(format!("format must be '{DOCUMENT_EXPORT_FORMAT}'")));
+    }
+    if body.version != DOCUMENT_EXPORT_VERSION {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported export version {}; expected {DOCUMENT_EXPORT_VERSION}",
+            body.version
+        )));
+    }
+
+    let doc = body.document;
+    let title = validate::title("title", &doc.title)?;
+    if !is_valid_document_type(&doc.document_type) {
+        return Err(AppError::BadRequest("Invalid document type".into()));
+    }
+    let locale = doc.locale.trim();
+    if locale.is_empty() || locale.len() > 8 {
+        return Err(AppError::BadRequest("locale must be 1-8 characters".into()));
+    }
+
+    let layout_spec_json = bounded_json_string("layout_spec", &doc.layout_spec, &state.config)?;
+    let content_json = bounded_json_string("content", &doc.content, &state.config)?;
+    validate_layout_for_type(&doc.document_type, &doc.layout_spec)?;
+    validate_content_matches_layout(&doc.content, &doc.layout_spec)?;
+
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO visual_support_documents
+         (id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by)
+         VALUES (?, ?, NULL, NULL, ?, ?, ?, ?, ?, 1, ?)",
+    )
+    .bind(&id)
+    .bind(&user.user_id)
+    .bind(&title)
+    .bind(&doc.document_type)
+    .bind(locale)
+    .bind(&layout_spec_json)
+    .bind(&content_json)
+    .bind(&user.user_id)
+    .execute(&state.pool)
+    .await?;
+
+    let row = get_document_row_for_user(&state.pool, &id, &user).await?;
+    Ok((StatusCode::CREATED, Json(to_document_dto(row))))
+}
+
 async fn update_document(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -1351,6 +1679,17 @@ async fn update_document(
 
     let row = get_document_row_for_user(&state.pool, &id, &user).await?;
 
+    let layout_spec_json = body
+        .layout_spec
+        .as_ref()
+        .map(|v| bounded_json_string("layout_spec", v, &state.config))
+        .transpose()?;
+    let content_json = body
+        .content
+        .as_ref()
+        .map(|v| bounded_json_string("content", v, &state.config))
+        .transpose()?;
+
     if body.layout_spec.is_some() || body.content.is_some() {
         let existing_layout = parse_json_safe(&row.layout_spec_json);
         let effective_layout = body.layout_spec.as_ref().unwrap_or(&existing_layout);
@@ -1372,7 +1711,7 @@ async fn update_document(
         assert_child_access(&state.pool, &tmp, &user).await?;
     }
 
-    if let Some(title) = body.title {
+    if let Some(title) = validate::optional_title("title", body.title.as_deref())? {
         sqlx::query("UPDATE visual_support_documents SET title = ? WHERE id = ?")
             .bind(title)
             .bind(&id)
@@ -1396,9 +1735,7 @@ async fn update_document(
             .await?;
     }
 
-    if let Some(layout_spec) = body.layout_spec {
-        let layout_spec_json = serde_json::to_string(&layout_spec)
-            .map_err(|_| AppError::BadRequest("Invalid layout_spec JSON".into()))?;
+    if let Some(layout_spec_json) = layout_spec_json {
         sqlx::query("UPDATE visual_support_documents SET layout_spec_json = ? WHERE id = ?")
             .bind(layout_spec_json)
             .bind(&id)
@@ -1406,9 +1743,7 @@ async fn update_document(
             .await?;
     }
 
-    if let Some(content) = body.content {
-        let content_json = serde_json::to_string(&content)
-            .map_err(|_| AppError::BadRequest("Invalid content JSON".into()))?;
+    if let Some(content_json) = content_json {
         sqlx::query("UPDATE visual_support_documents SET content_json = ? WHERE id = ?")
             .bind(content_json)
             .bind(&id)
@@ -1416,13 +1751,88 @@ async fn update_document(
             .await?;
     }
 
-    sqlx::query("UPDATE visual_support_documents SET version = version + 1 WHERE id = ?")
+    sqlx::query("UPDATE visual_support_documents SET version = version + 1, updated_by = ? WHERE id = ?")
+        .bind(&user.user_id)
         .bind(&id)
         .execute(&state.pool)
         .await?;
 
     let updated: DocumentRow = sqlx::query_as::<_, DocumentRow>(
-        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, created_at, updated_at
+        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by, created_at, updated_at
+         FROM visual_support_documents
+         WHERE id = ?",
+    )
+    .bind(&id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(to_document_dto(updated)))
+}
+
+async fn patch_document(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(body): Json<PatchDocumentBody>,
+) -> AppResult<Json<DocumentDto>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+    let title = validate::optional_title("title", body.title.as_deref())?;
+
+    let row = get_document_row_for_user(&state.pool, &id, &user).await?;
+
+    if let Some(expected) = body.expected_version {
+        if expected != row.version {
+            return Err(AppError::Conflict("Version conflict. Reload and retry.".into()));
+        }
+    }
+
+    let content_json = match &body.content {
+        Some(patch) => {
+            let mut merged = parse_json_safe(&row.content_json);
+            json_merge_patch(&mut merged, patch);
+            let layout = parse_json_safe(&row.layout_spec_json);
+            validate_content_matches_layout(&merged, &layout)?;
+            Some(bounded_json_string("content", &merged, &state.config)?)
+        }
+        None => None,
+    };
+
+    if let Some(child_id) = &body.child_id {
+        let tmp = Some(child_id.clone());
+        assert_child_access(&state.pool, &tmp, &user).await?;
+    }
+
+    // Single guarded UPDATE: a concurrent write between our read and this
+    // statement bumps the version and turns this into a conflict instead of
+    // silently merging onto stale content.
+    let result = sqlx::query(
+        "UPDATE visual_support_documents
+         SET title = COALESCE(?, title),
+             locale = COALESCE(?, locale),
+             child_id = COALESCE(?, child_id),
+             content_json = COALESCE(?, content_json),
+             version = version + 1,
+             updated_by = ?
+         WHERE id = ? AND version = ?",
+    )
+    .bind(title)
+    .bind(body.locale)
+    .bind(body.child_id)
+    .bind(content_json)
+    .bind(&user.user_id)
+    .bind(&id)
+    .bind(row.version)
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::Conflict("Version conflict. Reload and retry.".into()));
+    }
+
+    let updated: DocumentRow = sqlx::query_as::<_, DocumentRow>(
+        "SELECT id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version, updated_by, created_at, updated_at
          FROM visual_support_documents
          WHERE id = ?",
     )
@@ -1433,6 +1843,127 @@ async fn update_document(
     Ok(Json(to_document_dto(updated)))
 }
 
+/// Rebuild a document's content from the template it was created from,
+/// keeping its title, locale and child binding.
+async fn reset_document_to_template(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> AppResult<Json<DocumentDto>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let row = get_document_row_for_user(&state.pool, &id, &user).await?;
+    let template_id = row
+        .template_id
+        .clone()
+        .ok_or_else(|| AppError::BadRequest("Document was not created from a template".into()))?;
+
+    // Same share lock as the copy, so the rebuilt content matches one
+    // version of the template.
+    let mut tx = state.pool.begin().await?;
+
+    let template: TemplateRow = sqlx::query_as::<_, TemplateRow>(
+        "SELECT id, owner_id, name, description, document_type, scenario_type, language, is_system, metadata_json, created_by, updated_by, created_at, updated_at
+         FROM visual_support_documents_templates
+         WHERE id = ?
+         LOCK IN SHARE MODE",
+    )
+    .bind(&template_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::Conflict("The source template no longer exists".into()))?;
+
+    let template_layout = extract_layout_spec(&parse_json_safe_bytes(&template.metadata_json));
+    validate_layout_for_type(&row.document_type, &template_layout)?;
+    if template.document_type != row.document_type || parse_json_safe(&row.layout_spec_json) != template_layout {
+        return Err(AppError::Conflict("Document layout no longer matches its template".into()));
+    }
+
+    let content = build_template_initial_content(&mut tx, &template_id, &template_layout).await?;
+    let content_json = serde_json::to_string(&content)
+        .map_err(|_| AppError::BadRequest("Invalid initial content JSON".into()))?;
+
+    let result = sqlx::query(
+        "UPDATE visual_support_documents
+         SET content_json = ?, version = version + 1, updated_by = ?
+         WHERE id = ? AND version = ?",
+    )
+    .bind(content_json)
+    .bind(&user.user_id)
+    .bind(&id)
+    .bind(row.version)
+    .execute(&mut *tx)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::Conflict("Version conflict. Reload and retry.".into()));
+    }
+
+    tx.commit().await?;
+
+    let updated = get_document_row_for_user(&state.pool, &id, &user).await?;
+    Ok(Json(to_document_dto(updated)))
+}
+
+/// Replace one slot (0-based `index`) of a slot-based document, leaving the
+/// other slots untouched. Missing trailing slots are filled with `null`.
+async fn update_document_slot(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((id, index)): Path<(String, usize)>,
+    Json(body): Json<UpdateSlotBody>,
+) -> AppResult<Json<DocumentDto>> {
+    if user.role == UserRole::Child {
+        return Err(AppError::Forbidden);
+    }
+
+    let row = get_document_row_for_user(&state.pool, &id, &user).await?;
+
+    if let Some(expected) = body.expected_version {
+        if expected != row.version {
+            return Err(AppError::Conflict("Version conflict. Reload and retry.".into()));
+        }
+    }
+
+    let layout = parse_json_safe(&row.layout_spec_json);
+    let slot_count = extract_slot_count(&layout)
+        .ok_or_else(|| AppError::BadRequest("Document layout has no slotCount".into()))? as usize;
+    if index >= slot_count {
+        return Err(AppError::BadRequest(format!(
+            "Slot index {index} is out of range (layout has {slot_count} slots, 0-based)"
+        )));
+    }
+
+    let mut content = parse_json_safe(&row.content_json);
+    let slots = content_slots_mut(&mut content);
+    if slots.len() < slot_count {
+        slots.resize(slot_count, serde_json::Value::Null);
+    }
+    slots[index] = body.slot;
+    validate_content_matches_layout(&content, &layout)?;
+    let content_json = bounded_json_string("content", &content, &state.config)?;
+
+    // Guarded like PATCH: a concurrent write since our read is a conflict.
+    let result = sqlx::query(
+        "UPDATE visual_support_documents
+         SET content_json = ?, version = version + 1, updated_by = ?
+         WHERE id = ? AND version = ?",
+    )
+    .bind(content_json)
+    .bind(&user.user_id)
+    .bind(&id)
+    .bind(row.version)
+    .execute(&state.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::Conflict("Version conflict. Reload and retry.".into()));
+    }
+
+    let updated = get_document_row_for_user(&state.pool, &id, &user).await?;
+    Ok(Json(to_document_dto(updated)))
+}
+
 async fn delete_document(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -1451,3 +1982,368 @@ async fn delete_document(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{db::Db, test_support};
+
+    #[test]
+    fn merge_patch_follows_rfc_7396() {
+        let mut target = json!({ "a": "b", "c": { "d": "e", "f": "g" }, "list": [1, 2] });
+        json_merge_patch(&mut target, &json!({ "a": "z", "c": { "f": null }, "list": [3] }));
+        assert_eq!(target, json!({ "a": "z", "c": { "d": "e" }, "list": [3] }));
+
+        // Objects patched onto non-objects start from an empty object.
+        let mut target = json!({ "a": "text" });
+        json_merge_patch(&mut target, &json!({ "a": { "b": 1 } }));
+        assert_eq!(target, json!({ "a": { "b": 1 } }));
+
+        // A non-object patch replaces the whole target.
+        let mut target = json!({ "a": 1 });
+        json_merge_patch(&mut target, &json!(["x"]));
+        assert_eq!(target, json!(["x"]));
+    }
+
+    #[test]
+    fn json_depth_counts_nested_containers() {
+        assert_eq!(json_depth(&json!("text")), 0);
+        assert_eq!(json_depth(&json!([])), 1);
+        assert_eq!(json_depth(&json!({ "a": [1, { "b": [] }] })), 4);
+    }
+
+    #[test]
+    fn bounded_json_rejects_values_past_the_depth_limit() {
+        let mut config = Config::for_tests();
+        config.document_json_max_depth = 3;
+        assert!(bounded_json_string("content", &json!({ "a": { "b": [1] } }), &config).is_ok());
+        let err = test_support::expect_err(bounded_json_string("content", &json!({ "a": { "b": [[1]] } }), &config));
+        assert!(matches!(err, AppError::BadRequest(msg) if msg.contains("nested too deeply")));
+    }
+
+    #[test]
+    fn bounded_json_rejects_values_past_the_size_limit() {
+        let mut config = Config::for_tests();
+        config.document_json_max_bytes = 12;
+        // `{"a":"1234"}` is exactly 12 bytes.
+        assert_eq!(bounded_json_string("layout_spec", &json!({ "a": "1234" }), &config).unwrap(), r#"{"a":"1234"}"#);
+        let err = test_support::expect_err(bounded_json_string("layout_spec", &json!({ "a": "12345" }), &config));
+        assert!(matches!(err, AppError::BadRequest(msg) if msg.contains("too large")));
+    }
+
+    /// Seeded system First/Then template "Brush teeth → Tablet time".
+    const BRUSH_TEETH_TEMPLATE: &str = "f1a00000-0000-4000-8000-000000000001";
+
+    async fn copy_brush_teeth(state: &AppState, user: &AuthUser, child_id: &str) -> DocumentDto {
+        let body = CopyTemplateBody { title: None, child_id: Some(child_id.into()) };
+        let (status, Json(doc)) =
+            copy_template_to_document(State(state.clone()), Extension(user.clone()), Path(BRUSH_TEETH_TEMPLATE.into()), Json(body))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        doc
+    }
+
+    async fn patch(state: &AppState, user: &AuthUser, id: &str, content: serde_json::Value) -> AppResult<DocumentDto> {
+        let body = PatchDocumentBody { title: None, locale: None, child_id: None, content: Some(content), expected_version: None };
+        let Json(doc) = patch_document(State(state.clone()), Extension(user.clone()), Path(id.into()), Json(body)).await?;
+        Ok(doc)
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn copying_a_template_creates_a_filled_document(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+
+        let doc = copy_brush_teeth(&state, &parent, &child).await;
+        assert_eq!(doc.template_id.as_deref(), Some(BRUSH_TEETH_TEMPLATE));
+        assert_eq!(doc.child_id.as_deref(), Some(child.as_str()));
+        assert_eq!(doc.owner_id, parent.user_id);
+        assert_eq!(doc.document_type, "FIRST_THEN");
+        assert_eq!(doc.title, "First then: Brush teeth → Tablet time");
+        assert_eq!(doc.layout_spec["slotCount"], 2);
+        let labels: Vec<&str> = doc.content["slots"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|slot| slot["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, ["Brush teeth", "Tablet time"]);
+
+        // The transaction committed: the row is visible to other connections.
+        let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM visual_support_documents WHERE id = ?")
+            .bind(&doc.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, 1);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn patch_merges_into_stored_content_and_revalidates(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let doc = copy_brush_teeth(&state, &parent, &child).await;
+
+        let patched = patch(&state, &parent, &doc.id, json!({ "style": { "titleColor": "#ff0000" } })).await.unwrap();
+        assert_eq!(patched.version, doc.version + 1);
+        assert_eq!(patched.content["style"]["titleColor"], "#ff0000");
+        assert_eq!(patched.content["slots"], doc.content["slots"]);
+
+        // The merged result must still fit the two-slot layout.
+        let err = test_support::expect_err(patch(&state, &parent, &doc.id, json!({ "slots": [null] })).await);
+        assert!(matches!(err, AppError::Validation { .. }), "{err:?}");
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn slot_update_replaces_one_slot_and_rejects_out_of_range(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let doc = copy_brush_teeth(&state, &parent, &child).await;
+        let update = |index: usize, slot: serde_json::Value, expected_version: Option<i32>| {
+            let body = UpdateSlotBody { slot, expected_version };
+            update_document_slot(State(state.clone()), Extension(parent.clone()), Path((doc.id.clone(), index)), Json(body))
+        };
+
+        let mut slot = doc.content["slots"][1].clone();
+        slot["label"] = json!("Play outside");
+        let Json(updated) = update(1, slot.clone(), Some(doc.version)).await.unwrap();
+        assert_eq!(updated.version, doc.version + 1);
+        assert_eq!(updated.content["slots"][0], doc.content["slots"][0]);
+        assert_eq!(updated.content["slots"][1], slot);
+
+        // The layout has two slots, so index 2 is out of range.
+        let err = test_support::expect_err(update(2, slot.clone(), None).await);
+        assert!(matches!(&err, AppError::BadRequest(m) if m.contains("out of range")), "{err:?}");
+        let err = test_support::expect_err(update(0, slot, Some(doc.version)).await);
+        assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+
+        let Json(stored) = get_document(State(state.clone()), Extension(parent.clone()), Path(doc.id.clone())).await.unwrap();
+        assert_eq!(stored.version, updated.version);
+        assert_eq!(stored.content, updated.content);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn deleting_a_used_template_lists_the_blocking_documents(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let admin = test_support::user(&pool, UserRole::Admin).await;
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let doc = copy_brush_teeth(&state, &parent, &child).await;
+        let delete = || delete_template(State(state.clone()), Extension(admin.clone()), Path(BRUSH_TEETH_TEMPLATE.into()));
+
+        let AppError::ConflictDetails { code, details, .. } = test_support::expect_err(delete().await) else {
+            panic!("expected a structured conflict");
+        };
+        assert_eq!(code, "template_in_use");
+        assert_eq!(details["documents"], 1);
+        assert_eq!(
+            details["sample"],
+            json!([{ "id": doc.id, "title": doc.title, "owner_id": parent.user_id }])
+        );
+        assert_eq!(details["list"], format!("/visual-documents?template_id={BRUSH_TEETH_TEMPLATE}"));
+
+        sqlx::query("DELETE FROM visual_support_documents WHERE id = ?")
+            .bind(&doc.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(delete().await.unwrap(), StatusCode::NO_CONTENT);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn exported_document_imports_as_a_copy_owned_by_the_importer(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let doc = copy_brush_teeth(&state, &parent, &child).await;
+        let export = |user: &AuthUser| export_document(State(state.clone()), Extension(user.clone()), Path(doc.id.clone()));
+
+        let err = test_support::expect_err(export(&other).await);
+        assert!(matches!(err, AppError::NotOwned), "{err:?}");
+
+        let (headers, Json(envelope)) = export(&parent).await.unwrap();
+        assert_eq!(headers[header::CACHE_CONTROL], "no-store");
+        // Through JSON, as a backup file would be.
+        let file = serde_json::to_string(&envelope).unwrap();
+        let backup: DocumentExport = serde_json::from_str(&file).unwrap();
+        assert_eq!((backup.format.as_str(), backup.version), (DOCUMENT_EXPORT_FORMAT, DOCUMENT_EXPORT_VERSION));
+
+        let (status, Json(imported)) = import_document(State(state.clone()), Extension(other.clone()), Json(backup)).await.unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_ne!(imported.id, doc.id);
+        assert_eq!(imported.owner_id, other.user_id);
+        assert_eq!((imported.child_id, imported.template_id), (None, None));
+        assert_eq!(imported.version, 1);
+        assert_eq!(
+            (&imported.title, &imported.document_type, &imported.locale, &imported.layout_spec, &imported.content),
+            (&doc.title, &doc.document_type, &doc.locale, &doc.layout_spec, &doc.content)
+        );
+
+        // An envelope from a newer export version is refused.
+        let mut foreign: DocumentExport = serde_json::from_str(&file).unwrap();
+        foreign.version = DOCUMENT_EXPORT_VERSION + 1;
+        let err = test_support::expect_err(import_document(State(state.clone()), Extension(other.clone()), Json(foreign)).await);
+        assert!(matches!(err, AppError::BadRequest(_)), "{err:?}");
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn documents_cannot_use_another_parents_private_template(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let source = copy_brush_teeth(&state, &parent, &child).await;
+        let private_template = |owner: &AuthUser| {
+            let pool = pool.clone();
+            let owner_id = owner.user_id.clone();
+            async move {
+                let id = Uuid::new_v4().to_string();
+                sqlx::query(
+                    "INSERT INTO visual_support_documents_templates (id, owner_id, name, document_type, metadata_json)
+                     VALUES (?, ?, 'Private', 'FIRST_THEN', '{}')",
+                )
+                .bind(&id)
+                .bind(owner_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+                id
+            }
+        };
+        let theirs = private_template(&other).await;
+        let mine = private_template(&parent).await;
+        let create = |template_id: &str| {
+            let body = CreateDocumentBody {
+                title: "Board".into(),
+                document_type: source.document_type.clone(),
+                locale: None,
+                child_id: Some(child.clone()),
+                template_id: Some(template_id.into()),
+                layout_spec: source.layout_spec.clone(),
+                content: source.content.clone(),
+            };
+            create_document(State(state.clone()), Extension(parent.clone()), HeaderMap::new(), Json(body))
+        };
+
+        let err = test_support::expect_err(create(&theirs).await);
+        assert!(matches!(err, AppError::Forbidden), "{err:?}");
+
+        for template_id in [BRUSH_TEETH_TEMPLATE, mine.as_str()] {
+            let (status, Json(doc)) = create(template_id).await.unwrap();
+            assert_eq!(status, StatusCode::CREATED);
+            assert_eq!(doc.template_id.as_deref(), Some(template_id));
+        }
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn deleting_a_library_card_keeps_schedule_cards_that_used_it(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let schedule = test_support::schedule(&pool, &parent, "Week", json!({})).await;
+        let library_card = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO visual_support_activity_library (id, owner_id, language, label_text, local_image_path, is_system)
+             VALUES (?, ?, 'en', 'Brush teeth', 'uploads/brush.png', 0)",
+        )
+        .bind(&library_card)
+        .bind(&parent.user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // One card leans on the library card entirely, one has its own label and picture.
+        let plain = Uuid::new_v4().to_string();
+        let custom = Uuid::new_v4().to_string();
+        for (id, order, label, metadata) in [
+            (&plain, 0, "", json!({})),
+            (&custom, 1, "Teeth!", json!({ "picture_path": "uploads/mine.png" })),
+        ] {
+            sqlx::query(
+                "INSERT INTO visual_support_template_activities
+                    (id, template_id, activity_order, activity_card_id, text_label, metadata_json)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(&schedule)
+            .bind(order)
+            .bind(&library_card)
+            .bind(label)
+            .bind(metadata.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let status = delete_activity_card(State(state.clone()), Extension(parent.clone()), Path(library_card.clone()))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let cards: Vec<(String, Option<String>, String, Option<String>)> = sqlx::query_as(
+            "SELECT id, activity_card_id, text_label,
+                    CAST(JSON_UNQUOTE(JSON_EXTRACT(metadata_json, '$.picture_path')) AS CHAR) AS picture_path
+             FROM visual_support_template_activities
+             WHERE template_id = ?
+             ORDER BY activity_order",
+        )
+        .bind(&schedule)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            cards,
+            [
+                (plain, None, "Brush teeth".to_string(), Some("uploads/brush.png".to_string())),
+                (custom, None, "Teeth!".to_string(), Some("uploads/mine.png".to_string())),
+            ]
+        );
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn replaying_a_key_for_a_deleted_document_is_not_found(pool: Db) {
+        let state = test_support::state(pool.clone());
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        let child = test_support::child(&pool, Some(&parent.user_id)).await;
+        let source = copy_brush_teeth(&state, &parent, &child).await;
+        let create = || {
+            let body = CreateDocumentBody {
+                title: "Board".into(),
+                document_type: source.document_type.clone(),
+                locale: None,
+                child_id: Some(child.clone()),
+                template_id: None,
+                layout_spec: source.layout_spec.clone(),
+                content: source.content.clone(),
+            };
+            let mut headers = HeaderMap::new();
+            headers.insert("idempotency-key", "board-1".parse().unwrap());
+            create_document(State(state.clone()), Extension(parent.clone()), headers, Json(body))
+        };
+
+        let (_, Json(first)) = create().await.unwrap();
+        let (_, Json(replayed)) = create().await.unwrap();
+        assert_eq!(replayed.id, first.id);
+
+        sqlx::query("DELETE FROM visual_support_documents WHERE id = ?")
+            .bind(&first.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let err = test_support::expect_err(create().await);
+        assert!(matches!(err, AppError::NotFound), "{err:?}");
+    }
+}
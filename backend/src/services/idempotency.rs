@@ -0,0 +1,240 @@
+//! `Idempotency-Key` support for create endpoints.
+//!
+//! A client may send `Idempotency-Key: <opaque string>` with a create `POST`.
+//! The first request reserves the key for the caller; once the resource exists
+//! its id is stored, and a retry with the same key within [`KEY_TTL_HOURS`]
+//! gets the original resource back instead of creating a second one. A key
+//! reused with a different request body is refused with 422. The stored id
+//! is returned as is: if that resource has been deleted since, callers look
+//! it up and answer 404 until the key expires.
+//!
+//! A reservation whose request never finished (the client disconnected and
+//! the handler was dropped) only blocks retries for [`LEASE_SECONDS`].
+
+use std::future::Future;
+
+use axum::http::HeaderMap;
+use chrono::Utc;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    db::Db,
+    errors::{AppError, AppResult},
+};
+
+const HEADER: &str = "idempotency-key";
+const MAX_KEY_LEN: usize = 255;
+const KEY_TTL_HOURS: i64 = 24;
+/// How long an unfinished reservation holds its key.
+const LEASE_SECONDS: i64 = 60;
+
+/// The trimmed `Idempotency-Key` header, if the client sent one.
+pub fn key_from_headers(headers: &HeaderMap) -> AppResult<Option<String>> {
+    let Some(raw) = headers.get(HEADER) else {
+        return Ok(None);
+    };
+    let key = raw
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Idempotency-Key must be visible ASCII".into()))?
+        .trim();
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Idempotency-Key must be 1-{MAX_KEY_LEN} characters"
+        )));
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// SHA-256 of the request's JSON form, compared when a key is reused.
+fn request_hash(request: &impl Serialize) -> AppResult<String> {
+    let bytes = serde_json::to_vec(request)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to hash request: {e}")))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+/// Run `create` at most once per `(user_id, scope, key)` and return the id of
+/// the resource it created. A repeat key with the same `request` yields the
+/// stored id without polling `create`; one with a different `request` gets
+/// `Unprocessable`, and one that arrives while the first request is still
+/// running gets `Conflict`. Without a key, `create` simply runs.
+///
+/// If `create` fails the reservation is released so the client can retry.
+pub async fn run_once<F>(
+    pool: &Db,
+    user_id: &str,
+    scope: &str,
+    key: Option<&str>,
+    request: &impl Serialize,
+    create: F,
+) -> AppResult<String>
+where
+    F: Future<Output = AppResult<String>>,
+{
+    let Some(key) = key else {
+        return create.await;
+    };
+    let hash = request_hash(request)?;
+
+    sqlx::query(
+        "DELETE FROM idempotency_keys
+         WHERE user_id = ? AND scope = ? AND idem_key = ?
+           AND (expires_at <= NOW()
+                OR (resource_id IS NULL AND created_at <= NOW() - INTERVAL ? SECOND))",
+    )
+    .bind(user_id)
+    .bind(scope)
+    .bind(key)
+    .bind(LEASE_SECONDS)
+    .execute(pool)
+    .await?;
+
+    let expires_at = (Utc::now() + chrono::Duration::hours(KEY_TTL_HOURS)).naive_utc();
+    let reserved = sqlx::query(
+        "INSERT IGNORE INTO idempotency_keys (id, user_id, scope, idem_key, request_hash, expires_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(user_id)
+    .bind(scope)
+    .bind(key)
+    .bind(&hash)
+    .bind(expires_at)
+    .execute(pool)
+    .await?
+    .rows_affected()
+        == 1;
+
+    if !reserved {
+        let existing: Option<(Option<String>, String)> = sqlx::query_as(
+            "SELECT resource_id, request_hash FROM idempotency_keys
+             WHERE user_id = ? AND scope = ? AND idem_key = ?",
+        )
+        .bind(user_id)
+        .bind(scope)
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+        // Gone between the insert and this read: its lease ran out.
+        let Some((resource_id, stored_hash)) = existing else {
+            return Err(AppError::Conflict("A request with this Idempotency-Key is still in progress".into()));
+        };
+
+        if stored_hash != hash {
+            return Err(AppError::Unprocessable(
+                "Idempotency-Key was already used with a different request".into(),
+            ));
+        }
+        return resource_id.ok_or_else(|| {
+            AppError::Conflict("A request with this Idempotency-Key is still in progress".into())
+        });
+    }
+
+    match create.await {
+        Ok(resource_id) => {
+            sqlx::query(
+                "UPDATE idempotency_keys SET resource_id = ?
+                 WHERE user_id = ? AND scope = ? AND idem_key = ?",
+            )
+            .bind(&resource_id)
+            .bind(user_id)
+            .bind(scope)
+            .bind(key)
+            .execute(pool)
+            .await?;
+            Ok(resource_id)
+        }
+        Err(err) => {
+            sqlx::query(
+                "DELETE FROM idempotency_keys
+                 WHERE user_id = ? AND scope = ? AND idem_key = ? AND resource_id IS NULL",
+            )
+            .bind(user_id)
+            .bind(scope)
+            .bind(key)
+            .execute(pool)
+            .await?;
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{models::UserRole, test_support};
+
+    #[test]
+    fn request_hash_depends_on_the_body() {
+        let a = request_hash(&json!({ "name": "Morning" })).unwrap();
+        assert_eq!(a, request_hash(&json!({ "name": "Morning" })).unwrap());
+        assert_ne!(a, request_hash(&json!({ "name": "Evening" })).unwrap());
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn replay_returns_the_first_resource(pool: Db) {
+        let user = test_support::user(&pool, UserRole::Parent).await;
+        let calls = AtomicUsize::new(0);
+        let body = json!({ "name": "Morning" });
+        let create = || async {
+            let n = calls.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("resource-{n}"))
+        };
+
+        let first = run_once(&pool, &user.user_id, "test", Some("k1"), &body, create()).await.unwrap();
+        let second = run_once(&pool, &user.user_id, "test", Some("k1"), &body, create()).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Without a key every call creates.
+        run_once(&pool, &user.user_id, "test", None, &body, create()).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn reused_key_with_other_body_is_unprocessable(pool: Db) {
+        let user = test_support::user(&pool, UserRole::Parent).await;
+        let ok = || async { Ok("resource".to_string()) };
+
+        run_once(&pool, &user.user_id, "test", Some("k1"), &json!({ "name": "A" }), ok()).await.unwrap();
+        let err = run_once(&pool, &user.user_id, "test", Some("k1"), &json!({ "name": "B" }), ok()).await;
+        assert!(matches!(err, Err(AppError::Unprocessable(_))));
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn abandoned_reservation_expires_after_lease(pool: Db) {
+        let user = test_support::user(&pool, UserRole::Parent).await;
+        let body = json!({ "name": "A" });
+        let hash = request_hash(&body).unwrap();
+
+        // A reservation left behind by a dropped request.
+        sqlx::query(
+            "INSERT INTO idempotency_keys (id, user_id, scope, idem_key, request_hash, expires_at, created_at)
+             VALUES (UUID(), ?, 'test', 'k1', ?, NOW() + INTERVAL 1 DAY, NOW())",
+        )
+        .bind(&user.user_id)
+        .bind(&hash)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let ok = || async { Ok("resource".to_string()) };
+        let err = run_once(&pool, &user.user_id, "test", Some("k1"), &body, ok()).await;
+        assert!(matches!(err, Err(AppError::Conflict(_))));
+
+        sqlx::query("UPDATE idempotency_keys SET created_at = NOW() - INTERVAL ? SECOND")
+            .bind(LEASE_SECONDS + 1)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let id = run_once(&pool, &user.user_id, "test", Some("k1"), &body, ok()).await.unwrap();
+        assert_eq!(id, "resource");
+    }
+}
@@ -0,0 +1,169 @@
+//! Validation and sanitising of user-uploaded images.
+//!
+//! Uploads are served statically from `/uploads`, so nothing is stored as
+//! received: raster formats are identified by their magic bytes, decoded and
+//! re-encoded (dropping EXIF and any trailing payload). SVGs are rejected:
+//! they are documents that can carry script in too many spellings to filter
+//! reliably, and `/uploads` is served from the app's own origin.
+
+use std::io::Cursor;
+
+use image::{ImageFormat, ImageReader, Limits};
+
+use crate::errors::{AppError, AppResult};
+
+/// Largest accepted width or height, in pixels.
+pub const MAX_IMAGE_DIMENSION: u32 = 4096;
+
+/// An upload ready to be written to disk.
+pub struct SanitizedImage {
+    pub bytes: Vec<u8>,
+    /// File extension matching `bytes` (`png` or `jpg`).
+    pub ext: &'static str,
+}
+
+/// Identify `bytes` by content and return a safe copy. JPEGs stay JPEG; other
+/// raster formats (PNG, GIF, WebP) become PNG, keeping only the first frame.
+pub fn sanitize(bytes: &[u8]) -> AppResult<SanitizedImage> {
+    if let Ok(format) = image::guess_format(bytes) {
+        return reencode(bytes, format);
+    }
+    if looks_like_svg(bytes) {
+        return Err(AppError::BadRequest(
+            "SVG images are not accepted; upload PNG, JPEG, GIF or WebP".into(),
+        ));
+    }
+    Err(AppError::BadRequest("File is not a supported image".into()))
+}
+
+fn reencode(bytes: &[u8], format: ImageFormat) -> AppResult<SanitizedImage> {
+    let (output, ext) = match format {
+        ImageFormat::Jpeg => (ImageFormat::Jpeg, "jpg"),
+        ImageFormat::Png | ImageFormat::Gif | ImageFormat::WebP => (ImageFormat::Png, "png"),
+        _ => return Err(AppError::BadRequest("Unsupported image type".into())),
+    };
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    let decoded = reader.decode().map_err(|e| match e {
+        image::ImageError::Limits(_) => AppError::BadRequest(format!(
+            "Image dimensions exceed {MAX_IMAGE_DIMENSION}x{MAX_IMAGE_DIMENSION}"
+        )),
+        _ => AppError::BadRequest("Image data is corrupt or unsupported".into()),
+    })?;
+
+    // The JPEG encoder has no alpha channel.
+    let decoded = if output == ImageFormat::Jpeg {
+        image::DynamicImage::ImageRgb8(decoded.to_rgb8())
+    } else {
+        decoded
+    };
+
+    let mut out = Cursor::new(Vec::new());
+    decoded
+        .write_to(&mut out, output)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to re-encode image: {e}")))?;
+
+    Ok(SanitizedImage { bytes: out.into_inner(), ext })
+}
+
+fn looks_like_svg(bytes: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return false;
+    };
+    let head = text.trim_start_matches('\u{feff}').trim_start();
+    (head.starts_with("<?xml") || head.starts_with("<svg") || head.starts_with("<!--"))
+        && text.to_ascii_lowercase().contains("<svg")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_with_text(keyword: &str, text: &str) -> Vec<u8> {
+        let mut out = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut out, 2, 2);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.add_text_chunk(keyword.to_string(), text.to_string()).unwrap();
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&[255; 12]).unwrap();
+        }
+        out
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn rejects_non_images() {
+        for bytes in [&b"hello world"[..], b"%PDF-1.7\n", b"", b"<html><body>hi</body></html>"] {
+            assert!(matches!(sanitize(bytes), Err(AppError::BadRequest(_))));
+        }
+    }
+
+    #[test]
+    fn rejects_svg_including_filter_bypasses() {
+        let samples = [
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"><circle r=\"1\"/></svg>",
+            "<svg/onload=alert(1)>",
+            "<svg\ronload=alert(1)>",
+            "<?xml version=\"1.0\"?><svg><a href='http://evil'>x</a></svg>",
+            "<svg><a href=\"java&#115;cript:alert(1)\">x</a></svg>",
+        ];
+        for svg in samples {
+            assert!(sanitize(svg.as_bytes()).is_err(), "accepted {svg}");
+        }
+    }
+
+    #[test]
+    fn png_text_metadata_and_trailing_payload_are_stripped() {
+        let mut bytes = png_with_text("Comment", "GPS 55.6761,12.5683");
+        bytes.extend_from_slice(b"<script>trailing</script>");
+
+        let out = sanitize(&bytes).unwrap();
+        assert_eq!(out.ext, "png");
+        assert!(image::load_from_memory(&out.bytes).is_ok());
+        assert!(!contains(&out.bytes, b"GPS 55.6761"));
+        assert!(!contains(&out.bytes, b"trailing"));
+    }
+
+    #[test]
+    fn jpeg_stays_jpeg_without_exif() {
+        let img = image::RgbImage::from_pixel(4, 4, image::Rgb([10, 20, 30]));
+        let mut jpeg = Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgb8(img).write_to(&mut jpeg, ImageFormat::Jpeg).unwrap();
+        let jpeg = jpeg.into_inner();
+
+        // Splice an APP1/Exif segment in right after the SOI marker.
+        let payload = b"Exif\0\0secret-camera-serial";
+        let len = (payload.len() + 2) as u16;
+        let mut with_exif = jpeg[..2].to_vec();
+        with_exif.extend_from_slice(&[0xFF, 0xE1]);
+        with_exif.extend_from_slice(&len.to_be_bytes());
+        with_exif.extend_from_slice(payload);
+        with_exif.extend_from_slice(&jpeg[2..]);
+
+        let out = sanitize(&with_exif).unwrap();
+        assert_eq!(out.ext, "jpg");
+        assert!(!contains(&out.bytes, b"secret-camera-serial"));
+    }
+
+    #[test]
+    fn oversized_dimensions_are_rejected() {
+        let mut out = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut out, MAX_IMAGE_DIMENSION + 1, 1);
+            encoder.set_color(png::ColorType::Grayscale);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&vec![0; (MAX_IMAGE_DIMENSION + 1) as usize]).unwrap();
+        }
+        assert!(matches!(sanitize(&out), Err(AppError::BadRequest(_))));
+    }
+}
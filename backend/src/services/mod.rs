@@ -1 +1,5 @@
+pub mod idempotency;
+pub mod image_upload;
 pub mod pictograms;
+pub mod qr;
+pub mod storage;
@@ -1,23 +1,46 @@
 use std::{
+    collections::{HashMap, HashSet, VecDeque},
     path::Path,
     sync::{
         atomic::{AtomicU64, Ordering},
-        OnceLock,
+        Arc, Mutex, OnceLock,
     },
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use serde::{Deserialize, Serialize};
 
-use crate::errors::{AppError, AppResult};
+use crate::{
+    errors::{AppError, AppResult},
+    events::{self, AdminEvent, EventBus},
+};
 
 const ARASAAC_API_BASE: &str = "https://api.arasaac.org/v1";
 const ARASAAC_STATIC_BASE: &str = "https://static.arasaac.org/pictograms";
 const DEFAULT_LICENSE: &str = "CC BY-NC-SA 4.0 (ARASAAC / Gobierno de Aragón; author Sergio Palao)";
 const STORE_ROOT: &str = "backend/assets_seed/pictograms";
+const SKIN_OPTIONS: [&str; 5] = ["white", "black", "assian", "mulatto", "aztec"];
+const HAIR_OPTIONS: [&str; 7] = ["blonde", "brown", "darkBrown", "gray", "darkGray", "red", "black"];
+const ACTION_OPTIONS: [&str; 2] = ["past", "future"];
 
 static LAST_PICTOGRAM_ACTIVITY_UNIX: OnceLock<AtomicU64> = OnceLock::new();
 
+/// Seeded activity pictogram used as the health probe target.
+const ARASAAC_PROBE_ID: i32 = 8988;
+const ARASAAC_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Probes closer together than this return the previous result.
+const ARASAAC_PROBE_MIN_INTERVAL: Duration = Duration::from_secs(30);
+const ARASAAC_RECENT_ERRORS: usize = 10;
+
+static ARASAAC_ERRORS: OnceLock<Mutex<VecDeque<ArasaacErrorDto>>> = OnceLock::new();
+static ARASAAC_LAST_PROBE: OnceLock<Mutex<Option<(Instant, ArasaacProbeDto)>>> = OnceLock::new();
+
+/// ARASAAC keyword lists per language, with the time they were fetched.
+type KeywordsCache = HashMap<String, (Instant, Arc<Vec<String>>)>;
+static KEYWORDS_CACHE: OnceLock<Mutex<KeywordsCache>> = OnceLock::new();
+/// Languages whose keyword list is being refreshed in the background.
+static KEYWORDS_REFRESHING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
 #[derive(Debug, Serialize, Clone)]
 pub struct PictogramPrefetchSettingsDto {
     pub enabled: bool,
@@ -26,6 +49,8 @@ pub struct PictogramPrefetchSettingsDto {
     pub last_run_at: Option<String>,
     pub last_result: Option<serde_json::Value>,
     pub idle_seconds: u64,
+    /// Until then the worker runs regardless of `idle_minutes`.
+    pub ignore_idle_until: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -35,6 +60,25 @@ pub struct PictogramPrefetchRunResultDto {
     pub already_cached: u64,
     pub hydrated_seeded: u64,
     pub idle_seconds: u64,
+    /// Where the next run continues; 0 after a run reached the highest id.
+    pub next_cursor: i32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PictogramReindexResultDto {
+    pub scanned: u64,
+    pub updated: u64,
+}
+
+#[derive(sqlx::FromRow)]
+struct ReindexRow {
+    id: u64,
+    keywords_json: Option<String>,
+    categories_json: Option<String>,
+    tags_json: Option<String>,
+    keywords_text: String,
+    categories_text: Option<String>,
+    tags_text: Option<String>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -42,13 +86,18 @@ struct PrefetchSettingsRow {
     enabled: bool,
     idle_minutes: i32,
     batch_size: i32,
+    cursor_arasaac_id: i32,
+    ignore_idle_until: Option<chrono::NaiveDateTime>,
     last_run_at: Option<chrono::NaiveDateTime>,
     last_result_json: Option<String>,
 }
 
 #[derive(sqlx::FromRow)]
-struct ArasaacIdRow {
+struct PrefetchCandidateRow {
     arasaac_id: i32,
+    /// Comma-separated languages of the library rows / saving users that
+    /// reference this id.
+    languages: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -65,6 +114,99 @@ pub struct PictogramDto {
     pub height: Option<i32>,
     pub license: String,
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<PictogramOptions>,
+    /// Only populated when the search was enriched for a signed-in user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_count: Option<i32>,
+}
+
+/// Optional ARASAAC rendering customizations. Every field left unset keeps the
+/// stock pictogram, so an empty option set is the plain cached asset.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct PictogramOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plural: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+}
+
+impl PictogramOptions {
+    /// Drop values equal to ARASAAC's defaults and reject unknown ones, so the
+    /// same visual variant always maps to the same cache key.
+    pub fn normalized(&self) -> AppResult<Self> {
+        fn pick(value: Option<&str>, allowed: &[&str], field: &str) -> AppResult<Option<String>> {
+            let Some(v) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+                return Ok(None);
+            };
+            allowed
+                .iter()
+                .find(|a| a.eq_ignore_ascii_case(v))
+                .map(|a| Some((*a).to_string()))
+                .ok_or_else(|| AppError::BadRequest(format!("Invalid pictogram {field} option")))
+        }
+
+        Ok(Self {
+            skin: pick(self.skin.as_deref(), &SKIN_OPTIONS, "skin")?,
+            hair: pick(self.hair.as_deref(), &HAIR_OPTIONS, "hair")?,
+            plural: self.plural.filter(|v| *v),
+            color: self.color.filter(|v| !*v),
+            action: pick(self.action.as_deref(), &ACTION_OPTIONS, "action")?,
+        })
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Stable, filesystem-safe suffix identifying this option set.
+    fn variant_key(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(v) = &self.skin {
+            parts.push(format!("skin-{}", v.to_ascii_lowercase()));
+        }
+        if let Some(v) = &self.hair {
+            parts.push(format!("hair-{}", v.to_ascii_lowercase()));
+        }
+        if self.plural == Some(true) {
+            parts.push("plural".to_string());
+        }
+        if self.color == Some(false) {
+            parts.push("bw".to_string());
+        }
+        if let Some(v) = &self.action {
+            parts.push(format!("action-{v}"));
+        }
+        parts.join("_")
+    }
+
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![("download", "false".to_string())];
+        if let Some(v) = &self.skin {
+            pairs.push(("skin", v.clone()));
+        }
+        if let Some(v) = &self.hair {
+            pairs.push(("hair", v.clone()));
+        }
+        if let Some(v) = self.plural {
+            pairs.push(("plural", v.to_string()));
+        }
+        if let Some(v) = self.color {
+            pairs.push(("color", v.to_string()));
+        }
+        if let Some(v) = &self.action {
+            pairs.push(("action", v.clone()));
+        }
+        pairs
+    }
 }
 
 /// A saved pictogram entry returned to the client, enriching the bookmark record
@@ -74,6 +216,8 @@ pub struct SavedPictogramDto {
     pub arasaac_id: i32,
     pub label: Option<String>,
     pub used_count: i32,
+    /// When the user bookmarked it, RFC 3339 UTC.
+    pub saved_at: String,
     pub keywords: Vec<String>,
     pub categories: Vec<String>,
     pub tags: Vec<String>,
@@ -110,6 +254,7 @@ struct SavedRow {
     arasaac_id: i32,
     label: Option<String>,
     used_count: i32,
+    saved_at: chrono::NaiveDateTime,
     keywords_text: Option<String>,
     categories_text: Option<String>,
     tags_text: Option<String>,
@@ -140,10 +285,25 @@ struct ArasaacPictogram {
     desc: Option<String>,
 }
 
+/// Search pictograms, preferring the local cache. When `user_id` is given each
+/// result is annotated with the user's saved state and `used_count`.
 pub async fn search_local_first(
     pool: &crate::db::Db,
     language: &str,
     query: &str,
+    user_id: Option<&str>,
+) -> AppResult<Vec<PictogramDto>> {
+    let mut items = search_local_first_inner(pool, language, query).await?;
+    if let Some(user_id) = user_id {
+        annotate_saved_state(pool, user_id, &mut items).await?;
+    }
+    Ok(items)
+}
+
+async fn search_local_first_inner(
+    pool: &crate::db::Db,
+    language: &str,
+    query: &str,
 ) -> AppResult<Vec<PictogramDto>> {
     let local_db_ready = match ensure_pictograms_table(pool).await {
         Ok(_) => true,
@@ -238,6 +398,82 @@ pub async fn get_or_fetch_by_id(
     Ok(remote_to_dto(&language, &remote))
 }
 
+/// Like [`get_or_fetch_by_id`], but renders the pictogram with the given
+/// customization options. Each option set is cached as its own file next to the
+/// stock asset and recorded under `metadata_json.variants`.
+pub async fn get_or_fetch_variant_by_id(
+    pool: &crate::db::Db,
+    language: &str,
+    arasaac_id: i32,
+    options: &PictogramOptions,
+) -> AppResult<PictogramDto> {
+    let options = options.normalized()?;
+    let mut dto = get_or_fetch_by_id(pool, language, arasaac_id).await?;
+    if options.is_default() {
+        return Ok(dto);
+    }
+
+    let key = options.variant_key();
+    let category_slug = sanitize_segment(dto.category.as_deref().unwrap_or("uncategorized"));
+    let public = variant_public_path(&category_slug, arasaac_id, &key);
+
+    if !local_path_exists(&public).await {
+        download_pictogram_variant(arasaac_id, &category_slug, &key, &options).await?;
+
+        let variant = serde_json::json!({ "options": &options, "local_file_path": &public });
+        let result = sqlx::query(
+            "UPDATE pictograms
+             SET metadata_json = JSON_SET(
+                    JSON_SET(
+                        COALESCE(metadata_json, JSON_OBJECT()),
+                        '$.variants', COALESCE(JSON_EXTRACT(metadata_json, '$.variants'), JSON_OBJECT())
+                    ),
+                    ?, JSON_EXTRACT(?, '$')
+                 )
+             WHERE arasaac_id = ?",
+        )
+        .bind(format!("$.variants.\"{key}\""))
+        .bind(variant.to_string())
+        .bind(arasaac_id)
+        .execute(pool)
+        .await;
+        if let Err(err) = result {
+            tracing::warn!(error = ?err, arasaac_id, key, "Failed recording pictogram variant metadata");
+        }
+    }
+
+    dto.image_url = Some(build_remote_variant_url(arasaac_id, &options));
+    dto.local_file_path = Some(public);
+    dto.options = Some(options);
+    Ok(dto)
+}
+
+/// Point list results (search, browse) at the `options` rendering without
+/// downloading it for every item: the cached variant file when there is one,
+/// otherwise ARASAAC's rendering URL. A variant is cached the first time it
+/// is fetched on its own via [`get_or_fetch_variant_by_id`].
+pub async fn apply_variant_options(items: &mut [PictogramDto], options: &PictogramOptions) -> AppResult<()> {
+    let options = options.normalized()?;
+    if options.is_default() {
+        return Ok(());
+    }
+
+    let key = options.variant_key();
+    for item in items.iter_mut() {
+        let category_slug = sanitize_segment(item.category.as_deref().unwrap_or("uncategorized"));
+        let public = variant_public_path(&category_slug, item.arasaac_id, &key);
+        item.local_file_path = if local_path_exists(&public).await { Some(public) } else { None };
+        item.image_url = Some(build_remote_variant_url(item.arasaac_id, &options));
+        item.options = Some(options.clone());
+    }
+    Ok(())
+}
+
+/// Public path of a cached variant; sits next to the stock asset.
+fn variant_public_path(category_slug: &str, arasaac_id: i32, key: &str) -> String {
+    format!("/assets/pictograms/{category_slug}/{arasaac_id}_{key}.png")
+}
+
 async fn query_local(pool: &crate::db::Db, language: &str, query: &str) -> AppResult<Vec<PictogramDto>> {
     // MySQL FULLTEXT requires words ≥ ft_min_word_len (default 4).
     // For very short queries fall back to a LIKE scan so single words like "eat"
@@ -387,14 +623,42 @@ fn row_to_dto(row: PictogramRow) -> PictogramDto {
         category: row.category,
         categories: split_tokens(row.categories_text.as_deref().unwrap_or_default()),
         tags: split_tokens(row.tags_text.as_deref().unwrap_or_default()),
-        language: row.language,
         image_url: row.image_url,
         local_file_path: row.local_file_path,
         width: row.width,
         height: row.height,
-        license: row.license,
+        license: localized_license(&row.license, &row.language),
+        language: row.language,
         description: row.description,
+        options: None,
+        saved: None,
+        used_count: None,
+    }
+}
+
+/// Attribution for print/export footers in `language`.
+pub fn attribution(language: &str) -> String {
+    localized_license(DEFAULT_LICENSE, language)
+}
+
+/// ARASAAC attribution in `language` (primary subtag, e.g. `da` for
+/// `da-DK`), falling back to English. Only the built-in default is
+/// translated; a different license stored on a pictogram is kept verbatim.
+pub fn localized_license(stored: &str, language: &str) -> String {
+    if stored != DEFAULT_LICENSE {
+        return stored.to_string();
     }
+    let primary = language.split(['-', '_']).next().unwrap_or("").to_ascii_lowercase();
+    let text = match primary.as_str() {
+        "da" => "CC BY-NC-SA 4.0 (ARASAAC / Aragoniens regering; forfatter Sergio Palao)",
+        "sv" => "CC BY-NC-SA 4.0 (ARASAAC / Aragoniens regering; upphovsperson Sergio Palao)",
+        "nb" | "nn" | "no" => "CC BY-NC-SA 4.0 (ARASAAC / Aragóns regjering; forfatter Sergio Palao)",
+        "de" => "CC BY-NC-SA 4.0 (ARASAAC / Regierung von Aragonien; Autor Sergio Palao)",
+        "es" => "CC BY-NC-SA 4.0 (ARASAAC / Gobierno de Aragón; autor Sergio Palao)",
+        "fr" => "CC BY-NC-SA 4.0 (ARASAAC / Gouvernement d'Aragon ; auteur Sergio Palao)",
+        _ => DEFAULT_LICENSE,
+    };
+    text.to_string()
 }
 
 fn split_tokens(s: &str) -> Vec<String> {
@@ -456,6 +720,104 @@ pub fn idle_seconds() -> u64 {
     now_unix_seconds().saturating_sub(last)
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ArasaacErrorDto {
+    pub at: String,
+    pub url: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArasaacProbeDto {
+    pub reachable: bool,
+    pub latency_ms: u64,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub checked_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArasaacHealthDto {
+    pub probe: ArasaacProbeDto,
+    /// True when `probe` is the result of an earlier request (rate limit).
+    pub cached: bool,
+    /// Most recent failed upstream calls, newest first.
+    pub recent_errors: Vec<ArasaacErrorDto>,
+}
+
+fn record_arasaac_error(url: &str, error: &str) {
+    let errors = ARASAAC_ERRORS.get_or_init(|| Mutex::new(VecDeque::new()));
+    let mut errors = errors.lock().unwrap_or_else(|e| e.into_inner());
+    if errors.len() == ARASAAC_RECENT_ERRORS {
+        errors.pop_back();
+    }
+    errors.push_front(ArasaacErrorDto {
+        at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        url: url.to_string(),
+        error: error.to_string(),
+    });
+}
+
+/// Probe ARASAAC with a single known-id fetch. At most one real request is
+/// made per [`ARASAAC_PROBE_MIN_INTERVAL`]; callers in between get the cached
+/// result so the endpoint cannot be used to hammer upstream.
+pub async fn arasaac_health() -> ArasaacHealthDto {
+    let last_probe = ARASAAC_LAST_PROBE.get_or_init(|| Mutex::new(None));
+    let cached = last_probe
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .filter(|(at, _)| at.elapsed() < ARASAAC_PROBE_MIN_INTERVAL)
+        .map(|(_, probe)| probe.clone());
+
+    let (probe, cached) = match cached {
+        Some(probe) => (probe, true),
+        None => {
+            let probe = probe_arasaac().await;
+            *last_probe.lock().unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), probe.clone()));
+            (probe, false)
+        }
+    };
+
+    let recent_errors = ARASAAC_ERRORS
+        .get()
+        .map(|errors| errors.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect())
+        .unwrap_or_default();
+
+    ArasaacHealthDto { probe, cached, recent_errors }
+}
+
+async fn probe_arasaac() -> ArasaacProbeDto {
+    let url = format!("{ARASAAC_API_BASE}/pictograms/en/{ARASAAC_PROBE_ID}");
+    let started = Instant::now();
+    let outcome = match http_client() {
+        Ok(client) => client.get(&url).timeout(ARASAAC_PROBE_TIMEOUT).send().await.map_err(|e| e.to_string()),
+        Err(err) => Err(err.to_string()),
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let (reachable, status, error) = match outcome {
+        Ok(resp) if resp.status().is_success() => (true, Some(resp.status().as_u16()), None),
+        Ok(resp) => {
+            let error = format!("ARASAAC responded with status {}", resp.status());
+            record_arasaac_error(&url, &error);
+            (false, Some(resp.status().as_u16()), Some(error))
+        }
+        Err(err) => {
+            record_arasaac_error(&url, &err);
+            (false, None, Some(err))
+        }
+    };
+
+    ArasaacProbeDto {
+        reachable,
+        latency_ms,
+        status,
+        error,
+        checked_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    }
+}
+
 pub fn spawn_idle_prefetch_worker(state: crate::state::AppState) {
     let interval_secs = state.config.pictogram_prefetch_interval_seconds.max(10);
     tracing::info!(
@@ -478,23 +840,58 @@ pub fn spawn_idle_prefetch_worker(state: crate::state::AppState) {
                 }
             };
 
-            if !settings.enabled {
-                continue;
-            }
-
-            let required_idle_secs = (settings.idle_minutes.max(1) as u64).saturating_mul(60);
             let current_idle = idle_seconds();
-            if current_idle < required_idle_secs {
+            if !worker_should_run(&state.pool, &settings, chrono::Utc::now().naive_utc(), current_idle).await {
                 continue;
             }
 
-            if let Err(err) = prefetch_once_internal(&state.pool, settings.batch_size as u64, current_idle).await {
+            if let Err(err) = prefetch_once_internal(&state.pool, &state.events, &settings, current_idle).await {
                 tracing::warn!(error = ?err, "Idle pictogram prefetch run failed");
             }
         }
     });
 }
 
+/// Whether the enabled worker runs a batch on this tick: after `idle_minutes`
+/// without activity, or on every tick while an ignore-idle window is open.
+/// A window that has passed is cleared.
+async fn worker_should_run(
+    pool: &crate::db::Db,
+    settings: &PrefetchSettingsRow,
+    now: chrono::NaiveDateTime,
+    current_idle_seconds: u64,
+) -> bool {
+    if !settings.enabled {
+        return false;
+    }
+
+    let ignore_idle = ignore_idle_window_open(settings.ignore_idle_until, now);
+    if settings.ignore_idle_until.is_some() && !ignore_idle {
+        if let Err(err) = clear_expired_ignore_idle(pool).await {
+            tracing::warn!(error = ?err, "Unable to clear expired prefetch idle override");
+        }
+    }
+
+    let required_idle_secs = (settings.idle_minutes.max(1) as u64).saturating_mul(60);
+    ignore_idle || current_idle_seconds >= required_idle_secs
+}
+
+/// Whether an admin-set "ignore idle until" window is still open at `now`.
+fn ignore_idle_window_open(until: Option<chrono::NaiveDateTime>, now: chrono::NaiveDateTime) -> bool {
+    until.is_some_and(|until| now < until)
+}
+
+async fn clear_expired_ignore_idle(pool: &crate::db::Db) -> AppResult<()> {
+    sqlx::query(
+        "UPDATE pictogram_prefetch_settings
+         SET ignore_idle_until = NULL
+         WHERE id = 1 AND ignore_idle_until <= UTC_TIMESTAMP()",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 pub async fn get_prefetch_settings(
     pool: &crate::db::Db,
     config: &crate::config::Config,
@@ -509,6 +906,7 @@ pub async fn update_prefetch_settings(
     enabled: Option<bool>,
     idle_minutes: Option<u64>,
     batch_size: Option<u64>,
+    ignore_idle_until: Option<Option<chrono::NaiveDateTime>>,
 ) -> AppResult<PictogramPrefetchSettingsDto> {
     ensure_prefetch_settings_row(pool, config).await?;
 
@@ -535,38 +933,137 @@ pub async fn update_prefetch_settings(
             .await?;
     }
 
+    if let Some(v) = ignore_idle_until {
+        sqlx::query("UPDATE pictogram_prefetch_settings SET ignore_idle_until = ? WHERE id = 1")
+            .bind(v)
+            .execute(pool)
+            .await?;
+    }
+
     get_prefetch_settings(pool, config).await
 }
 
 pub async fn run_prefetch_now(
     pool: &crate::db::Db,
     config: &crate::config::Config,
+    events: &EventBus,
 ) -> AppResult<PictogramPrefetchRunResultDto> {
     let settings = get_prefetch_settings_internal(pool, config).await?;
-    prefetch_once_internal(pool, settings.batch_size as u64, idle_seconds()).await
+    prefetch_once_internal(pool, events, &settings, idle_seconds()).await
+}
+
+/// Rows read per batch by [`reindex_fulltext`].
+const REINDEX_BATCH_SIZE: i64 = 500;
+
+/// Recompute the `_text` search columns of every cached pictogram from its
+/// `_json` columns, the way [`upsert_remote_pictogram`] writes them, so rows with
+/// stale or missing text become findable through the FULLTEXT index again.
+///
+/// Rows are read in id-ordered batches and only changed rows are written,
+/// one short statement each, so the table is never locked for long.
+pub async fn reindex_fulltext(pool: &crate::db::Db) -> AppResult<PictogramReindexResultDto> {
+    let mut scanned = 0u64;
+    let mut updated = 0u64;
+    let mut after_id = 0u64;
+
+    loop {
+        let rows: Vec<ReindexRow> = sqlx::query_as::<_, ReindexRow>(
+            "SELECT id,
+                    CAST(keywords_json AS CHAR) AS keywords_json,
+                    CAST(categories_json AS CHAR) AS categories_json,
+                    CAST(tags_json AS CHAR) AS tags_json,
+                    keywords_text, categories_text, tags_text
+             FROM pictograms
+             WHERE id > ?
+             ORDER BY id
+             LIMIT ?",
+        )
+        .bind(after_id)
+        .bind(REINDEX_BATCH_SIZE)
+        .fetch_all(pool)
+        .await?;
+
+        let Some(last) = rows.last() else { break };
+        after_id = last.id;
+        let batch_len = rows.len() as i64;
+
+        for row in rows {
+            scanned += 1;
+            let keywords_text = join_tokens(&json_tokens(row.keywords_json.as_deref()));
+            let categories_text = row.categories_json.as_deref().map(|raw| join_tokens(&json_tokens(Some(raw))));
+            let tags_text = row.tags_json.as_deref().map(|raw| join_tokens(&json_tokens(Some(raw))));
+            if keywords_text == row.keywords_text && categories_text == row.categories_text && tags_text == row.tags_text {
+                continue;
+            }
+
+            sqlx::query(
+                "UPDATE pictograms
+                 SET keywords_text = ?, categories_text = ?, tags_text = ?
+                 WHERE id = ?",
+            )
+            .bind(keywords_text)
+            .bind(categories_text)
+            .bind(tags_text)
+            .bind(row.id)
+            .execute(pool)
+            .await?;
+            updated += 1;
+        }
+
+        if batch_len < REINDEX_BATCH_SIZE {
+            break;
+        }
+    }
+
+    tracing::info!(scanned, updated, "Pictogram fulltext columns rebuilt");
+    Ok(PictogramReindexResultDto { scanned, updated })
+}
+
+/// String entries of a stored JSON array; anything else yields no tokens.
+fn json_tokens(raw: Option<&str>) -> Vec<String> {
+    raw.and_then(|raw| serde_json::from_str::<Vec<serde_json::Value>>(raw).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|v| v.as_str().map(ToString::to_string))
+        .collect()
 }
 
+/// Emit a progress event every this many processed ids.
+const PREFETCH_PROGRESS_EVERY: u64 = 10;
+
 async fn prefetch_once_internal(
     pool: &crate::db::Db,
-    batch_size: u64,
+    events: &EventBus,
+    settings: &PrefetchSettingsRow,
     current_idle_seconds: u64,
 ) -> AppResult<PictogramPrefetchRunResultDto> {
     let hydrated_seeded = ensure_seeded_activity_assets(pool).await.unwrap_or(0) as u64;
-    let ids = load_prefetch_candidate_ids(pool, batch_size).await?;
+    let languages = load_prefetch_languages(pool).await?;
+    let limit = prefetch_limit(settings.batch_size.max(1) as u64);
+    let candidates = load_prefetch_candidates(pool, settings.cursor_arasaac_id, limit, &languages).await?;
+    let next_cursor = next_prefetch_cursor(candidates.last().map(|(id, _)| *id), candidates.len(), limit);
+    let total = candidates.len() as u64;
 
     let mut processed_ids = 0u64;
     let mut downloaded = 0u64;
     let mut already_cached = 0u64;
 
-    for id in ids {
+    for (id, language) in candidates {
         processed_ids += 1;
+        if processed_ids.is_multiple_of(PREFETCH_PROGRESS_EVERY) {
+            events::publish(events, AdminEvent::PrefetchProgress {
+                processed: processed_ids,
+                total,
+                downloaded,
+            });
+        }
 
         if is_arasaac_id_cached_locally(pool, id).await? {
             already_cached += 1;
             continue;
         }
 
-        match get_or_fetch_by_id(pool, "en", id).await {
+        match get_or_fetch_by_id(pool, &language, id).await {
             Ok(dto) => {
                 if let Some(path) = dto.local_file_path {
                     if local_path_exists(&path).await {
@@ -586,40 +1083,138 @@ async fn prefetch_once_internal(
         already_cached,
         hydrated_seeded,
         idle_seconds: current_idle_seconds,
+        next_cursor,
     };
 
+    events::publish(events, AdminEvent::PrefetchCompleted {
+        processed_ids,
+        downloaded,
+        already_cached,
+    });
+
     sqlx::query(
         "UPDATE pictogram_prefetch_settings
          SET last_run_at = UTC_TIMESTAMP(),
-             last_result_json = ?
+             last_result_json = ?,
+             cursor_arasaac_id = ?
          WHERE id = 1",
     )
     .bind(serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string()))
+    .bind(next_cursor)
     .execute(pool)
     .await?;
 
     Ok(result)
 }
 
-async fn load_prefetch_candidate_ids(pool: &crate::db::Db, batch_size: u64) -> AppResult<Vec<i32>> {
-    let limit = batch_size.clamp(1, 2_000) as i64;
-    let rows: Vec<ArasaacIdRow> = sqlx::query_as::<_, ArasaacIdRow>(
-        "SELECT DISTINCT t.arasaac_id
+/// Languages in use across active users and the activity library, most
+/// common first. Falls back to `["en"]` on an empty deployment.
+async fn load_prefetch_languages(pool: &crate::db::Db) -> AppResult<Vec<String>> {
+    let rows: Vec<String> = sqlx::query_scalar(
+        "SELECT t.language
+         FROM (
+            SELECT language FROM users
+            WHERE is_active = 1 AND deleted_at IS NULL AND language IS NOT NULL
+            UNION ALL
+            SELECT language FROM visual_support_activity_library
+         ) t
+         GROUP BY t.language
+         ORDER BY COUNT(*) DESC, t.language ASC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut languages: Vec<String> = Vec::new();
+    for language in rows.iter().map(|l| normalize_language(l)) {
+        if !languages.contains(&language) {
+            languages.push(language);
+        }
+    }
+    if languages.is_empty() {
+        languages.push("en".to_string());
+    }
+    Ok(languages)
+}
+
+fn prefetch_limit(batch_size: u64) -> i64 {
+    batch_size.clamp(1, 2_000) as i64
+}
+
+/// Cursor for the run after one that loaded `loaded` candidates (the last
+/// being `last_id`) out of at most `limit`. A short batch means the walk
+/// reached the highest id, so the next run starts over from the beginning.
+fn next_prefetch_cursor(last_id: Option<i32>, loaded: usize, limit: i64) -> i32 {
+    match last_id {
+        Some(id) if (loaded as i64) >= limit => id,
+        _ => 0,
+    }
+}
+
+/// Next batch of ids above `cursor` to prefetch, in ascending id order, each
+/// paired with the language to fetch it in: the highest-ranked entry of
+/// `languages` among those referencing it.
+///
+/// Walking by id (with the cursor persisted between runs) means every
+/// candidate is eventually visited, instead of each run re-checking the same
+/// lowest ids. Ids with a recorded local file are left out so the batch is
+/// spent on downloads rather than on files we already have.
+async fn load_prefetch_candidates(
+    pool: &crate::db::Db,
+    cursor: i32,
+    limit: i64,
+    languages: &[String],
+) -> AppResult<Vec<(i32, String)>> {
+    let rows: Vec<PrefetchCandidateRow> = sqlx::query_as::<_, PrefetchCandidateRow>(
+        "SELECT t.arasaac_id, CAST(GROUP_CONCAT(DISTINCT t.language) AS CHAR(255)) AS languages
          FROM (
-            SELECT arasaac_id FROM visual_support_activity_library WHERE arasaac_id IS NOT NULL
+            SELECT arasaac_id, language
+            FROM visual_support_activity_library WHERE arasaac_id IS NOT NULL
             UNION
-            SELECT arasaac_id FROM saved_pictograms WHERE arasaac_id IS NOT NULL
+            SELECT sp.arasaac_id, u.language
+            FROM saved_pictograms sp
+            JOIN users u ON u.id = sp.user_id
+            WHERE sp.arasaac_id IS NOT NULL
             UNION
-            SELECT arasaac_id FROM pictograms WHERE arasaac_id IS NOT NULL
+            SELECT arasaac_id, NULL FROM pictograms WHERE arasaac_id IS NOT NULL
          ) t
+         WHERE t.arasaac_id > ?
+           AND NOT EXISTS (
+               SELECT 1 FROM pictograms cached
+               WHERE cached.arasaac_id = t.arasaac_id
+                 AND cached.local_file_path IS NOT NULL
+                 AND cached.local_file_path <> ''
+           )
+         GROUP BY t.arasaac_id
          ORDER BY t.arasaac_id ASC
          LIMIT ?",
     )
+    .bind(cursor)
     .bind(limit)
     .fetch_all(pool)
     .await?;
 
-    Ok(rows.into_iter().map(|r| r.arasaac_id).collect())
+    let fallback = languages.first().map(String::as_str).unwrap_or("en");
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let referenced: Vec<String> = r
+                .languages
+                .as_deref()
+                .unwrap_or("")
+                .split(',')
+                .filter(|l| !l.trim().is_empty())
+                .map(normalize_language)
+                .collect();
+            let language = languages
+                .iter()
+                .find(|l| referenced.contains(l))
+                .map(String::as_str)
+                .or_else(|| referenced.first().map(String::as_str))
+                .unwrap_or(fallback)
+                .to_string();
+            (r.arasaac_id, language)
+        })
+        .collect())
 }
 
 async fn is_arasaac_id_cached_locally(pool: &crate::db::Db, arasaac_id: i32) -> AppResult<bool> {
@@ -643,6 +1238,8 @@ async fn ensure_prefetch_settings_table(pool: &crate::db::Db) -> AppResult<()> {
             enabled           BOOLEAN NOT NULL DEFAULT FALSE,
             idle_minutes      INT NOT NULL DEFAULT 20,
             batch_size        INT NOT NULL DEFAULT 50,
+            cursor_arasaac_id INT NOT NULL DEFAULT 0,
+            ignore_idle_until DATETIME NULL,
             last_run_at       DATETIME NULL,
             last_result_json  JSON NULL,
             created_at        DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
@@ -686,6 +1283,8 @@ async fn get_prefetch_settings_internal(
             enabled,
             idle_minutes,
             batch_size,
+            cursor_arasaac_id,
+            ignore_idle_until,
             last_run_at,
             CAST(last_result_json AS CHAR) AS last_result_json
          FROM pictogram_prefetch_settings
@@ -697,10 +1296,11 @@ async fn get_prefetch_settings_internal(
 }
 
 fn prefetch_row_to_dto(row: PrefetchSettingsRow) -> PictogramPrefetchSettingsDto {
-    let last_run_at = row.last_run_at.map(|dt| {
+    let fmt_utc = |dt: chrono::NaiveDateTime| {
         chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(dt, chrono::Utc)
             .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
-    });
+    };
+    let last_run_at = row.last_run_at.map(fmt_utc);
 
     let last_result = row
         .last_result_json
@@ -714,6 +1314,7 @@ fn prefetch_row_to_dto(row: PrefetchSettingsRow) -> PictogramPrefetchSettingsDto
         last_run_at,
         last_result,
         idle_seconds: idle_seconds(),
+        ignore_idle_until: row.ignore_idle_until.map(fmt_utc),
     }
 }
 
@@ -725,12 +1326,10 @@ fn disk_path_from_public_path(public_path: &str) -> Option<String> {
 fn extract_keyword_tokens(p: &ArasaacPictogram) -> Vec<String> {
     let mut out = Vec::new();
     for k in &p.keywords {
-        for cand in [k.keyword.as_ref(), k.plural.as_ref(), k.meaning.as_ref()] {
-            if let Some(v) = cand {
-                let v = v.trim();
-                if !v.is_empty() {
-                    out.push(v.to_string());
-                }
+        for v in [k.keyword.as_ref(), k.plural.as_ref(), k.meaning.as_ref()].into_iter().flatten() {
+            let v = v.trim();
+            if !v.is_empty() {
+                out.push(v.to_string());
             }
         }
     }
@@ -813,14 +1412,14 @@ async fn fetch_remote_by_id(language: &str, arasaac_id: i32) -> AppResult<Arasaa
 }
 
 async fn fetch_remote_vec(client: &reqwest::Client, url: &str) -> AppResult<Vec<ArasaacPictogram>> {
-    let resp = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| AppError::Internal(anyhow::anyhow!("ARASAAC request failed: {e}")))?;
+    let resp = client.get(url).send().await.map_err(|e| {
+        record_arasaac_error(url, &e.to_string());
+        AppError::Internal(anyhow::anyhow!("ARASAAC request failed: {e}"))
+    })?;
 
     let status = resp.status();
     if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        record_arasaac_error(url, "rate limited (429)");
         return Err(AppError::BadRequest("ARASAAC rate limit reached. Please retry shortly.".into()));
     }
     // ARASAAC returns 404 - not an empty array - when no pictograms match the query.
@@ -828,12 +1427,14 @@ async fn fetch_remote_vec(client: &reqwest::Client, url: &str) -> AppResult<Vec<
         return Ok(vec![]);
     }
     if !status.is_success() {
+        record_arasaac_error(url, &format!("status {status}"));
         return Err(AppError::Internal(anyhow::anyhow!("ARASAAC request failed with status {status}")));
     }
 
-    resp.json::<Vec<ArasaacPictogram>>()
-        .await
-        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to parse ARASAAC response: {e}")))
+    resp.json::<Vec<ArasaacPictogram>>().await.map_err(|e| {
+        record_arasaac_error(url, &format!("invalid response: {e}"));
+        AppError::Internal(anyhow::anyhow!("Failed to parse ARASAAC response: {e}"))
+    })
 }
 
 async fn upsert_remote_pictogram(
@@ -888,7 +1489,11 @@ async fn upsert_remote_pictogram(
             width = VALUES(width),
             height = VALUES(height),
             license = VALUES(license),
-            metadata_json = VALUES(metadata_json),
+            metadata_json = IF(
+                JSON_CONTAINS_PATH(metadata_json, 'one', '$.variants'),
+                JSON_SET(VALUES(metadata_json), '$.variants', JSON_EXTRACT(metadata_json, '$.variants')),
+                VALUES(metadata_json)
+            ),
             updated_at = CURRENT_TIMESTAMP",
     )
     .bind(p.id)
@@ -920,10 +1525,44 @@ async fn local_path_exists(public_path: &str) -> bool {
     tokio::fs::metadata(disk).await.is_ok()
 }
 
+/// Public path an activity-library card should point at for `arasaac_id`:
+/// the cached file when there is one, otherwise the seed location that
+/// [`ensure_seeded_activity_assets`] downloads into.
+pub async fn activity_asset_path(
+    pool: &crate::db::Db,
+    arasaac_id: i32,
+    category: Option<&str>,
+) -> AppResult<String> {
+    let cached: Option<String> = sqlx::query_scalar(
+        "SELECT local_file_path FROM pictograms WHERE arasaac_id = ? AND local_file_path IS NOT NULL LIMIT 1",
+    )
+    .bind(arasaac_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(cached.unwrap_or_else(|| {
+        format!("/assets/pictograms/{}/{arasaac_id}.png", sanitize_segment(category.unwrap_or("")))
+    }))
+}
+
+/// Small (300 px) rendition on the ARASAAC static host.
+pub fn build_remote_thumb_url(arasaac_id: i32) -> String {
+    format!("{ARASAAC_STATIC_BASE}/{arasaac_id}/{arasaac_id}_300.png")
+}
+
 fn build_remote_png_url(arasaac_id: i32) -> String {
     format!("{ARASAAC_STATIC_BASE}/{arasaac_id}/{arasaac_id}_500.png")
 }
 
+fn build_remote_variant_url(arasaac_id: i32, options: &PictogramOptions) -> String {
+    let query = options
+        .query_pairs()
+        .into_iter()
+        .map(|(k, v)| format!("{k}={}", urlencoding::encode(&v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{ARASAAC_API_BASE}/pictograms/{arasaac_id}?{query}")
+}
+
 fn remote_to_dto(language: &str, p: &ArasaacPictogram) -> PictogramDto {
     let keywords = extract_keyword_tokens(p);
     let categories = p.categories.clone();
@@ -939,8 +1578,11 @@ fn remote_to_dto(language: &str, p: &ArasaacPictogram) -> PictogramDto {
         local_file_path: None,
         width: None,
         height: None,
-        license: DEFAULT_LICENSE.to_string(),
+        license: localized_license(DEFAULT_LICENSE, language),
         description: p.desc.clone(),
+        options: None,
+        saved: None,
+        used_count: None,
     }
 }
 
@@ -1007,6 +1649,47 @@ async fn download_pictogram_asset(
     Ok((Some(png_url), Some(public)))
 }
 
+/// Download a customized rendering. ARASAAC only renders options through the
+/// API endpoint, which always returns PNG.
+async fn download_pictogram_variant(
+    arasaac_id: i32,
+    category_slug: &str,
+    key: &str,
+    options: &PictogramOptions,
+) -> AppResult<()> {
+    let dir = format!("{STORE_ROOT}/{category_slug}");
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create pictogram directory: {e}")))?;
+
+    let client = http_client()?;
+    let url = build_remote_variant_url(arasaac_id, options);
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed downloading ARASAAC variant: {e}")))?;
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(AppError::NotFound);
+    }
+    if !status.is_success() {
+        return Err(AppError::Internal(anyhow::anyhow!("ARASAAC variant request failed with status {status}")));
+    }
+
+    let png = resp
+        .bytes()
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed reading ARASAAC variant bytes: {e}")))?;
+    let disk = format!("{dir}/{arasaac_id}_{key}.png");
+    tokio::fs::write(&disk, png)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed writing PNG pictogram variant file: {e}")))?;
+
+    Ok(())
+}
+
 #[derive(sqlx::FromRow)]
 struct SeedActivityAssetRow {
     arasaac_id: i32,
@@ -1169,12 +1852,99 @@ pub async fn get_new_pictograms(
     Ok(remote.iter().map(|p| remote_to_dto(&language, p)).collect())
 }
 
-/// Return the full keyword list for the given language from ARASAAC.
-/// Used to populate datalist autocomplete on the search page.
-pub async fn get_keywords(language: &str) -> AppResult<Vec<String>> {
+#[derive(Debug, Serialize, Clone)]
+pub struct KeywordsRefreshDto {
+    pub language: String,
+    pub words: usize,
+}
+
+fn keywords_cache() -> &'static Mutex<KeywordsCache> {
+    KEYWORDS_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The full keyword list for the given language, used to populate datalist
+/// autocomplete on the search page.
+///
+/// Lists are cached in memory per language. Within `ttl` the cached list is
+/// returned as is; after that it is still returned, and a background task
+/// fetches a fresh one. Only the very first request for a language waits
+/// for ARASAAC.
+pub async fn get_keywords(language: &str, ttl: Duration) -> AppResult<Arc<Vec<String>>> {
     let language = normalize_language(language);
+    let (words, stale) = cached_or_fetch_keywords(keywords_cache(), ARASAAC_API_BASE, &language, ttl, Instant::now()).await?;
+    if stale {
+        spawn_keywords_refresh(language);
+    }
+    Ok(words)
+}
+
+/// `language`'s list from `cache`, fetched from `api_base` on a miss, and
+/// whether it is at least `ttl` old at `now`.
+async fn cached_or_fetch_keywords(
+    cache: &Mutex<KeywordsCache>,
+    api_base: &str,
+    language: &str,
+    ttl: Duration,
+    now: Instant,
+) -> AppResult<(Arc<Vec<String>>, bool)> {
+    let cached = cache.lock().unwrap_or_else(|e| e.into_inner()).get(language).cloned();
+    match cached {
+        Some((fetched_at, words)) => Ok((words, now.saturating_duration_since(fetched_at) >= ttl)),
+        None => Ok((refresh_keywords(cache, api_base, language, now).await?, false)),
+    }
+}
+
+/// Fetch `language`'s keyword list from `api_base` and store it in `cache`
+/// as fetched at `now`. On failure the cached list is left as it was.
+async fn refresh_keywords(
+    cache: &Mutex<KeywordsCache>,
+    api_base: &str,
+    language: &str,
+    now: Instant,
+) -> AppResult<Arc<Vec<String>>> {
+    let words = Arc::new(fetch_keywords(api_base, language).await?);
+    cache
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(language.to_string(), (now, words.clone()));
+    Ok(words)
+}
+
+/// Refresh a stale list without making the caller wait; at most one refresh
+/// per language runs at a time.
+fn spawn_keywords_refresh(language: String) {
+    let refreshing = KEYWORDS_REFRESHING.get_or_init(|| Mutex::new(HashSet::new()));
+    if !refreshing.lock().unwrap_or_else(|e| e.into_inner()).insert(language.clone()) {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(err) = refresh_keywords(keywords_cache(), ARASAAC_API_BASE, &language, Instant::now()).await {
+            tracing::warn!(error = ?err, language, "Refreshing ARASAAC keywords failed; keeping the cached list");
+        }
+        refreshing.lock().unwrap_or_else(|e| e.into_inner()).remove(&language);
+    });
+}
+
+/// Admin force-refresh: re-fetch `language`, or every cached language when
+/// `None`, regardless of age.
+pub async fn refresh_keywords_cache(language: Option<&str>) -> AppResult<Vec<KeywordsRefreshDto>> {
+    let mut languages: Vec<String> = match language {
+        Some(l) => vec![normalize_language(l)],
+        None => keywords_cache().lock().unwrap_or_else(|e| e.into_inner()).keys().cloned().collect(),
+    };
+    languages.sort();
+
+    let mut out = Vec::with_capacity(languages.len());
+    for language in languages {
+        let words = refresh_keywords(keywords_cache(), ARASAAC_API_BASE, &language, Instant::now()).await?;
+        out.push(KeywordsRefreshDto { language, words: words.len() });
+    }
+    Ok(out)
+}
+
+async fn fetch_keywords(api_base: &str, language: &str) -> AppResult<Vec<String>> {
     let client = http_client()?;
-    let url = format!("{ARASAAC_API_BASE}/keywords/{language}");
+    let url = format!("{api_base}/keywords/{language}");
 
     let resp = client
         .get(&url)
@@ -1211,19 +1981,47 @@ pub async fn get_keywords(language: &str) -> AppResult<Vec<String>> {
 
 // ── Saved pictogram library ───────────────────────────────────────────────────
 
-/// Return all pictograms bookmarked by `user_id`, enriched with pictogram data,
-/// ordered by most-used first then most-recently-saved.
+/// Largest page of saved pictograms returned at once.
+pub const SAVED_PAGE_MAX: i64 = 500;
+/// Page size when the caller does not ask for one.
+pub const SAVED_PAGE_DEFAULT: i64 = 200;
+/// Longest `q` accepted by the saved-pictogram search; the rest is dropped.
+const SAVED_QUERY_MAX_CHARS: usize = 100;
+
+/// One page of a user's saved pictograms, most used first, plus the number of
+/// saves matching `query` (label or keywords, case-insensitive substring).
 pub async fn list_saved_pictograms(
     pool: &crate::db::Db,
     user_id: &str,
     language: &str,
-) -> AppResult<Vec<SavedPictogramDto>> {
+    query: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> AppResult<(i64, Vec<SavedPictogramDto>)> {
     let language = normalize_language(language);
-    let rows: Vec<SavedRow> = sqlx::query_as::<_, SavedRow>(
+    let like = query
+        .map(|q| q.trim().chars().take(SAVED_QUERY_MAX_CHARS).collect::<String>())
+        .filter(|q| !q.is_empty())
+        .map(|q| format!("%{}%", escape_like(&q.to_lowercase())));
+
+    const FILTER: &str = "FROM saved_pictograms sp
+         LEFT JOIN pictograms p
+            ON p.arasaac_id = sp.arasaac_id
+         WHERE sp.user_id = ?
+           AND (? IS NULL OR LOWER(COALESCE(sp.label, '')) LIKE ? OR LOWER(COALESCE(p.keywords_text, '')) LIKE ?)";
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) {FILTER}"))
+        .bind(user_id)
+        .bind(&like).bind(&like).bind(&like)
+        .fetch_one(pool)
+        .await?;
+
+    let rows: Vec<SavedRow> = sqlx::query_as::<_, SavedRow>(&format!(
         "SELECT
             sp.arasaac_id,
             sp.label,
             sp.used_count,
+            sp.saved_at,
             p.keywords_text,
             p.categories_text,
             p.tags_text,
@@ -1232,19 +2030,24 @@ pub async fn list_saved_pictograms(
             p.local_file_path,
             p.license,
             p.description
-         FROM saved_pictograms sp
-         LEFT JOIN pictograms p
-            ON p.arasaac_id = sp.arasaac_id
-         WHERE sp.user_id = ?
-         ORDER BY sp.used_count DESC, sp.saved_at DESC
-         LIMIT 200",
-    )
+         {FILTER}
+         ORDER BY sp.used_count DESC, sp.saved_at DESC, sp.arasaac_id ASC
+         LIMIT ? OFFSET ?"
+    ))
     .bind(&language)
     .bind(user_id)
+    .bind(&like).bind(&like).bind(&like)
+    .bind(limit.clamp(1, SAVED_PAGE_MAX))
+    .bind(offset.max(0))
     .fetch_all(pool)
     .await?;
 
-    Ok(rows.into_iter().map(saved_row_to_dto).collect())
+    Ok((total, rows.into_iter().map(saved_row_to_dto).collect()))
+}
+
+/// Escape `LIKE` wildcards so user input only matches literally.
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
 }
 
 /// Bookmark a pictogram for a user. Idempotent — calling it again merely updates
@@ -1282,6 +2085,29 @@ pub async fn unsave_pictogram(
     Ok(())
 }
 
+/// Remove several bookmarks in one statement. Returns how many existed.
+pub async fn unsave_pictograms(pool: &crate::db::Db, user_id: &str, arasaac_ids: &[i32]) -> AppResult<u64> {
+    if arasaac_ids.is_empty() {
+        return Ok(0);
+    }
+    let placeholders = vec!["?"; arasaac_ids.len()].join(", ");
+    let sql = format!("DELETE FROM saved_pictograms WHERE user_id = ? AND arasaac_id IN ({placeholders})");
+    let mut query = sqlx::query(&sql).bind(user_id);
+    for id in arasaac_ids {
+        query = query.bind(id);
+    }
+    Ok(query.execute(pool).await?.rows_affected())
+}
+
+/// Remove every bookmark of a user. Returns how many were removed.
+pub async fn clear_saved_pictograms(pool: &crate::db::Db, user_id: &str) -> AppResult<u64> {
+    let result = sqlx::query("DELETE FROM saved_pictograms WHERE user_id = ?")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
 /// Increment `used_count` for a saved pictogram.  Silently ignored if the
 /// pictogram has not been saved (the row doesn't exist).
 pub async fn record_pictogram_use(
@@ -1310,18 +2136,408 @@ pub async fn saved_ids_for_user(pool: &crate::db::Db, user_id: &str) -> AppResul
     Ok(ids.into_iter().map(|(id,)| id).collect())
 }
 
+/// Whether a single pictogram is in the user's saved library.
+#[derive(Debug, Serialize)]
+pub struct SavedStateDto {
+    pub arasaac_id: i32,
+    pub saved: bool,
+    /// 0 when not saved.
+    pub used_count: i32,
+    /// When it was saved, RFC 3339 UTC; absent when not saved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved_at: Option<String>,
+}
+
+/// Saved state of one pictogram for `user_id` — the single-item counterpart
+/// of [`saved_ids_for_user`].
+pub async fn saved_state_for_user(
+    pool: &crate::db::Db,
+    user_id: &str,
+    arasaac_id: i32,
+) -> AppResult<SavedStateDto> {
+    let row: Option<(i32, chrono::NaiveDateTime)> = sqlx::query_as(
+        "SELECT used_count, saved_at FROM saved_pictograms WHERE user_id = ? AND arasaac_id = ?",
+    )
+    .bind(user_id)
+    .bind(arasaac_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(match row {
+        Some((used_count, saved_at)) => SavedStateDto {
+            arasaac_id,
+            saved: true,
+            used_count,
+            saved_at: Some(
+                chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(saved_at, chrono::Utc)
+                    .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            ),
+        },
+        None => SavedStateDto { arasaac_id, saved: false, used_count: 0, saved_at: None },
+    })
+}
+
+async fn annotate_saved_state(
+    pool: &crate::db::Db,
+    user_id: &str,
+    items: &mut [PictogramDto],
+) -> AppResult<()> {
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    // Only the saved rows for these results, not the user's whole list.
+    let placeholders = vec!["?"; items.len()].join(", ");
+    let sql = format!(
+        "SELECT arasaac_id, used_count FROM saved_pictograms WHERE user_id = ? AND arasaac_id IN ({placeholders})"
+    );
+    let mut query = sqlx::query_as::<_, (i32, i32)>(&sql).bind(user_id);
+    for item in items.iter() {
+        query = query.bind(item.arasaac_id);
+    }
+    let saved: std::collections::HashMap<i32, i32> = query.fetch_all(pool).await?.into_iter().collect();
+
+    for item in items.iter_mut() {
+        let used = saved.get(&item.arasaac_id).copied();
+        item.saved = Some(used.is_some());
+        item.used_count = Some(used.unwrap_or(0));
+    }
+    Ok(())
+}
+
 fn saved_row_to_dto(row: SavedRow) -> SavedPictogramDto {
     SavedPictogramDto {
         arasaac_id: row.arasaac_id,
         label: row.label,
         used_count: row.used_count,
+        saved_at: chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(row.saved_at, chrono::Utc)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
         keywords: split_tokens(row.keywords_text.as_deref().unwrap_or_default()),
         categories: split_tokens(row.categories_text.as_deref().unwrap_or_default()),
         tags: split_tokens(row.tags_text.as_deref().unwrap_or_default()),
-        language: row.language.unwrap_or_else(|| "en".to_string()),
         image_url: row.image_url,
         local_file_path: row.local_file_path,
-        license: row.license.unwrap_or_else(|| DEFAULT_LICENSE.to_string()),
+        license: localized_license(
+            row.license.as_deref().unwrap_or(DEFAULT_LICENSE),
+            row.language.as_deref().unwrap_or("en"),
+        ),
+        language: row.language.unwrap_or_else(|| "en".to_string()),
         description: row.description,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::UserRole, test_support};
+
+    fn dto(arasaac_id: i32) -> PictogramDto {
+        PictogramDto {
+            arasaac_id,
+            keywords: Vec::new(),
+            category: None,
+            categories: Vec::new(),
+            tags: Vec::new(),
+            language: "en".into(),
+            image_url: None,
+            local_file_path: None,
+            width: None,
+            height: None,
+            license: DEFAULT_LICENSE.into(),
+            description: None,
+            options: None,
+            saved: None,
+            used_count: None,
+        }
+    }
+
+    fn options(skin: Option<&str>, hair: Option<&str>, plural: Option<bool>, color: Option<bool>, action: Option<&str>) -> PictogramOptions {
+        PictogramOptions {
+            skin: skin.map(Into::into),
+            hair: hair.map(Into::into),
+            plural,
+            color,
+            action: action.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn normalized_options_drop_defaults_and_canonicalize_case() {
+        let normalized = options(Some(" BLACK "), Some("darkbrown"), Some(false), Some(true), Some("")).normalized().unwrap();
+        assert_eq!(normalized, options(Some("black"), Some("darkBrown"), None, None, None));
+        assert!(options(None, None, Some(false), Some(true), None).normalized().unwrap().is_default());
+    }
+
+    #[test]
+    fn unknown_option_values_are_rejected() {
+        for bad in [
+            options(Some("green"), None, None, None, None),
+            options(None, Some("purple"), None, None, None),
+            options(None, None, None, None, Some("present")),
+        ] {
+            assert!(matches!(bad.normalized(), Err(AppError::BadRequest(_))));
+        }
+    }
+
+    #[test]
+    fn variant_key_and_url_identify_the_option_set() {
+        let opts = options(Some("black"), Some("darkBrown"), Some(true), Some(false), Some("past"));
+        assert_eq!(opts.variant_key(), "skin-black_hair-darkbrown_plural_bw_action-past");
+        assert_eq!(
+            build_remote_variant_url(2349, &opts),
+            format!("{ARASAAC_API_BASE}/pictograms/2349?download=false&skin=black&hair=darkBrown&plural=true&color=false&action=past")
+        );
+        assert_eq!(variant_public_path("food", 2349, "bw"), "/assets/pictograms/food/2349_bw.png");
+    }
+
+    #[test]
+    fn default_license_is_translated_by_primary_language() {
+        assert!(localized_license(DEFAULT_LICENSE, "da-DK").contains("Aragoniens regering; forfatter"));
+        assert!(localized_license(DEFAULT_LICENSE, "nb").contains("Aragóns regjering"));
+        assert_eq!(localized_license(DEFAULT_LICENSE, "nb"), localized_license(DEFAULT_LICENSE, "no_NO"));
+        assert_eq!(localized_license(DEFAULT_LICENSE, "tlh"), DEFAULT_LICENSE);
+        assert_eq!(localized_license(DEFAULT_LICENSE, ""), DEFAULT_LICENSE);
+        assert_eq!(attribution("en-GB"), DEFAULT_LICENSE);
+
+        let custom = "CC BY 4.0 (Own drawing)";
+        assert_eq!(localized_license(custom, "da-DK"), custom);
+    }
+
+    #[tokio::test]
+    async fn list_results_point_at_the_variant_rendering() {
+        let mut items = vec![dto(2349)];
+        apply_variant_options(&mut items, &PictogramOptions::default()).await.unwrap();
+        assert_eq!(items[0].options, None);
+
+        let opts = options(Some("black"), None, None, None, None);
+        apply_variant_options(&mut items, &opts).await.unwrap();
+        assert_eq!(items[0].options.as_ref(), Some(&opts));
+        assert_eq!(items[0].image_url.as_deref(), Some(build_remote_variant_url(2349, &opts).as_str()));
+        // Not cached on disk yet, so clients load the remote rendering.
+        assert_eq!(items[0].local_file_path, None);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn annotate_marks_only_the_users_saved_results(pool: crate::db::Db) {
+        let user = test_support::user(&pool, UserRole::Parent).await;
+        let other = test_support::user(&pool, UserRole::Parent).await;
+        for (user_id, arasaac_id, used_count) in [(&user.user_id, 1, 4), (&user.user_id, 99, 1), (&other.user_id, 2, 7)] {
+            sqlx::query("INSERT INTO saved_pictograms (user_id, arasaac_id, used_count) VALUES (?, ?, ?)")
+                .bind(user_id)
+                .bind(arasaac_id)
+                .bind(used_count)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let mut items = vec![dto(1), dto(2), dto(3)];
+        annotate_saved_state(&pool, &user.user_id, &mut items).await.unwrap();
+        let annotated: Vec<_> = items.iter().map(|i| (i.arasaac_id, i.saved, i.used_count)).collect();
+        assert_eq!(
+            annotated,
+            vec![(1, Some(true), Some(4)), (2, Some(false), Some(0)), (3, Some(false), Some(0))]
+        );
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn saved_pictograms_list_in_a_stable_order(pool: crate::db::Db) {
+        let user = test_support::user(&pool, UserRole::Parent).await;
+        // Three rows tie on used_count and saved_at; the id breaks the tie.
+        for (arasaac_id, used_count) in [(30, 1), (10, 1), (40, 5), (20, 1)] {
+            sqlx::query(
+                "INSERT INTO saved_pictograms (user_id, arasaac_id, used_count, saved_at)
+                 VALUES (?, ?, ?, '2026-03-01 10:00:00')",
+            )
+            .bind(&user.user_id)
+            .bind(arasaac_id)
+            .bind(used_count)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let page = |limit: i64, offset: i64| list_saved_pictograms(&pool, &user.user_id, "en", None, limit, offset);
+        let ids = |items: &[SavedPictogramDto]| items.iter().map(|i| i.arasaac_id).collect::<Vec<_>>();
+
+        for _ in 0..3 {
+            let (total, items) = page(10, 0).await.unwrap();
+            assert_eq!(total, 4);
+            assert_eq!(ids(&items), [40, 10, 20, 30]);
+        }
+        let (_, first) = page(2, 0).await.unwrap();
+        let (_, second) = page(2, 2).await.unwrap();
+        assert_eq!((ids(&first), ids(&second)), (vec![40, 10], vec![20, 30]));
+
+        assert_eq!(first[0].saved_at, "2026-03-01T10:00:00Z");
+        assert!(chrono::DateTime::parse_from_rfc3339(&first[0].saved_at).is_ok());
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn prefetch_prefers_an_uncached_library_id_over_a_cached_one(pool: crate::db::Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        for (arasaac_id, label) in [(100, "Cached"), (200, "Missing")] {
+            sqlx::query("INSERT INTO visual_support_activity_library (id, owner_id, label_text, arasaac_id) VALUES (UUID(), ?, ?, ?)")
+                .bind(&parent.user_id)
+                .bind(label)
+                .bind(arasaac_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO pictograms (arasaac_id, keywords_json, keywords_text, local_file_path)
+             VALUES (100, JSON_ARRAY(), 'cached', '/assets/pictograms/100.png')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // A one-id batch from the start goes to the id that needs a download.
+        let candidates = load_prefetch_candidates(&pool, 0, 1, &["en".to_string()]).await.unwrap();
+        assert_eq!(candidates, [(200, "en".to_string())]);
+    }
+
+    #[test]
+    fn prefetch_cursor_advances_on_full_batches_and_wraps_on_short_ones() {
+        assert_eq!(next_prefetch_cursor(Some(420), 50, 50), 420);
+        assert_eq!(next_prefetch_cursor(Some(420), 49, 50), 0);
+        assert_eq!(next_prefetch_cursor(None, 0, 50), 0);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn prefetch_walk_continues_after_the_cursor(pool: crate::db::Db) {
+        let parent = test_support::user(&pool, UserRole::Parent).await;
+        // Above every seeded library id, so the walk ends with these.
+        for (arasaac_id, language) in [(900_010, "en"), (900_020, "da"), (900_030, "en")] {
+            sqlx::query(
+                "INSERT INTO visual_support_activity_library (id, owner_id, label_text, arasaac_id, language)
+                 VALUES (UUID(), ?, ?, ?, ?)",
+            )
+            .bind(&parent.user_id)
+            .bind(format!("Card {arasaac_id}"))
+            .bind(arasaac_id)
+            .bind(language)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let languages = ["da".to_string(), "en".to_string()];
+
+        let first = load_prefetch_candidates(&pool, 900_000, 2, &languages).await.unwrap();
+        assert_eq!(first, [(900_010, "en".to_string()), (900_020, "da".to_string())]);
+        let cursor = next_prefetch_cursor(first.last().map(|(id, _)| *id), first.len(), 2);
+        assert_eq!(cursor, 900_020);
+
+        let second = load_prefetch_candidates(&pool, cursor, 2, &languages).await.unwrap();
+        assert_eq!(second, [(900_030, "en".to_string())]);
+        assert_eq!(next_prefetch_cursor(second.last().map(|(id, _)| *id), second.len(), 2), 0);
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn worker_honors_then_clears_the_ignore_idle_window(pool: crate::db::Db) {
+        let config = crate::config::Config::for_tests();
+        let now = chrono::Utc::now().naive_utc();
+        let open_until = now + chrono::Duration::hours(1);
+        update_prefetch_settings(&pool, &config, Some(true), Some(20), None, Some(Some(open_until)))
+            .await
+            .unwrap();
+        let settings = || get_prefetch_settings_internal(&pool, &config);
+
+        // Inside the window the worker runs without any idle time.
+        let open = settings().await.unwrap();
+        assert!(worker_should_run(&pool, &open, now, 0).await);
+        assert!(open.ignore_idle_until.is_some());
+
+        // Past it, idle_minutes applies again and the window is cleared.
+        sqlx::query("UPDATE pictogram_prefetch_settings SET ignore_idle_until = UTC_TIMESTAMP() - INTERVAL 1 MINUTE WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let expired = settings().await.unwrap();
+        assert!(!worker_should_run(&pool, &expired, now, 0).await);
+        let cleared = settings().await.unwrap();
+        assert_eq!(cleared.ignore_idle_until, None);
+        assert!(!worker_should_run(&pool, &cleared, now, 19 * 60).await);
+        assert!(worker_should_run(&pool, &cleared, now, 20 * 60).await);
+
+        update_prefetch_settings(&pool, &config, Some(false), None, None, None).await.unwrap();
+        assert!(!worker_should_run(&pool, &settings().await.unwrap(), now, 20 * 60).await);
+    }
+
+    /// A stand-in for ARASAAC's `/keywords/{lang}` answering with whatever
+    /// `reply` holds; its base URL and a count of the requests it served.
+    async fn mock_keywords_api(reply: Arc<Mutex<(u16, Vec<&'static str>)>>) -> (String, Arc<AtomicU64>) {
+        let hits = Arc::new(AtomicU64::new(0));
+        let handler = {
+            let hits = hits.clone();
+            move || {
+                let (reply, hits) = (reply.clone(), hits.clone());
+                async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    let (status, words) = reply.lock().unwrap().clone();
+                    (axum::http::StatusCode::from_u16(status).unwrap(), axum::Json(serde_json::json!({ "words": words })))
+                }
+            }
+        };
+        let app = axum::Router::new().route("/keywords/{lang}", axum::routing::get(handler));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (base, hits)
+    }
+
+    #[tokio::test]
+    async fn keywords_are_fetched_once_then_served_from_cache_until_stale() {
+        let reply = Arc::new(Mutex::new((200, vec!["apple", "bath"])));
+        let (api, hits) = mock_keywords_api(reply.clone()).await;
+        let cache = Mutex::new(KeywordsCache::new());
+        let ttl = Duration::from_secs(60);
+        let t0 = Instant::now();
+        let lookup = |now: Instant| cached_or_fetch_keywords(&cache, &api, "en", ttl, now);
+
+        // Miss: fetched and cached.
+        let (words, stale) = lookup(t0).await.unwrap();
+        assert_eq!((words.as_slice(), stale), (&["apple".to_string(), "bath".to_string()][..], false));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Hits within the TTL and past it are served from memory; past it
+        // the list is reported stale so the caller refreshes in the background.
+        *reply.lock().unwrap() = (200, vec!["cat"]);
+        assert!(!lookup(t0 + ttl - Duration::from_secs(1)).await.unwrap().1);
+        let (words, stale) = lookup(t0 + ttl).await.unwrap();
+        assert_eq!((words.len(), stale), (2, true));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // A refresh replaces the list and restarts its age.
+        let refreshed = refresh_keywords(&cache, &api, "en", t0 + ttl).await.unwrap();
+        assert_eq!(refreshed.as_slice(), ["cat".to_string()]);
+        let (words, stale) = lookup(t0 + ttl + Duration::from_secs(1)).await.unwrap();
+        assert_eq!((words.as_slice(), stale), (&["cat".to_string()][..], false));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_keyword_refresh_keeps_the_cached_list() {
+        let reply = Arc::new(Mutex::new((200, vec!["apple"])));
+        let (api, hits) = mock_keywords_api(reply.clone()).await;
+        let cache = Mutex::new(KeywordsCache::new());
+        let ttl = Duration::from_secs(60);
+        let t0 = Instant::now();
+        cached_or_fetch_keywords(&cache, &api, "da", ttl, t0).await.unwrap();
+
+        *reply.lock().unwrap() = (500, vec![]);
+        assert!(refresh_keywords(&cache, &api, "da", t0 + ttl).await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        let (words, stale) = cached_or_fetch_keywords(&cache, &api, "da", ttl, t0 + ttl).await.unwrap();
+        assert_eq!((words.as_slice(), stale), (&["apple".to_string()][..], true));
+
+        // Other languages are cached separately: a miss goes to the API.
+        assert!(cached_or_fetch_keywords(&cache, &api, "en", ttl, t0).await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+}
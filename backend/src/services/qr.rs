@@ -0,0 +1,47 @@
+//! QR code rendering for child device pairing.
+
+use qrcode::{Color, QrCode};
+
+use crate::errors::{AppError, AppResult};
+
+/// Pixels per QR module; large enough to scan reliably from a printout.
+const MODULE_PX: usize = 8;
+/// Blank border in modules, as required by the QR spec.
+const QUIET_ZONE: usize = 4;
+
+/// Render `data` as a black-on-white grayscale PNG.
+pub fn render_png(data: &str) -> AppResult<Vec<u8>> {
+    let code = QrCode::new(data.as_bytes())
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to encode QR code: {e}")))?;
+
+    let modules = code.width();
+    let colors = code.to_colors();
+    let side = (modules + QUIET_ZONE * 2) * MODULE_PX;
+
+    let mut pixels = vec![0xFFu8; side * side];
+    for (i, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let x0 = (i % modules + QUIET_ZONE) * MODULE_PX;
+        let y0 = (i / modules + QUIET_ZONE) * MODULE_PX;
+        for y in y0..y0 + MODULE_PX {
+            pixels[y * side + x0..y * side + x0 + MODULE_PX].fill(0x00);
+        }
+    }
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, side as u32, side as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write QR PNG header: {e}")))?;
+        writer
+            .write_image_data(&pixels)
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write QR PNG data: {e}")))?;
+    }
+
+    Ok(out)
+}
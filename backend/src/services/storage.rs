@@ -0,0 +1,177 @@
+//! Per-user upload storage accounting and quota.
+//!
+//! Every upload reserves its size up front with a single conditional
+//! `UPDATE`, so concurrent uploads cannot overshoot the quota. Deletions and
+//! failed uploads [`release`] exactly the bytes they reserved, again in one
+//! `UPDATE`, so reservations of uploads still in flight are left alone.
+//! Recomputing the total from `image_library.size_bytes` would drop those
+//! reservations, so that only happens in the startup backfill.
+
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    db::Db,
+    errors::{AppError, AppResult},
+};
+
+/// Where `/images` uploads live on disk, relative to the working directory.
+pub const IMAGE_UPLOAD_DIR: &str = "uploads/images";
+
+#[derive(Debug, Serialize)]
+pub struct StorageUsageDto {
+    pub used_bytes: u64,
+    pub quota_bytes: u64,
+    pub remaining_bytes: u64,
+}
+
+/// Add `bytes` to the user's usage, or fail with `Conflict` if that would
+/// exceed `USER_STORAGE_QUOTA_BYTES`.
+pub async fn reserve(pool: &Db, config: &Config, user_id: &str, bytes: u64) -> AppResult<()> {
+    let result = sqlx::query(
+        "UPDATE users
+         SET storage_used_bytes = storage_used_bytes + ?
+         WHERE id = ? AND storage_used_bytes + ? <= ?",
+    )
+    .bind(bytes)
+    .bind(user_id)
+    .bind(bytes)
+    .bind(config.user_storage_quota_bytes)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::Conflict(format!(
+            "Storage quota exceeded ({} bytes)",
+            config.user_storage_quota_bytes
+        )));
+    }
+    Ok(())
+}
+
+/// Give back `bytes` reserved by [`reserve`], never going below zero. The
+/// signed cast keeps the subtraction from overflowing the unsigned column.
+pub async fn release(pool: &Db, user_id: &str, bytes: u64) -> AppResult<()> {
+    sqlx::query(
+        "UPDATE users
+         SET storage_used_bytes = GREATEST(CAST(storage_used_bytes AS SIGNED) - ?, 0)
+         WHERE id = ?",
+    )
+    .bind(bytes)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Reset the cached usage to the sum of the user's stored files. Only safe
+/// while no upload is in flight, i.e. at startup.
+async fn recompute_usage(pool: &Db, user_id: &str) -> AppResult<()> {
+    sqlx::query(
+        "UPDATE users
+         SET storage_used_bytes = (
+             SELECT COALESCE(SUM(size_bytes), 0) FROM image_library WHERE owner_id = ?
+         )
+         WHERE id = ?",
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_usage(pool: &Db, config: &Config, user_id: &str) -> AppResult<StorageUsageDto> {
+    let used: u64 = sqlx::query_scalar("SELECT storage_used_bytes FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let quota = config.user_storage_quota_bytes;
+    Ok(StorageUsageDto {
+        used_bytes: used,
+        quota_bytes: quota,
+        remaining_bytes: quota.saturating_sub(used),
+    })
+}
+
+/// Fill in `size_bytes` for images uploaded before sizes were recorded, then
+/// recompute every affected owner's usage. Missing files count as 0.
+pub async fn backfill_image_sizes(pool: &Db) -> anyhow::Result<usize> {
+    #[derive(sqlx::FromRow)]
+    struct UnsizedImage {
+        id: String,
+        owner_id: Option<String>,
+        filename: String,
+    }
+
+    let rows: Vec<UnsizedImage> = sqlx::query_as::<_, UnsizedImage>(
+        "SELECT id, owner_id, filename FROM image_library WHERE size_bytes = 0",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut owners: Vec<String> = Vec::new();
+    let mut sized = 0usize;
+    for row in rows {
+        let Ok(meta) = tokio::fs::metadata(format!("{IMAGE_UPLOAD_DIR}/{}", row.filename)).await else {
+            continue;
+        };
+        sqlx::query("UPDATE image_library SET size_bytes = ? WHERE id = ?")
+            .bind(meta.len())
+            .bind(&row.id)
+            .execute(pool)
+            .await?;
+        sized += 1;
+        if let Some(owner) = row.owner_id {
+            if !owners.contains(&owner) {
+                owners.push(owner);
+            }
+        }
+    }
+
+    for owner in owners {
+        recompute_usage(pool, &owner).await?;
+    }
+    Ok(sized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::UserRole, test_support};
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn release_keeps_other_reservations(pool: Db) {
+        let mut config = Config::for_tests();
+        config.user_storage_quota_bytes = 100;
+        let user = test_support::user(&pool, UserRole::Parent).await;
+
+        // Two uploads in flight; neither has an image_library row yet.
+        reserve(&pool, &config, &user.user_id, 60).await.unwrap();
+        reserve(&pool, &config, &user.user_id, 30).await.unwrap();
+        // The first one fails and gives its bytes back.
+        release(&pool, &user.user_id, 60).await.unwrap();
+        assert_eq!(get_usage(&pool, &config, &user.user_id).await.unwrap().used_bytes, 30);
+
+        // The second reservation still counts against the quota.
+        assert!(matches!(
+            reserve(&pool, &config, &user.user_id, 71).await,
+            Err(AppError::Conflict(_))
+        ));
+        reserve(&pool, &config, &user.user_id, 70).await.unwrap();
+    }
+
+    #[sqlx::test(migrations = "../database/migrations")]
+    #[ignore = "needs DATABASE_URL"]
+    async fn release_never_goes_below_zero(pool: Db) {
+        let config = Config::for_tests();
+        let user = test_support::user(&pool, UserRole::Parent).await;
+
+        reserve(&pool, &config, &user.user_id, 10).await.unwrap();
+        release(&pool, &user.user_id, 25).await.unwrap();
+        assert_eq!(get_usage(&pool, &config, &user.user_id).await.unwrap().used_bytes, 0);
+    }
+}
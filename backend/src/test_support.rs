@@ -0,0 +1,97 @@
+//! Fixtures for tests that need a database.
+//!
+//! Those tests use `#[sqlx::test]`, which creates a fresh database per test
+//! and applies `database/migrations`. They are `#[ignore]`d so a plain
+//! `cargo test` passes without MariaDB; run them with
+//! `DATABASE_URL=mysql://root:<password>@localhost:3307 cargo test -- --include-ignored`.
+
+use uuid::Uuid;
+
+use crate::{
+    config::Config,
+    db::Db,
+    errors::{AppError, AppResult},
+    events,
+    middleware::auth_guard::AuthUser,
+    models::UserRole,
+    state::AppState,
+};
+
+pub fn state(pool: Db) -> AppState {
+    AppState { pool, config: Config::for_tests(), events: events::channel() }
+}
+
+/// Insert an active, verified user with `role`.
+pub async fn user(pool: &Db, role: UserRole) -> AuthUser {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO users (id, email, password_hash, role, is_verified, is_active)
+         VALUES (?, ?, 'x', ?, 1, 1)",
+    )
+    .bind(&id)
+    .bind(format!("{id}@example.test"))
+    .bind(role.to_string())
+    .execute(pool)
+    .await
+    .expect("insert user");
+    AuthUser { user_id: id, role }
+}
+
+/// Insert a child profile; `parent_id` may be `None` for orphaned profiles.
+pub async fn child(pool: &Db, parent_id: Option<&str>) -> String {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO child_profiles (id, parent_id, display_name) VALUES (?, ?, 'Test child')")
+        .bind(&id)
+        .bind(parent_id)
+        .execute(pool)
+        .await
+        .expect("insert child");
+    id
+}
+
+/// Insert a weekly schedule owned by `owner` with `metadata` as its
+/// `metadata_json` (e.g. `{"schedule": {"status": "active"}}`); its id.
+pub async fn schedule(pool: &Db, owner: &AuthUser, name: &str, metadata: serde_json::Value) -> String {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO visual_support_documents_templates (id, owner_id, document_type, name, metadata_json)
+         VALUES (?, ?, 'WEEKLY_SCHEDULE', ?, ?)",
+    )
+    .bind(&id)
+    .bind(&owner.user_id)
+    .bind(name)
+    .bind(metadata.to_string())
+    .execute(pool)
+    .await
+    .expect("insert schedule");
+    id
+}
+
+/// Assign `schedule_id` to `child_id` the way `POST /calendar/{child_id}/assign`
+/// stores it, with `assignment` (`day_of_week`, `persistent`, dates,
+/// `override`) under `content_json.assignment`; the assignment id.
+pub async fn assign(pool: &Db, owner: &AuthUser, child_id: &str, schedule_id: &str, assignment: serde_json::Value) -> String {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO visual_support_documents
+            (id, owner_id, child_id, template_id, title, document_type, locale, layout_spec_json, content_json, version)
+         VALUES (?, ?, ?, ?, 'Assignment', 'WEEKLY_SCHEDULE', 'en', '{}', ?, 1)",
+    )
+    .bind(&id)
+    .bind(&owner.user_id)
+    .bind(child_id)
+    .bind(schedule_id)
+    .bind(serde_json::json!({ "assignment": assignment }).to_string())
+    .execute(pool)
+    .await
+    .expect("insert assignment");
+    id
+}
+
+/// The error of `result`; for handler results whose body is not `Debug`.
+pub fn expect_err<T>(result: AppResult<T>) -> AppError {
+    match result {
+        Ok(_) => panic!("expected an error"),
+        Err(err) => err,
+    }
+}
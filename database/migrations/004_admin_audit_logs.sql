@@ -0,0 +1,14 @@
+-- Audit trail for privileged admin actions that change ownership or access.
+
+CREATE TABLE IF NOT EXISTS admin_audit_logs (
+    id            CHAR(36) NOT NULL PRIMARY KEY,
+    actor_user_id CHAR(36) NULL,
+    action        VARCHAR(64) NOT NULL,
+    target_table  VARCHAR(128) NOT NULL,
+    target_id     VARCHAR(128) NULL,
+    details       JSON NULL,
+    created_at    TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+
+    INDEX idx_admin_audit_created_at (created_at),
+    INDEX idx_admin_audit_target (target_table, target_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
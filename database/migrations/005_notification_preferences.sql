@@ -0,0 +1,5 @@
+-- Per-user opt-in flags for non-transactional email (reminders, digests).
+-- NULL means "never set"; the backend falls back to all-off defaults.
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS notification_preferences JSON NULL AFTER week_start;
@@ -0,0 +1,4 @@
+-- Persisted visual theme preference (accessibility: high-contrast support).
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS theme ENUM('light','dark','high_contrast','system') NOT NULL DEFAULT 'system' AFTER week_start;
@@ -0,0 +1,13 @@
+-- Single-use signed download links for DSR data exports.
+-- The token itself is HMAC-signed; this table only tracks issuance and use.
+
+CREATE TABLE IF NOT EXISTS export_download_tokens (
+    id          CHAR(36) PRIMARY KEY,
+    user_id     CHAR(36) NOT NULL,
+    expires_at  DATETIME NOT NULL,
+    used_at     DATETIME NULL,
+    created_at  TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_edt_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    INDEX idx_edt_user (user_id),
+    INDEX idx_edt_expires (expires_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
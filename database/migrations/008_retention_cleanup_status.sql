@@ -0,0 +1,17 @@
+-- Last/next run bookkeeping for the retention cleanup job, shown in the
+-- Compliance Center. Single row (id = 1), mirrors pictogram_prefetch_settings.
+
+CREATE TABLE IF NOT EXISTS retention_cleanup_status (
+    id                   TINYINT NOT NULL PRIMARY KEY,
+    last_run_at          DATETIME NULL,
+    last_trigger         VARCHAR(16) NULL,
+    last_deleted_counts  JSON NULL,
+    last_error           TEXT NULL,
+    next_run_at          DATETIME NULL,
+    updated_at           DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+INSERT INTO retention_cleanup_status (id)
+VALUES (1)
+ON DUPLICATE KEY UPDATE
+    id = VALUES(id);
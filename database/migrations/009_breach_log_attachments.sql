@@ -0,0 +1,16 @@
+-- Evidence files (log exports, screenshots) attached to breach log entries.
+-- Files live outside the statically served directories and are only
+-- reachable through the admin compliance API.
+
+CREATE TABLE IF NOT EXISTS breach_log_attachments (
+    id                 CHAR(36) PRIMARY KEY,
+    breach_log_id      CHAR(36) NOT NULL,
+    original_filename  VARCHAR(255) NOT NULL,
+    stored_filename    VARCHAR(64) NOT NULL,
+    content_type       VARCHAR(100) NOT NULL,
+    size_bytes         BIGINT NOT NULL,
+    uploaded_by        CHAR(36) NULL,
+    created_at         TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_bla_breach FOREIGN KEY (breach_log_id) REFERENCES breach_logs(id) ON DELETE CASCADE,
+    INDEX idx_bla_breach (breach_log_id, created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
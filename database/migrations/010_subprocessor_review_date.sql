@@ -0,0 +1,6 @@
+-- Optional DPA review/renewal date so the subprocessor register can flag
+-- agreements that are due for review.
+
+ALTER TABLE subprocessor_register
+    ADD COLUMN IF NOT EXISTS dpa_review_date DATE NULL AFTER dpa_signed_date,
+    ADD INDEX IF NOT EXISTS idx_subprocessor_review (is_active, dpa_review_date);
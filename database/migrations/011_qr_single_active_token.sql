@@ -0,0 +1,17 @@
+-- Enforce at most one active QR token per child.
+-- MariaDB has no partial unique indexes, so a generated column that is only
+-- populated for active rows carries the unique key (NULLs never collide).
+
+-- Keep only the newest active token per child before adding the constraint.
+UPDATE qr_tokens q
+JOIN qr_tokens newer
+  ON newer.child_id = q.child_id
+ AND newer.is_active = 1
+ AND (newer.created_at > q.created_at OR (newer.created_at = q.created_at AND newer.id > q.id))
+SET q.is_active = 0
+WHERE q.is_active = 1;
+
+ALTER TABLE qr_tokens
+    ADD COLUMN IF NOT EXISTS active_child_id CHAR(36)
+        AS (IF(is_active = 1, child_id, NULL)) PERSISTENT,
+    ADD UNIQUE INDEX IF NOT EXISTS uniq_qr_active_child (active_child_id);
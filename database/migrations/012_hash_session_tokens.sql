@@ -0,0 +1,26 @@
+-- Store SHA-256 hashes of parent session and email tokens instead of the raw
+-- values. The raw token only lives in the cookie / emailed link.
+--
+-- Transition: `token_hash` is added and backfilled here, and the plaintext
+-- column is made nullable and cleared. Lookups still fall back to `token`
+-- for rows without a hash (e.g. written by an instance running the previous
+-- release during a rolling deploy). A later migration can drop `token` once
+-- no such rows remain.
+
+ALTER TABLE user_sessions
+    ADD COLUMN IF NOT EXISTS token_hash CHAR(64) NULL AFTER token,
+    MODIFY COLUMN token CHAR(64) NULL,
+    ADD UNIQUE INDEX IF NOT EXISTS uniq_user_sessions_token_hash (token_hash);
+
+UPDATE user_sessions
+SET token_hash = SHA2(token, 256), token = NULL
+WHERE token_hash IS NULL AND token IS NOT NULL;
+
+ALTER TABLE email_tokens
+    ADD COLUMN IF NOT EXISTS token_hash CHAR(64) NULL AFTER token,
+    MODIFY COLUMN token CHAR(64) NULL,
+    ADD UNIQUE INDEX IF NOT EXISTS uniq_email_tokens_token_hash (token_hash);
+
+UPDATE email_tokens
+SET token_hash = SHA2(token, 256), token = NULL
+WHERE token_hash IS NULL AND token IS NOT NULL;
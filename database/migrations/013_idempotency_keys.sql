@@ -0,0 +1,23 @@
+-- Idempotency-Key reservations for create endpoints. A row is inserted when
+-- a keyed request starts and `resource_id` is filled in once the resource
+-- exists; retries with the same key return that resource. `request_hash`
+-- fingerprints the first request, so a reused key with a different payload
+-- is refused instead of replayed.
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    id           CHAR(36) PRIMARY KEY,
+    user_id      CHAR(36) NOT NULL,
+    scope        VARCHAR(64) NOT NULL,
+    idem_key     VARCHAR(255) NOT NULL,
+    request_hash CHAR(64) NOT NULL,
+    resource_id  CHAR(36) NULL,
+    expires_at   DATETIME NOT NULL,
+    created_at   TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_idem_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE KEY uniq_idem_user_scope_key (user_id, scope, idem_key),
+    INDEX idx_idem_expires (expires_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+INSERT INTO retention_rules (id, name, table_name, timestamp_column, retention_days, enabled)
+VALUES ('e34bce28-36d0-4987-8a7a-483f5df43cd8', 'Expired idempotency keys', 'idempotency_keys', 'expires_at', 1, TRUE)
+ON DUPLICATE KEY UPDATE name = VALUES(name);
@@ -0,0 +1,13 @@
+-- "Where am I" pointer a child moves through the day's activity cards from
+-- their paired device. One row per child per local calendar date.
+
+CREATE TABLE IF NOT EXISTS child_day_progress (
+    child_id       CHAR(36) NOT NULL,
+    progress_date  DATE NOT NULL,
+    current_index  INT NOT NULL,
+    device_id      CHAR(36) NULL,
+    updated_at     DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    PRIMARY KEY (child_id, progress_date),
+    CONSTRAINT fk_cdp_child FOREIGN KEY (child_id) REFERENCES child_profiles(id) ON DELETE CASCADE,
+    CONSTRAINT fk_cdp_device FOREIGN KEY (device_id) REFERENCES child_device_tokens(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,6 @@
+-- Accounts flagged here must change their password before any other
+-- authenticated route will serve them (set for the seeded admin while it
+-- still uses the built-in default password).
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS must_change_password BOOLEAN NOT NULL DEFAULT FALSE AFTER password_hash;
@@ -0,0 +1,5 @@
+-- Optional name a child device gives itself (e.g. "Kitchen tablet") so the
+-- parent can tell paired devices apart.
+
+ALTER TABLE child_device_tokens
+    ADD COLUMN IF NOT EXISTS label VARCHAR(64) NULL AFTER child_id;
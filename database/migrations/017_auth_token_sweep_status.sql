@@ -0,0 +1,16 @@
+-- Last/next run bookkeeping for the expired session/token sweep. Single row
+-- (id = 1), mirrors retention_cleanup_status.
+
+CREATE TABLE IF NOT EXISTS auth_token_sweep_status (
+    id                   TINYINT NOT NULL PRIMARY KEY,
+    last_run_at          DATETIME NULL,
+    last_deleted_counts  JSON NULL,
+    last_error           TEXT NULL,
+    next_run_at          DATETIME NULL,
+    updated_at           DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+INSERT INTO auth_token_sweep_status (id)
+VALUES (1)
+ON DUPLICATE KEY UPDATE
+    id = VALUES(id);
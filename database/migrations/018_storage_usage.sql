@@ -0,0 +1,10 @@
+-- Per-user upload accounting for the storage quota. `image_library.size_bytes`
+-- is the source of truth; `users.storage_used_bytes` is its cached sum.
+-- Rows uploaded before this migration have size 0 until the startup backfill
+-- stats their files.
+
+ALTER TABLE image_library
+    ADD COLUMN IF NOT EXISTS size_bytes BIGINT UNSIGNED NOT NULL DEFAULT 0 AFTER alt_text;
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS storage_used_bytes BIGINT UNSIGNED NOT NULL DEFAULT 0;
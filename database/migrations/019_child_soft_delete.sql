@@ -0,0 +1,12 @@
+-- Soft-delete for child profiles: DELETE /children/{id} only stamps
+-- deleted_at so the profile (assignments, devices, progress) can be restored.
+-- The retention job hard-deletes after the rule's window; FK cascades then
+-- remove the dependent rows as before.
+
+ALTER TABLE child_profiles
+    ADD COLUMN IF NOT EXISTS deleted_at DATETIME NULL,
+    ADD INDEX IF NOT EXISTS idx_child_profiles_deleted_at (deleted_at);
+
+INSERT INTO retention_rules (id, name, table_name, timestamp_column, retention_days, enabled)
+VALUES ('4b0e2f4c-8d6a-4f0e-9a51-2f7c3d1e6b90', 'Deleted child profiles', 'child_profiles', 'deleted_at', 30, TRUE)
+ON DUPLICATE KEY UPDATE name = VALUES(name);
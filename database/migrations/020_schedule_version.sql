@@ -0,0 +1,5 @@
+-- Optimistic concurrency for schedule edits: every schedule mutation bumps
+-- `version`, and clients may send `If-Match: "<version>"` to refuse
+-- overwriting someone else's change.
+ALTER TABLE visual_support_documents_templates
+    ADD COLUMN IF NOT EXISTS version INT NOT NULL DEFAULT 1;
@@ -0,0 +1,42 @@
+-- Opt-in reminder emails per child (optionally limited to one schedule), and
+-- the outbox they are queued in. Delivery also requires the parent's
+-- `schedule_reminders` notification preference. Reminders go to whoever is
+-- the child's parent when they are sent, so they carry no parent of their own.
+
+CREATE TABLE IF NOT EXISTS child_reminders (
+    id            CHAR(36) NOT NULL PRIMARY KEY,
+    child_id      CHAR(36) NOT NULL,
+    schedule_id   CHAR(36) NULL,
+    kind          ENUM('daily_summary','upcoming_change') NOT NULL,
+    -- HH:MM in the parent's timezone
+    send_at       CHAR(5) NOT NULL DEFAULT '07:00',
+    is_enabled    BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Parent-local date of the last run that handled this reminder
+    last_sent_on  DATE NULL,
+    created_at    DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    UNIQUE KEY uq_child_reminders (child_id, kind, schedule_id),
+    INDEX idx_child_reminders_enabled (is_enabled),
+    CONSTRAINT fk_child_reminders_child FOREIGN KEY (child_id) REFERENCES child_profiles(id) ON DELETE CASCADE,
+    CONSTRAINT fk_child_reminders_schedule FOREIGN KEY (schedule_id) REFERENCES visual_support_documents_templates(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Outbox rows are claimed ('sending') before delivery so overlapping ticks
+-- or several backend instances cannot send the same email twice.
+-- `claimed_at` lets a row left in 'sending' by a crashed sender be picked up
+-- again once its lease has run out.
+CREATE TABLE IF NOT EXISTS email_outbox (
+    id          CHAR(36) NOT NULL PRIMARY KEY,
+    user_id     CHAR(36) NOT NULL,
+    kind        VARCHAR(32) NOT NULL,
+    subject     VARCHAR(255) NOT NULL,
+    body        TEXT NOT NULL,
+    status      ENUM('pending','sending','sent','skipped','failed') NOT NULL DEFAULT 'pending',
+    attempts    INT NOT NULL DEFAULT 0,
+    claimed_at  DATETIME NULL,
+    last_error  TEXT NULL,
+    created_at  DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at     DATETIME NULL,
+    INDEX idx_email_outbox_status (status, created_at),
+    CONSTRAINT fk_email_outbox_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,6 @@
+-- When each account last logged in with email + password. Set in the same
+-- transaction that creates the session; NULL until the first login.
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS last_login_at DATETIME NULL,
+    ADD INDEX IF NOT EXISTS idx_users_last_login_at (last_login_at);
@@ -0,0 +1,6 @@
+-- Where the pictogram prefetch worker left off, so successive runs (also
+-- across restarts) walk through all candidate ids instead of re-checking
+-- the lowest ones. Reset to 0 once a run reaches the highest id.
+
+ALTER TABLE pictogram_prefetch_settings
+    ADD COLUMN IF NOT EXISTS cursor_arasaac_id INT NOT NULL DEFAULT 0 AFTER batch_size;
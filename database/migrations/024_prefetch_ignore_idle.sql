@@ -0,0 +1,6 @@
+-- One-shot maintenance window for the pictogram prefetch worker: until this
+-- UTC time it runs on every tick regardless of `idle_minutes`. Cleared by the
+-- worker once passed.
+
+ALTER TABLE pictogram_prefetch_settings
+    ADD COLUMN IF NOT EXISTS ignore_idle_until DATETIME NULL AFTER cursor_arasaac_id;
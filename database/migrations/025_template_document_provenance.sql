@@ -0,0 +1,10 @@
+-- Who created a template and who last changed a template or document, set
+-- by the visual-document and admin template handlers. NULL for rows that
+-- existed before this migration.
+
+ALTER TABLE visual_support_documents_templates
+    ADD COLUMN IF NOT EXISTS created_by CHAR(36) NULL,
+    ADD COLUMN IF NOT EXISTS updated_by CHAR(36) NULL;
+
+ALTER TABLE visual_support_documents
+    ADD COLUMN IF NOT EXISTS updated_by CHAR(36) NULL;
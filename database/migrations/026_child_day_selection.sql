@@ -0,0 +1,15 @@
+-- Schedule a child (or their carer) picked from a paired device for a date
+-- on which several assignments apply. One row per child per local date; the
+-- normal priority order decides while no row exists.
+
+CREATE TABLE IF NOT EXISTS child_day_selection (
+    child_id        CHAR(36) NOT NULL,
+    selection_date  DATE NOT NULL,
+    schedule_id     CHAR(36) NOT NULL,
+    device_id       CHAR(36) NULL,
+    updated_at      DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    PRIMARY KEY (child_id, selection_date),
+    CONSTRAINT fk_cds_child FOREIGN KEY (child_id) REFERENCES child_profiles(id) ON DELETE CASCADE,
+    CONSTRAINT fk_cds_schedule FOREIGN KEY (schedule_id) REFERENCES visual_support_documents_templates(id) ON DELETE CASCADE,
+    CONSTRAINT fk_cds_device FOREIGN KEY (device_id) REFERENCES child_device_tokens(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;